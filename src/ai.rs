@@ -0,0 +1,277 @@
+use clap::ValueEnum;
+use serde::Serialize;
+use std::{path::PathBuf, time::Duration};
+
+use crate::error::Result;
+#[cfg(feature = "ai")]
+use crate::{error::ReportError, warnings};
+
+/// A language model that can write reports. Code that optionally uses AI takes
+/// `&dyn Backend`, so it compiles the same with or without the `ai` feature.
+pub trait Backend {
+    /// Human-readable name of the service, used in progress messages.
+    fn name(&self) -> &str;
+    fn model(&self) -> &str;
+    /// Verifies that the backend is reachable and the model is available.
+    fn check(&self) -> Result<()>;
+    /// Sends a single completion request and returns the raw response text.
+    fn generate(&self, prompt: &str, temperature: f64) -> Result<String>;
+    /// Requests and tokens used so far; zero for backends that do not
+    /// report usage.
+    fn usage(&self) -> TokenUsage {
+        TokenUsage::default()
+    }
+}
+
+/// A model that turns texts into vectors that lie close together for texts
+/// about the same thing, used by `--cluster`.
+pub trait Embedder {
+    /// Human-readable name of the service, used in progress messages.
+    fn name(&self) -> &str;
+    fn model(&self) -> &str;
+    /// Returns one vector per text, in order.
+    fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>>;
+}
+
+/// Completed requests and the tokens the service counted for them.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct TokenUsage {
+    pub requests: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+}
+
+impl TokenUsage {
+    /// This usage plus one request with the given token counts.
+    pub fn with_request(self, input_tokens: u64, output_tokens: u64) -> Self {
+        Self {
+            requests: self.requests + 1,
+            input_tokens: self.input_tokens + input_tokens,
+            output_tokens: self.output_tokens + output_tokens,
+        }
+    }
+}
+
+/// How failed requests to the backend are retried: up to `retries` more
+/// attempts, waiting `delay` before the first and twice as long before each
+/// next one. Only failures that may pass are retried, such as a server that
+/// is not reachable yet, a timeout or an overloaded API.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub retries: u32,
+    pub delay: Duration,
+}
+
+#[cfg(feature = "ai")]
+impl RetryPolicy {
+    fn run<T>(&self, backend: &str, mut request: impl FnMut() -> Result<T>) -> Result<T> {
+        let mut delay = self.delay;
+        let mut attempt = 0;
+        loop {
+            match request() {
+                Err(e) if e.is_transient() && attempt < self.retries => {
+                    attempt += 1;
+                    warnings::warn(format!(
+                        "{}; retrying {} in {}s ({}/{})",
+                        e,
+                        backend,
+                        delay.as_secs_f64(),
+                        attempt,
+                        self.retries
+                    ));
+                    std::thread::sleep(delay);
+                    delay *= 2;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// Describes a request to `service` that got no response. Connection
+/// failures and timeouts are transient, so the request is retried.
+#[cfg(feature = "ai")]
+pub(crate) fn request_error(service: &str, url: &str, error: reqwest::Error) -> ReportError {
+    let (kind, message): (fn(String) -> ReportError, _) = if error.is_connect() {
+        (
+            ReportError::ai_unavailable,
+            format!("Cannot connect to {} at {}", service, url),
+        )
+    } else if error.is_timeout() {
+        (
+            ReportError::ai_unavailable,
+            format!("{} at {} did not respond in time", service, url),
+        )
+    } else {
+        (
+            ReportError::ai,
+            format!("Request to {} at {} failed", service, url),
+        )
+    };
+    kind(message).with_source(Box::new(error))
+}
+
+/// The error for a failed response: rate limits and server errors are
+/// transient, anything else needs a change before trying again.
+#[cfg(feature = "ai")]
+pub(crate) fn status_error(status: reqwest::StatusCode, message: String) -> ReportError {
+    if status.as_u16() == 429 || status.is_server_error() {
+        ReportError::ai_unavailable(message)
+    } else {
+        ReportError::ai(message)
+    }
+}
+
+/// A backend or embedder whose requests are retried by a [`RetryPolicy`].
+#[cfg(feature = "ai")]
+struct Retrying<B: ?Sized> {
+    backend: Box<B>,
+    policy: RetryPolicy,
+}
+
+#[cfg(feature = "ai")]
+impl Backend for Retrying<dyn Backend> {
+    fn name(&self) -> &str {
+        self.backend.name()
+    }
+
+    fn model(&self) -> &str {
+        self.backend.model()
+    }
+
+    fn check(&self) -> Result<()> {
+        self.policy.run(self.name(), || self.backend.check())
+    }
+
+    fn generate(&self, prompt: &str, temperature: f64) -> Result<String> {
+        self.policy
+            .run(self.name(), || self.backend.generate(prompt, temperature))
+    }
+
+    fn usage(&self) -> TokenUsage {
+        self.backend.usage()
+    }
+}
+
+#[cfg(feature = "ai")]
+impl Embedder for Retrying<dyn Embedder> {
+    fn name(&self) -> &str {
+        self.backend.name()
+    }
+
+    fn model(&self) -> &str {
+        self.backend.model()
+    }
+
+    fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        self.policy.run(self.name(), || self.backend.embed(texts))
+    }
+}
+
+/// The service that hosts the model.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Provider {
+    /// A local Ollama server on localhost:11434
+    Ollama,
+    /// Claude through the Anthropic Messages API (needs ANTHROPIC_API_KEY)
+    Anthropic,
+}
+
+impl Provider {
+    /// The model used when `--model` is not given.
+    pub fn default_model(self) -> &'static str {
+        match self {
+            Provider::Ollama => "gemma3",
+            Provider::Anthropic => "claude-sonnet-4-5",
+        }
+    }
+
+    /// The prompt size used when `--max-prompt-tokens` is not given: what a
+    /// local model's memory comfortably holds, or most of Claude's 200k
+    /// context.
+    pub fn default_prompt_tokens(self) -> usize {
+        match self {
+            Provider::Ollama => 6000,
+            Provider::Anthropic => 150_000,
+        }
+    }
+}
+
+/// Estimates the tokens of a prompt at four characters each, which is
+/// close for English text and code with common tokenizers and errs high for
+/// other text.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// Creates the configured backend; fails when AI support was compiled out.
+/// `max_tokens` caps the length of a response where the provider supports it;
+/// `prompt_tokens` is the largest prompt that will be sent, which sizes
+/// Ollama's context window so prompts are not cut silently. Failed requests
+/// are retried as `retry` allows.
+pub fn backend(
+    provider: Provider,
+    model: Option<&str>,
+    max_tokens: u32,
+    prompt_tokens: usize,
+    retry: RetryPolicy,
+    transcript_dir: Option<PathBuf>,
+) -> Result<Box<dyn Backend>> {
+    let model = model.unwrap_or(provider.default_model());
+
+    #[cfg(feature = "ai")]
+    {
+        let backend: Box<dyn Backend> = match provider {
+            Provider::Ollama => Box::new(crate::ollama::Ollama::new(
+                model,
+                prompt_tokens,
+                transcript_dir,
+            )),
+            Provider::Anthropic => Box::new(crate::anthropic::Anthropic::new(
+                model,
+                max_tokens,
+                transcript_dir,
+            )),
+        };
+        Ok(Box::new(Retrying {
+            backend,
+            policy: retry,
+        }))
+    }
+    #[cfg(not(feature = "ai"))]
+    {
+        let _ = (model, max_tokens, prompt_tokens, retry, transcript_dir);
+        Err(crate::features::unavailable("AI reports", "ai"))
+    }
+}
+
+/// Creates the embedder for `--cluster`: the OpenAI-compatible embeddings
+/// API at `url` when given, otherwise the local Ollama server. Fails when AI
+/// support was compiled out.
+pub fn embedder(
+    model: Option<&str>,
+    url: Option<&str>,
+    retry: RetryPolicy,
+) -> Result<Box<dyn Embedder>> {
+    #[cfg(feature = "ai")]
+    {
+        let backend: Box<dyn Embedder> = match url {
+            Some(url) => Box::new(crate::openai::OpenAiEmbeddings::new(
+                model.unwrap_or(crate::openai::DEFAULT_EMBEDDING_MODEL),
+                url,
+            )),
+            None => Box::new(crate::ollama::OllamaEmbeddings::new(
+                model.unwrap_or(crate::ollama::DEFAULT_EMBEDDING_MODEL),
+            )),
+        };
+        Ok(Box::new(Retrying {
+            backend,
+            policy: retry,
+        }))
+    }
+    #[cfg(not(feature = "ai"))]
+    {
+        let _ = (model, url, retry);
+        Err(crate::features::unavailable("Clustered reports", "ai"))
+    }
+}
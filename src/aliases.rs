@@ -0,0 +1,93 @@
+use std::collections::{BTreeMap, HashSet};
+
+use crate::git::Commit;
+
+/// The person an alias stands for: `Jane Doe <jane@example.com>`, or just a
+/// name, which keeps the commit's email.
+struct Identity {
+    name: String,
+    email: Option<String>,
+}
+
+impl Identity {
+    fn parse(value: &str) -> Self {
+        let value = value.trim();
+        match value
+            .strip_suffix('>')
+            .and_then(|rest| rest.rsplit_once('<'))
+        {
+            Some((name, email)) => Self {
+                name: name.trim().to_string(),
+                email: Some(email.trim().to_string()),
+            },
+            None => Self {
+                name: value.to_string(),
+                email: None,
+            },
+        }
+    }
+}
+
+/// Merges the identities of one person after `.mailmap` was applied by git:
+/// `[authors.aliases]` maps author names and emails, compared without case,
+/// to the identity they are counted as, so stats and groupings list the
+/// person once.
+pub struct AuthorAliases {
+    aliases: Vec<(String, Identity)>,
+}
+
+impl AuthorAliases {
+    pub fn new(aliases: &BTreeMap<String, String>) -> Self {
+        Self {
+            aliases: aliases
+                .iter()
+                .map(|(alias, identity)| (alias.to_lowercase(), Identity::parse(identity)))
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.aliases.is_empty()
+    }
+
+    fn find(&self, name: &str, email: &str) -> Option<&Identity> {
+        let (name, email) = (name.to_lowercase(), email.to_lowercase());
+        self.aliases
+            .iter()
+            .find(|(alias, _)| *alias == name || (!email.is_empty() && *alias == email))
+            .map(|(_, identity)| identity)
+    }
+
+    /// Rewrites the author of every commit written under an alias.
+    pub fn apply(&self, commits: &mut [Commit]) {
+        if self.is_empty() {
+            return;
+        }
+        for commit in commits {
+            if let Some(identity) = self.find(&commit.author, &commit.email) {
+                commit.author = identity.name.clone();
+                if let Some(email) = &identity.email {
+                    commit.email = email.clone();
+                }
+            }
+        }
+    }
+
+    /// Adds the identities behind the aliases among `known`, lowercased
+    /// author names and emails as [`crate::git::authors_before`] returns
+    /// them, so earlier commits under an alias count for the person.
+    pub fn extend_known(&self, known: &mut HashSet<String>) {
+        let found: Vec<&Identity> = self
+            .aliases
+            .iter()
+            .filter(|(alias, _)| known.contains(alias))
+            .map(|(_, identity)| identity)
+            .collect();
+        for identity in found {
+            known.insert(identity.name.to_lowercase());
+            if let Some(email) = &identity.email {
+                known.insert(email.to_lowercase());
+            }
+        }
+    }
+}
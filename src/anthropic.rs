@@ -0,0 +1,182 @@
+use chrono::Utc;
+use serde_json::{json, Value};
+use std::{cell::Cell, env, path::PathBuf, time::Duration};
+
+use crate::{
+    ai::{request_error, status_error, Backend, TokenUsage},
+    error::{Context, ReportError, Result},
+    transcript::TranscriptLog,
+    warnings,
+};
+
+const DEFAULT_BASE_URL: &str = "https://api.anthropic.com";
+const API_VERSION: &str = "2023-06-01";
+
+/// A Claude model used through the Anthropic Messages API. The API key is read
+/// from `ANTHROPIC_API_KEY`; `ANTHROPIC_BASE_URL` can point at a proxy.
+pub struct Anthropic {
+    pub model: String,
+    max_tokens: u32,
+    base_url: String,
+    transcripts: TranscriptLog,
+    usage: Cell<TokenUsage>,
+}
+
+impl Anthropic {
+    pub fn new(model: &str, max_tokens: u32, transcript_dir: Option<PathBuf>) -> Self {
+        Self {
+            model: model.to_string(),
+            max_tokens,
+            base_url: env::var("ANTHROPIC_BASE_URL")
+                .unwrap_or_else(|_| DEFAULT_BASE_URL.to_string())
+                .trim_end_matches('/')
+                .to_string(),
+            transcripts: TranscriptLog::new(transcript_dir),
+            usage: Cell::default(),
+        }
+    }
+
+    fn client(&self) -> Result<reqwest::blocking::Client> {
+        reqwest::blocking::Client::builder()
+            .timeout(Duration::from_secs(300))
+            .build()
+            .context(ReportError::ai, "Failed to create HTTP client")
+    }
+}
+
+fn api_key() -> Result<String> {
+    env::var("ANTHROPIC_API_KEY")
+        .ok()
+        .filter(|key| !key.trim().is_empty())
+        .ok_or_else(|| ReportError::ai("ANTHROPIC_API_KEY is not set".to_string()))
+}
+
+/// Extracts the message of an API error body, falling back to the raw body.
+fn error_message(body: &str) -> String {
+    serde_json::from_str::<Value>(body)
+        .ok()
+        .and_then(|value| value["error"]["message"].as_str().map(String::from))
+        .unwrap_or_else(|| body.trim().to_string())
+}
+
+impl Backend for Anthropic {
+    fn name(&self) -> &str {
+        "Anthropic"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// Verifies the API key and that the model exists for it.
+    fn check(&self) -> Result<()> {
+        let key = api_key()?;
+        let response = self
+            .client()?
+            .get(format!("{}/v1/models/{}", self.base_url, self.model))
+            .header("x-api-key", key)
+            .header("anthropic-version", API_VERSION)
+            .send()
+            .map_err(|e| request_error("the Anthropic API", &self.base_url, e))?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+
+        let body = response.text().unwrap_or_default();
+        match status.as_u16() {
+            401 => Err(ReportError::ai(format!("Anthropic rejected ANTHROPIC_API_KEY: {}", error_message(&body)))),
+            404 => Err(ReportError::ai(format!(
+                "Model '{}' is not available on the Anthropic API. Pass a Claude model with --model",
+                self.model
+            ))),
+            _ => Err(status_error(
+                status,
+                format!(
+                    "Anthropic API returned status {}: {}",
+                    status,
+                    error_message(&body)
+                ),
+            )),
+        }
+    }
+
+    fn generate(&self, prompt: &str, temperature: f64) -> Result<String> {
+        let key = api_key()?;
+        let endpoint = format!("{}/v1/messages", self.base_url);
+        let payload = json!({
+            "model": self.model,
+            "max_tokens": self.max_tokens,
+            "temperature": temperature,
+            "messages": [{ "role": "user", "content": prompt }],
+        });
+
+        let sent_at = Utc::now();
+        let response = self
+            .client()?
+            .post(&endpoint)
+            .header("x-api-key", key)
+            .header("anthropic-version", API_VERSION)
+            .json(&payload)
+            .send()
+            .map_err(|e| request_error("the Anthropic API", &self.base_url, e))?;
+
+        let status = response.status();
+        let raw_response = response
+            .text()
+            .context(ReportError::ai, "Failed to read Anthropic response")?;
+
+        self.transcripts.save(
+            &endpoint,
+            &self.model,
+            &payload,
+            sent_at,
+            status.as_u16(),
+            &raw_response,
+        )?;
+
+        if !status.is_success() {
+            return Err(status_error(
+                status,
+                format!(
+                    "Anthropic API request failed with status {} for model '{}': {}",
+                    status,
+                    self.model,
+                    error_message(&raw_response)
+                ),
+            ));
+        }
+
+        let message: Value = serde_json::from_str(&raw_response)
+            .context(ReportError::parse, "Failed to parse Anthropic response")?;
+        let content = message["content"].as_array().ok_or_else(|| {
+            ReportError::parse(format!(
+                "Invalid response format from Anthropic for model '{}'",
+                self.model
+            ))
+        })?;
+        let text: String = content
+            .iter()
+            .filter(|block| block["type"] == "text")
+            .filter_map(|block| block["text"].as_str())
+            .collect();
+        self.usage.set(self.usage.get().with_request(
+            message["usage"]["input_tokens"].as_u64().unwrap_or(0),
+            message["usage"]["output_tokens"].as_u64().unwrap_or(0),
+        ));
+
+        if message["stop_reason"] == "max_tokens" {
+            warnings::warn(format!(
+                "the response reached the {} token limit and is cut off. Raise it with --max-tokens.",
+                self.max_tokens
+            ));
+        }
+
+        Ok(text)
+    }
+
+    fn usage(&self) -> TokenUsage {
+        self.usage.get()
+    }
+}
@@ -0,0 +1,159 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    config::ArchiveConfig,
+    error::{Context, ReportError, Result},
+    git::Commit,
+};
+
+const DEFAULT_ARCHIVE_DIR: &str = ".git-report/archive";
+
+/// Everything collected for one generated report, stored next to a copy of the
+/// report so later runs (e.g. `rollup`) can work without re-reading history.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Dataset {
+    pub generated_at: DateTime<Utc>,
+    pub repository: String,
+    pub from: String,
+    pub to: String,
+    /// Whether FROM itself was part of the range (`--inclusive`).
+    #[serde(default)]
+    pub inclusive: bool,
+    pub ai: bool,
+    pub commits: Vec<Commit>,
+}
+
+/// Resolves the archive directory; relative paths are relative to the repository root.
+pub fn archive_dir(repo_path: &str, config: &ArchiveConfig) -> PathBuf {
+    let dir = Path::new(config.dir.as_deref().unwrap_or(DEFAULT_ARCHIVE_DIR));
+    if dir.is_absolute() {
+        dir.to_path_buf()
+    } else {
+        Path::new(repo_path).join(dir)
+    }
+}
+
+/// Writes `<timestamp>-<from>-<to>.json` with the dataset and a copy of the
+/// report with the same stem, returning the dataset path.
+pub fn save(dir: &Path, dataset: &Dataset, report_content: &str) -> Result<PathBuf> {
+    fs::create_dir_all(dir).with_context(ReportError::io, || {
+        format!("Failed to create archive directory {}", dir.display())
+    })?;
+
+    let stem = format!(
+        "{}-{}-{}",
+        dataset.generated_at.format("%Y%m%d_%H%M%S"),
+        short_hash(&dataset.from),
+        short_hash(&dataset.to)
+    );
+    let suffix = if dataset.ai { "-ai" } else { "" };
+
+    let dataset_path = dir.join(format!("{}{}.json", stem, suffix));
+    let json = serde_json::to_string_pretty(dataset)
+        .context(ReportError::parse, "Failed to serialize dataset")?;
+    fs::write(&dataset_path, json).with_context(ReportError::io, || {
+        format!("Failed to write dataset {}", dataset_path.display())
+    })?;
+
+    let report_path = dir.join(format!("{}{}.txt", stem, suffix));
+    fs::write(&report_path, report_content).with_context(ReportError::io, || {
+        format!("Failed to write archived report {}", report_path.display())
+    })?;
+
+    Ok(dataset_path)
+}
+
+pub fn load_dataset(path: &Path) -> Result<Dataset> {
+    let content = fs::read_to_string(path).with_context(ReportError::io, || {
+        format!("Failed to read dataset {}", path.display())
+    })?;
+    serde_json::from_str(&content).with_context(ReportError::parse, || {
+        format!("Failed to parse dataset {}", path.display())
+    })
+}
+
+/// Loads every dataset in the archive directory, oldest first.
+pub fn load_datasets(dir: &Path) -> Result<Vec<Dataset>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut datasets = Vec::new();
+    for entry in fs::read_dir(dir).with_context(ReportError::io, || {
+        format!("Failed to read archive directory {}", dir.display())
+    })? {
+        let path = entry
+            .with_context(ReportError::io, || {
+                format!("Failed to read archive directory {}", dir.display())
+            })?
+            .path();
+        if path.extension().is_some_and(|ext| ext == "json") {
+            datasets.push(load_dataset(&path)?);
+        }
+    }
+
+    datasets.sort_by_key(|d| d.generated_at);
+
+    Ok(datasets)
+}
+
+/// Applies the retention settings, removing datasets (and their report copies)
+/// beyond `keep_last` or older than `keep_days`. Returns how many were removed.
+pub fn prune(dir: &Path, config: &ArchiveConfig) -> Result<usize> {
+    if (config.keep_last.is_none() && config.keep_days.is_none()) || !dir.exists() {
+        return Ok(0);
+    }
+
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(dir).with_context(ReportError::io, || {
+        format!("Failed to read archive directory {}", dir.display())
+    })? {
+        let path = entry
+            .with_context(ReportError::io, || {
+                format!("Failed to read archive directory {}", dir.display())
+            })?
+            .path();
+        if path.extension().is_some_and(|ext| ext == "json") {
+            let generated_at = load_dataset(&path)?.generated_at;
+            entries.push((generated_at, path));
+        }
+    }
+
+    // Newest first, so the position is the number of newer reports.
+    entries.sort_by_key(|(generated_at, _)| std::cmp::Reverse(*generated_at));
+
+    let cutoff = config
+        .keep_days
+        .map(|days| Utc::now() - chrono::Duration::days(days));
+    let mut removed = 0;
+
+    for (i, (generated_at, path)) in entries.iter().enumerate() {
+        let over_count = config.keep_last.is_some_and(|keep| i >= keep);
+        let too_old = cutoff.is_some_and(|cutoff| *generated_at < cutoff);
+        if !over_count && !too_old {
+            continue;
+        }
+
+        fs::remove_file(path).with_context(ReportError::io, || {
+            format!("Failed to remove dataset {}", path.display())
+        })?;
+        let report_path = path.with_extension("txt");
+        if report_path.exists() {
+            fs::remove_file(&report_path).with_context(ReportError::io, || {
+                format!("Failed to remove archived report {}", report_path.display())
+            })?;
+        }
+        removed += 1;
+    }
+
+    Ok(removed)
+}
+
+fn short_hash(hash: &str) -> &str {
+    &hash[..hash.len().min(8)]
+}
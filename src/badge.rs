@@ -0,0 +1,66 @@
+use std::collections::HashSet;
+
+use crate::{git::Commit, render::html::escape};
+
+/// Approximate width of an 11px Verdana character, used to size the badge
+/// without font metrics.
+const CHAR_WIDTH: usize = 7;
+const PADDING: usize = 10;
+
+/// A flat two-part badge for the range, e.g. `7 days | 42 commits / 7 authors`.
+pub fn range_badge(commits: &[Commit]) -> String {
+    let authors: HashSet<&str> = commits.iter().map(|c| c.author.as_str()).collect();
+    let message = format!(
+        "{} / {}",
+        plural(commits.len(), "commit"),
+        plural(authors.len(), "author")
+    );
+
+    let span = match (
+        commits.iter().map(|c| c.date).min(),
+        commits.iter().map(|c| c.date).max(),
+    ) {
+        (Some(first), Some(last)) => plural(((last - first).num_days() + 1) as usize, "day"),
+        _ => "no commits".to_string(),
+    };
+
+    render(&span, &message, "#007ec6")
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("{} {}", count, noun)
+    } else {
+        format!("{} {}s", count, noun)
+    }
+}
+
+/// Renders a shields.io-style badge with a grey label and a colored message.
+pub fn render(label: &str, message: &str, color: &str) -> String {
+    let label_width = label.chars().count() * CHAR_WIDTH + PADDING;
+    let message_width = message.chars().count() * CHAR_WIDTH + PADDING;
+    let width = label_width + message_width;
+    let (label, message) = (escape(label), escape(message));
+
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="20" role="img" aria-label="{label}: {message}">
+<title>{label}: {message}</title>
+<linearGradient id="s" x2="0" y2="100%"><stop offset="0" stop-color="#bbb" stop-opacity=".1"/><stop offset="1" stop-opacity=".1"/></linearGradient>
+<clipPath id="r"><rect width="{width}" height="20" rx="3" fill="#fff"/></clipPath>
+<g clip-path="url(#r)">
+<rect width="{label_width}" height="20" fill="#555"/>
+<rect x="{label_width}" width="{message_width}" height="20" fill="{color}"/>
+<rect width="{width}" height="20" fill="url(#s)"/>
+</g>
+<g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11">
+<text x="{label_x}" y="15" fill="#010101" fill-opacity=".3">{label}</text>
+<text x="{label_x}" y="14">{label}</text>
+<text x="{message_x}" y="15" fill="#010101" fill-opacity=".3">{message}</text>
+<text x="{message_x}" y="14">{message}</text>
+</g>
+</svg>
+"##,
+        label_x = label_width / 2,
+        message_x = label_width + message_width / 2,
+    )
+}
@@ -0,0 +1,43 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    error::{Context, ReportError, Result},
+    git,
+};
+
+/// A temporary repository cloned from a bundle file, removed when dropped.
+pub struct BundleClone {
+    dir: PathBuf,
+}
+
+impl BundleClone {
+    pub fn new(bundle: &Path) -> Result<Self> {
+        // git runs inside the new repository, so relative paths would not resolve.
+        let bundle = bundle.canonicalize().with_context(ReportError::io, || {
+            format!("Failed to open bundle {}", bundle.display())
+        })?;
+        let dir = std::env::temp_dir().join(format!("git-report-bundle-{}", std::process::id()));
+        if dir.exists() {
+            fs::remove_dir_all(&dir).with_context(ReportError::io, || {
+                format!("Failed to remove stale bundle clone {}", dir.display())
+            })?;
+        }
+
+        let clone = Self { dir };
+        git::clone_bundle(&bundle, &clone.dir)?;
+        Ok(clone)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.dir
+    }
+}
+
+impl Drop for BundleClone {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
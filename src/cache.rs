@@ -0,0 +1,97 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    error::{Context, ReportError, Result},
+    sign,
+};
+
+const DEFAULT_CACHE_FILE: &str = ".git-report/cache/summaries.json";
+
+/// Summaries unused for this long are dropped when the cache is saved.
+const KEEP_DAYS: i64 = 90;
+
+/// AI summaries of single commits from earlier runs, so regenerating a range
+/// that overlaps a previous one only sends the new commits. Entries are keyed
+/// by a digest of the backend, model and prompt: a summary is reused only
+/// for the same commit data sent to the same model.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SummaryCache {
+    #[serde(default)]
+    summaries: BTreeMap<String, CachedSummary>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedSummary {
+    commit: String,
+    summary: String,
+    used_at: DateTime<Utc>,
+}
+
+/// Resolves the cache file: `GIT_REPORT_CACHE` when set, otherwise
+/// `.git-report/cache/summaries.json` in the repository root.
+pub fn cache_path(repo_path: &str) -> PathBuf {
+    env::var("GIT_REPORT_CACHE")
+        .map(Into::into)
+        .unwrap_or_else(|_| Path::new(repo_path).join(DEFAULT_CACHE_FILE))
+}
+
+impl SummaryCache {
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path).with_context(ReportError::io, || {
+            format!("Failed to read summary cache {}", path.display())
+        })?;
+        serde_json::from_str(&content).with_context(ReportError::parse, || {
+            format!("Failed to parse summary cache {}", path.display())
+        })
+    }
+
+    pub fn key(backend: &str, model: &str, prompt: &str) -> String {
+        sign::sha256_hex(format!("{}\0{}\0{}", backend, model, prompt).as_bytes())
+    }
+
+    /// The cached summary for `key`, marking it as used.
+    pub fn get(&mut self, key: &str) -> Option<String> {
+        let entry = self.summaries.get_mut(key)?;
+        entry.used_at = Utc::now();
+        Some(entry.summary.clone())
+    }
+
+    pub fn insert(&mut self, key: String, commit: &str, summary: &str) {
+        self.summaries.insert(
+            key,
+            CachedSummary {
+                commit: commit.to_string(),
+                summary: summary.to_string(),
+                used_at: Utc::now(),
+            },
+        );
+    }
+
+    /// Writes the cache, without the summaries no run has used for
+    /// [`KEEP_DAYS`] days.
+    pub fn save(&mut self, path: &Path) -> Result<()> {
+        let cutoff = Utc::now() - Duration::days(KEEP_DAYS);
+        self.summaries.retain(|_, entry| entry.used_at >= cutoff);
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).with_context(ReportError::io, || {
+                format!("Failed to create cache directory {}", dir.display())
+            })?;
+        }
+        let json = serde_json::to_string_pretty(self)
+            .context(ReportError::parse, "Failed to serialize the summary cache")?;
+        fs::write(path, json).with_context(ReportError::io, || {
+            format!("Failed to write summary cache {}", path.display())
+        })
+    }
+}
@@ -0,0 +1,96 @@
+use std::{fs, path::Path};
+
+use crate::{
+    classify::{self, Category},
+    error::{Context, ReportError, Result},
+    git::Commit,
+    version::{self, parse_conventional},
+};
+
+fn heading(category: Category) -> &'static str {
+    match category {
+        Category::Feature => "Features",
+        Category::Fix => "Bug Fixes",
+        Category::Refactor => "Refactoring",
+        Category::Docs => "Documentation",
+        Category::Test => "Tests",
+        Category::Build => "Build and CI",
+        Category::Other => "Other Changes",
+    }
+}
+
+/// `feat(ui): add a button` -> `**ui:** add a button`; subjects that are not
+/// conventional commits are kept as they are.
+fn entry(commit: &Commit) -> String {
+    let text = match parse_conventional(&commit.subject) {
+        Some(header) => match header.scope {
+            Some(scope) => format!("**{}:** {}", scope, header.description),
+            None => header.description.to_string(),
+        },
+        None => commit.subject.clone(),
+    };
+    format!("- {} ({})", text, &commit.hash[..8])
+}
+
+/// One Markdown changelog entry: `## <title> - <date>`, breaking changes
+/// first, then a list per change type in category order.
+pub fn render(commits: &[Commit], title: &str, date: &str) -> String {
+    let mut changelog = format!("## {} - {}\n", title, date);
+    if commits.is_empty() {
+        changelog.push_str("\nNo changes.\n");
+        return changelog;
+    }
+
+    let breaking: Vec<&Commit> = commits.iter().filter(|c| version::is_breaking(c)).collect();
+    if !breaking.is_empty() {
+        changelog.push_str("\n### Breaking Changes\n\n");
+        for commit in &breaking {
+            changelog.push_str(&entry(commit));
+            changelog.push('\n');
+        }
+    }
+
+    for category in Category::ALL {
+        let entries: Vec<String> = commits
+            .iter()
+            .filter(|c| classify::classify(c) == category && !version::is_breaking(c))
+            .map(entry)
+            .collect();
+        if entries.is_empty() {
+            continue;
+        }
+        changelog.push_str(&format!("\n### {}\n\n", heading(category)));
+        for entry in entries {
+            changelog.push_str(&entry);
+            changelog.push('\n');
+        }
+    }
+
+    changelog
+}
+
+/// Writes the entry to `path`. An existing changelog keeps its title and
+/// introduction: the entry goes above its first `## ` heading, or at the end
+/// when it has none.
+pub fn write(path: &Path, entry: &str) -> Result<()> {
+    let content = if path.exists() {
+        let existing = fs::read_to_string(path).with_context(ReportError::io, || {
+            format!("Failed to read changelog {}", path.display())
+        })?;
+        let position = existing
+            .match_indices("## ")
+            .map(|(i, _)| i)
+            .find(|&i| i == 0 || existing[..i].ends_with('\n'));
+        match position {
+            Some(i) => format!("{}{}\n{}", &existing[..i], entry, &existing[i..]),
+            None if existing.trim().is_empty() => entry.to_string(),
+            None => format!("{}\n\n{}", existing.trim_end(), entry),
+        }
+    } else {
+        entry.to_string()
+    };
+
+    fs::write(path, content).with_context(ReportError::io, || {
+        format!("Failed to write changelog {}", path.display())
+    })
+}
@@ -0,0 +1,145 @@
+use crate::{
+    error::Result,
+    exclude,
+    git::{Commit, FileStatus, SignatureStatus},
+    i18n::Locale,
+    report::{Block, Section},
+};
+
+/// Paths `--assert-no-sensitive-paths` looks for when `[check]
+/// sensitive_paths` is not set: environment files, private keys, key stores,
+/// credential files and Terraform state.
+pub const SENSITIVE_PATHS: &[&str] = &[
+    ".env",
+    ".env.local",
+    ".env.*.local",
+    ".env.production",
+    "*.pem",
+    "*.key",
+    "*.p12",
+    "*.pfx",
+    "*.jks",
+    "*.keystore",
+    "id_rsa",
+    "id_dsa",
+    "id_ecdsa",
+    "id_ed25519",
+    ".netrc",
+    ".npmrc",
+    ".pypirc",
+    "credentials",
+    "credentials.json",
+    "*.tfstate",
+    "*.tfstate.backup",
+];
+
+/// The assertions of `git-report check`; `None` and `false` skip one.
+#[derive(Debug, Default)]
+pub struct Assertions {
+    pub max_commits: Option<usize>,
+    pub all_signed: bool,
+    /// Patterns of paths no commit may add or change.
+    pub sensitive_paths: Option<Vec<String>>,
+}
+
+/// A violated assertion with the commits or files that violate it.
+pub struct Finding {
+    /// The option of the assertion, e.g. `--assert-all-signed`.
+    pub assertion: &'static str,
+    pub message: String,
+    pub items: Vec<String>,
+}
+
+/// Checks the commits of the range against the assertions. Signatures must
+/// already be checked with [`crate::git::signatures`] for `all_signed`; a
+/// commit passes when its signature is good, also by a key of unknown trust.
+pub fn check(assertions: &Assertions, commits: &[Commit]) -> Result<Vec<Finding>> {
+    let mut findings = Vec::new();
+    let line = |commit: &Commit| format!("{} {}", &commit.hash[..8], commit.subject);
+
+    if let Some(max) = assertions.max_commits {
+        if commits.len() > max {
+            findings.push(Finding {
+                assertion: "--assert-max-commits",
+                message: format!(
+                    "{} commits in the range, at most {} allowed",
+                    commits.len(),
+                    max
+                ),
+                items: Vec::new(),
+            });
+        }
+    }
+
+    if assertions.all_signed {
+        let unsigned: Vec<String> = commits
+            .iter()
+            .filter(|commit| {
+                !matches!(
+                    commit.signature.as_ref().map(|signature| signature.status),
+                    Some(SignatureStatus::Good | SignatureStatus::UnknownValidity)
+                )
+            })
+            .map(|commit| match &commit.signature {
+                Some(signature) => format!("{} ({})", line(commit), signature.status.label()),
+                None => format!("{} (not checked)", line(commit)),
+            })
+            .collect();
+        if !unsigned.is_empty() {
+            findings.push(Finding {
+                assertion: "--assert-all-signed",
+                message: format!(
+                    "{} of {} commits lack a good signature",
+                    unsigned.len(),
+                    commits.len()
+                ),
+                items: unsigned,
+            });
+        }
+    }
+
+    if let Some(patterns) = &assertions.sensitive_paths {
+        let sensitive = exclude::glob_set(patterns, "sensitive path")?;
+        // Deleting a file removes it from the tree, which is what a leak
+        // calls for, so only additions and changes count.
+        let touched: Vec<String> = commits
+            .iter()
+            .flat_map(|commit| {
+                commit
+                    .file_stats
+                    .iter()
+                    .filter(|stat| stat.status != FileStatus::Deleted)
+                    .filter(|stat| sensitive.is_match(&stat.path))
+                    .map(move |stat| format!("{} in {}", stat.path, line(commit)))
+            })
+            .collect();
+        if !touched.is_empty() {
+            findings.push(Finding {
+                assertion: "--assert-no-sensitive-paths",
+                message: format!("{} sensitive file change(s)", touched.len()),
+                items: touched,
+            });
+        }
+    }
+
+    Ok(findings)
+}
+
+/// The findings as a report section: one line per violated assertion, then
+/// the commits or files behind each.
+pub fn findings_section(findings: &[Finding], locale: &Locale) -> Section {
+    let mut section =
+        Section::new("check", locale.text("check-findings")).with_block(Block::Fields(
+            findings
+                .iter()
+                .map(|finding| (finding.assertion.to_string(), finding.message.clone()))
+                .collect(),
+        ));
+    for finding in findings.iter().filter(|finding| !finding.items.is_empty()) {
+        section = section.with_block(Block::List {
+            title: finding.assertion.to_string(),
+            items: finding.items.clone(),
+        });
+    }
+    section
+}
@@ -0,0 +1,379 @@
+use clap::ValueEnum;
+use serde_json::Value;
+use std::{env, fs, io::Write};
+
+use crate::{
+    error::{Context, ReportError, Result},
+    git,
+    render::markdown,
+    report::ReportData,
+};
+
+/// The CI service a `--ci` run takes its range from and reports back to.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CiProvider {
+    /// GitHub Actions: range from the event payload, job summary and annotations
+    GithubActions,
+    /// GitLab CI: range from the pipeline variables, outputs in a dotenv report
+    Gitlab,
+    /// Any other CI: range from common commit variables, or since the previous tag
+    Generic,
+}
+
+impl CiProvider {
+    /// The CI service the current process runs in, from the variables each
+    /// one sets in every job; generic for any other service that sets `CI`.
+    pub fn detect() -> Option<Self> {
+        let set = |name: &str| {
+            env::var(name).is_ok_and(|value| !matches!(value.as_str(), "" | "0" | "false"))
+        };
+        if set("GITHUB_ACTIONS") {
+            Some(CiProvider::GithubActions)
+        } else if set("GITLAB_CI") {
+            Some(CiProvider::Gitlab)
+        } else if set("CI") {
+            Some(CiProvider::Generic)
+        } else {
+            None
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            CiProvider::GithubActions => "GitHub Actions",
+            CiProvider::Gitlab => "GitLab CI",
+            CiProvider::Generic => "CI",
+        }
+    }
+}
+
+/// Commit variables of other CI services, first set wins: the commit being
+/// built, and the one built last (Jenkins' last successful build, Drone's
+/// and Woodpecker's previous tip).
+const GENERIC_TO: &[&str] = &[
+    "GIT_COMMIT",
+    "BUILDKITE_COMMIT",
+    "CIRCLE_SHA1",
+    "DRONE_COMMIT_SHA",
+    "CI_COMMIT_SHA",
+    "TRAVIS_COMMIT",
+];
+const GENERIC_FROM: &[&str] = &[
+    "GIT_PREVIOUS_SUCCESSFUL_COMMIT",
+    "DRONE_COMMIT_BEFORE",
+    "CI_PREV_COMMIT_SHA",
+];
+
+/// Where `--ci gitlab` writes its outputs, for `artifacts:reports:dotenv`.
+pub const GITLAB_DOTENV: &str = "git-report.env";
+
+/// The range a CI run reports on, with a description of where it came from.
+pub struct CiRange {
+    pub from: String,
+    pub to: String,
+    pub origin: String,
+}
+
+/// What a SHA is set to when there is no commit, e.g. `before` of a new
+/// branch.
+fn is_null_sha(sha: &str) -> bool {
+    sha.is_empty() || sha.bytes().all(|b| b == b'0')
+}
+
+fn env_var(name: &str, provider: CiProvider) -> Result<String> {
+    optional_var(name).ok_or_else(|| {
+        ReportError::parse(format!(
+            "{} is not set; this --ci mode only works inside a {} job",
+            name,
+            provider.name()
+        ))
+    })
+}
+
+fn optional_var(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|value| !value.is_empty())
+}
+
+/// The tag before `rev`, for runs whose event has no previous commit.
+fn previous_tag(rev: &str, origin: &str) -> Result<String> {
+    git::latest_tag(&format!("{}^", rev))?.ok_or_else(|| {
+        ReportError::git(format!(
+            "{} has no previous commit and the history has no earlier tag to start from; pass --from",
+            origin
+        ))
+    })
+}
+
+/// The name and payload of the event that started the GitHub Actions run.
+fn github_event() -> Result<(String, Value)> {
+    let provider = CiProvider::GithubActions;
+    let event_name = env_var("GITHUB_EVENT_NAME", provider)?;
+    let event_path = env_var("GITHUB_EVENT_PATH", provider)?;
+    let payload = fs::read_to_string(&event_path).with_context(ReportError::io, || {
+        format!("Failed to read the event payload {}", event_path)
+    })?;
+    let event = serde_json::from_str(&payload).with_context(ReportError::parse, || {
+        format!("Failed to parse the event payload {}", event_path)
+    })?;
+    Ok((event_name, event))
+}
+
+/// Reads the range from the GitHub Actions event: a pull request covers its
+/// commits since it branched off the base, a push the commits between the
+/// old and the new tip, and a release, a new tag or branch, or a manual run
+/// everything since the previous tag.
+fn github_range() -> Result<CiRange> {
+    let provider = CiProvider::GithubActions;
+    let (event_name, event) = github_event()?;
+    let text = |value: &Value| value.as_str().unwrap_or_default().to_string();
+
+    match event_name.as_str() {
+        "pull_request" | "pull_request_target" => {
+            let pull = &event["pull_request"];
+            let head = text(&pull["head"]["sha"]);
+            let base = text(&pull["base"]["sha"]);
+            Ok(CiRange {
+                from: git::merge_base(&base, &head)?,
+                to: head,
+                origin: format!("pull request #{}", pull["number"]),
+            })
+        }
+        "push" => {
+            let before = text(&event["before"]);
+            let to = text(&event["after"]);
+            let created = event["created"].as_bool().unwrap_or(false);
+            if !created && !is_null_sha(&before) {
+                return Ok(CiRange {
+                    from: before,
+                    to,
+                    origin: format!("push to {}", text(&event["ref"])),
+                });
+            }
+            let origin = format!("new ref {}", text(&event["ref"]));
+            Ok(CiRange {
+                from: previous_tag(&to, &origin)?,
+                to,
+                origin,
+            })
+        }
+        "release" => {
+            let tag = text(&event["release"]["tag_name"]);
+            let origin = format!("release {}", tag);
+            Ok(CiRange {
+                from: previous_tag(&tag, &origin)?,
+                to: tag,
+                origin,
+            })
+        }
+        _ => {
+            let to = env_var("GITHUB_SHA", provider)?;
+            let origin = format!("{} event", event_name);
+            Ok(CiRange {
+                from: previous_tag(&to, &origin)?,
+                to,
+                origin,
+            })
+        }
+    }
+}
+
+/// Reads the range from the GitLab pipeline: a merge request pipeline
+/// covers the merge request's commits, a branch pipeline the commits pushed
+/// since the previous pipeline, and a tag pipeline, a new branch or a
+/// scheduled run everything since the previous tag.
+fn gitlab_range() -> Result<CiRange> {
+    let provider = CiProvider::Gitlab;
+    let to = env_var("CI_COMMIT_SHA", provider)?;
+
+    if let Some(iid) = optional_var("CI_MERGE_REQUEST_IID") {
+        let base = match optional_var("CI_MERGE_REQUEST_DIFF_BASE_SHA") {
+            Some(base) => base,
+            None => git::merge_base(
+                &env_var("CI_MERGE_REQUEST_TARGET_BRANCH_SHA", provider)?,
+                &to,
+            )?,
+        };
+        return Ok(CiRange {
+            from: base,
+            to,
+            origin: format!("merge request !{}", iid),
+        });
+    }
+    if let Some(tag) = optional_var("CI_COMMIT_TAG") {
+        let origin = format!("tag {}", tag);
+        return Ok(CiRange {
+            from: previous_tag(&to, &origin)?,
+            to,
+            origin,
+        });
+    }
+
+    let branch = optional_var("CI_COMMIT_REF_NAME").unwrap_or_default();
+    match optional_var("CI_COMMIT_BEFORE_SHA").filter(|before| !is_null_sha(before)) {
+        Some(before) => Ok(CiRange {
+            from: before,
+            to,
+            origin: format!("push to {}", branch),
+        }),
+        None => {
+            let origin = format!(
+                "{} pipeline on {}",
+                optional_var("CI_PIPELINE_SOURCE").unwrap_or_else(|| "new".to_string()),
+                branch
+            );
+            Ok(CiRange {
+                from: previous_tag(&to, &origin)?,
+                to,
+                origin,
+            })
+        }
+    }
+}
+
+/// Reads the range from the commit variables other CI services set, see
+/// [`GENERIC_TO`] and [`GENERIC_FROM`]; without them, from the previous tag
+/// to `HEAD`.
+fn generic_range() -> Result<CiRange> {
+    let first = |names: &[&str]| {
+        names
+            .iter()
+            .find_map(|name| optional_var(name).filter(|sha| !is_null_sha(sha)))
+    };
+    let to = first(GENERIC_TO).unwrap_or_else(|| "HEAD".to_string());
+    match first(GENERIC_FROM) {
+        Some(from) => Ok(CiRange {
+            from,
+            to,
+            origin: "commits since the previous build".to_string(),
+        }),
+        None => {
+            let origin = "CI build".to_string();
+            Ok(CiRange {
+                from: previous_tag(&to, &origin)?,
+                to,
+                origin,
+            })
+        }
+    }
+}
+
+/// The range the CI job reports on.
+pub fn range(provider: CiProvider) -> Result<CiRange> {
+    match provider {
+        CiProvider::GithubActions => github_range(),
+        CiProvider::Gitlab => gitlab_range(),
+        CiProvider::Generic => generic_range(),
+    }
+}
+
+/// The pull or merge request the job runs for, if it runs for one.
+pub fn request_number(provider: CiProvider) -> Result<Option<u64>> {
+    match provider {
+        CiProvider::GithubActions => {
+            let (event_name, event) = github_event()?;
+            Ok(match event_name.as_str() {
+                "pull_request" | "pull_request_target" => event["pull_request"]["number"].as_u64(),
+                _ => None,
+            })
+        }
+        CiProvider::Gitlab => {
+            Ok(optional_var("CI_MERGE_REQUEST_IID").and_then(|iid| iid.parse().ok()))
+        }
+        CiProvider::Generic => Ok(None),
+    }
+}
+
+fn append(path: &str, content: &str) -> Result<()> {
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(ReportError::io, || format!("Failed to open {}", path))?;
+    file.write_all(content.as_bytes())
+        .with_context(ReportError::io, || format!("Failed to write {}", path))
+}
+
+/// Escapes workflow command data, which ends at a newline.
+fn escape(message: &str) -> String {
+    message
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn range_label(data: &ReportData) -> String {
+    format!(
+        "{}..{}",
+        &data.from_commit.hash[..8],
+        &data.to_commit.hash[..8]
+    )
+}
+
+fn result_line(data: &ReportData) -> String {
+    if data.commits.is_empty() {
+        format!("No commits to report in {}", range_label(data))
+    } else {
+        format!(
+            "Reported {} commits in {}",
+            data.commits.len(),
+            range_label(data)
+        )
+    }
+}
+
+/// Reports back to the workflow: the Markdown report goes to the job
+/// summary, `commits` and `report` (the first output file) to the step
+/// outputs, and a notice annotation tells how many commits were reported;
+/// an empty range is a warning instead.
+fn publish_github(data: &ReportData, output_files: &[String]) -> Result<()> {
+    if let Ok(path) = env::var("GITHUB_STEP_SUMMARY") {
+        append(&path, &format!("{}\n", markdown::render(data)))?;
+    }
+    if let Ok(path) = env::var("GITHUB_OUTPUT") {
+        let mut outputs = format!("commits={}\n", data.commits.len());
+        if let Some(report) = output_files.first() {
+            outputs.push_str(&format!("report={}\n", report));
+        }
+        append(&path, &outputs)?;
+    }
+
+    let level = if data.commits.is_empty() {
+        "warning"
+    } else {
+        "notice"
+    };
+    println!(
+        "::{} title=git-report::{}",
+        level,
+        escape(&result_line(data))
+    );
+    Ok(())
+}
+
+/// GitLab has no job summary; the outputs go to [`GITLAB_DOTENV`] as
+/// `GIT_REPORT_COMMITS` and `GIT_REPORT_FILE`, which later jobs see as
+/// variables once the file is declared as a dotenv report.
+fn publish_gitlab(data: &ReportData, output_files: &[String]) -> Result<()> {
+    let mut outputs = format!("GIT_REPORT_COMMITS={}\n", data.commits.len());
+    if let Some(report) = output_files.first() {
+        outputs.push_str(&format!("GIT_REPORT_FILE={}\n", report));
+    }
+    fs::write(GITLAB_DOTENV, outputs).with_context(ReportError::io, || {
+        format!("Failed to write {}", GITLAB_DOTENV)
+    })?;
+    println!("git-report: {}", result_line(data));
+    Ok(())
+}
+
+/// Reports back to the CI job in the way its service supports; other
+/// services get the result in the job log.
+pub fn publish(provider: CiProvider, data: &ReportData, output_files: &[String]) -> Result<()> {
+    match provider {
+        CiProvider::GithubActions => publish_github(data, output_files),
+        CiProvider::Gitlab => publish_gitlab(data, output_files),
+        CiProvider::Generic => {
+            println!("git-report: {}", result_line(data));
+            Ok(())
+        }
+    }
+}
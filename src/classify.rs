@@ -0,0 +1,207 @@
+use std::fmt;
+
+use crate::{git::Commit, i18n::Locale, report::Block, version::parse_conventional};
+
+/// A coarse commit category, assigned without AI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Category {
+    Feature,
+    Fix,
+    Refactor,
+    Docs,
+    Test,
+    Build,
+    Other,
+}
+
+impl Category {
+    pub const ALL: [Category; 7] = [
+        Category::Feature,
+        Category::Fix,
+        Category::Refactor,
+        Category::Docs,
+        Category::Test,
+        Category::Build,
+        Category::Other,
+    ];
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Category::Feature => "feature",
+            Category::Fix => "fix",
+            Category::Refactor => "refactor",
+            Category::Docs => "docs",
+            Category::Test => "test",
+            Category::Build => "build",
+            Category::Other => "other",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+fn from_conventional_type(kind: &str) -> Option<Category> {
+    Some(match kind.to_lowercase().as_str() {
+        "feat" | "feature" => Category::Feature,
+        "fix" | "bugfix" | "hotfix" => Category::Fix,
+        "refactor" | "perf" | "style" => Category::Refactor,
+        "docs" | "doc" => Category::Docs,
+        "test" | "tests" => Category::Test,
+        "build" | "ci" | "deps" => Category::Build,
+        _ => return None,
+    })
+}
+
+/// Word prefixes in subjects that hint at a category, e.g. "Fixed", "Adds".
+const KEYWORDS: &[(&str, Category)] = &[
+    ("fix", Category::Fix),
+    ("bug", Category::Fix),
+    ("hotfix", Category::Fix),
+    ("resolv", Category::Fix),
+    ("repair", Category::Fix),
+    ("correct", Category::Fix),
+    ("refactor", Category::Refactor),
+    ("clean", Category::Refactor),
+    ("renam", Category::Refactor),
+    ("simplif", Category::Refactor),
+    ("restructur", Category::Refactor),
+    ("reorganiz", Category::Refactor),
+    ("extract", Category::Refactor),
+    ("doc", Category::Docs),
+    ("readme", Category::Docs),
+    ("changelog", Category::Docs),
+    ("test", Category::Test),
+    ("spec", Category::Test),
+    ("build", Category::Build),
+    ("bump", Category::Build),
+    ("depend", Category::Build),
+    ("upgrad", Category::Build),
+    ("releas", Category::Build),
+    ("add", Category::Feature),
+    ("implement", Category::Feature),
+    ("introduc", Category::Feature),
+    ("support", Category::Feature),
+    ("feat", Category::Feature),
+    ("creat", Category::Feature),
+    ("allow", Category::Feature),
+    ("enabl", Category::Feature),
+];
+
+fn from_keywords(subject: &str) -> Option<Category> {
+    let subject = subject.to_lowercase();
+    subject
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .find_map(|word| {
+            if word == "ci" || word == "deps" {
+                return Some(Category::Build);
+            }
+            KEYWORDS
+                .iter()
+                .find(|(stem, _)| word.starts_with(stem))
+                .map(|&(_, category)| category)
+        })
+}
+
+/// The category implied by a file path alone: docs, tests or build files.
+pub fn path_category(path: &str) -> Option<Category> {
+    let lower = path.to_lowercase();
+    let name = lower.rsplit('/').next().unwrap_or(&lower);
+    let in_dir = |dir: &str| {
+        lower.starts_with(&format!("{}/", dir)) || lower.contains(&format!("/{}/", dir))
+    };
+
+    if in_dir("docs")
+        || in_dir("doc")
+        || [".md", ".rst", ".adoc"]
+            .iter()
+            .any(|ext| name.ends_with(ext))
+        || name.starts_with("license")
+    {
+        Some(Category::Docs)
+    } else if in_dir("tests")
+        || in_dir("test")
+        || in_dir("spec")
+        || name.contains("_test.")
+        || name.contains(".test.")
+        || name.contains(".spec.")
+        || name.starts_with("test_")
+    {
+        Some(Category::Test)
+    } else if in_dir(".github")
+        || name.ends_with(".lock")
+        || name.ends_with("-lock.json")
+        || name.ends_with(".gitlab-ci.yml")
+        || [
+            "cargo.toml",
+            "build.rs",
+            "package.json",
+            "makefile",
+            "dockerfile",
+            "pom.xml",
+            "build.gradle",
+            "go.mod",
+            "go.sum",
+        ]
+        .contains(&name)
+    {
+        Some(Category::Build)
+    } else {
+        None
+    }
+}
+
+/// The category shared by all changed files, if they are all docs, tests or build files.
+fn from_paths(files: &[String]) -> Option<Category> {
+    let first = path_category(files.first()?)?;
+    files
+        .iter()
+        .all(|file| path_category(file) == Some(first))
+        .then_some(first)
+}
+
+/// Classifies a commit by its conventional type, then by the kind of files it
+/// touches, then by keywords in the subject.
+pub fn classify(commit: &Commit) -> Category {
+    parse_conventional(&commit.subject)
+        .and_then(|header| from_conventional_type(header.kind))
+        .or_else(|| from_paths(&commit.files_changed))
+        .or_else(|| from_keywords(&commit.subject))
+        .unwrap_or(Category::Other)
+}
+
+/// Counts commits per category, in category order, skipping empty ones.
+pub fn counts(commits: &[Commit]) -> Vec<(Category, usize)> {
+    let mut counts = [0usize; Category::ALL.len()];
+    for commit in commits {
+        counts[classify(commit) as usize] += 1;
+    }
+
+    Category::ALL
+        .into_iter()
+        .zip(counts)
+        .filter(|(_, count)| *count > 0)
+        .collect()
+}
+
+/// The category counts as a `Type | Commits | Share` table.
+pub fn breakdown(commits: &[Commit], locale: &Locale) -> Block {
+    let rows = counts(commits)
+        .into_iter()
+        .map(|(category, count)| {
+            vec![
+                category.to_string(),
+                count.to_string(),
+                format!("{}%", count * 100 / commits.len().max(1)),
+            ]
+        })
+        .collect();
+
+    Block::Table {
+        headers: ["type", "commits", "share"]
+            .map(|key| locale.text(key).to_string())
+            .to_vec(),
+        rows,
+    }
+}
@@ -0,0 +1,336 @@
+use std::collections::{BTreeSet, HashMap};
+
+use crate::{
+    ai::Embedder,
+    classify::{self, Category},
+    error::{ReportError, Result},
+    git::Commit,
+    i18n::Locale,
+    progress::Progress,
+    report::{Block, Section},
+    version::parse_conventional,
+};
+
+/// Commit messages sent to the embedder per request.
+const BATCH_SIZE: usize = 64;
+
+/// Most themes picked when `--clusters` is not given.
+const MAX_THEMES: usize = 12;
+
+/// Rounds of k-means before giving up on the assignment settling.
+const MAX_ROUNDS: usize = 50;
+
+/// Words too common in commit messages to name a theme.
+const STOP_WORDS: &[&str] = &[
+    "the", "and", "for", "with", "from", "into", "when", "that", "this", "not", "use", "add",
+    "adds", "added", "fix", "fixes", "fixed", "update", "updates", "updated", "remove", "removed",
+    "make", "makes", "more", "less", "new", "all", "its", "are", "was", "can", "now", "also",
+    "only", "after", "before", "via", "out", "our", "don", "doesn", "change", "changes", "bump",
+    "merge", "branch",
+];
+
+/// A group of commits about the same thing.
+pub struct Theme {
+    pub name: String,
+    /// The theme's commits, in range order.
+    pub commits: Vec<Commit>,
+}
+
+/// `--cluster`: embeds the commit messages, groups them with k-means into
+/// `count` themes (about the square root of half the commits when `None`)
+/// and names every theme after its most distinctive word and most common
+/// commit type, e.g. "Auth fixes". The biggest theme comes first.
+pub fn themes(
+    commits: &[Commit],
+    embedder: &dyn Embedder,
+    count: Option<usize>,
+) -> Result<Vec<Theme>> {
+    if commits.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let texts: Vec<String> = commits
+        .iter()
+        .map(|commit| format!("{}\n\n{}", commit.subject, commit.body.trim()))
+        .collect();
+    let progress = Progress::bar(texts.len(), "Embedding commit messages");
+    let mut vectors = Vec::with_capacity(texts.len());
+    for batch in texts.chunks(BATCH_SIZE) {
+        let embedded = embedder.embed(batch)?;
+        if embedded.len() != batch.len() {
+            return Err(ReportError::ai(format!(
+                "{} returned {} embeddings for {} commit messages",
+                embedder.name(),
+                embedded.len(),
+                batch.len()
+            )));
+        }
+        for _ in 0..batch.len() {
+            progress.inc();
+        }
+        vectors.extend(embedded);
+    }
+    progress.finish();
+
+    let count = count.unwrap_or_else(|| default_count(commits.len()));
+    let assignment = k_means(
+        &vectors.iter().map(|v| normalized(v)).collect::<Vec<_>>(),
+        count,
+    );
+    let mut groups: Vec<Vec<&Commit>> = vec![Vec::new(); count.min(commits.len())];
+    for (commit, &cluster) in commits.iter().zip(&assignment) {
+        groups[cluster].push(commit);
+    }
+    groups.retain(|group| !group.is_empty());
+    groups.sort_by_key(|group| std::cmp::Reverse(group.len()));
+
+    let document_counts = word_counts(commits.iter());
+    let mut names = BTreeSet::new();
+    Ok(groups
+        .into_iter()
+        .map(|group| {
+            let name = theme_name(&group, &document_counts, commits.len(), &names);
+            names.insert(name.clone());
+            Theme {
+                name,
+                commits: group.into_iter().cloned().collect(),
+            }
+        })
+        .collect())
+}
+
+fn default_count(commits: usize) -> usize {
+    ((commits as f64 / 2.0).sqrt().round() as usize).clamp(1, MAX_THEMES)
+}
+
+fn normalized(vector: &[f32]) -> Vec<f32> {
+    let length = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if length == 0.0 {
+        return vector.to_vec();
+    }
+    vector.iter().map(|x| x / length).collect()
+}
+
+fn similarity(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+/// Assigns every unit vector to one of `count` clusters by cosine
+/// similarity. The first centre is the vector nearest the mean and each next
+/// one the vector farthest from the centres so far, so the result is the
+/// same on every run.
+fn k_means(vectors: &[Vec<f32>], count: usize) -> Vec<usize> {
+    let count = count.clamp(1, vectors.len());
+    let mean = normalized(&centroid(vectors.iter()));
+    let nearest = |centres: &[Vec<f32>], vector: &[f32]| {
+        centres
+            .iter()
+            .map(|centre| similarity(centre, vector))
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map_or(0, |(index, _)| index)
+    };
+
+    let first = nearest(vectors, &mean);
+    let mut centres = vec![vectors[first].clone()];
+    while centres.len() < count {
+        let farthest = vectors
+            .iter()
+            .enumerate()
+            .map(|(index, vector)| {
+                let closest = centres
+                    .iter()
+                    .map(|centre| similarity(centre, vector))
+                    .fold(f32::MIN, f32::max);
+                (index, closest)
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map_or(0, |(index, _)| index);
+        centres.push(vectors[farthest].clone());
+    }
+
+    let mut assignment: Vec<usize> = vectors.iter().map(|v| nearest(&centres, v)).collect();
+    for _ in 0..MAX_ROUNDS {
+        for (cluster, centre) in centres.iter_mut().enumerate() {
+            let members = vectors
+                .iter()
+                .zip(&assignment)
+                .filter(|(_, &assigned)| assigned == cluster)
+                .map(|(vector, _)| vector);
+            let mean = centroid(members);
+            if mean.iter().any(|x| *x != 0.0) {
+                *centre = normalized(&mean);
+            }
+        }
+        let next: Vec<usize> = vectors.iter().map(|v| nearest(&centres, v)).collect();
+        if next == assignment {
+            break;
+        }
+        assignment = next;
+    }
+    assignment
+}
+
+fn centroid<'a>(vectors: impl Iterator<Item = &'a Vec<f32>>) -> Vec<f32> {
+    let mut sum: Vec<f32> = Vec::new();
+    for vector in vectors {
+        if sum.is_empty() {
+            sum = vec![0.0; vector.len()];
+        }
+        for (total, x) in sum.iter_mut().zip(vector) {
+            *total += x;
+        }
+    }
+    sum
+}
+
+/// The words of a commit that can name a theme: the conventional scope and
+/// the words of the subject, in their first spelling. Short words only count
+/// as scopes or acronyms such as "CI".
+fn words(commit: &Commit) -> Vec<String> {
+    let (scope, description) = match parse_conventional(&commit.subject) {
+        Some(header) => (header.scope, header.description),
+        None => (None, commit.subject.as_str()),
+    };
+    let words = description
+        .split(|c: char| !c.is_alphanumeric() && c != '-')
+        .map(|word| word.trim_matches('-'))
+        .filter(|word| {
+            word.chars().count() >= 3
+                || (word.len() == 2 && word.chars().all(|c| c.is_ascii_uppercase()))
+        });
+    scope
+        .into_iter()
+        .chain(words)
+        .filter(|word| {
+            !word.is_empty()
+                && !word.chars().all(|c| c.is_ascii_digit())
+                && !STOP_WORDS.contains(&word.to_lowercase().as_str())
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+/// In how many commits every word appears, by lowercase word.
+fn word_counts<'a>(commits: impl Iterator<Item = &'a Commit>) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for commit in commits {
+        let unique: BTreeSet<String> = words(commit).iter().map(|w| w.to_lowercase()).collect();
+        for word in unique {
+            *counts.entry(word).or_default() += 1;
+        }
+    }
+    counts
+}
+
+/// Names a theme after the words frequent in it but rare in the range, and
+/// its most common commit type. A name already taken gets the next word.
+fn theme_name(
+    group: &[&Commit],
+    document_counts: &HashMap<String, usize>,
+    total: usize,
+    taken: &BTreeSet<String>,
+) -> String {
+    let mut spellings: HashMap<String, String> = HashMap::new();
+    for commit in group {
+        for word in words(commit) {
+            spellings.entry(word.to_lowercase()).or_insert(word);
+        }
+    }
+    let mut scored: Vec<(String, f64)> = word_counts(group.iter().copied())
+        .into_iter()
+        .map(|(word, count)| {
+            let rarity = (total as f64 / document_counts[&word] as f64).ln() + 1.0;
+            (word, count as f64 * rarity)
+        })
+        .collect();
+    scored.sort_by(|(a_word, a), (b_word, b)| b.total_cmp(a).then(a_word.cmp(b_word)));
+    let keywords: Vec<String> = scored
+        .into_iter()
+        .map(|(word, _)| capitalize(&spellings[&word]))
+        .collect();
+
+    let mut types: HashMap<Category, usize> = HashMap::new();
+    for commit in group {
+        *types.entry(classify::classify(commit)).or_default() += 1;
+    }
+    // Ties go to the type listed first.
+    let category = Category::ALL
+        .into_iter()
+        .rev()
+        .max_by_key(|category| types.get(category).copied().unwrap_or(0))
+        .unwrap_or(Category::Other);
+    let noun = match category {
+        Category::Feature => "features",
+        Category::Fix => "fixes",
+        Category::Refactor => "refactoring",
+        Category::Docs => "documentation",
+        Category::Test => "tests",
+        Category::Build => "build changes",
+        Category::Other => "changes",
+    };
+
+    let mut candidates: Vec<String> = keywords
+        .iter()
+        .map(|keyword| format!("{} {}", keyword, noun))
+        .collect();
+    if let [first, second, ..] = &keywords[..] {
+        candidates.push(format!("{}, {} {}", first, second.to_lowercase(), noun));
+    }
+    candidates
+        .into_iter()
+        .find(|name| !taken.contains(name))
+        .unwrap_or_else(|| {
+            let base = capitalize(noun);
+            (1..)
+                .map(|n| {
+                    if n == 1 {
+                        base.clone()
+                    } else {
+                        format!("{} {}", base, n)
+                    }
+                })
+                .find(|name| !taken.contains(name))
+                .unwrap_or(base)
+        })
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// An overview of the themes, then the commits of each.
+pub fn themes_section(themes: &[Theme], locale: &Locale) -> Section {
+    let mut section = Section::new("themes", locale.text("themes")).with_block(Block::Table {
+        headers: ["theme", "commits", "authors"]
+            .map(|key| locale.text(key).to_string())
+            .to_vec(),
+        rows: themes
+            .iter()
+            .map(|theme| {
+                let authors: BTreeSet<&str> =
+                    theme.commits.iter().map(|c| c.author.as_str()).collect();
+                vec![
+                    theme.name.clone(),
+                    theme.commits.len().to_string(),
+                    authors.len().to_string(),
+                ]
+            })
+            .collect(),
+    });
+    for theme in themes {
+        section = section.with_block(Block::List {
+            title: theme.name.clone(),
+            items: theme
+                .commits
+                .iter()
+                .map(|commit| format!("{} {}", &commit.hash[..8], commit.subject))
+                .collect(),
+        });
+    }
+    section
+}
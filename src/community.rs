@@ -0,0 +1,188 @@
+use std::collections::HashSet;
+
+use crate::{
+    config::{Acknowledgment, AcknowledgmentsConfig, CommunityConfig},
+    git::Commit,
+    i18n::Locale,
+    report::{Block, Section},
+};
+
+/// Domain of the private commit emails GitHub hands out,
+/// `12345+handle@users.noreply.github.com`.
+const GITHUB_NOREPLY: &str = "@users.noreply.github.com";
+
+/// Tells maintainers from community contributors and finds the handle to
+/// thank each contributor by.
+pub struct Community<'a> {
+    config: &'a CommunityConfig,
+}
+
+impl<'a> Community<'a> {
+    pub fn new(config: &'a CommunityConfig) -> Self {
+        Self { config }
+    }
+
+    /// Whether the author is listed in `[community] maintainers`, by name,
+    /// email or handle (a leading `@` is ignored), ignoring case.
+    pub fn is_maintainer(&self, commit: &Commit) -> bool {
+        let handle = self.handle(commit);
+        self.config.maintainers.iter().any(|maintainer| {
+            let maintainer = maintainer.trim().trim_start_matches('@');
+            maintainer.eq_ignore_ascii_case(&commit.author)
+                || maintainer.eq_ignore_ascii_case(&commit.email)
+                || handle
+                    .as_deref()
+                    .is_some_and(|handle| maintainer.eq_ignore_ascii_case(handle))
+        })
+    }
+
+    /// The author's handle from `[community] handles` (keyed by name or
+    /// email), or from a GitHub noreply address.
+    pub fn handle(&self, commit: &Commit) -> Option<String> {
+        let configured = self.config.handles.iter().find(|(author, _)| {
+            author.eq_ignore_ascii_case(&commit.author)
+                || author.eq_ignore_ascii_case(&commit.email)
+        });
+        if let Some((_, handle)) = configured {
+            return Some(handle.trim_start_matches('@').to_string());
+        }
+
+        let local = commit
+            .email
+            .to_lowercase()
+            .strip_suffix(GITHUB_NOREPLY)
+            .map(str::to_string)?;
+        let handle = local.split_once('+').map_or(local.as_str(), |(_, h)| h);
+        Some(handle.to_string())
+    }
+
+    /// `@handle`, or the author name when there is no handle.
+    fn mention(&self, commit: &Commit) -> String {
+        match self.handle(commit) {
+            Some(handle) => format!("@{}", handle),
+            None => commit.author.clone(),
+        }
+    }
+}
+
+/// Joins names as `a`, `a and b` or `a, b and c`.
+fn join_names(names: &[String]) -> String {
+    match names {
+        [] => String::new(),
+        [name] => name.clone(),
+        [rest @ .., last] => format!("{} and {}", rest.join(", "), last),
+    }
+}
+
+/// Commits by contributors who are not maintainers, laid out like GitHub's
+/// generated release notes: a thank-you line, "What's Changed" with one
+/// `subject by @handle in hash` item per commit, and "New Contributors" for
+/// authors without commits before the range. `previous_authors` holds the
+/// lowercased names and emails of earlier authors; without it, first
+/// contributions are not listed.
+pub fn community_section(
+    commits: &[Commit],
+    config: &CommunityConfig,
+    previous_authors: Option<&HashSet<String>>,
+    locale: &Locale,
+) -> Section {
+    let community = Community::new(config);
+    let contributions: Vec<&Commit> = commits
+        .iter()
+        .filter(|commit| !community.is_maintainer(commit))
+        .collect();
+
+    let mut section = Section::new("community", locale.text("community-contributions"));
+    if contributions.is_empty() {
+        return section.with_block(Block::Text(
+            locale.text("no-community-contributions").to_string(),
+        ));
+    }
+
+    // Each contributor with their first commit in the range.
+    let mut firsts: Vec<(String, &Commit)> = Vec::new();
+    for commit in &contributions {
+        let mention = community.mention(commit);
+        if !firsts.iter().any(|(seen, _)| *seen == mention) {
+            firsts.push((mention, commit));
+        }
+    }
+    let names: Vec<String> = firsts.iter().map(|(mention, _)| mention.clone()).collect();
+
+    section = section.with_block(Block::Text(
+        locale.format("community-thanks", &[("names", &join_names(&names))]),
+    ));
+    section = section.with_block(Block::List {
+        title: locale.text("whats-changed").to_string(),
+        items: contributions
+            .iter()
+            .map(|commit| {
+                locale.format(
+                    "contribution",
+                    &[
+                        ("subject", &commit.subject),
+                        ("author", &community.mention(commit)),
+                        ("hash", &commit.hash[..8]),
+                    ],
+                )
+            })
+            .collect(),
+    });
+
+    if let Some(previous) = previous_authors {
+        let newcomers: Vec<String> = firsts
+            .iter()
+            .filter(|(_, commit)| {
+                !previous.contains(&commit.author.to_lowercase())
+                    && !previous.contains(&commit.email.to_lowercase())
+            })
+            .map(|(mention, commit)| {
+                locale.format(
+                    "first-contribution",
+                    &[("author", mention), ("hash", &commit.hash[..8])],
+                )
+            })
+            .collect();
+        if !newcomers.is_empty() {
+            section = section.with_block(Block::List {
+                title: locale.text("new-contributors").to_string(),
+                items: newcomers,
+            });
+        }
+    }
+
+    section
+}
+
+/// The sponsors and funding links from `[acknowledgments]`, for release
+/// notes; `None` when nothing is configured.
+pub fn acknowledgments_section(config: &AcknowledgmentsConfig, locale: &Locale) -> Option<Section> {
+    if config.is_empty() {
+        return None;
+    }
+
+    let entry = |acknowledgment: &Acknowledgment| match acknowledgment {
+        Acknowledgment::Name(name) => name.clone(),
+        Acknowledgment::Link { name, url } => format!("{} ({})", name, url),
+    };
+    let mut section = Section::new(
+        "acknowledgments",
+        config
+            .title
+            .as_deref()
+            .unwrap_or(locale.text("acknowledgments")),
+    );
+    if let Some(message) = &config.message {
+        section = section.with_block(Block::Text(message.trim().to_string()));
+    }
+    for (title, entries) in [("sponsors", &config.sponsors), ("funding", &config.funding)] {
+        if !entries.is_empty() {
+            section = section.with_block(Block::List {
+                title: locale.text(title).to_string(),
+                items: entries.iter().map(entry).collect(),
+            });
+        }
+    }
+
+    Some(section)
+}
@@ -0,0 +1,228 @@
+use clap::{Arg, ArgAction, Command, ValueEnum};
+
+/// Shells `completions` writes a script for.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    /// Source from ~/.bashrc or save to /etc/bash_completion.d/git-report
+    Bash,
+    /// Save as _git-report in a directory on $fpath
+    Zsh,
+    /// Save to ~/.config/fish/completions/git-report.fish
+    Fish,
+}
+
+/// Options whose value is a commit; they complete branch and tag names.
+const REVISION_OPTIONS: &[&str] = &["from", "to"];
+
+fn takes_value(arg: &Arg) -> bool {
+    matches!(arg.get_action(), ArgAction::Set | ArgAction::Append)
+}
+
+fn options(command: &Command) -> impl Iterator<Item = &Arg> {
+    command
+        .get_arguments()
+        .filter(|arg| !arg.is_positional() && !arg.is_hide_set())
+}
+
+fn possible_values(arg: &Arg) -> Vec<String> {
+    arg.get_possible_values()
+        .iter()
+        .filter(|value| !value.is_hide_set())
+        .map(|value| value.get_name().to_string())
+        .collect()
+}
+
+fn flags(arg: &Arg) -> Vec<String> {
+    let mut flags = Vec::new();
+    if let Some(short) = arg.get_short() {
+        flags.push(format!("-{}", short));
+    }
+    if let Some(long) = arg.get_long() {
+        flags.push(format!("--{}", long));
+    }
+    flags
+}
+
+fn is_revision(arg: &Arg) -> bool {
+    REVISION_OPTIONS.contains(&arg.get_id().as_str())
+}
+
+/// Help text cut to its first line, with quotes that would end a fish string
+/// escaped.
+fn description(arg_help: Option<String>) -> String {
+    arg_help
+        .unwrap_or_default()
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .replace('\\', "\\\\")
+        .replace('\'', "\\'")
+}
+
+fn bash(command: &Command) -> String {
+    let name = command.get_name();
+    let subcommands: Vec<&str> = command
+        .get_subcommands()
+        .map(|sub| sub.get_name())
+        .filter(|sub| *sub != "help")
+        .collect();
+    let global: Vec<String> = options(command).flat_map(flags).collect();
+
+    let mut values = String::new();
+    let mut seen = Vec::new();
+    for arg in std::iter::once(command)
+        .chain(command.get_subcommands())
+        .flat_map(options)
+        .filter(|arg| takes_value(arg))
+    {
+        let patterns = flags(arg).join("|");
+        if seen.contains(&patterns) {
+            continue;
+        }
+        let reply = if is_revision(arg) {
+            "COMPREPLY=($(compgen -W \"$(git for-each-ref --format='%(refname:short)' 2>/dev/null)\" -- \"$cur\"))".to_string()
+        } else if possible_values(arg).is_empty() {
+            "COMPREPLY=($(compgen -f -- \"$cur\"))".to_string()
+        } else {
+            format!(
+                "COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))",
+                possible_values(arg).join(" ")
+            )
+        };
+        values.push_str(&format!(
+            "        {})\n            {}\n            return ;;\n",
+            patterns, reply
+        ));
+        seen.push(patterns);
+    }
+
+    let mut per_subcommand = String::new();
+    for sub in command
+        .get_subcommands()
+        .filter(|sub| sub.get_name() != "help")
+    {
+        let own: Vec<String> = options(sub)
+            .filter(|arg| !arg.is_global_set())
+            .flat_map(flags)
+            .collect();
+        per_subcommand.push_str(&format!(
+            "        {}) opts=\"{}\" ;;\n",
+            sub.get_name(),
+            own.join(" ")
+        ));
+    }
+
+    format!(
+        r#"# bash completion for {name}, written by `{name} completions bash`
+_{function}() {{
+    local cur prev sub="" opts word
+    cur="${{COMP_WORDS[COMP_CWORD]}}"
+    prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+    for word in "${{COMP_WORDS[@]:1:COMP_CWORD-1}}"; do
+        case "$word" in
+            {subcommand_patterns}) sub="$word" ;;
+        esac
+    done
+
+    case "$prev" in
+{values}    esac
+
+    case "$sub" in
+        "") opts="{subcommands}" ;;
+{per_subcommand}    esac
+    COMPREPLY=($(compgen -W "$opts {global}" -- "$cur"))
+}}
+complete -F _{function} {name}
+"#,
+        name = name,
+        function = name.replace('-', "_"),
+        subcommand_patterns = subcommands.join("|"),
+        subcommands = subcommands.join(" "),
+        global = global.join(" "),
+        values = values,
+        per_subcommand = per_subcommand,
+    )
+}
+
+fn zsh(command: &Command) -> String {
+    format!(
+        "#compdef {name}\n# zsh completion for {name}, written by `{name} completions zsh`\nautoload -U +X bashcompinit && bashcompinit\n{bash}",
+        name = command.get_name(),
+        bash = bash(command)
+    )
+}
+
+fn fish_arg(name: &str, condition: &str, arg: &Arg) -> String {
+    let mut line = format!("complete -c {}", name);
+    if !condition.is_empty() {
+        line.push_str(&format!(" -n '{}'", condition));
+    }
+    if let Some(short) = arg.get_short() {
+        line.push_str(&format!(" -s {}", short));
+    }
+    if let Some(long) = arg.get_long() {
+        line.push_str(&format!(" -l {}", long));
+    }
+    if takes_value(arg) {
+        let values = possible_values(arg);
+        if is_revision(arg) {
+            line.push_str(" -x -a '(__fish_git_refs)'");
+        } else if !values.is_empty() {
+            line.push_str(&format!(" -x -a '{}'", values.join(" ")));
+        } else {
+            line.push_str(" -r -F");
+        }
+    }
+    let help = description(arg.get_help().map(|help| help.to_string()));
+    if !help.is_empty() {
+        line.push_str(&format!(" -d '{}'", help));
+    }
+    line + "\n"
+}
+
+fn fish(command: &Command) -> String {
+    let name = command.get_name();
+    let mut script = format!(
+        "# fish completion for {name}, written by `{name} completions fish`\ncomplete -c {name} -f\n",
+        name = name
+    );
+
+    for sub in command
+        .get_subcommands()
+        .filter(|sub| sub.get_name() != "help")
+    {
+        let about = description(sub.get_about().map(|about| about.to_string()));
+        script.push_str(&format!(
+            "complete -c {} -n '__fish_use_subcommand' -a {} -d '{}'\n",
+            name,
+            sub.get_name(),
+            about
+        ));
+    }
+    for arg in options(command) {
+        script.push_str(&fish_arg(name, "", arg));
+    }
+    for sub in command
+        .get_subcommands()
+        .filter(|sub| sub.get_name() != "help")
+    {
+        let condition = format!("__fish_seen_subcommand_from {}", sub.get_name());
+        for arg in options(sub).filter(|arg| !arg.is_global_set()) {
+            script.push_str(&fish_arg(name, &condition, arg));
+        }
+    }
+
+    script
+}
+
+/// The completion script for `shell`, covering the subcommands, every option
+/// and the values of options with a fixed set of them. Commit options
+/// complete branch and tag names, other options with a value complete paths.
+pub fn script(shell: Shell, command: &mut Command) -> String {
+    command.build();
+    match shell {
+        Shell::Bash => bash(command),
+        Shell::Zsh => zsh(command),
+        Shell::Fish => fish(command),
+    }
+}
@@ -0,0 +1,251 @@
+use serde::Deserialize;
+use std::{collections::BTreeMap, env, fs, path::Path};
+
+use crate::{
+    error::{Context, ReportError, Result},
+    report::{Banner, Branding},
+};
+
+const CONFIG_FILE_NAME: &str = ".git-report.toml";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub email: EmailConfig,
+    pub archive: ArchiveConfig,
+    pub stats: StatsConfig,
+    pub notes: NotesConfig,
+    pub impact: ImpactConfig,
+    pub check: CheckConfig,
+    pub community: CommunityConfig,
+    pub authors: AuthorsConfig,
+    pub acknowledgments: AcknowledgmentsConfig,
+    pub monorepo: MonorepoConfig,
+    /// Saved filters for `--filter-name`, by name.
+    pub filters: BTreeMap<String, NamedFilter>,
+    /// Header and footer added to every report.
+    pub banner: Banner,
+    /// Title, author and project name of every report.
+    pub branding: Branding,
+    /// Links shown with every commit, by label, e.g. `Build =
+    /// "https://ci.example.com/builds/{hash}"`.
+    pub commit_links: BTreeMap<String, String>,
+    /// Labels attached to matching commits.
+    pub labels: Vec<LabelRule>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct EmailConfig {
+    pub smtp_host: Option<String>,
+    pub smtp_port: Option<u16>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub from: Option<String>,
+    pub tls: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ArchiveConfig {
+    pub enabled: bool,
+    pub dir: Option<String>,
+    /// Keep only this many of the most recent archived reports.
+    pub keep_last: Option<usize>,
+    /// Remove archived reports older than this many days.
+    pub keep_days: Option<i64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct StatsConfig {
+    /// Metric sections added to every report, in this order.
+    pub metrics: Vec<String>,
+    /// Email domains of the company's own contributors for the `domains`
+    /// metric; subdomains match too.
+    pub company_domains: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct NotesConfig {
+    /// Notes refs shown as annotations, e.g. `commits` for `refs/notes/commits`.
+    pub refs: Vec<String>,
+}
+
+impl Default for NotesConfig {
+    fn default() -> Self {
+        Self {
+            refs: vec!["commits".to_string()],
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ImpactConfig {
+    /// Paths whose changes raise a commit's impact score, e.g. `src/core/**`.
+    pub core_paths: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct CheckConfig {
+    /// Paths `check --assert-no-sensitive-paths` fails on, e.g. `*.pem`;
+    /// the built-in list when empty.
+    pub sensitive_paths: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct CommunityConfig {
+    /// Names, emails or handles of the maintainers, whose commits
+    /// `--community` leaves out.
+    pub maintainers: Vec<String>,
+    /// Handles to thank contributors by, keyed by author name or email.
+    pub handles: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct AuthorsConfig {
+    /// Identities authors are counted as, keyed by author name or email,
+    /// e.g. `"jdoe@old.example.com" = "Jane Doe <jane@example.com>"`.
+    pub aliases: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct MonorepoConfig {
+    /// Directories whose subdirectories are projects, e.g. `packages` for
+    /// `packages/web` and `packages/api`.
+    pub roots: Vec<String>,
+    /// Projects by name with the patterns of their files, e.g.
+    /// `docs = ["docs/**", "*.md"]`; checked in name order, before `roots`.
+    pub projects: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct AcknowledgmentsConfig {
+    /// Section title (default: Acknowledgments).
+    pub title: Option<String>,
+    /// Paragraph shown above the lists, e.g. a thank-you to the sponsors.
+    pub message: Option<String>,
+    pub sponsors: Vec<Acknowledgment>,
+    /// Where readers can fund the project, e.g. GitHub Sponsors or Open Collective.
+    pub funding: Vec<Acknowledgment>,
+}
+
+impl AcknowledgmentsConfig {
+    pub fn is_empty(&self) -> bool {
+        self.message.is_none() && self.sponsors.is_empty() && self.funding.is_empty()
+    }
+}
+
+/// A sponsor or funding entry: a name, or a name with a link, e.g.
+/// `"Acme"` or `{ name = "Acme", url = "https://acme.example" }`.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum Acknowledgment {
+    Name(String),
+    Link { name: String, url: String },
+}
+
+/// A label attached to the commits whose subject or body matches `pattern`
+/// or that change a file matching `paths`, e.g.
+/// `[[labels]] name = "security" pattern = "(?i)cve-|xss" paths = ["src/auth/**"]`.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LabelRule {
+    pub name: String,
+    /// Regular expression matched against the commit message.
+    #[serde(default)]
+    pub pattern: Option<String>,
+    /// `.gitignore`-style patterns of changed files.
+    #[serde(default)]
+    pub paths: Vec<String>,
+}
+
+/// A saved filter: either a `--filter` expression or a table of filter flags,
+/// e.g. `backend = 'files ~ "server/**"'` or
+/// `[filters.release] grep = ["^Bump "], invert_grep = true`.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum NamedFilter {
+    Expression(String),
+    Options(FilterOptions),
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FilterOptions {
+    pub filter: Option<String>,
+    pub grep: Vec<String>,
+    pub invert_grep: bool,
+    pub exclude: Vec<String>,
+    pub ignore_authors: Vec<String>,
+    pub no_bots: bool,
+}
+
+impl NamedFilter {
+    pub fn options(&self) -> FilterOptions {
+        match self {
+            NamedFilter::Expression(expr) => FilterOptions {
+                filter: Some(expr.clone()),
+                ..Default::default()
+            },
+            NamedFilter::Options(options) => options.clone(),
+        }
+    }
+}
+
+/// Loads `.git-report.toml` from the repository root (or `GIT_REPORT_CONFIG`
+/// when set) and applies environment variable overrides on top of it.
+pub fn load_config(repo_path: &str) -> Result<Config> {
+    let config_path = env::var("GIT_REPORT_CONFIG")
+        .map(Into::into)
+        .unwrap_or_else(|_| Path::new(repo_path).join(CONFIG_FILE_NAME));
+
+    let mut config = if config_path.exists() {
+        let content = fs::read_to_string(&config_path).with_context(ReportError::io, || {
+            format!("Failed to read config file {}", config_path.display())
+        })?;
+        toml::from_str(&content).with_context(ReportError::parse, || {
+            format!("Failed to parse config file {}", config_path.display())
+        })?
+    } else {
+        Config::default()
+    };
+
+    apply_env_overrides(&mut config)?;
+
+    Ok(config)
+}
+
+fn apply_env_overrides(config: &mut Config) -> Result<()> {
+    let email = &mut config.email;
+
+    if let Ok(host) = env::var("GIT_REPORT_SMTP_HOST") {
+        email.smtp_host = Some(host);
+    }
+    if let Ok(port) = env::var("GIT_REPORT_SMTP_PORT") {
+        email.smtp_port = Some(port.parse().with_context(ReportError::parse, || {
+            format!("Invalid GIT_REPORT_SMTP_PORT value '{}'", port)
+        })?);
+    }
+    if let Ok(username) = env::var("GIT_REPORT_SMTP_USER") {
+        email.username = Some(username);
+    }
+    if let Ok(password) = env::var("GIT_REPORT_SMTP_PASSWORD") {
+        email.password = Some(password);
+    }
+    if let Ok(from) = env::var("GIT_REPORT_SMTP_FROM") {
+        email.from = Some(from);
+    }
+    if let Ok(tls) = env::var("GIT_REPORT_SMTP_TLS") {
+        email.tls = Some(tls);
+    }
+
+    Ok(())
+}
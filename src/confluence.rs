@@ -0,0 +1,351 @@
+use serde_json::{json, Value};
+
+use crate::{
+    error::{Context, ReportError, Result},
+    git::Commit,
+    postprocess::{self, Markup},
+    render::html::escape,
+    report::{Block, Part, ReportData, Section},
+};
+
+/// Where and as whom `--confluence-space` uploads the report.
+pub struct ConfluenceOptions<'a> {
+    /// Base URL of the Confluence site, e.g. `https://example.atlassian.net/wiki`.
+    pub url: &'a str,
+    pub space: &'a str,
+    /// Id of the page the report page is created under.
+    pub parent: Option<&'a str>,
+    pub user: Option<&'a str>,
+    pub token: Option<&'a str>,
+}
+
+/// The uploaded page.
+pub struct Page {
+    /// Whether the page was created rather than a new version of it stored.
+    pub created: bool,
+    /// Link to the page, when Confluence returns one.
+    pub url: Option<String>,
+}
+
+/// The title of the report's page: the report title and its date range, so
+/// running the report for the same range again updates the same page.
+pub fn page_title(data: &ReportData) -> String {
+    format!("{} ({})", data.title(), data.date_range())
+}
+
+/// Converts the report into Confluence storage format, the XHTML pages are
+/// stored in. Field lists become two-column tables, since Confluence has no
+/// definition lists, and the HTML page's style, timeline and banners are left
+/// to the Confluence theme.
+pub fn to_storage(data: &ReportData) -> String {
+    let mut xhtml = String::new();
+
+    if data.shows(Part::Summary) {
+        let mut fields: Vec<(String, String)> = data
+            .branding_fields()
+            .into_iter()
+            .map(|(label, value)| (label.to_string(), escape(value)))
+            .collect();
+        fields.extend([
+            (
+                data.locale.text("repository").to_string(),
+                escape(&data.repo_path),
+            ),
+            (
+                data.locale.text("generated").to_string(),
+                escape(&data.dates.format(&data.generated_at)),
+            ),
+            (
+                data.locale.text("from").to_string(),
+                format!(
+                    "{} (<code>{}</code>)",
+                    escape(&data.from_commit.subject),
+                    data.from_commit.hash
+                ),
+            ),
+            (
+                data.locale.text("to").to_string(),
+                format!(
+                    "{} (<code>{}</code>)",
+                    escape(&data.to_commit.subject),
+                    data.to_commit.hash
+                ),
+            ),
+            (
+                data.locale.text("date-range").to_string(),
+                escape(&data.date_range()),
+            ),
+            (
+                data.locale.text("total-commits").to_string(),
+                data.commits.len().to_string(),
+            ),
+            (
+                data.locale.text("lines-changed").to_string(),
+                escape(&data.lines_changed()),
+            ),
+        ]);
+        if let Some(releases) = data.releases() {
+            fields.push((data.locale.text("releases").to_string(), escape(&releases)));
+        }
+        xhtml.push_str(&format!(
+            "<h2>{}</h2>\n{}",
+            escape(data.locale.text("summary")),
+            fields_table(&fields)
+        ));
+    }
+
+    if let Some(summary) = data.visible_ai_summary() {
+        xhtml.push_str(&format!(
+            "<h2>{}</h2>\n",
+            escape(data.locale.text("ai-summary"))
+        ));
+        if data.convert_ai_summary {
+            xhtml.push_str(&postprocess::convert(summary, Markup::Html));
+            xhtml.push('\n');
+        } else {
+            xhtml.push_str(&format!("<pre>{}</pre>\n", escape(summary.trim())));
+        }
+    } else if data.lists_commits() {
+        xhtml.push_str(&format!(
+            "<h2>{}</h2>\n",
+            escape(data.locale.text("detailed-commits"))
+        ));
+        for (i, commit) in data.detailed_commits().into_iter().enumerate() {
+            xhtml.push_str(&format!(
+                "<h3>{}. {}</h3>\n",
+                i + 1,
+                escape(&commit.subject)
+            ));
+            if data.shows(Part::Details) {
+                xhtml.push_str(&details(data, commit));
+            }
+            if data.shows(Part::Files) && !commit.file_stats.is_empty() {
+                let files: Vec<String> = commit
+                    .file_stats
+                    .iter()
+                    .map(|stat| match &stat.old_path {
+                        Some(old_path) => format!(
+                            "<code>{}</code> -&gt; <code>{}</code>",
+                            escape(old_path),
+                            escape(&stat.path)
+                        ),
+                        None => format!("<code>{}</code>", escape(&stat.path)),
+                    })
+                    .collect();
+                xhtml.push_str(&list(data.locale.text("files-changed"), &files));
+            }
+        }
+    }
+
+    if data.shows(Part::Stats) {
+        for section in &data.sections {
+            xhtml.push_str(&section_storage(section));
+        }
+    }
+
+    xhtml
+}
+
+fn details(data: &ReportData, commit: &Commit) -> String {
+    let mut fields = vec![
+        (
+            data.locale.text("hash").to_string(),
+            format!("<code>{}</code>", commit.hash),
+        ),
+        (
+            data.locale.text("author").to_string(),
+            escape(&commit.author),
+        ),
+        (
+            data.locale.text("date").to_string(),
+            escape(&data.dates.format(&commit.date)),
+        ),
+    ];
+    for (key, refs) in [("tags", &commit.tags), ("branches", &commit.branches)] {
+        if !refs.is_empty() {
+            fields.push((data.locale.text(key).to_string(), escape(&refs.join(", "))));
+        }
+    }
+    if !commit.labels.is_empty() {
+        fields.push((
+            data.locale.text("labels").to_string(),
+            escape(&commit.labels.join(", ")),
+        ));
+    }
+    for (label, url) in data.links(commit) {
+        fields.push((
+            label.to_string(),
+            format!("<a href=\"{0}\">{0}</a>", escape(&url)),
+        ));
+    }
+    if let Some(summary) = data.commit_summaries.get(&commit.hash) {
+        fields.push((data.locale.text("ai-summary").to_string(), escape(summary)));
+    }
+
+    let mut xhtml = fields_table(&fields);
+    if !commit.body.trim().is_empty() {
+        xhtml.push_str(&format!("<pre>{}</pre>\n", escape(commit.body.trim())));
+    }
+    if let Some(translation) = data.translations.get(&commit.hash) {
+        xhtml.push_str(&format!(
+            "<p>{}:</p>\n<pre>{}</pre>\n",
+            escape(&data.translation_label(translation)),
+            escape(translation.text.trim())
+        ));
+    }
+    xhtml
+}
+
+fn section_storage(section: &Section) -> String {
+    let mut xhtml = format!("<h2>{}</h2>\n", escape(&section.title));
+    for block in &section.blocks {
+        match block {
+            Block::Fields(fields) => {
+                let fields: Vec<(String, String)> = fields
+                    .iter()
+                    .map(|(label, value)| (label.clone(), escape(value)))
+                    .collect();
+                xhtml.push_str(&fields_table(&fields));
+            }
+            Block::List { title, items } => {
+                let items: Vec<String> = items.iter().map(|item| escape(item)).collect();
+                xhtml.push_str(&list(title, &items));
+            }
+            Block::Table { headers, rows } => {
+                xhtml.push_str("<table>\n<tbody>\n<tr>");
+                for header in headers {
+                    xhtml.push_str(&format!("<th>{}</th>", escape(header)));
+                }
+                xhtml.push_str("</tr>\n");
+                for row in rows {
+                    xhtml.push_str("<tr>");
+                    for cell in row {
+                        xhtml.push_str(&format!("<td>{}</td>", escape(cell)));
+                    }
+                    xhtml.push_str("</tr>\n");
+                }
+                xhtml.push_str("</tbody>\n</table>\n");
+            }
+            Block::Text(text) => xhtml.push_str(&format!("<p>{}</p>\n", escape(text))),
+            Block::Code(code) | Block::Mermaid(code) => {
+                xhtml.push_str(&format!("<pre>{}</pre>\n", escape(code.trim_end())))
+            }
+        }
+    }
+    xhtml
+}
+
+/// A table with a label and a value per row; values must already be escaped.
+fn fields_table(fields: &[(String, String)]) -> String {
+    let mut xhtml = String::from("<table>\n<tbody>\n");
+    for (label, value) in fields {
+        xhtml.push_str(&format!(
+            "<tr><th>{}</th><td>{}</td></tr>\n",
+            escape(label),
+            value
+        ));
+    }
+    xhtml.push_str("</tbody>\n</table>\n");
+    xhtml
+}
+
+/// A titled bullet list; items must already be escaped.
+fn list(title: &str, items: &[String]) -> String {
+    let mut xhtml = format!("<p>{}:</p>\n<ul>\n", escape(title));
+    for item in items {
+        xhtml.push_str(&format!("<li>{}</li>\n", item));
+    }
+    xhtml.push_str("</ul>\n");
+    xhtml
+}
+
+/// Creates the report page in the space, or stores a new version of it when
+/// a page with the same title exists. A `user` selects basic auth as used by
+/// Confluence Cloud API tokens; otherwise the token is sent as a bearer token
+/// (a Data Center personal access token).
+pub fn publish(options: &ConfluenceOptions<'_>, title: &str, body: &str) -> Result<Page> {
+    let base_url = options.url.trim_end_matches('/');
+    let content_url = format!("{}/rest/api/content", base_url);
+    let client = reqwest::blocking::Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .context(ReportError::delivery, "Failed to create HTTP client")?;
+    let authorized = |request: reqwest::blocking::RequestBuilder| {
+        let request = request.header("Accept", "application/json");
+        match (options.user, options.token) {
+            (Some(user), token) => request.basic_auth(user, token),
+            (None, Some(token)) => request.bearer_auth(token),
+            (None, None) => request,
+        }
+    };
+
+    let existing = authorized(client.get(&content_url))
+        .query(&[
+            ("spaceKey", options.space),
+            ("title", title),
+            ("expand", "version"),
+        ])
+        .send()
+        .with_context(ReportError::delivery, || {
+            format!("Failed to connect to Confluence at {}", base_url)
+        })?;
+    let existing = response_json(existing, "looking up the report page")?;
+    let existing = &existing["results"][0];
+
+    let mut page = json!({
+        "type": "page",
+        "title": title,
+        "space": { "key": options.space },
+        "body": { "storage": { "value": body, "representation": "storage" } },
+    });
+    if let Some(parent) = options.parent {
+        page["ancestors"] = json!([{ "id": parent }]);
+    }
+
+    let (request, created) = match existing["id"].as_str() {
+        Some(id) => {
+            let version = existing["version"]["number"].as_u64().unwrap_or(0);
+            page["version"] = json!({ "number": version + 1 });
+            (client.put(format!("{}/{}", content_url, id)), false)
+        }
+        None => (client.post(&content_url), true),
+    };
+    let response = authorized(request)
+        .json(&page)
+        .send()
+        .with_context(ReportError::delivery, || {
+            format!("Failed to connect to Confluence at {}", base_url)
+        })?;
+    let saved = response_json(
+        response,
+        if created {
+            "creating the report page"
+        } else {
+            "updating the report page"
+        },
+    )?;
+
+    let url = match (
+        saved["_links"]["base"].as_str(),
+        saved["_links"]["webui"].as_str(),
+    ) {
+        (Some(base), Some(webui)) => Some(format!("{}{}", base, webui)),
+        (None, Some(webui)) => Some(format!("{}{}", base_url, webui)),
+        _ => None,
+    };
+    Ok(Page { created, url })
+}
+
+fn response_json(response: reqwest::blocking::Response, doing: &str) -> Result<Value> {
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().unwrap_or_default();
+        return Err(ReportError::delivery(format!(
+            "Confluence returned error status {} {}: {}",
+            status, doing, body
+        )));
+    }
+    response.json().with_context(ReportError::parse, || {
+        format!("Failed to parse Confluence response {}", doing)
+    })
+}
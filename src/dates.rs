@@ -0,0 +1,98 @@
+use chrono::{
+    format::{Item, StrftimeItems},
+    DateTime, FixedOffset, Local, TimeZone, Utc,
+};
+use chrono_tz::Tz;
+use std::{fmt::Display, str::FromStr};
+
+pub const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S %Z";
+
+/// The timezone dates are shown in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Timezone {
+    #[default]
+    Utc,
+    /// The timezone of the machine generating the report.
+    Local,
+    /// Each commit's own author offset, as recorded by git.
+    Author,
+    /// A fixed offset such as `+02:00`.
+    Offset(FixedOffset),
+    /// An IANA timezone such as `Europe/Berlin`.
+    Named(Tz),
+}
+
+impl FromStr for Timezone {
+    type Err = String;
+
+    /// Accepts `utc`, `local`, `author`, an offset (`+02:00`, `-0530`) or an IANA name.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "utc" | "z" => return Ok(Timezone::Utc),
+            "local" => return Ok(Timezone::Local),
+            "author" => return Ok(Timezone::Author),
+            _ => {}
+        }
+
+        if value.starts_with(['+', '-']) {
+            return value
+                .parse::<FixedOffset>()
+                .map(Timezone::Offset)
+                .map_err(|_| format!("invalid UTC offset '{}', expected e.g. +02:00", value));
+        }
+
+        value.parse::<Tz>().map(Timezone::Named).map_err(|_| {
+            format!(
+                "unknown timezone '{}', expected utc, local, author, an offset like +02:00 or an IANA name like Europe/Berlin",
+                value
+            )
+        })
+    }
+}
+
+/// Checks a `--date-format` string up front, since chrono fails only when formatting.
+pub fn parse_date_format(value: &str) -> Result<String, String> {
+    if StrftimeItems::new(value).any(|item| matches!(item, Item::Error)) {
+        return Err(format!("invalid strftime format '{}'", value));
+    }
+    Ok(value.to_string())
+}
+
+/// How dates are rendered in reports: a timezone and a strftime format.
+#[derive(Debug, Clone)]
+pub struct DateStyle {
+    pub timezone: Timezone,
+    pub format: String,
+}
+
+impl Default for DateStyle {
+    fn default() -> Self {
+        Self {
+            timezone: Timezone::Utc,
+            format: DEFAULT_DATE_FORMAT.to_string(),
+        }
+    }
+}
+
+impl DateStyle {
+    pub fn format<T: TimeZone>(&self, date: &DateTime<T>) -> String
+    where
+        T::Offset: Display,
+    {
+        self.format_with(date, &self.format)
+    }
+
+    /// Formats with another format string, e.g. a date-only one, in the same timezone.
+    pub fn format_with<T: TimeZone>(&self, date: &DateTime<T>, format: &str) -> String
+    where
+        T::Offset: Display,
+    {
+        match self.timezone {
+            Timezone::Utc => date.with_timezone(&Utc).format(format).to_string(),
+            Timezone::Local => date.with_timezone(&Local).format(format).to_string(),
+            Timezone::Author => date.format(format).to_string(),
+            Timezone::Offset(offset) => date.with_timezone(&offset).format(format).to_string(),
+            Timezone::Named(tz) => date.with_timezone(&tz).format(format).to_string(),
+        }
+    }
+}
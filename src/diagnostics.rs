@@ -0,0 +1,102 @@
+use std::{collections::BTreeMap, sync::Mutex};
+
+use crate::{
+    i18n::Locale,
+    report::{Block, Section},
+};
+
+/// A kind of gap in the collected data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum IssueKind {
+    /// An author date git could not read, shown as the Unix epoch.
+    InvalidDate,
+    /// Text that was not valid UTF-8, with the bad bytes replaced by U+FFFD.
+    InvalidUtf8,
+    /// A commit whose body or changed files could not be read.
+    MissingDetails,
+    /// Text cut short, such as a diff over the AI prompt limit.
+    Truncated,
+    /// A ticket, merge request or issue that could not be fetched.
+    LookupFailed,
+}
+
+impl IssueKind {
+    /// Catalog key of the label the Data Quality section shows.
+    pub fn key(self) -> &'static str {
+        match self {
+            IssueKind::InvalidDate => "issue-invalid-date",
+            IssueKind::InvalidUtf8 => "issue-invalid-utf8",
+            IssueKind::MissingDetails => "issue-missing-details",
+            IssueKind::Truncated => "issue-truncated",
+            IssueKind::LookupFailed => "issue-lookup-failed",
+        }
+    }
+}
+
+/// A data issue met while collecting the report.
+#[derive(Debug, Clone)]
+pub struct Issue {
+    pub kind: IssueKind,
+    /// The commit the issue is about, when it is about one.
+    pub commit: Option<String>,
+    pub detail: String,
+}
+
+static ISSUES: Mutex<Vec<Issue>> = Mutex::new(Vec::new());
+
+/// Notes a data issue for the report's Data Quality section. The same issue
+/// about the same commit is only kept once.
+pub fn record(kind: IssueKind, commit: Option<&str>, detail: impl Into<String>) {
+    let issue = Issue {
+        kind,
+        commit: commit.map(str::to_string),
+        detail: detail.into(),
+    };
+    let mut issues = ISSUES
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if !issues.iter().any(|known| {
+        known.kind == issue.kind && known.commit == issue.commit && known.detail == issue.detail
+    }) {
+        issues.push(issue);
+    }
+}
+
+/// The issues noted since the last call, clearing them for the next report.
+pub fn take() -> Vec<Issue> {
+    std::mem::take(
+        &mut *ISSUES
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()),
+    )
+}
+
+/// The issues per kind, then every issue with the commit it is about, so
+/// readers know which parts of the report rest on incomplete data.
+pub fn diagnostics_section(issues: &[Issue], locale: &Locale) -> Section {
+    let mut counts: BTreeMap<IssueKind, usize> = BTreeMap::new();
+    for issue in issues {
+        *counts.entry(issue.kind).or_default() += 1;
+    }
+    let mut section =
+        Section::new("diagnostics", locale.text("data-quality")).with_block(Block::Fields(
+            counts
+                .iter()
+                .map(|(kind, count)| (locale.text(kind.key()).to_string(), count.to_string()))
+                .collect(),
+        ));
+    for &kind in counts.keys() {
+        section = section.with_block(Block::List {
+            title: locale.text(kind.key()).to_string(),
+            items: issues
+                .iter()
+                .filter(|issue| issue.kind == kind)
+                .map(|issue| match &issue.commit {
+                    Some(hash) => format!("{} {}", &hash[..8.min(hash.len())], issue.detail),
+                    None => issue.detail.clone(),
+                })
+                .collect(),
+        });
+    }
+    section
+}
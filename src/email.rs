@@ -0,0 +1,105 @@
+use lettre::{
+    message::{header::ContentType, Attachment, MultiPart, SinglePart},
+    transport::smtp::authentication::Credentials,
+    Message, SmtpTransport, Transport,
+};
+use std::path::Path;
+
+use crate::{
+    config::EmailConfig,
+    error::{Context, ReportError, Result},
+};
+
+/// Sends a generated report to `to`, either inline as the message body or as
+/// a file attachment with a short body.
+pub fn send_report(
+    config: &EmailConfig,
+    to: &str,
+    subject: &str,
+    report_path: &str,
+    report_content: &str,
+    as_attachment: bool,
+) -> Result<()> {
+    let host = config.smtp_host.as_deref().ok_or_else(|| {
+        ReportError::delivery("SMTP host is not configured. Set [email] smtp_host in .git-report.toml or GIT_REPORT_SMTP_HOST".to_string())
+    })?;
+    let from = config
+        .from
+        .as_deref()
+        .or(config.username.as_deref())
+        .ok_or_else(|| {
+            ReportError::delivery("Sender address is not configured. Set [email] from in .git-report.toml or GIT_REPORT_SMTP_FROM".to_string())
+        })?;
+
+    let builder = Message::builder()
+        .from(from.parse().with_context(ReportError::parse, || {
+            format!("Invalid sender address '{}'", from)
+        })?)
+        .to(to.parse().with_context(ReportError::parse, || {
+            format!("Invalid recipient address '{}'", to)
+        })?)
+        .subject(subject);
+
+    let message = if as_attachment {
+        let file_name = Path::new(report_path)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| report_path.to_string());
+
+        builder.multipart(
+            MultiPart::mixed()
+                .singlepart(SinglePart::plain(format!(
+                    "The generated report is attached: {}\n",
+                    file_name
+                )))
+                .singlepart(
+                    Attachment::new(file_name)
+                        .body(report_content.to_string(), ContentType::TEXT_PLAIN),
+                ),
+        )
+    } else {
+        builder
+            .header(ContentType::TEXT_PLAIN)
+            .body(report_content.to_string())
+    }
+    .context(ReportError::delivery, "Failed to build email message")?;
+
+    let tls = config.tls.as_deref().unwrap_or("starttls");
+    let transport = match tls {
+        "starttls" => SmtpTransport::starttls_relay(host)
+            .with_context(ReportError::delivery, || {
+                format!("Failed to set up STARTTLS connection to {}", host)
+            })?,
+        "tls" => SmtpTransport::relay(host).with_context(ReportError::delivery, || {
+            format!("Failed to set up TLS connection to {}", host)
+        })?,
+        "none" => SmtpTransport::builder_dangerous(host),
+        other => {
+            return Err(ReportError::delivery(format!(
+                "Unknown SMTP TLS mode '{}'. Expected one of: starttls, tls, none",
+                other
+            )))
+        }
+    };
+
+    let transport = match config.smtp_port {
+        Some(port) => transport.port(port),
+        None => transport,
+    };
+
+    let transport = match (&config.username, &config.password) {
+        (Some(username), Some(password)) => {
+            transport.credentials(Credentials::new(username.clone(), password.clone()))
+        }
+        _ => transport,
+    };
+
+    transport
+        .build()
+        .send(&message)
+        .with_context(ReportError::delivery, || {
+            format!("Failed to send report email to {} via {}", to, host)
+        })?;
+
+    Ok(())
+}
@@ -0,0 +1,182 @@
+use thiserror::Error;
+
+type Source = Box<dyn std::error::Error + Send + Sync>;
+
+pub type Result<T, E = ReportError> = std::result::Result<T, E>;
+
+/// Errors returned by the library. Every variant carries a human-readable
+/// message and, where there is one, the lower-level error as its source.
+#[derive(Debug, Error)]
+pub enum ReportError {
+    /// A git command could not be run or exited unsuccessfully.
+    #[error("{message}")]
+    GitCommandFailed {
+        message: String,
+        #[source]
+        source: Option<Source>,
+    },
+    /// Output of git, a config/state/archive file or an API response was
+    /// malformed, or data could not be serialized.
+    #[error("{message}")]
+    ParseError {
+        message: String,
+        #[source]
+        source: Option<Source>,
+    },
+    /// The AI backend was unreachable, rejected the request or returned no text.
+    #[error("{message}")]
+    AiBackend {
+        message: String,
+        #[source]
+        source: Option<Source>,
+    },
+    /// The AI backend could not be reached, timed out or was overloaded; a
+    /// later attempt may succeed.
+    #[error("{message}")]
+    AiUnavailable {
+        message: String,
+        #[source]
+        source: Option<Source>,
+    },
+    /// Rendering an output format failed.
+    #[error("{message}")]
+    Render {
+        message: String,
+        #[source]
+        source: Option<Source>,
+    },
+    /// Sending a report by email or Slack, or fetching data from Jira, failed.
+    #[error("{message}")]
+    Delivery {
+        message: String,
+        #[source]
+        source: Option<Source>,
+    },
+    /// Reading or writing a local file failed.
+    #[error("{message}")]
+    Io {
+        message: String,
+        #[source]
+        source: Option<Source>,
+    },
+    /// Signing a report with gpg or sq failed.
+    #[error("{message}")]
+    Signing {
+        message: String,
+        #[source]
+        source: Option<Source>,
+    },
+    /// The option needs a Cargo feature that was not enabled at build time.
+    #[error("{what} are not available in this build. Reinstall with `cargo install git-report --features {feature}` (or `--features full`)")]
+    FeatureDisabled {
+        what: &'static str,
+        feature: &'static str,
+    },
+}
+
+impl ReportError {
+    pub fn git(message: String) -> Self {
+        Self::GitCommandFailed {
+            message,
+            source: None,
+        }
+    }
+
+    pub fn parse(message: String) -> Self {
+        Self::ParseError {
+            message,
+            source: None,
+        }
+    }
+
+    pub fn ai(message: String) -> Self {
+        Self::AiBackend {
+            message,
+            source: None,
+        }
+    }
+
+    pub fn ai_unavailable(message: String) -> Self {
+        Self::AiUnavailable {
+            message,
+            source: None,
+        }
+    }
+
+    /// Whether retrying the failed request may help.
+    pub fn is_transient(&self) -> bool {
+        matches!(self, Self::AiUnavailable { .. })
+    }
+
+    pub fn render(message: String) -> Self {
+        Self::Render {
+            message,
+            source: None,
+        }
+    }
+
+    pub fn delivery(message: String) -> Self {
+        Self::Delivery {
+            message,
+            source: None,
+        }
+    }
+
+    pub fn io(message: String) -> Self {
+        Self::Io {
+            message,
+            source: None,
+        }
+    }
+
+    pub fn sign(message: String) -> Self {
+        Self::Signing {
+            message,
+            source: None,
+        }
+    }
+
+    pub(crate) fn with_source(mut self, error: Source) -> Self {
+        match &mut self {
+            Self::GitCommandFailed { source, .. }
+            | Self::ParseError { source, .. }
+            | Self::AiBackend { source, .. }
+            | Self::AiUnavailable { source, .. }
+            | Self::Render { source, .. }
+            | Self::Delivery { source, .. }
+            | Self::Io { source, .. }
+            | Self::Signing { source, .. } => *source = Some(error),
+            Self::FeatureDisabled { .. } => {}
+        }
+        self
+    }
+}
+
+/// Wraps a lower-level error into the `ReportError` variant built by `kind`,
+/// keeping the original error as its source.
+pub(crate) trait Context<T> {
+    fn context(self, kind: fn(String) -> ReportError, message: &str) -> Result<T>;
+
+    fn with_context(
+        self,
+        kind: fn(String) -> ReportError,
+        message: impl FnOnce() -> String,
+    ) -> Result<T>;
+}
+
+impl<T, E> Context<T> for std::result::Result<T, E>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    fn context(self, kind: fn(String) -> ReportError, message: &str) -> Result<T> {
+        self.map_err(|error| kind(message.to_string()).with_source(Box::new(error)))
+    }
+
+    fn with_context(
+        self,
+        kind: fn(String) -> ReportError,
+        message: impl FnOnce() -> String,
+    ) -> Result<T> {
+        self.map_err(|error| kind(message()).with_source(Box::new(error)))
+    }
+}
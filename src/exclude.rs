@@ -0,0 +1,74 @@
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+
+use crate::{
+    error::{Context, ReportError, Result},
+    git::Commit,
+};
+
+/// Paths dropped from file lists and stats, matched like `.gitignore`
+/// entries: `*` stays within one directory, `**` spans any number, and a
+/// pattern without `/` (e.g. `Cargo.lock`, `*.min.js`) matches at any depth.
+pub struct PathExclusions {
+    globs: GlobSet,
+}
+
+impl PathExclusions {
+    pub fn new(patterns: &[String]) -> Result<Self> {
+        Ok(Self {
+            globs: glob_set(patterns, "exclude")?,
+        })
+    }
+
+    pub fn is_excluded(&self, path: &str) -> bool {
+        self.globs.is_match(path)
+    }
+
+    /// Removes excluded paths from the file lists and stats of every commit.
+    /// Commits themselves are kept even when all their files are excluded.
+    pub fn apply(&self, commits: &mut [Commit]) {
+        if self.globs.is_empty() {
+            return;
+        }
+        for commit in commits {
+            commit.files_changed.retain(|path| !self.is_excluded(path));
+            commit
+                .file_stats
+                .retain(|stat| !self.is_excluded(&stat.path));
+        }
+    }
+}
+
+/// Compiles `.gitignore`-style path patterns into one set. `kind` names the
+/// patterns in errors, e.g. `exclude` or `core path`.
+pub fn glob_set(patterns: &[String], kind: &str) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let pattern = pattern.trim_start_matches('/');
+        let anchored = if pattern.contains('/') {
+            pattern.to_string()
+        } else {
+            format!("**/{}", pattern)
+        };
+        let glob = GlobBuilder::new(&anchored)
+            .literal_separator(true)
+            .build()
+            .with_context(ReportError::parse, || {
+                format!("Invalid {} pattern '{}'", kind, pattern)
+            })?;
+        builder.add(glob);
+    }
+
+    builder.build().with_context(ReportError::parse, || {
+        format!("Failed to compile {} patterns", kind)
+    })
+}
+
+/// Clap value parser that rejects malformed globs up front.
+pub fn parse_pattern(pattern: &str) -> std::result::Result<String, String> {
+    PathExclusions::new(&[pattern.to_string()])
+        .map(|_| pattern.to_string())
+        .map_err(|error| match std::error::Error::source(&error) {
+            Some(source) => format!("{}: {}", error, source),
+            None => error.to_string(),
+        })
+}
@@ -0,0 +1,6 @@
+use crate::error::ReportError;
+
+/// The error for an option whose Cargo feature was not enabled at build time.
+pub fn unavailable(what: &'static str, feature: &'static str) -> ReportError {
+    ReportError::FeatureDisabled { what, feature }
+}
@@ -0,0 +1,698 @@
+//! The `--filter` expression language, e.g.
+//! `author =~ "alice" && files ~ "src/**" && !merge`.
+//!
+//! Comparisons test a commit field against a quoted string or a number and
+//! combine with `&&`, `||`, `!` and parentheses:
+//!
+//! - `author`, `subject`, `body`, `message` (subject and body) and `hash`
+//!   take `==`, `!=`, `=~` (regex), `!~` (no regex match) and `~` (glob);
+//!   `hash == "abc123"` matches by prefix.
+//! - `files` holds when any changed file matches; `~` globs work like
+//!   `--exclude` patterns. With a number, `files` is the file count.
+//! - `added`, `deleted` and `lines` (both) compare line counts with `==`,
+//!   `!=`, `<`, `<=`, `>` and `>=`; `date` compares the author date with a
+//!   `"YYYY-MM-DD"` string the same way.
+//! - `merge` holds for commits with more than one parent.
+
+use std::cmp::Ordering;
+
+use chrono::NaiveDate;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use regex_automata::{meta::Regex, util::syntax};
+
+use crate::{
+    error::{Context, ReportError, Result},
+    exclude,
+    git::Commit,
+};
+
+/// A parsed filter expression.
+pub struct Filter {
+    expr: Expr,
+}
+
+enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Merge,
+    Text(TextField, TextTest),
+    Files(TextTest),
+    FileCount(Comparison, u64),
+    Lines(LineField, Comparison, u64),
+    Date(Comparison, NaiveDate),
+}
+
+#[derive(Clone, Copy)]
+enum TextField {
+    Author,
+    Subject,
+    Body,
+    Message,
+    Hash,
+}
+
+#[derive(Clone, Copy)]
+enum LineField {
+    Added,
+    Deleted,
+    Lines,
+}
+
+enum TextTest {
+    Equals(String),
+    Matches(Regex),
+    Glob(GlobSet),
+}
+
+#[derive(Clone, Copy)]
+enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Comparison {
+    fn holds(self, ordering: Ordering) -> bool {
+        match self {
+            Comparison::Eq => ordering.is_eq(),
+            Comparison::Ne => ordering.is_ne(),
+            Comparison::Lt => ordering.is_lt(),
+            Comparison::Le => ordering.is_le(),
+            Comparison::Gt => ordering.is_gt(),
+            Comparison::Ge => ordering.is_ge(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Number(u64),
+    Op(&'static str),
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+}
+
+/// Operators, longest first so `!=` is not read as `!`.
+const OPERATORS: [&str; 9] = ["==", "!=", "=~", "!~", "<=", ">=", "<", ">", "~"];
+
+fn error(column: usize, message: impl std::fmt::Display) -> ReportError {
+    ReportError::parse(format!("Invalid filter at column {}: {}", column, message))
+}
+
+/// Splits an expression into tokens with their 1-based columns. Strings are
+/// quoted with `"` or `'`; a backslash only escapes the closing quote, so
+/// regex escapes like `\d` need no doubling.
+fn tokenize(input: &str) -> Result<Vec<(usize, Token)>> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let column = i + 1;
+        let rest: String = chars[i..chars.len().min(i + 2)].iter().collect();
+        let c = chars[i];
+
+        if c.is_whitespace() {
+            i += 1;
+        } else if rest == "&&" || rest == "||" {
+            tokens.push((column, if c == '&' { Token::And } else { Token::Or }));
+            i += 2;
+        } else if let Some(op) = OPERATORS.iter().find(|op| rest.starts_with(**op)) {
+            tokens.push((column, Token::Op(op)));
+            i += op.len();
+        } else if c == '!' {
+            tokens.push((column, Token::Not));
+            i += 1;
+        } else if c == '(' || c == ')' {
+            tokens.push((column, if c == '(' { Token::Open } else { Token::Close }));
+            i += 1;
+        } else if c == '"' || c == '\'' {
+            let mut value = String::new();
+            i += 1;
+            loop {
+                match chars.get(i) {
+                    None => return Err(error(column, "unterminated string")),
+                    Some('\\') if chars.get(i + 1) == Some(&c) => {
+                        value.push(c);
+                        i += 2;
+                    }
+                    Some(&next) if next == c => {
+                        i += 1;
+                        break;
+                    }
+                    Some(&next) => {
+                        value.push(next);
+                        i += 1;
+                    }
+                }
+            }
+            tokens.push((column, Token::Str(value)));
+        } else if c.is_ascii_digit() {
+            let start = i;
+            while chars.get(i).is_some_and(|c| c.is_ascii_digit()) {
+                i += 1;
+            }
+            let digits: String = chars[start..i].iter().collect();
+            let number = digits
+                .parse()
+                .map_err(|_| error(column, format!("number {} is too large", digits)))?;
+            tokens.push((column, Token::Number(number)));
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while chars
+                .get(i)
+                .is_some_and(|c| c.is_alphanumeric() || *c == '_')
+            {
+                i += 1;
+            }
+            tokens.push((column, Token::Ident(chars[start..i].iter().collect())));
+        } else {
+            return Err(error(column, format!("unexpected character '{}'", c)));
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    position: usize,
+    /// Column just past the input, for errors at the end.
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(_, token)| token)
+    }
+
+    fn column(&self) -> usize {
+        self.tokens
+            .get(self.position)
+            .map_or(self.end, |(column, _)| *column)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .map(|(_, token)| token.clone());
+        self.position += 1;
+        token
+    }
+
+    fn or(&mut self) -> Result<Expr> {
+        let mut expr = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.next();
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr> {
+        let mut expr = self.unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.next();
+            expr = Expr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expr> {
+        if self.peek() == Some(&Token::Not) {
+            self.next();
+            return Ok(Expr::Not(Box::new(self.unary()?)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expr> {
+        let column = self.column();
+        match self.next() {
+            Some(Token::Open) => {
+                let expr = self.or()?;
+                if self.peek() != Some(&Token::Close) {
+                    return Err(error(self.column(), "expected ')'"));
+                }
+                self.next();
+                Ok(expr)
+            }
+            Some(Token::Ident(name)) => self.comparison(column, &name),
+            _ => Err(error(column, "expected a field, '!' or '('")),
+        }
+    }
+
+    fn comparison(&mut self, column: usize, name: &str) -> Result<Expr> {
+        if name == "merge" {
+            return Ok(Expr::Merge);
+        }
+
+        let text_field = match name {
+            "author" => Some(TextField::Author),
+            "subject" => Some(TextField::Subject),
+            "body" => Some(TextField::Body),
+            "message" => Some(TextField::Message),
+            "hash" => Some(TextField::Hash),
+            _ => None,
+        };
+        let line_field = match name {
+            "added" => Some(LineField::Added),
+            "deleted" => Some(LineField::Deleted),
+            "lines" => Some(LineField::Lines),
+            _ => None,
+        };
+        if text_field.is_none() && line_field.is_none() && name != "files" && name != "date" {
+            return Err(error(
+                column,
+                format!(
+                    "unknown field '{}' (expected author, subject, body, message, hash, files, added, deleted, lines, date or merge)",
+                    name
+                ),
+            ));
+        }
+
+        let op_column = self.column();
+        let Some(Token::Op(op)) = self.next() else {
+            return Err(error(
+                op_column,
+                format!("expected an operator after '{}'", name),
+            ));
+        };
+        let value_column = self.column();
+        let value = self.next();
+
+        match (value, text_field, line_field) {
+            (Some(Token::Str(value)), Some(field), _) => {
+                let (test, negated) = text_test(op_column, op, &value, false)?;
+                Ok(negate(Expr::Text(field, test), negated))
+            }
+            (Some(Token::Str(value)), _, _) if name == "files" => {
+                let (test, negated) = text_test(op_column, op, &value, true)?;
+                Ok(negate(Expr::Files(test), negated))
+            }
+            (Some(Token::Number(value)), _, _) if name == "files" => {
+                Ok(Expr::FileCount(comparison(op_column, op)?, value))
+            }
+            (Some(Token::Number(value)), _, Some(field)) => {
+                Ok(Expr::Lines(field, comparison(op_column, op)?, value))
+            }
+            (Some(Token::Str(value)), _, _) if name == "date" => {
+                let date = NaiveDate::parse_from_str(&value, "%Y-%m-%d").map_err(|_| {
+                    error(
+                        value_column,
+                        format!("'{}' is not a YYYY-MM-DD date", value),
+                    )
+                })?;
+                Ok(Expr::Date(comparison(op_column, op)?, date))
+            }
+            _ if line_field.is_some() => Err(error(value_column, "expected a number")),
+            _ if name == "date" => Err(error(value_column, "expected a quoted date")),
+            _ => Err(error(value_column, "expected a quoted string")),
+        }
+    }
+}
+
+fn negate(expr: Expr, negated: bool) -> Expr {
+    if negated {
+        Expr::Not(Box::new(expr))
+    } else {
+        expr
+    }
+}
+
+/// The string test for an operator, and whether it is negated (`!=`, `!~`).
+fn text_test(column: usize, op: &str, value: &str, paths: bool) -> Result<(TextTest, bool)> {
+    let regex = || compile_regex(value, "filter", false);
+    match op {
+        "==" => Ok((TextTest::Equals(value.to_string()), false)),
+        "!=" => Ok((TextTest::Equals(value.to_string()), true)),
+        "=~" => Ok((TextTest::Matches(regex()?), false)),
+        "!~" => Ok((TextTest::Matches(regex()?), true)),
+        "~" if paths => Ok((
+            TextTest::Glob(exclude::glob_set(&[value.to_string()], "filter")?),
+            false,
+        )),
+        "~" => {
+            let glob = Glob::new(value).with_context(ReportError::parse, || {
+                format!("Invalid filter pattern '{}'", value)
+            })?;
+            let globs = GlobSetBuilder::new()
+                .add(glob)
+                .build()
+                .context(ReportError::parse, "Failed to compile filter pattern")?;
+            Ok((TextTest::Glob(globs), false))
+        }
+        _ => Err(error(
+            column,
+            format!("'{}' does not apply to text (use ==, !=, =~, !~ or ~)", op),
+        )),
+    }
+}
+
+/// Compiles a regex, naming where it came from in errors, e.g. `filter`.
+pub(crate) fn compile_regex(pattern: &str, kind: &str, case_insensitive: bool) -> Result<Regex> {
+    // The syntax error explains the problem; the build error only says
+    // which pattern failed.
+    Regex::builder()
+        .syntax(syntax::Config::new().case_insensitive(case_insensitive))
+        .build(pattern)
+        .map_err(|error| {
+            let reason = match error.syntax_error() {
+                Some(syntax) => syntax.to_string(),
+                None => error.to_string(),
+            };
+            ReportError::parse(format!(
+                "Invalid regex '{}' in {}: {}",
+                pattern, kind, reason
+            ))
+        })
+}
+
+fn comparison(column: usize, op: &str) -> Result<Comparison> {
+    match op {
+        "==" => Ok(Comparison::Eq),
+        "!=" => Ok(Comparison::Ne),
+        "<" => Ok(Comparison::Lt),
+        "<=" => Ok(Comparison::Le),
+        ">" => Ok(Comparison::Gt),
+        ">=" => Ok(Comparison::Ge),
+        _ => Err(error(
+            column,
+            format!(
+                "'{}' does not apply to numbers and dates (use ==, !=, <, <=, > or >=)",
+                op
+            ),
+        )),
+    }
+}
+
+impl TextTest {
+    fn matches(&self, value: &str, prefix: bool) -> bool {
+        match self {
+            TextTest::Equals(expected) if prefix => value.starts_with(expected.as_str()),
+            TextTest::Equals(expected) => value == expected,
+            TextTest::Matches(regex) => regex.is_match(value),
+            TextTest::Glob(globs) => globs.is_match(value),
+        }
+    }
+}
+
+impl Filter {
+    pub fn parse(input: &str) -> Result<Self> {
+        let tokens = tokenize(input)?;
+        if tokens.is_empty() {
+            return Err(error(1, "the expression is empty"));
+        }
+        let mut parser = Parser {
+            tokens,
+            position: 0,
+            end: input.chars().count() + 1,
+        };
+        let expr = parser.or()?;
+        if parser.peek().is_some() {
+            return Err(error(parser.column(), "expected '&&', '||' or the end"));
+        }
+        Ok(Self { expr })
+    }
+
+    pub fn matches(&self, commit: &Commit) -> bool {
+        evaluate(&self.expr, commit)
+    }
+}
+
+fn evaluate(expr: &Expr, commit: &Commit) -> bool {
+    match expr {
+        Expr::And(left, right) => evaluate(left, commit) && evaluate(right, commit),
+        Expr::Or(left, right) => evaluate(left, commit) || evaluate(right, commit),
+        Expr::Not(inner) => !evaluate(inner, commit),
+        Expr::Merge => commit.is_merge(),
+        Expr::Text(field, test) => match field {
+            TextField::Author => test.matches(&commit.author, false),
+            TextField::Subject => test.matches(&commit.subject, false),
+            TextField::Body => test.matches(&commit.body, false),
+            TextField::Message => {
+                test.matches(&format!("{}\n\n{}", commit.subject, commit.body), false)
+            }
+            TextField::Hash => test.matches(&commit.hash, true),
+        },
+        Expr::Files(test) => commit
+            .files_changed
+            .iter()
+            .any(|path| test.matches(path, false)),
+        Expr::FileCount(comparison, count) => {
+            comparison.holds((commit.files_changed.len() as u64).cmp(count))
+        }
+        Expr::Lines(field, comparison, count) => {
+            let added: u64 = commit.file_stats.iter().map(|f| f.additions).sum();
+            let deleted: u64 = commit.file_stats.iter().map(|f| f.deletions).sum();
+            let lines = match field {
+                LineField::Added => added,
+                LineField::Deleted => deleted,
+                LineField::Lines => added + deleted,
+            };
+            comparison.holds(lines.cmp(count))
+        }
+        Expr::Date(comparison, date) => comparison.holds(commit.date.date_naive().cmp(date)),
+    }
+}
+
+/// Author names of common bots and automation accounts, for `--no-bots`.
+pub const BOT_AUTHORS: &[&str] = &[
+    r"\[bot\]$",
+    r"^dependabot",
+    r"^renovate",
+    r"^github-actions",
+    r"^gitlab-bot$",
+    r"^greenkeeper",
+    r"^snyk-bot$",
+    r"^pre-commit-ci",
+    r"^imgbot",
+    r"^allcontributors",
+    r"^mergify",
+    r"^semantic-release-bot$",
+    r"^copilot",
+];
+
+/// Authors whose commits are dropped from the range, matched as
+/// case-insensitive regexes anywhere in the author name.
+pub struct AuthorExclusions {
+    patterns: Vec<Regex>,
+}
+
+impl AuthorExclusions {
+    /// `patterns` are `--ignore-authors` values; `bots` adds [`BOT_AUTHORS`].
+    pub fn new(patterns: &[String], bots: bool) -> Result<Self> {
+        let builtin = BOT_AUTHORS.iter().filter(|_| bots).map(|p| p.to_string());
+        let patterns = patterns
+            .iter()
+            .cloned()
+            .chain(builtin)
+            .map(|pattern| compile_regex(&pattern, "--ignore-authors", true))
+            .collect::<Result<_>>()?;
+        Ok(Self { patterns })
+    }
+
+    pub fn is_ignored(&self, author: &str) -> bool {
+        self.patterns.iter().any(|regex| regex.is_match(author))
+    }
+
+    /// Removes commits by ignored authors and returns how many were removed.
+    pub fn apply(&self, commits: &mut Vec<Commit>) -> usize {
+        let total = commits.len();
+        commits.retain(|commit| !self.is_ignored(&commit.author));
+        total - commits.len()
+    }
+}
+
+/// Clap value parser for `--ignore-authors`.
+pub fn parse_author_pattern(pattern: &str) -> std::result::Result<String, String> {
+    AuthorExclusions::new(&[pattern.to_string()], false)
+        .map(|_| pattern.to_string())
+        .map_err(|error| error.to_string())
+}
+
+/// Clap value parser that rejects malformed expressions up front.
+pub fn parse_expression(expr: &str) -> std::result::Result<String, String> {
+    Filter::parse(expr)
+        .map(|_| expr.to_string())
+        .map_err(|error| match std::error::Error::source(&error) {
+            Some(source) => format!("{}: {}", error, source),
+            None => error.to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+
+    use super::*;
+    use crate::git::{FileStat, FileStatus};
+
+    fn commit() -> Commit {
+        Commit {
+            hash: "4f3c2a1b9e8d7c6b5a4f3e2d1c0b9a8f7e6d5c4b".to_string(),
+            author: "Alice Example".to_string(),
+            email: "alice@example.com".to_string(),
+            date: DateTime::parse_from_rfc3339("2024-03-15T23:30:00-05:00").unwrap(),
+            subject: "fix: handle empty ranges".to_string(),
+            body: "Closes #12".to_string(),
+            files_changed: vec!["src/git.rs".to_string(), "README.md".to_string()],
+            file_stats: vec![
+                FileStat {
+                    path: "src/git.rs".to_string(),
+                    additions: 10,
+                    deletions: 2,
+                    binary: false,
+                    status: FileStatus::default(),
+                    old_path: None,
+                },
+                FileStat {
+                    path: "README.md".to_string(),
+                    additions: 3,
+                    deletions: 0,
+                    binary: false,
+                    status: FileStatus::default(),
+                    old_path: None,
+                },
+            ],
+            parents: vec!["1111111".to_string()],
+            signature: None,
+            labels: Vec::new(),
+            tags: Vec::new(),
+            branches: Vec::new(),
+        }
+    }
+
+    fn matches(expr: &str) -> bool {
+        match Filter::parse(expr) {
+            Ok(filter) => filter.matches(&commit()),
+            Err(error) => panic!("'{}' did not parse: {}", expr, error),
+        }
+    }
+
+    fn parse_error(expr: &str) -> String {
+        match Filter::parse(expr) {
+            Ok(_) => panic!("'{}' parsed", expr),
+            Err(error) => error.to_string(),
+        }
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        // Read as `merge || (author ... && subject ...)`.
+        assert!(matches(
+            r#"merge || author == "Alice Example" && subject =~ "^fix""#
+        ));
+        assert!(!matches(r#"merge || author == "Bob" && subject =~ "^fix""#));
+        assert!(!matches(
+            r#"(merge || author == "Alice Example") && subject =~ "^feat""#
+        ));
+        assert!(matches(r#"subject =~ "^feat" && merge || added > 5"#));
+    }
+
+    #[test]
+    fn not_binds_to_the_next_term() {
+        assert!(matches("!merge"));
+        assert!(!matches("!!merge"));
+        // `!merge && x` is `(!merge) && x`, not `!(merge && x)`.
+        assert!(!matches(r#"!merge && author == "Bob""#));
+        assert!(matches(r#"!(merge && author == "Bob")"#));
+    }
+
+    #[test]
+    fn negated_operators_are_not_read_as_not() {
+        assert!(matches(r#"author != "Bob""#));
+        assert!(matches(r#"subject !~ "^feat""#));
+        assert!(!matches(r#"files !~ "\.rs$""#));
+    }
+
+    #[test]
+    fn hash_matches_by_prefix() {
+        assert!(matches(r#"hash == "4f3c2a1""#));
+        assert!(matches(
+            r#"hash == "4f3c2a1b9e8d7c6b5a4f3e2d1c0b9a8f7e6d5c4b""#
+        ));
+        assert!(!matches(r#"hash == "3c2a1b""#));
+        assert!(matches(r#"hash != "3c2a1b""#));
+        // Only the hash matches by prefix.
+        assert!(!matches(r#"author == "Alice""#));
+    }
+
+    #[test]
+    fn identifiers_include_underscores() {
+        // `merge_x` is one unknown field, not `merge` followed by `_x`.
+        assert!(parse_error("merge_x").contains("unknown field 'merge_x'"));
+        assert!(parse_error(r#"_author == "x""#).contains("unknown field '_author'"));
+        assert!(parse_error("merge && files_count > 1").contains("column 10"));
+    }
+
+    #[test]
+    fn files_match_any_path_or_count() {
+        assert!(matches(r#"files ~ "src/**""#));
+        assert!(matches(r#"files == "README.md""#));
+        assert!(!matches(r#"files ~ "tests/**""#));
+        assert!(matches("files == 2 && files >= 2 && files < 3"));
+        assert!(matches("added == 13 && deleted == 2 && lines == 15"));
+        assert!(!matches("lines > 15"));
+    }
+
+    #[test]
+    fn message_joins_subject_and_body() {
+        assert!(matches(r#"message =~ "ranges\n\nCloses""#));
+        assert!(matches(r#"body == "Closes #12""#));
+        assert!(matches(r#"subject ~ "fix:*""#));
+    }
+
+    #[test]
+    fn dates_compare_the_author_local_day() {
+        // 23:30 at -05:00 is already the 16th in UTC.
+        assert!(matches(r#"date == "2024-03-15""#));
+        assert!(matches(r#"date >= "2024-03-01" && date < "2024-04-01""#));
+        assert!(!matches(r#"date > "2024-03-15""#));
+        assert!(parse_error(r#"date == "2024-13-01""#)
+            .contains("column 9: '2024-13-01' is not a YYYY-MM-DD date"));
+        assert!(parse_error("date == 2024").contains("column 9: expected a quoted date"));
+        assert!(parse_error(r#"date =~ "2024-03-15""#).contains("column 6: '=~' does not apply"));
+    }
+
+    #[test]
+    fn strings_escape_only_the_closing_quote() {
+        assert!(matches(r#"subject =~ '^fix: \w+'"#));
+        assert!(matches(r#"author != "Al\"ice""#));
+        assert!(parse_error(r#"author == "Alice"#).contains("column 11: unterminated string"));
+    }
+
+    #[test]
+    fn errors_name_the_column() {
+        assert_eq!(
+            parse_error(""),
+            "Invalid filter at column 1: the expression is empty"
+        );
+        assert!(parse_error("added > ").contains("column 9: expected a number"));
+        assert!(parse_error("author").contains("column 7: expected an operator after 'author'"));
+        assert!(parse_error("author > \"x\"").contains("column 8: '>' does not apply to text"));
+        assert!(parse_error("added ~ 3").contains("column 7: '~' does not apply to numbers"));
+        assert!(parse_error("(merge").contains("column 7: expected ')'"));
+        assert!(parse_error("merge merge").contains("column 7: expected '&&', '||' or the end"));
+        assert!(parse_error("merge & merge").contains("column 7: unexpected character '&'"));
+        assert!(parse_error("&& merge").contains("column 1: expected a field, '!' or '('"));
+        // Columns count characters, not bytes.
+        assert!(parse_error("author == \"é\" #").contains("column 15: unexpected character '#'"));
+    }
+
+    #[test]
+    fn invalid_regexes_are_rejected() {
+        assert!(parse_error(r#"subject =~ "(""#).starts_with("Invalid regex '(' in filter"));
+        assert!(parse_expression(r#"subject =~ "fix""#).is_ok());
+    }
+}
@@ -0,0 +1,802 @@
+use clap::ValueEnum;
+#[cfg(feature = "forge")]
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use crate::{
+    dates::DateStyle,
+    error::{ReportError, Result},
+    git::Commit,
+    i18n::Locale,
+    report::{Block, Section},
+};
+#[cfg(feature = "forge")]
+use crate::{
+    diagnostics::{self, IssueKind},
+    error::Context,
+    warnings,
+};
+
+/// Words that close the issue they precede on GitHub and GitLab, as in
+/// `Closes #12` or `fixes: #3, #4`.
+const CLOSING_KEYWORDS: &[&str] = &[
+    "close",
+    "closes",
+    "closed",
+    "closing",
+    "fix",
+    "fixes",
+    "fixed",
+    "fixing",
+    "resolve",
+    "resolves",
+    "resolved",
+    "resolving",
+    "implement",
+    "implements",
+    "implemented",
+    "implementing",
+];
+
+/// The code hosting service behind a git remote.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForgeKind {
+    /// GitHub or GitHub Enterprise (pull requests, GITHUB_TOKEN)
+    Github,
+    /// GitLab.com or a self-hosted GitLab (merge requests, GITLAB_TOKEN)
+    Gitlab,
+}
+
+/// A project on a forge, derived from a remote URL.
+pub struct Forge {
+    pub kind: ForgeKind,
+    pub host: String,
+    /// `owner/repo` on GitHub, `group/subgroup/project` on GitLab.
+    pub project: String,
+}
+
+/// A merge or pull request, or an issue closed by a commit, mentioned in a
+/// commit message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Reference {
+    Request(u64),
+    Issue(u64),
+}
+
+impl Reference {
+    /// How the forge writes the reference: `!12` for GitLab merge requests,
+    /// `#12` otherwise.
+    pub fn label(&self, kind: ForgeKind) -> String {
+        match (self, kind) {
+            (Reference::Request(n), ForgeKind::Gitlab) => format!("!{}", n),
+            (Reference::Request(n), ForgeKind::Github) | (Reference::Issue(n), _) => {
+                format!("#{}", n)
+            }
+        }
+    }
+}
+
+/// Title and state of a referenced request or issue from the forge API.
+pub struct LinkedItem {
+    pub title: String,
+    pub state: String,
+    pub url: String,
+}
+
+/// Splits `https://host/owner/repo.git`, `ssh://git@host:22/owner/repo` and
+/// scp-like `git@host:owner/repo.git` URLs into host and project path.
+pub fn parse_remote_url(url: &str) -> Option<(String, String)> {
+    let url = url.trim();
+    let (host, path) = match url.split_once("://") {
+        Some((_, rest)) => {
+            let (authority, path) = rest.split_once('/')?;
+            let host = authority.rsplit('@').next()?;
+            (host.split(':').next()?, path)
+        }
+        None => {
+            let (authority, path) = url.split_once(':')?;
+            (authority.rsplit('@').next()?, path)
+        }
+    };
+
+    let project = path.trim_matches('/').trim_end_matches(".git");
+    if host.is_empty() || !project.contains('/') {
+        return None;
+    }
+    Some((host.to_lowercase(), project.to_string()))
+}
+
+impl Forge {
+    /// Recognizes GitHub and GitLab by host name; `kind` overrides the guess
+    /// for self-hosted instances with other names.
+    pub fn from_remote_url(url: &str, kind: Option<ForgeKind>) -> Result<Self> {
+        let (host, project) = parse_remote_url(url).ok_or_else(|| {
+            ReportError::parse(format!(
+                "Cannot read a forge project from remote URL {}",
+                url
+            ))
+        })?;
+        let kind = match kind {
+            Some(kind) => kind,
+            None if host.contains("github") => ForgeKind::Github,
+            None if host.contains("gitlab") => ForgeKind::Gitlab,
+            None => {
+                return Err(ReportError::parse(format!(
+                    "Cannot tell whether {} is GitHub or GitLab; pass --forge",
+                    host
+                )))
+            }
+        };
+
+        Ok(Self {
+            kind,
+            host,
+            project,
+        })
+    }
+
+    /// The ref the forge publishes a pull or merge request's head under.
+    pub fn head_ref(&self, number: u64) -> String {
+        match self.kind {
+            ForgeKind::Github => format!("refs/pull/{}/head", number),
+            ForgeKind::Gitlab => format!("refs/merge-requests/{}/head", number),
+        }
+    }
+
+    /// What the forge calls a pull request, for messages.
+    pub fn request_name(&self) -> &'static str {
+        match self.kind {
+            ForgeKind::Github => "pull request",
+            ForgeKind::Gitlab => "merge request",
+        }
+    }
+
+    /// The web page of a referenced request or issue.
+    pub fn web_url(&self, reference: Reference) -> String {
+        match (self.kind, reference) {
+            (ForgeKind::Github, Reference::Request(n)) => {
+                format!("https://{}/{}/pull/{}", self.host, self.project, n)
+            }
+            (ForgeKind::Github, Reference::Issue(n)) => {
+                format!("https://{}/{}/issues/{}", self.host, self.project, n)
+            }
+            (ForgeKind::Gitlab, Reference::Request(n)) => {
+                format!(
+                    "https://{}/{}/-/merge_requests/{}",
+                    self.host, self.project, n
+                )
+            }
+            (ForgeKind::Gitlab, Reference::Issue(n)) => {
+                format!("https://{}/{}/-/issues/{}", self.host, self.project, n)
+            }
+        }
+    }
+
+    /// Finds merge request references (`!123`, or `group/project!123` as in
+    /// GitLab's merge commits) and issues closed with a keyword
+    /// (`Closes #12`) in a commit message. On GitHub, where pull requests
+    /// share the issue numbers, any other `#123` is taken as a pull request.
+    /// References to other projects are skipped.
+    pub fn references(&self, text: &str) -> Vec<Reference> {
+        let mut references = Vec::new();
+        let mut closing = false;
+
+        for word in text.split_whitespace() {
+            let word = word
+                .trim_start_matches(['(', '['])
+                .trim_end_matches([',', '.', ';', ':', ')', ']']);
+            if CLOSING_KEYWORDS.contains(&word.to_lowercase().as_str()) {
+                closing = true;
+                continue;
+            }
+
+            let Some(marker) = word.rfind(['!', '#']) else {
+                closing = closing && word.eq_ignore_ascii_case("and");
+                continue;
+            };
+            let (prefix, digits) = (&word[..marker], &word[marker + 1..]);
+            let project = prefix.is_empty() || prefix.eq_ignore_ascii_case(&self.project);
+            let number = match digits.parse::<u64>() {
+                Ok(number) if project && digits.bytes().all(|b| b.is_ascii_digit()) => number,
+                _ => {
+                    closing = false;
+                    continue;
+                }
+            };
+
+            let reference = match (&word[marker..marker + 1], self.kind) {
+                ("#", _) if closing => Reference::Issue(number),
+                ("#", ForgeKind::Github) => Reference::Request(number),
+                ("!", ForgeKind::Gitlab) => Reference::Request(number),
+                _ => continue,
+            };
+            if !references.contains(&reference) {
+                references.push(reference);
+            }
+        }
+
+        references
+    }
+
+    #[cfg(feature = "forge")]
+    fn api_url(&self) -> String {
+        match self.kind {
+            ForgeKind::Github if self.host == "github.com" => "https://api.github.com".to_string(),
+            ForgeKind::Github => format!("https://{}/api/v3", self.host),
+            ForgeKind::Gitlab => format!("https://{}/api/v4", self.host),
+        }
+    }
+
+    /// The API token from `GITHUB_TOKEN` or `GITLAB_TOKEN`; public projects
+    /// can be read without one.
+    pub fn token(&self) -> Option<String> {
+        std::env::var(self.token_variable())
+            .ok()
+            .filter(|token| !token.is_empty())
+    }
+
+    pub fn token_variable(&self) -> &'static str {
+        match self.kind {
+            ForgeKind::Github => "GITHUB_TOKEN",
+            ForgeKind::Gitlab => "GITLAB_TOKEN",
+        }
+    }
+}
+
+/// A review decision on a pull request, e.g. `alice: approved`.
+pub struct Review {
+    pub reviewer: String,
+    pub state: String,
+}
+
+/// Pull or merge request metadata from the forge API.
+pub struct PullRequest {
+    pub number: u64,
+    pub title: String,
+    pub description: String,
+    pub author: String,
+    pub state: String,
+    pub draft: bool,
+    pub base_branch: String,
+    pub base_sha: String,
+    pub head_branch: String,
+    pub head_sha: String,
+    pub url: String,
+    pub created_at: Option<String>,
+    pub merged_at: Option<String>,
+    pub labels: Vec<String>,
+    pub reviews: Vec<Review>,
+}
+
+/// Marks the comment `--post-pr-comment` writes, so later runs update it
+/// instead of adding another.
+pub const COMMENT_MARKER: &str = "<!-- git-report -->";
+
+/// Comments longer than this are cut; GitHub rejects bodies over 65536
+/// characters and GitLab notes over 1000000.
+const MAX_COMMENT_CHARS: usize = 60_000;
+
+/// The body of the report comment: the marker, then the Markdown summary,
+/// cut at a line boundary when it is too long for a comment.
+pub fn comment_body(summary: &str) -> String {
+    let summary = summary.trim();
+    if summary.chars().count() <= MAX_COMMENT_CHARS {
+        return format!("{}\n{}\n", COMMENT_MARKER, summary);
+    }
+    let cut: String = summary.chars().take(MAX_COMMENT_CHARS).collect();
+    let cut = cut.rsplit_once('\n').map_or(cut.as_str(), |(kept, _)| kept);
+    format!(
+        "{}\n{}\n\n_The summary was cut to fit in a comment; see the full report for the rest._\n",
+        COMMENT_MARKER, cut
+    )
+}
+
+#[cfg(feature = "forge")]
+impl Forge {
+    fn get(&self, client: &reqwest::blocking::Client, path: &str) -> Result<Value> {
+        self.send(client, reqwest::Method::GET, path, None)
+    }
+
+    fn send(
+        &self,
+        client: &reqwest::blocking::Client,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<&Value>,
+    ) -> Result<Value> {
+        let url = format!("{}{}", self.api_url(), path);
+        let mut request = client
+            .request(method, &url)
+            .header("User-Agent", "git-report");
+        if let Some(body) = body {
+            request = request.json(body);
+        }
+        request = match (self.kind, self.token()) {
+            (ForgeKind::Github, Some(token)) => request
+                .bearer_auth(token)
+                .header("Accept", "application/vnd.github+json"),
+            (ForgeKind::Github, None) => request.header("Accept", "application/vnd.github+json"),
+            (ForgeKind::Gitlab, Some(token)) => request.header("PRIVATE-TOKEN", token),
+            (ForgeKind::Gitlab, None) => request,
+        };
+
+        let response = request.send().with_context(ReportError::delivery, || {
+            format!("Failed to connect to {}", self.host)
+        })?;
+        if !response.status().is_success() {
+            return Err(ReportError::delivery(format!(
+                "{} returned status {} for {}",
+                self.host,
+                response.status(),
+                url
+            )));
+        }
+
+        response.json().with_context(ReportError::parse, || {
+            format!("Failed to parse the response of {}", url)
+        })
+    }
+
+    /// Fetches a pull request (GitHub) or merge request (GitLab) with its
+    /// reviews or approvals.
+    pub fn pull_request(&self, number: u64) -> Result<PullRequest> {
+        let client = http_client()?;
+        let text = |value: &Value| value.as_str().unwrap_or_default().to_string();
+
+        match self.kind {
+            ForgeKind::Github => {
+                let base = format!("/repos/{}/pulls/{}", self.project, number);
+                let pull = self.get(&client, &base)?;
+                let reviews = self.get(&client, &format!("{}/reviews?per_page=100", base))?;
+
+                // Each reviewer's latest decision counts; later comments do
+                // not replace an approval.
+                let mut latest: Vec<Review> = Vec::new();
+                for review in reviews.as_array().into_iter().flatten() {
+                    let reviewer = text(&review["user"]["login"]);
+                    let state = text(&review["state"]).to_lowercase().replace('_', " ");
+                    if state == "pending" {
+                        continue;
+                    }
+                    match latest.iter_mut().find(|r| r.reviewer == reviewer) {
+                        Some(existing) if state != "commented" => existing.state = state,
+                        Some(_) => {}
+                        None => latest.push(Review { reviewer, state }),
+                    }
+                }
+
+                let merged = pull["merged_at"].is_string();
+                Ok(PullRequest {
+                    number,
+                    title: text(&pull["title"]),
+                    description: text(&pull["body"]),
+                    author: text(&pull["user"]["login"]),
+                    state: if merged {
+                        "merged".to_string()
+                    } else {
+                        text(&pull["state"])
+                    },
+                    draft: pull["draft"].as_bool().unwrap_or(false),
+                    base_branch: text(&pull["base"]["ref"]),
+                    base_sha: text(&pull["base"]["sha"]),
+                    head_branch: text(&pull["head"]["ref"]),
+                    head_sha: text(&pull["head"]["sha"]),
+                    url: text(&pull["html_url"]),
+                    created_at: pull["created_at"].as_str().map(str::to_string),
+                    merged_at: pull["merged_at"].as_str().map(str::to_string),
+                    labels: pull["labels"]
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .map(|label| text(&label["name"]))
+                        .collect(),
+                    reviews: latest,
+                })
+            }
+            ForgeKind::Gitlab => {
+                let base = format!(
+                    "/projects/{}/merge_requests/{}",
+                    encode_path(&self.project),
+                    number
+                );
+                let request = self.get(&client, &base)?;
+                let approvals = self.get(&client, &format!("{}/approvals", base))?;
+
+                Ok(PullRequest {
+                    number,
+                    title: text(&request["title"]),
+                    description: text(&request["description"]),
+                    author: text(&request["author"]["username"]),
+                    state: text(&request["state"]),
+                    draft: request["draft"].as_bool().unwrap_or(false),
+                    base_branch: text(&request["target_branch"]),
+                    base_sha: text(&request["diff_refs"]["base_sha"]),
+                    head_branch: text(&request["source_branch"]),
+                    head_sha: text(&request["sha"]),
+                    url: text(&request["web_url"]),
+                    created_at: request["created_at"].as_str().map(str::to_string),
+                    merged_at: request["merged_at"].as_str().map(str::to_string),
+                    labels: request["labels"]
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .map(text)
+                        .collect(),
+                    reviews: approvals["approved_by"]
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .map(|approval| Review {
+                            reviewer: text(&approval["user"]["username"]),
+                            state: "approved".to_string(),
+                        })
+                        .collect(),
+                })
+            }
+        }
+    }
+}
+
+#[cfg(feature = "forge")]
+impl Forge {
+    /// Fetches the title and state of a referenced request or issue. GitHub
+    /// serves both from its issues API; a `#123` that turns out to be an
+    /// issue there is returned as one.
+    fn linked_item(
+        &self,
+        client: &reqwest::blocking::Client,
+        reference: Reference,
+    ) -> Result<(Reference, LinkedItem)> {
+        let text = |value: &Value| value.as_str().unwrap_or_default().to_string();
+        match self.kind {
+            ForgeKind::Github => {
+                let number = match reference {
+                    Reference::Request(n) | Reference::Issue(n) => n,
+                };
+                let item = self.get(
+                    client,
+                    &format!("/repos/{}/issues/{}", self.project, number),
+                )?;
+                let pull = &item["pull_request"];
+                let reference = match reference {
+                    Reference::Request(n) if pull.is_null() => Reference::Issue(n),
+                    reference => reference,
+                };
+                Ok((
+                    reference,
+                    LinkedItem {
+                        title: text(&item["title"]),
+                        state: if pull["merged_at"].is_string() {
+                            "merged".to_string()
+                        } else {
+                            text(&item["state"])
+                        },
+                        url: text(&item["html_url"]),
+                    },
+                ))
+            }
+            ForgeKind::Gitlab => {
+                let path = match reference {
+                    Reference::Request(n) => format!("merge_requests/{}", n),
+                    Reference::Issue(n) => format!("issues/{}", n),
+                };
+                let item = self.get(
+                    client,
+                    &format!("/projects/{}/{}", encode_path(&self.project), path),
+                )?;
+                Ok((
+                    reference,
+                    LinkedItem {
+                        title: text(&item["title"]),
+                        state: text(&item["state"]),
+                        url: text(&item["web_url"]),
+                    },
+                ))
+            }
+        }
+    }
+
+    /// Posts `body` as a comment on a pull or merge request, or replaces the
+    /// text of the comment an earlier run posted (the one containing
+    /// [`COMMENT_MARKER`]). Returns the comment's web address.
+    pub fn post_comment(&self, number: u64, body: &str) -> Result<String> {
+        if self.token().is_none() {
+            return Err(ReportError::delivery(format!(
+                "Commenting on a {} needs an API token in {}",
+                self.request_name(),
+                self.token_variable()
+            )));
+        }
+        let client = http_client()?;
+        let payload = serde_json::json!({ "body": body });
+        let id = |comment: &Value| comment["id"].as_u64();
+
+        match self.kind {
+            ForgeKind::Github => {
+                let comments = self.get(
+                    &client,
+                    &format!(
+                        "/repos/{}/issues/{}/comments?per_page=100",
+                        self.project, number
+                    ),
+                )?;
+                let comment = match find_marked(&comments).and_then(id) {
+                    Some(comment_id) => self.send(
+                        &client,
+                        reqwest::Method::PATCH,
+                        &format!("/repos/{}/issues/comments/{}", self.project, comment_id),
+                        Some(&payload),
+                    )?,
+                    None => self.send(
+                        &client,
+                        reqwest::Method::POST,
+                        &format!("/repos/{}/issues/{}/comments", self.project, number),
+                        Some(&payload),
+                    )?,
+                };
+                Ok(comment["html_url"].as_str().unwrap_or_default().to_string())
+            }
+            ForgeKind::Gitlab => {
+                let notes = format!(
+                    "/projects/{}/merge_requests/{}/notes",
+                    encode_path(&self.project),
+                    number
+                );
+                let existing = self.get(&client, &format!("{}?per_page=100", notes))?;
+                let note = match find_marked(&existing).and_then(id) {
+                    Some(note_id) => self.send(
+                        &client,
+                        reqwest::Method::PUT,
+                        &format!("{}/{}", notes, note_id),
+                        Some(&payload),
+                    )?,
+                    None => self.send(&client, reqwest::Method::POST, &notes, Some(&payload))?,
+                };
+                Ok(format!(
+                    "{}#note_{}",
+                    self.web_url(Reference::Request(number)),
+                    id(&note).unwrap_or_default()
+                ))
+            }
+        }
+    }
+
+    /// Looks up every reference, keyed by the reference as written; ones that
+    /// cannot be fetched are reported as a warning and left out of the result.
+    fn linked_items(
+        &self,
+        references: &[Reference],
+    ) -> Result<BTreeMap<Reference, (Reference, LinkedItem)>> {
+        let client = http_client()?;
+        let mut items = BTreeMap::new();
+        for &reference in references {
+            match self.linked_item(&client, reference) {
+                Ok(item) => {
+                    items.insert(reference, item);
+                }
+                Err(e) => {
+                    warnings::warn(format!(
+                        "could not fetch {}: {:#}",
+                        reference.label(self.kind),
+                        e
+                    ));
+                    diagnostics::record(
+                        IssueKind::LookupFailed,
+                        None,
+                        format!("{}: {:#}", reference.label(self.kind), e),
+                    );
+                }
+            }
+        }
+        Ok(items)
+    }
+}
+
+/// The comment an earlier `--post-pr-comment` run wrote.
+#[cfg(feature = "forge")]
+fn find_marked(comments: &Value) -> Option<&Value> {
+    comments.as_array()?.iter().find(|comment| {
+        comment["body"]
+            .as_str()
+            .is_some_and(|body| body.contains(COMMENT_MARKER))
+    })
+}
+
+#[cfg(feature = "forge")]
+fn http_client() -> Result<reqwest::blocking::Client> {
+    reqwest::blocking::Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .context(ReportError::delivery, "Failed to create HTTP client")
+}
+
+#[cfg(not(feature = "forge"))]
+impl Forge {
+    pub fn pull_request(&self, _number: u64) -> Result<PullRequest> {
+        Err(crate::features::unavailable(
+            "Pull request reports",
+            "forge",
+        ))
+    }
+
+    pub fn post_comment(&self, _number: u64, _body: &str) -> Result<String> {
+        Err(crate::features::unavailable(
+            "Pull request comments",
+            "forge",
+        ))
+    }
+
+    fn linked_items(
+        &self,
+        _references: &[Reference],
+    ) -> Result<BTreeMap<Reference, (Reference, LinkedItem)>> {
+        Err(crate::features::unavailable(
+            "Merge request and issue lookups",
+            "forge",
+        ))
+    }
+}
+
+/// Percent-encodes a GitLab project path for use as a single URL segment.
+#[cfg(feature = "forge")]
+fn encode_path(path: &str) -> String {
+    path.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// Merge or pull requests and closed issues the commits mention, with their
+/// titles, states and links from the forge API, and the commits mentioning
+/// each of them.
+pub fn links_section(forge: &Forge, commits: &[Commit], locale: &Locale) -> Result<Section> {
+    let mut mentioned: BTreeMap<Reference, Vec<&Commit>> = BTreeMap::new();
+    let mut unlinked = 0;
+    for commit in commits {
+        let references = forge.references(&format!("{}\n{}", commit.subject, commit.body));
+        if references.is_empty() {
+            unlinked += 1;
+        }
+        for reference in references {
+            mentioned.entry(reference).or_default().push(commit);
+        }
+    }
+
+    let written: Vec<Reference> = mentioned.keys().copied().collect();
+    let mut items = forge.linked_items(&written)?;
+
+    // Regroup under what the forge says each reference is.
+    let mut rows: BTreeMap<Reference, (Option<LinkedItem>, Vec<&Commit>)> = BTreeMap::new();
+    for (reference, reference_commits) in mentioned {
+        let (resolved, item) = match items.remove(&reference) {
+            Some((resolved, item)) => (resolved, Some(item)),
+            None => (reference, None),
+        };
+        let row = rows.entry(resolved).or_insert((None, Vec::new()));
+        row.0 = row.0.take().or(item);
+        row.1.extend(reference_commits);
+    }
+
+    let requests = rows
+        .keys()
+        .filter(|r| matches!(r, Reference::Request(_)))
+        .count();
+    let table = rows
+        .into_iter()
+        .map(|(reference, (item, reference_commits))| {
+            let (title, state, url) = match item {
+                Some(item) => (item.title, item.state, item.url),
+                None => (
+                    "(unavailable)".to_string(),
+                    String::new(),
+                    forge.web_url(reference),
+                ),
+            };
+            vec![
+                reference.label(forge.kind),
+                match reference {
+                    Reference::Request(_) => forge.request_name().to_string(),
+                    Reference::Issue(_) => "issue".to_string(),
+                },
+                title,
+                state,
+                url,
+                reference_commits
+                    .iter()
+                    .map(|c| format!("{} {}", &c.hash[..8], c.subject))
+                    .collect::<Vec<_>>()
+                    .join("; "),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let (title, request_label) = match forge.kind {
+        ForgeKind::Github => ("pull-requests-and-issues", "pull-requests-referenced"),
+        ForgeKind::Gitlab => ("merge-requests-and-issues", "merge-requests-referenced"),
+    };
+    let mut section =
+        Section::new("forge_links", locale.text(title)).with_block(Block::Fields(vec![
+            (locale.text(request_label).to_string(), requests.to_string()),
+            (
+                locale.text("issues").to_string(),
+                (table.len() - requests).to_string(),
+            ),
+            (
+                locale.text("commits-without-reference").to_string(),
+                unlinked.to_string(),
+            ),
+        ]));
+    if !table.is_empty() {
+        section = section.with_block(Block::Table {
+            headers: ["reference", "type", "title", "state", "link", "commits"]
+                .map(|key| locale.text(key).to_string())
+                .to_vec(),
+            rows: table,
+        });
+    }
+
+    Ok(section)
+}
+
+/// The pull request's title, branches, state, labels and reviews, followed
+/// by its description.
+pub fn pull_request_section(pr: &PullRequest, dates: &DateStyle, locale: &Locale) -> Section {
+    let date = |value: &Option<String>| {
+        value.as_deref().map(|value| {
+            chrono::DateTime::parse_from_rfc3339(value)
+                .map(|date| dates.format(&date))
+                .unwrap_or_else(|_| value.to_string())
+        })
+    };
+
+    let label = |key: &str| locale.text(key).to_string();
+    let mut fields = vec![
+        (label("title"), pr.title.clone()),
+        (label("number"), pr.number.to_string()),
+        (label("author"), pr.author.clone()),
+        (
+            label("state"),
+            if pr.draft {
+                locale.format("draft-state", &[("state", &pr.state)])
+            } else {
+                pr.state.clone()
+            },
+        ),
+        (
+            label("branches"),
+            format!("{} -> {}", pr.head_branch, pr.base_branch),
+        ),
+    ];
+    if let Some(created) = date(&pr.created_at) {
+        fields.push((label("opened"), created));
+    }
+    if let Some(merged) = date(&pr.merged_at) {
+        fields.push((label("merged"), merged));
+    }
+    if !pr.labels.is_empty() {
+        fields.push((label("labels"), pr.labels.join(", ")));
+    }
+    fields.push((label("link"), pr.url.clone()));
+
+    let mut section =
+        Section::new("pull_request", locale.text("pull-request")).with_block(Block::Fields(fields));
+    if !pr.reviews.is_empty() {
+        section = section.with_block(Block::List {
+            title: label("reviews"),
+            items: pr
+                .reviews
+                .iter()
+                .map(|review| format!("{}: {}", review.reviewer, review.state))
+                .collect(),
+        });
+    }
+    if !pr.description.trim().is_empty() {
+        section = section.with_block(Block::Text(pr.description.trim().to_string()));
+    }
+
+    section
+}
@@ -0,0 +1,1470 @@
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    io::{BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+    process::{ChildStdout, Command, Stdio},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Mutex,
+    },
+};
+
+use crate::{
+    diagnostics::{self, IssueKind},
+    error::{Context, ReportError, Result},
+    progress::Progress,
+};
+
+/// `git log` format with NUL-separated fields, so subjects may contain any
+/// printable character. `%aI` is strict ISO 8601 and parses as RFC 3339;
+/// `%D` lists the refs pointing at the commit, as in `HEAD -> main, tag: v1.0`.
+const LOG_FORMAT: &str = "--pretty=format:%H%x00%P%x00%aN%x00%aE%x00%aI%x00%D%x00%s";
+
+/// Maximum diff size per commit sent to the AI backend, to keep prompts bounded.
+const MAX_AI_DIFF_CHARS: usize = 4000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Commit {
+    pub hash: String,
+    pub author: String,
+    /// Author email; empty for datasets archived before emails were recorded.
+    #[serde(default)]
+    pub email: String,
+    /// Author date with the author's own UTC offset.
+    pub date: DateTime<FixedOffset>,
+    pub subject: String,
+    pub body: String,
+    pub files_changed: Vec<String>,
+    #[serde(default)]
+    pub file_stats: Vec<FileStat>,
+    /// Parent hashes; merges have more than one. Empty for root commits,
+    /// commits read from patches and datasets archived before parents were
+    /// recorded.
+    #[serde(default)]
+    pub parents: Vec<String>,
+    /// The signature check, filled in by [`signatures`]; `None` when the
+    /// commit was not checked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<Signature>,
+    /// The `[[labels]]` of the config that apply, filled in by
+    /// [`crate::labels::Labeler`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+    /// Tags pointing at the commit.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Local and remote-tracking branches pointing at the commit.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub branches: Vec<String>,
+}
+
+impl Commit {
+    pub fn is_merge(&self) -> bool {
+        self.parents.len() > 1
+    }
+}
+
+/// The result of verifying a commit's GPG, SSH or X.509 signature, with the
+/// key and the signer git reports for it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Signature {
+    pub status: SignatureStatus,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub key: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub signer: String,
+}
+
+/// The `%G?` letter of `git log`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SignatureStatus {
+    /// A valid signature (`G`).
+    Good,
+    /// A valid signature by a key of unknown trust (`U`).
+    UnknownValidity,
+    /// A valid signature that has expired (`X`).
+    Expired,
+    /// A valid signature by an expired key (`Y`).
+    ExpiredKey,
+    /// A valid signature by a revoked key (`R`).
+    RevokedKey,
+    /// A signature whose key is not available to check it (`E`).
+    Unverifiable,
+    /// A signature that does not match the commit (`B`).
+    Bad,
+    /// No signature (`N`).
+    Unsigned,
+}
+
+impl SignatureStatus {
+    pub fn from_letter(letter: &str) -> Self {
+        match letter {
+            "G" => SignatureStatus::Good,
+            "U" => SignatureStatus::UnknownValidity,
+            "X" => SignatureStatus::Expired,
+            "Y" => SignatureStatus::ExpiredKey,
+            "R" => SignatureStatus::RevokedKey,
+            "E" => SignatureStatus::Unverifiable,
+            "B" => SignatureStatus::Bad,
+            _ => SignatureStatus::Unsigned,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SignatureStatus::Good => "good",
+            SignatureStatus::UnknownValidity => "good, key of unknown validity",
+            SignatureStatus::Expired => "good, expired",
+            SignatureStatus::ExpiredKey => "good, key expired",
+            SignatureStatus::RevokedKey => "good, key revoked",
+            SignatureStatus::Unverifiable => "cannot be checked, key missing",
+            SignatureStatus::Bad => "bad",
+            SignatureStatus::Unsigned => "unsigned",
+        }
+    }
+
+    /// Whether the commit carries a signature at all, valid or not.
+    pub fn is_signed(self) -> bool {
+        self != SignatureStatus::Unsigned
+    }
+}
+
+impl Signature {
+    /// The status with the key and signer, e.g. `good (key 4AEE18F83AFDEB23, Jane Doe <jane@example.com>)`.
+    pub fn describe(&self) -> String {
+        let details: Vec<String> = [
+            (!self.key.is_empty()).then(|| format!("key {}", self.key)),
+            (!self.signer.is_empty()).then(|| self.signer.clone()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        if details.is_empty() {
+            self.status.label().to_string()
+        } else {
+            format!("{} ({})", self.status.label(), details.join(", "))
+        }
+    }
+}
+
+/// Lines added and deleted in one file of a commit; binary files have no line counts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileStat {
+    pub path: String,
+    pub additions: u64,
+    pub deletions: u64,
+    pub binary: bool,
+    /// Datasets archived before statuses were recorded read as modified.
+    #[serde(default)]
+    pub status: FileStatus,
+    /// The source path of a rename or copy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub old_path: Option<String>,
+}
+
+/// How a commit changed a file, from the `--name-status` letter.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileStatus {
+    Added,
+    #[default]
+    Modified,
+    Deleted,
+    Renamed,
+    Copied,
+}
+
+impl FileStatus {
+    /// Maps `A`, `D`, `R100`, `C75`, ...; type changes and other letters
+    /// count as modifications.
+    pub fn from_letter(status: &str) -> Self {
+        match status.chars().next() {
+            Some('A') => FileStatus::Added,
+            Some('D') => FileStatus::Deleted,
+            Some('R') => FileStatus::Renamed,
+            Some('C') => FileStatus::Copied,
+            _ => FileStatus::Modified,
+        }
+    }
+}
+
+impl FileStat {
+    /// A new file with the given counts and no rename or status information.
+    pub fn new(path: &str, additions: u64, deletions: u64, binary: bool) -> Self {
+        Self {
+            path: path.to_string(),
+            additions,
+            deletions,
+            binary,
+            status: FileStatus::Modified,
+            old_path: None,
+        }
+    }
+
+    /// Annotations shown after the path in file listings. Renames need none,
+    /// they are shown as `old -> new`.
+    pub fn markers(&self) -> Vec<&'static str> {
+        let mut markers = Vec::new();
+        match self.status {
+            FileStatus::Added => markers.push("added"),
+            FileStatus::Deleted => markers.push("deleted"),
+            FileStatus::Copied => markers.push("copied"),
+            FileStatus::Modified | FileStatus::Renamed => {}
+        }
+        if self.binary {
+            markers.push("binary");
+        }
+        markers
+    }
+
+    /// The path as shown in plain-text listings, e.g. `src/old.rs -> src/new.rs [binary]`.
+    pub fn label(&self) -> String {
+        let mut label = match &self.old_path {
+            Some(old_path) => format!("{} -> {}", old_path, self.path),
+            None => self.path.clone(),
+        };
+        for marker in self.markers() {
+            label.push_str(&format!(" [{}]", marker));
+        }
+        label
+    }
+}
+
+static NO_REPLACE_OBJECTS: AtomicBool = AtomicBool::new(false);
+static FIRST_PARENT: AtomicBool = AtomicBool::new(false);
+/// Zero for no cap.
+static MAX_COMMITS: AtomicUsize = AtomicUsize::new(0);
+static REPOSITORY: Mutex<Option<PathBuf>> = Mutex::new(None);
+static GIT_DIR: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Runs all following git commands in `dir` instead of the current directory.
+pub fn set_repository(dir: PathBuf) {
+    *REPOSITORY
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(dir);
+}
+
+/// Makes all following git commands read the repository at `dir`, like
+/// `git --git-dir`, e.g. a bare repository on a server.
+pub fn set_git_dir(dir: PathBuf) {
+    *GIT_DIR
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(dir);
+}
+
+/// Makes all following git commands ignore `refs/replace` and the grafts
+/// file, so reports reflect the real object graph (`--no-replace-objects`).
+pub fn set_no_replace_objects(enabled: bool) {
+    NO_REPLACE_OBJECTS.store(enabled, Ordering::Relaxed);
+}
+
+/// Makes range walks follow only the first parent of merges, so a merged
+/// branch shows up as its merge commit, with the changes of the whole branch,
+/// the way GitHub lists the history of the main branch (`--first-parent`).
+pub fn set_first_parent(enabled: bool) {
+    FIRST_PARENT.store(enabled, Ordering::Relaxed);
+}
+
+/// Makes collecting commits with their details fail once a range or history
+/// turns out to have more than `max` commits, stopping git right there
+/// (`--max-commits`).
+pub fn set_max_commits(max: Option<usize>) {
+    MAX_COMMITS.store(max.unwrap_or(0), Ordering::Relaxed);
+}
+
+/// `--first-parent` when [`set_first_parent`] is on, for `git log` and
+/// `git show`.
+fn first_parent_args() -> &'static [&'static str] {
+    if FIRST_PARENT.load(Ordering::Relaxed) {
+        &["--first-parent"]
+    } else {
+        &[]
+    }
+}
+
+/// A `git` command with the global options chosen through this module.
+fn git() -> Command {
+    let mut command = Command::new("git");
+    if let Some(dir) = &*REPOSITORY
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+    {
+        command.current_dir(dir);
+    }
+    if let Some(dir) = &*GIT_DIR
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+    {
+        command.arg("--git-dir").arg(dir);
+    }
+    if NO_REPLACE_OBJECTS.load(Ordering::Relaxed) {
+        command.arg("--no-replace-objects").env(
+            "GIT_GRAFT_FILE",
+            std::env::temp_dir().join("git-report-no-grafts"),
+        );
+    }
+    command
+}
+
+/// Mechanisms that make `git log` show a history different from the stored
+/// commit objects.
+pub struct HistoryRewrites {
+    /// Number of `refs/replace/*` refs.
+    pub replace_refs: usize,
+    /// Whether a non-empty `info/grafts` file exists.
+    pub grafts: bool,
+    /// Whether the repository is a shallow clone, whose boundary commits appear without parents.
+    pub shallow: bool,
+}
+
+/// Detects replace refs, grafts and shallow boundaries in the current repository.
+pub fn history_rewrites() -> Result<HistoryRewrites> {
+    let replace_refs = git_output(&["for-each-ref", "--format=%(refname)", "refs/replace/"])?
+        .lines()
+        .count();
+    // `--git-path info/grafts` would follow the GIT_GRAFT_FILE override set by `git()`.
+    let common_dir = git_output(&["rev-parse", "--git-common-dir"])?;
+    let grafts = Path::new(common_dir.trim())
+        .join("info/grafts")
+        .metadata()
+        .is_ok_and(|metadata| metadata.len() > 0);
+    let shallow = git_output(&["rev-parse", "--is-shallow-repository"])?.trim() == "true";
+
+    Ok(HistoryRewrites {
+        replace_refs,
+        grafts,
+        shallow,
+    })
+}
+
+/// Returns the work tree root, or the git directory of a bare repository.
+pub fn check_git_repository() -> Result<String> {
+    let output = git()
+        .args(["rev-parse", "--is-bare-repository"])
+        .output()
+        .context(
+            ReportError::git,
+            "Failed to execute git command. Make sure you're in a git repository.",
+        )?;
+
+    if !output.status.success() {
+        return Err(ReportError::git("Not in a git repository".to_string()));
+    }
+
+    let location = if String::from_utf8_lossy(&output.stdout).trim() == "true" {
+        "--absolute-git-dir"
+    } else {
+        "--show-toplevel"
+    };
+    Ok(git_output(&["rev-parse", location])?.trim().to_string())
+}
+
+/// Returns whether the repository has no work tree.
+pub fn is_bare_repository() -> Result<bool> {
+    Ok(git_output(&["rev-parse", "--is-bare-repository"])?.trim() == "true")
+}
+
+/// Returns false for a freshly initialized repository without any commits.
+pub fn has_commits() -> Result<bool> {
+    let output = git()
+        .args(["rev-parse", "--verify", "--quiet", "HEAD"])
+        .output()
+        .context(ReportError::git, "Failed to check repository HEAD")?;
+
+    Ok(output.status.success())
+}
+
+/// Runs `git log` with [`DETAILS_FORMAT`] and the given extra arguments, and
+/// collects full details for every listed commit. Messages and changed files
+/// come from the same git call, read as git prints them, so a range takes one
+/// process however many commits it has.
+fn log_commits(args: &[&str], context: &str) -> Result<Vec<Commit>> {
+    let max = MAX_COMMITS.load(Ordering::Relaxed);
+    let mut commits = Vec::new();
+    let mut progress = None;
+    // Merges list their files against the first parent, as in
+    // [`get_commit_details`].
+    let mut log_args = vec![
+        DETAILS_FORMAT,
+        "-z",
+        "--raw",
+        "--numstat",
+        "-M",
+        "-C",
+        "--diff-merges=first-parent",
+    ];
+    log_args.extend(args);
+    stream_log(&log_args, context, |stdout| {
+        let mut fields = BufReader::new(stdout).split(b'\0');
+        let mut next = || -> Result<Option<Vec<u8>>> {
+            fields.next().transpose().context(ReportError::git, context)
+        };
+        let mut current: Option<LoggedCommit> = None;
+        while let Some(field) = next()? {
+            let head = field.strip_prefix(b"\n").unwrap_or(&field);
+            if head.is_empty() {
+                continue;
+            }
+            let Some(logged) = current
+                .as_mut()
+                .filter(|_| head[0] == b':' || head.contains(&b'\t'))
+            else {
+                // Anything but a file record starts the next commit.
+                let mut header = vec![field];
+                for _ in 0..7 {
+                    header.push(next()?.ok_or_else(|| {
+                        ReportError::parse("Truncated commit in git log output".to_string())
+                    })?);
+                }
+                if let Some(done) = current.replace(LoggedCommit::new(header)?) {
+                    commits.push(done.finish());
+                    if commits.len() == 1 {
+                        progress = Some(Progress::counter("Collecting commits"));
+                    }
+                    if let Some(progress) = &progress {
+                        progress.inc();
+                    }
+                }
+                if max > 0 && commits.len() == max {
+                    return Err(ReportError::git(format!(
+                        "{}: more than {} commits, the --max-commits cap; narrow the range or raise the cap",
+                        context, max
+                    )));
+                }
+                continue;
+            };
+
+            let head = logged.text(head.to_vec());
+            let mut path = || -> Result<String> {
+                let field = next()?.ok_or_else(|| {
+                    ReportError::parse(format!("Truncated file record: {:?}", head))
+                })?;
+                Ok(logged.text(field))
+            };
+            if let Some(raw) = head.strip_prefix(':') {
+                // `:old-mode new-mode old-blob new-blob status`, then the paths.
+                let status = raw.rsplit(' ').next().unwrap_or_default().to_string();
+                if status.starts_with(['R', 'C']) {
+                    path()?; // the old path
+                }
+                let path = path()?;
+                logged.statuses.push((status, path));
+            } else {
+                let stat = numstat_record(&head, &mut path)?;
+                logged.stats.push(stat);
+            }
+        }
+        if let Some(done) = current {
+            commits.push(done.finish());
+        }
+        Ok(())
+    })?;
+
+    if let Some(progress) = &progress {
+        progress.finish();
+    }
+
+    Ok(commits)
+}
+
+/// `git log -z` format of [`log_commits`]: the fields of [`LOG_FORMAT`] and
+/// the whole message, each ended by NUL, ahead of the commit's file records.
+const DETAILS_FORMAT: &str = "--format=%H%x00%P%x00%aN%x00%aE%x00%aI%x00%D%x00%s%x00%B";
+
+/// A commit read by [`log_commits`] whose file records are still coming.
+struct LoggedCommit {
+    commit: Commit,
+    /// `--raw` statuses with the new path, matched up with `stats` at the end.
+    statuses: Vec<(String, String)>,
+    stats: Vec<FileStat>,
+    invalid_paths: bool,
+}
+
+impl LoggedCommit {
+    /// Starts a commit from its eight header fields, the message last.
+    fn new(mut header: Vec<Vec<u8>>) -> Result<Self> {
+        let mut message = header.pop().unwrap_or_default();
+        // `git show`, read by [`get_commit_details`], ends the message with a
+        // newline of its own; adding it here gives both the same body.
+        message.push(b'\n');
+        let line = header.join(&b'\0');
+        let mut commit = parse_log_line(&String::from_utf8_lossy(&line))?;
+        if std::str::from_utf8(&line).is_err() {
+            diagnostics::record(
+                IssueKind::InvalidUtf8,
+                Some(&commit.hash),
+                "author or subject is not valid UTF-8; invalid bytes were replaced",
+            );
+        }
+        commit.body = message_body(&commit_text(message, &commit.hash, "message"));
+        Ok(Self {
+            commit,
+            statuses: Vec::new(),
+            stats: Vec::new(),
+            invalid_paths: false,
+        })
+    }
+
+    /// Decodes a file record field, noting bytes that are not UTF-8 once per
+    /// commit.
+    fn text(&mut self, field: Vec<u8>) -> String {
+        String::from_utf8(field).unwrap_or_else(|error| {
+            self.invalid_paths = true;
+            String::from_utf8_lossy(error.as_bytes()).into_owned()
+        })
+    }
+
+    fn finish(self) -> Commit {
+        let Self {
+            mut commit,
+            statuses,
+            mut stats,
+            invalid_paths,
+        } = self;
+        if invalid_paths {
+            diagnostics::record(
+                IssueKind::InvalidUtf8,
+                Some(&commit.hash),
+                "changed file paths is not valid UTF-8; invalid bytes were replaced",
+            );
+        }
+        for (status, path) in statuses {
+            if let Some(stat) = stats.iter_mut().find(|stat| stat.path == path) {
+                stat.status = FileStatus::from_letter(&status);
+            }
+        }
+        commit.files_changed = stats.iter().map(|stat| stat.path.clone()).collect();
+        commit.file_stats = stats;
+        commit
+    }
+}
+
+/// Runs `git log` with [`LOG_FORMAT`] and returns the listed commits without
+/// body and files, which takes one git call however many commits there are.
+fn log_headers(args: &[&str], context: &str) -> Result<Vec<Commit>> {
+    let mut commits = Vec::new();
+    for_each_header(args, context, |commit| {
+        commits.push(commit);
+        Ok(())
+    })?;
+    Ok(commits)
+}
+
+/// Runs `git log` with [`LOG_FORMAT`] and hands each listed commit, without
+/// body and files, to `each` as git prints it.
+fn for_each_header(
+    args: &[&str],
+    context: &str,
+    mut each: impl FnMut(Commit) -> Result<()>,
+) -> Result<()> {
+    let mut log_args = vec![LOG_FORMAT];
+    log_args.extend(args);
+    stream_log(&log_args, context, |stdout| {
+        BufReader::new(stdout).split(b'\n').try_for_each(|line| {
+            let line = line.context(ReportError::git, context)?;
+            if line.is_empty() {
+                return Ok(());
+            }
+            let commit = parse_log_line(&String::from_utf8_lossy(&line))?;
+            if std::str::from_utf8(&line).is_err() {
+                diagnostics::record(
+                    IssueKind::InvalidUtf8,
+                    Some(&commit.hash),
+                    "author or subject is not valid UTF-8; invalid bytes were replaced",
+                );
+            }
+            each(commit)
+        })
+    })
+}
+
+/// Runs `git log` with `args` and hands its output to `read` while git is
+/// still printing it. When `read` fails, git is stopped, so a capped range is
+/// not listed to its end.
+fn stream_log(
+    args: &[&str],
+    context: &str,
+    read: impl FnOnce(ChildStdout) -> Result<()>,
+) -> Result<()> {
+    let mut child = git()
+        .arg("log")
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context(ReportError::git, context)?;
+    let stdout = child.stdout.take().expect("stdout is piped");
+
+    let listed = read(stdout);
+    if listed.is_err() {
+        let _ = child.kill();
+        let _ = child.wait();
+        return listed;
+    }
+
+    let mut stderr = String::new();
+    if let Some(mut pipe) = child.stderr.take() {
+        let _ = pipe.read_to_string(&mut stderr);
+    }
+    let status = child.wait().context(ReportError::git, context)?;
+    if !status.success() {
+        return Err(ReportError::git(format!("{}: {}", context, stderr.trim())));
+    }
+    Ok(())
+}
+
+/// Parses one `hash NUL parents NUL author NUL email NUL date NUL refs NUL
+/// subject` line into a commit without body and files, failing loudly on
+/// malformed input instead of guessing.
+fn parse_log_line(line: &str) -> Result<Commit> {
+    let parts: Vec<&str> = line.splitn(7, '\0').collect();
+    let [hash, parents, author, email, date_str, refs, subject] = parts[..] else {
+        return Err(ReportError::parse(format!(
+            "Malformed git log line (expected 7 NUL-separated fields): {:?}",
+            line
+        )));
+    };
+
+    if hash.len() < 40 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(ReportError::parse(format!(
+            "Malformed commit hash in git log output: {:?}",
+            hash
+        )));
+    }
+
+    // git shows dates it cannot read from the commit object as the Unix
+    // epoch, so the commit is kept there and the gap noted.
+    let date = match DateTime::parse_from_rfc3339(date_str) {
+        Ok(date) if date.timestamp() != 0 => date,
+        parsed => {
+            diagnostics::record(
+                IssueKind::InvalidDate,
+                Some(hash),
+                format!(
+                    "author date {:?} is missing or unparseable, shown as 1970-01-01",
+                    date_str
+                ),
+            );
+            parsed.unwrap_or_else(|_| DateTime::UNIX_EPOCH.fixed_offset())
+        }
+    };
+
+    let (tags, branches) = parse_decorations(refs);
+    Ok(Commit {
+        hash: hash.to_string(),
+        author: author.to_string(),
+        email: email.to_string(),
+        date,
+        subject: subject.to_string(),
+        body: String::new(),
+        files_changed: Vec::new(),
+        file_stats: Vec::new(),
+        parents: parents.split_whitespace().map(str::to_string).collect(),
+        signature: None,
+        labels: Vec::new(),
+        tags,
+        branches,
+    })
+}
+
+/// Splits `%D` decorations into tags and branches. `HEAD` itself and
+/// symbolic refs such as `origin/HEAD` are left out.
+fn parse_decorations(refs: &str) -> (Vec<String>, Vec<String>) {
+    let mut tags = Vec::new();
+    let mut branches = Vec::new();
+    for decoration in refs.split(", ").filter(|d| !d.is_empty()) {
+        let decoration = decoration.strip_prefix("HEAD -> ").unwrap_or(decoration);
+        if let Some(tag) = decoration.strip_prefix("tag: ") {
+            tags.push(tag.to_string());
+        } else if decoration != "HEAD" && !decoration.ends_with("/HEAD") {
+            branches.push(decoration.to_string());
+        }
+    }
+    (tags, branches)
+}
+
+/// Commits reachable from HEAD, newest first, after skipping the first
+/// `skip`, up to `count` of them (all when `None`). Only hash, parents,
+/// author, date and subject are filled in, so complete history can be paged
+/// through cheaply; use [`get_commit`] for the details.
+pub fn get_commit_headers(skip: usize, count: Option<usize>) -> Result<Vec<Commit>> {
+    let skip = format!("--skip={}", skip);
+    let mut args = vec![skip.as_str()];
+    let count = count.map(|count| format!("--max-count={}", count));
+    if let Some(count) = &count {
+        args.push(count);
+    }
+    log_headers(&args, "Failed to get commit list")
+}
+
+/// Looks up a single commit by hash or reference.
+pub fn get_commit(reference: &str) -> Result<Commit> {
+    let resolved = git()
+        .args([
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("{}^{{commit}}", reference),
+        ])
+        .output()
+        .context(ReportError::git, "Failed to resolve commit reference")?;
+
+    if !resolved.status.success() {
+        return Err(ReportError::git(format!(
+            "Commit '{}' not found",
+            reference
+        )));
+    }
+
+    log_commits(
+        &["-1", reference],
+        &format!("Failed to get details of commit {}", reference),
+    )?
+    .pop()
+    .ok_or_else(|| ReportError::git(format!("Commit '{}' not found", reference)))
+}
+
+pub fn get_commit_details(hash: &str) -> Result<(String, Vec<FileStat>)> {
+    let body_output = git()
+        .args(["show", "--no-patch", "--format=%B", hash])
+        .output()
+        .context(ReportError::git, "Failed to get commit body")?;
+    if !body_output.status.success() {
+        missing_details(hash, "message", &body_output.stderr);
+    }
+
+    let body = message_body(&commit_text(body_output.stdout, hash, "message"));
+
+    // Merges show their changes against the first parent, where the branch
+    // they bring in stands for them; the combined diff git shows by default
+    // carries no added, deleted or renamed files.
+    let files_output = git()
+        .args(["show", "--numstat", "-z", "-M", "-C", "--format="])
+        .arg("--diff-merges=first-parent")
+        .arg(hash)
+        .output()
+        .context(ReportError::git, "Failed to get files changed")?;
+    if !files_output.status.success() {
+        missing_details(hash, "changed files", &files_output.stderr);
+        return Ok((body, Vec::new()));
+    }
+
+    let files = commit_text(files_output.stdout, hash, "changed file paths");
+    let mut file_stats = parse_numstat(&files).with_context(ReportError::parse, || {
+        format!("Failed to parse changed files of commit {}", hash)
+    })?;
+
+    let name_status = git()
+        .args(["show", "--name-status", "-z", "-M", "-C", "--format="])
+        .arg("--diff-merges=first-parent")
+        .arg(hash)
+        .output()
+        .context(ReportError::git, "Failed to get file statuses")?;
+    if !name_status.status.success() {
+        missing_details(hash, "file statuses", &name_status.stderr);
+        return Ok((body, file_stats));
+    }
+    // Bytes that are not UTF-8 in paths were noted with the numstat output.
+    let name_status = String::from_utf8_lossy(&name_status.stdout);
+    for (status, path) in parse_name_status(&name_status)? {
+        if let Some(stat) = file_stats.iter_mut().find(|stat| stat.path == path) {
+            stat.status = FileStatus::from_letter(status);
+        }
+    }
+
+    Ok((body, file_stats))
+}
+
+/// The message without its subject line, as kept in [`Commit::body`].
+fn message_body(message: &str) -> String {
+    message.lines().skip(1).collect::<Vec<_>>().join("\n")
+}
+
+/// Decodes git output about a commit, replacing bytes that are not UTF-8,
+/// as in messages written in a legacy encoding without an encoding header,
+/// and noting the replacement for the Data Quality section.
+fn commit_text(bytes: Vec<u8>, hash: &str, what: &str) -> String {
+    String::from_utf8(bytes).unwrap_or_else(|error| {
+        diagnostics::record(
+            IssueKind::InvalidUtf8,
+            Some(hash),
+            format!("{} is not valid UTF-8; invalid bytes were replaced", what),
+        );
+        String::from_utf8_lossy(error.as_bytes()).into_owned()
+    })
+}
+
+/// Notes that part of a commit could not be read, e.g. in a partial clone
+/// whose objects are not available offline.
+fn missing_details(hash: &str, what: &str, stderr: &[u8]) {
+    diagnostics::record(
+        IssueKind::MissingDetails,
+        Some(hash),
+        format!(
+            "{} could not be read: {}",
+            what,
+            String::from_utf8_lossy(stderr).trim()
+        ),
+    );
+}
+
+/// The files changed between two commits, as one commit going from `base`
+/// to `hash` would change them.
+pub fn diff_stats(base: &str, hash: &str) -> Result<Vec<FileStat>> {
+    let numstat = git_output(&["diff", "--numstat", "-z", "-M", "-C", base, hash])?;
+    let mut file_stats = parse_numstat(&numstat).with_context(ReportError::parse, || {
+        format!("Failed to parse changed files of {}..{}", base, hash)
+    })?;
+
+    let name_status = git_output(&["diff", "--name-status", "-z", "-M", "-C", base, hash])?;
+    for (status, path) in parse_name_status(&name_status)? {
+        if let Some(stat) = file_stats.iter_mut().find(|stat| stat.path == path) {
+            stat.status = FileStatus::from_letter(status);
+        }
+    }
+
+    Ok(file_stats)
+}
+
+/// Parses `git show --numstat -z` output, see [`numstat_record`]. Each record is
+/// `added TAB deleted TAB path NUL`, or for renames and copies
+/// `added TAB deleted TAB NUL old NUL new NUL`; binary files use `-` counts.
+fn parse_numstat(output: &str) -> Result<Vec<FileStat>> {
+    let mut fields = output.trim_start_matches('\n').split('\0');
+    let mut stats = Vec::new();
+
+    while let Some(record) = fields.next() {
+        let record = record.trim_start_matches('\n');
+        if record.is_empty() {
+            continue;
+        }
+
+        let stat = numstat_record(record, || {
+            fields.next().map(str::to_string).ok_or_else(|| {
+                ReportError::parse(format!("Malformed numstat rename record: {:?}", record))
+            })
+        })?;
+        stats.push(stat);
+    }
+
+    Ok(stats)
+}
+
+/// Parses one numstat record from its `added TAB deleted TAB path` head,
+/// taking the old and then the new path from `path` for a rename or copy,
+/// whose head has no path.
+fn numstat_record(record: &str, mut path: impl FnMut() -> Result<String>) -> Result<FileStat> {
+    let mut parts = record.splitn(3, '\t');
+    let (Some(additions), Some(deletions), Some(head_path)) =
+        (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(ReportError::parse(format!(
+            "Malformed numstat record: {:?}",
+            record
+        )));
+    };
+
+    let (old_path, path) = if head_path.is_empty() {
+        (Some(path()?), path()?)
+    } else {
+        (None, head_path.to_string())
+    };
+
+    let binary = additions == "-" && deletions == "-";
+    let mut stat = FileStat::new(
+        &path,
+        if binary { 0 } else { parse_count(additions)? },
+        if binary { 0 } else { parse_count(deletions)? },
+        binary,
+    );
+    if let Some(old_path) = old_path {
+        stat.status = FileStatus::Renamed;
+        stat.old_path = Some(old_path);
+    }
+    Ok(stat)
+}
+
+/// Parses `--name-status -z` output into `(status, path)` pairs. Records are
+/// `status NUL path NUL`; renames and copies carry the old and new path, and
+/// the new one is returned.
+fn parse_name_status(output: &str) -> Result<Vec<(&str, &str)>> {
+    let mut fields = output
+        .split('\0')
+        .map(|field| field.trim_start_matches('\n'))
+        .filter(|field| !field.is_empty());
+    let mut records = Vec::new();
+    while let Some(status) = fields.next() {
+        let mut path = fields.next().ok_or_else(|| {
+            ReportError::parse(format!("Malformed name-status record: {:?}", status))
+        })?;
+        if status.starts_with(['R', 'C']) {
+            path = fields.next().ok_or_else(|| {
+                ReportError::parse(format!("Malformed name-status rename: {:?}", path))
+            })?;
+        }
+        records.push((status, path));
+    }
+
+    Ok(records)
+}
+
+fn parse_count(count: &str) -> Result<u64> {
+    count.parse().with_context(ReportError::parse, || {
+        format!("Malformed numstat count: {:?}", count)
+    })
+}
+
+/// A file with uncommitted changes and its `git status` letter (M, A, D, R, ...).
+pub struct WorktreeChange {
+    pub status: String,
+    pub stat: FileStat,
+}
+
+/// Lists uncommitted changes, either staged (index vs HEAD) or unstaged
+/// (working tree vs index), with their line counts.
+pub fn worktree_changes(staged: bool) -> Result<Vec<WorktreeChange>> {
+    let mut args = vec!["diff", "-z"];
+    if staged {
+        args.push("--cached");
+    }
+
+    let stats = parse_numstat(&git_output(&[&args[..], &["--numstat"]].concat())?)
+        .context(ReportError::parse, "Failed to parse working tree diffstat")?;
+    let name_status = git_output(&[&args[..], &["--name-status"]].concat())?;
+
+    let mut changes = Vec::new();
+    for (status, path) in parse_name_status(&name_status)? {
+        let mut stat = stats
+            .iter()
+            .find(|stat| stat.path == path)
+            .cloned()
+            .unwrap_or_else(|| FileStat::new(path, 0, 0, false));
+        stat.status = FileStatus::from_letter(status);
+        changes.push(WorktreeChange {
+            status: status[..1].to_string(),
+            stat,
+        });
+    }
+
+    Ok(changes)
+}
+
+pub fn untracked_files() -> Result<Vec<String>> {
+    Ok(
+        git_output(&["ls-files", "--others", "--exclude-standard", "-z"])?
+            .split('\0')
+            .filter(|path| !path.is_empty())
+            .map(String::from)
+            .collect(),
+    )
+}
+
+fn git_output(args: &[&str]) -> Result<String> {
+    let output = git()
+        .args(args)
+        .output()
+        .with_context(ReportError::git, || {
+            format!("Failed to run git {}", args.join(" "))
+        })?;
+
+    if !output.status.success() {
+        return Err(ReportError::git(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    String::from_utf8(output.stdout).context(ReportError::parse, "git output is not valid UTF-8")
+}
+
+pub fn get_commit_diff(hash: &str) -> Result<String> {
+    let output = git()
+        .args(["show", "--format=", "--patch", hash])
+        .output()
+        .context(ReportError::git, "Failed to get commit diff")?;
+
+    if !output.status.success() {
+        return Err(ReportError::git(format!(
+            "Failed to get diff for commit {}",
+            hash
+        )));
+    }
+
+    let mut diff = String::from_utf8_lossy(&output.stdout).to_string();
+    if diff.len() > MAX_AI_DIFF_CHARS {
+        let mut cut = MAX_AI_DIFF_CHARS;
+        while !diff.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        diff.truncate(cut);
+        diff.push_str("\n[diff truncated]\n");
+        diagnostics::record(
+            IssueKind::Truncated,
+            Some(hash),
+            format!(
+                "diff cut to {} characters for the AI prompt",
+                MAX_AI_DIFF_CHARS
+            ),
+        );
+    }
+
+    Ok(diff)
+}
+
+/// Returns the most recent tag reachable from `reference`, if any.
+pub fn latest_tag(reference: &str) -> Result<Option<String>> {
+    let output = git()
+        .args(["describe", "--tags", "--abbrev=0", reference])
+        .output()
+        .context(ReportError::git, "Failed to describe commit")?;
+
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    let tag = String::from_utf8(output.stdout)
+        .context(ReportError::parse, "git output is not valid UTF-8")?;
+    Ok(Some(tag.trim().to_string()))
+}
+
+/// Copies every ref of a bundle file into a new repository at `dir` and
+/// detaches its HEAD at the bundle's HEAD (or first ref). The work tree is
+/// left empty; only history is needed for reports.
+pub fn clone_bundle(bundle: &Path, dir: &Path) -> Result<()> {
+    let bundle = bundle.to_string_lossy();
+    let dir = dir.to_string_lossy();
+
+    let heads = git_output(&["bundle", "list-heads", &bundle])
+        .map_err(|_| ReportError::git(format!("{} is not a valid git bundle", bundle)))?;
+    let head = heads
+        .lines()
+        .find(|line| line.ends_with(" HEAD"))
+        .or_else(|| heads.lines().next())
+        .and_then(|line| line.split_whitespace().next())
+        .ok_or_else(|| ReportError::git(format!("Bundle {} contains no refs", bundle)))?
+        .to_string();
+
+    git_output(&["init", "--quiet", &dir])?;
+    git_output(&[
+        "-C",
+        &dir,
+        "fetch",
+        "--quiet",
+        "--update-head-ok",
+        &bundle,
+        "+refs/*:refs/*",
+    ])?;
+    git_output(&["-C", &dir, "update-ref", "--no-deref", "HEAD", &head])?;
+
+    Ok(())
+}
+
+/// Lowercased author names and emails of the commits reachable from `rev`,
+/// leaving out `rev` itself when `exclude_rev` is set.
+pub fn authors_before(rev: &str, exclude_rev: bool) -> Result<HashSet<String>> {
+    let mut args = vec!["log", "--format=%aN%x00%aE"];
+    if exclude_rev {
+        args.push("--skip=1");
+    }
+    args.push(rev);
+    Ok(git_output(&args)?
+        .lines()
+        .flat_map(|line| line.split('\0'))
+        .filter(|value| !value.is_empty())
+        .map(str::to_lowercase)
+        .collect())
+}
+
+/// The `git log --graph` drawing of the range, newest first, with the short
+/// hash and subject of each commit.
+pub fn graph(from_hash: &str, to_hash: &str, inclusive: bool) -> Result<String> {
+    let range = range_spec(from_hash, to_hash, inclusive)?;
+    let mut args = vec!["log", "--graph", "--color=never", "--format=%h %s"];
+    args.extend(first_parent_args());
+    args.push(&range);
+    git_output(&args)
+}
+
+/// The commits of the range in topological order, oldest first, with the
+/// fields of [`get_commit_headers`]: the shape of the history for drawing it.
+pub fn range_headers(from_hash: &str, to_hash: &str, inclusive: bool) -> Result<Vec<Commit>> {
+    let range = range_spec(from_hash, to_hash, inclusive)?;
+    let mut args = vec!["--topo-order", "--reverse"];
+    args.extend(first_parent_args());
+    args.push(&range);
+    log_headers(&args, "Failed to get the history of the range")
+}
+
+/// Where git looks for hooks, following `core.hooksPath` and worktrees.
+pub fn hooks_dir() -> Result<PathBuf> {
+    Ok(PathBuf::from(
+        git_output(&["rev-parse", "--path-format=absolute", "--git-path", "hooks"])?.trim(),
+    ))
+}
+
+/// Branch and tag names, most recently committed to first.
+pub fn refs() -> Result<Vec<String>> {
+    Ok(git_output(&[
+        "for-each-ref",
+        "--sort=-creatordate",
+        "--format=%(refname:short)",
+        "refs/heads",
+        "refs/tags",
+    ])?
+    .lines()
+    .map(|line| line.trim().to_string())
+    .filter(|name| !name.is_empty())
+    .collect())
+}
+
+pub fn remotes() -> Result<Vec<String>> {
+    Ok(git_output(&["remote"])?
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .collect())
+}
+
+pub fn remote_url(remote: &str) -> Result<String> {
+    Ok(git_output(&["remote", "get-url", remote])?
+        .trim()
+        .to_string())
+}
+
+/// Fetches the given refs of a remote without updating any local ref, so
+/// their commits become available by hash.
+pub fn fetch_refs(remote: &str, refs: &[&str]) -> Result<()> {
+    let mut args = vec!["fetch", "--quiet", "--no-tags", remote];
+    args.extend(refs);
+    git_output(&args)?;
+    Ok(())
+}
+
+/// The best common ancestor of two commits, e.g. where a pull request branched off.
+pub fn merge_base(first: &str, second: &str) -> Result<String> {
+    Ok(git_output(&["merge-base", first, second])?
+        .trim()
+        .to_string())
+}
+
+/// The remote a remote-tracking ref like `upstream/main` or
+/// `refs/remotes/upstream/main` belongs to, preferring the longest match.
+pub fn remote_of<'a>(reference: &str, remotes: &'a [String]) -> Option<&'a str> {
+    let reference = reference
+        .strip_prefix("refs/remotes/")
+        .or_else(|| reference.strip_prefix("remotes/"))
+        .unwrap_or(reference);
+    remotes
+        .iter()
+        .filter(|remote| {
+            reference
+                .strip_prefix(remote.as_str())
+                .is_some_and(|rest| rest.starts_with('/'))
+        })
+        .max_by_key(|remote| remote.len())
+        .map(String::as_str)
+}
+
+/// Compares two versions of a patch series with `git range-diff`, e.g.
+/// `main..topic-v1` and `main..topic-v2`, and returns its uncolored output.
+pub fn range_diff(old_range: &str, new_range: &str) -> Result<String> {
+    git_output(&["range-diff", "--no-color", old_range, new_range])
+}
+
+/// Updates the remote-tracking refs of one remote, or of all remotes.
+pub fn fetch(remote: Option<&str>) -> Result<()> {
+    match remote {
+        Some(remote) => git_output(&["fetch", "--quiet", remote])?,
+        None => git_output(&["fetch", "--quiet", "--all"])?,
+    };
+    Ok(())
+}
+
+/// Reads the notes of the given commits from a notes ref (`commits` for
+/// `refs/notes/commits`), keyed by commit hash. Commits without one are left out.
+pub fn notes(namespace: &str, commits: &[Commit]) -> Result<HashMap<String, String>> {
+    let ref_arg = format!("--ref={}", namespace);
+    let wanted: HashSet<&str> = commits.iter().map(|c| c.hash.as_str()).collect();
+
+    let mut notes = HashMap::new();
+    for line in git_output(&["notes", &ref_arg, "list"])?.lines() {
+        let Some((note, commit)) = line.split_once(' ') else {
+            continue;
+        };
+        if wanted.contains(commit) {
+            let text = git_output(&["cat-file", "blob", note])?;
+            notes.insert(commit.to_string(), text.trim().to_string());
+        }
+    }
+
+    Ok(notes)
+}
+
+/// Commits checked per `git show` call, keeping the command line short.
+const SIGNATURE_BATCH: usize = 200;
+
+/// Verifies the signature of every commit. git checks GPG signatures with
+/// the keys in the keyring and SSH signatures with `gpg.ssh.allowedSignersFile`;
+/// signatures it has no key for are reported as unverifiable.
+pub fn signatures(commits: &mut [Commit]) -> Result<()> {
+    let mut signatures = HashMap::new();
+    for batch in commits.chunks(SIGNATURE_BATCH) {
+        let mut args = vec!["show", "--no-patch", "--format=%H%x00%G?%x00%GK%x00%GS"];
+        args.extend(batch.iter().map(|commit| commit.hash.as_str()));
+        for line in git_output(&args)?.lines() {
+            let mut fields = line.splitn(4, '\0');
+            let (Some(hash), Some(status)) = (fields.next(), fields.next()) else {
+                continue;
+            };
+            signatures.insert(
+                hash.to_string(),
+                Signature {
+                    status: SignatureStatus::from_letter(status),
+                    key: fields.next().unwrap_or_default().to_string(),
+                    signer: fields.next().unwrap_or_default().to_string(),
+                },
+            );
+        }
+    }
+
+    for commit in commits {
+        commit.signature = signatures.remove(&commit.hash);
+    }
+    Ok(())
+}
+
+/// Returns whether a commit has at least one parent, i.e. is not a root commit.
+pub fn has_parent(hash: &str) -> Result<bool> {
+    let output = git()
+        .args(["rev-parse", "--verify", "--quiet", &format!("{}^", hash)])
+        .output()
+        .context(ReportError::git, "Failed to look up commit parent")?;
+
+    Ok(output.status.success())
+}
+
+/// Whether `reference` names a commit.
+pub fn ref_exists(reference: &str) -> Result<bool> {
+    let output = git()
+        .args(["rev-parse", "--verify", "--quiet"])
+        .arg(format!("{}^{{commit}}", reference))
+        .output()
+        .context(ReportError::git, "Failed to look up reference")?;
+
+    Ok(output.status.success())
+}
+
+/// The upstream of the current branch (`@{upstream}`), e.g. `origin/main`;
+/// `None` when the branch has no upstream or HEAD is detached.
+pub fn upstream() -> Result<Option<String>> {
+    let output = git()
+        .args([
+            "rev-parse",
+            "--abbrev-ref",
+            "--symbolic-full-name",
+            "@{upstream}",
+        ])
+        .output()
+        .context(ReportError::git, "Failed to look up the upstream branch")?;
+
+    let upstream = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    Ok((output.status.success() && !upstream.is_empty()).then_some(upstream))
+}
+
+/// Returns whether `ancestor` is reachable from `descendant`; a commit counts
+/// as its own ancestor.
+pub fn is_ancestor(ancestor: &str, descendant: &str) -> Result<bool> {
+    let output = git()
+        .args(["merge-base", "--is-ancestor", ancestor, descendant])
+        .output()
+        .context(ReportError::git, "Failed to check commit ancestry")?;
+
+    // Exit code 1 means "not an ancestor"; anything else is a real failure.
+    match output.status.code() {
+        Some(0) => Ok(true),
+        Some(1) => Ok(false),
+        _ => Err(ReportError::git(format!(
+            "git merge-base --is-ancestor failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))),
+    }
+}
+
+/// Like [`merge_base`], but `None` for commits without common history.
+pub fn common_ancestor(first: &str, second: &str) -> Result<Option<String>> {
+    let output = git()
+        .args(["merge-base", first, second])
+        .output()
+        .context(ReportError::git, "Failed to find a common ancestor")?;
+
+    match output.status.code() {
+        Some(0) => Ok(Some(
+            String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        )),
+        Some(1) => Ok(None),
+        _ => Err(ReportError::git(format!(
+            "git merge-base failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))),
+    }
+}
+
+/// Builds the revision range for `git log`; see [`get_commits_in_range`].
+fn range_spec(from_hash: &str, to_hash: &str, inclusive: bool) -> Result<String> {
+    Ok(if !inclusive {
+        format!("{}..{}", from_hash, to_hash)
+    } else if has_parent(from_hash)? {
+        format!("{}^..{}", from_hash, to_hash)
+    } else {
+        to_hash.to_string()
+    })
+}
+
+/// A cheap overview of a range, computed without collecting commit details.
+#[cfg(feature = "tui")]
+pub struct RangeSummary {
+    pub count: usize,
+    pub first_date: Option<DateTime<FixedOffset>>,
+    pub last_date: Option<DateTime<FixedOffset>>,
+    /// Authors with their commit counts, most active first.
+    pub authors: Vec<(String, usize)>,
+}
+
+#[cfg(feature = "tui")]
+pub fn range_summary(from_hash: &str, to_hash: &str, inclusive: bool) -> Result<RangeSummary> {
+    let range = range_spec(from_hash, to_hash, inclusive)?;
+    let output = git()
+        .args(["log", "--pretty=format:%aN%x00%aI"])
+        .args(first_parent_args())
+        .arg(&range)
+        .output()
+        .context(ReportError::git, "Failed to summarize range")?;
+
+    if !output.status.success() {
+        return Err(ReportError::git(format!(
+            "Failed to summarize range: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    let mut summary = RangeSummary {
+        count: 0,
+        first_date: None,
+        last_date: None,
+        authors: Vec::new(),
+    };
+
+    let log = String::from_utf8(output.stdout)
+        .context(ReportError::parse, "git log output is not valid UTF-8")?;
+    for line in log.lines() {
+        let Some((author, date_str)) = line.split_once('\0') else {
+            return Err(ReportError::parse(format!(
+                "Malformed git log line: {:?}",
+                line
+            )));
+        };
+        let date = DateTime::parse_from_rfc3339(date_str)
+            .with_context(ReportError::parse, || {
+                format!("Failed to parse author date {:?}", date_str)
+            })?;
+
+        summary.count += 1;
+        summary.first_date = Some(summary.first_date.map_or(date, |d| d.min(date)));
+        summary.last_date = Some(summary.last_date.map_or(date, |d| d.max(date)));
+        match summary.authors.iter_mut().find(|(name, _)| name == author) {
+            Some((_, count)) => *count += 1,
+            None => summary.authors.push((author.to_string(), 1)),
+        }
+    }
+
+    summary
+        .authors
+        .sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    Ok(summary)
+}
+
+/// Restricts collected commits by message, passed to `git log` as `--grep`
+/// with extended regular expressions: commits whose subject or body match any
+/// pattern are kept, or with `invert` those matching none of them.
+#[derive(Debug, Default, Clone)]
+pub struct MessageFilter {
+    pub patterns: Vec<String>,
+    pub invert: bool,
+}
+
+impl MessageFilter {
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    fn log_args(&self) -> Vec<String> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+        let mut args = vec!["--extended-regexp".to_string()];
+        if self.invert {
+            args.push("--invert-grep".to_string());
+        }
+        args.extend(self.patterns.iter().map(|p| format!("--grep={}", p)));
+        args
+    }
+}
+
+/// Collects the commits reachable from `to` that pass `filter`, newest
+/// first, leaving out those reachable from `from` when given. See
+/// [`set_first_parent`] for leaving out the commits of merged branches.
+pub fn get_history(from: Option<&str>, to: &str, filter: &MessageFilter) -> Result<Vec<Commit>> {
+    let mut args: Vec<String> = first_parent_args()
+        .iter()
+        .map(|arg| arg.to_string())
+        .collect();
+    args.extend(filter.log_args());
+    args.push(match from {
+        Some(from) => format!("{}..{}", from, to),
+        None => to.to_string(),
+    });
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    log_commits(&args, "Failed to get history")
+}
+
+/// Collects the commits in `from..to`, sorted oldest first. With `inclusive`
+/// the FROM commit itself is included as well (`from^..to`); when FROM is a
+/// root commit that means all history up to TO. See [`set_first_parent`]
+/// for leaving out the commits of merged branches.
+pub fn get_commits_in_range(
+    from_hash: &str,
+    to_hash: &str,
+    inclusive: bool,
+) -> Result<Vec<Commit>> {
+    get_matching_commits_in_range(from_hash, to_hash, inclusive, &MessageFilter::default())
+}
+
+/// Like [`get_commits_in_range`], keeping only the commits that pass `filter`.
+pub fn get_matching_commits_in_range(
+    from_hash: &str,
+    to_hash: &str,
+    inclusive: bool,
+    filter: &MessageFilter,
+) -> Result<Vec<Commit>> {
+    let range = range_spec(from_hash, to_hash, inclusive)?;
+    let mut args = vec!["--reverse".to_string()];
+    args.extend(first_parent_args().iter().map(|arg| arg.to_string()));
+    args.extend(filter.log_args());
+    args.push(range);
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let mut commits = log_commits(&args, "Failed to get commits in range")?;
+
+    // TO is only added back when no filter could have dropped it on purpose.
+    if from_hash != to_hash && filter.is_empty() && !commits.iter().any(|c| c.hash == to_hash) {
+        commits.push(get_commit(to_hash)?);
+    }
+
+    commits.sort_by_key(|c| c.date);
+
+    Ok(commits)
+}
@@ -0,0 +1,176 @@
+use std::{fs, path::Path};
+
+use crate::{
+    error::{Context, ReportError, Result},
+    git::Commit,
+    i18n::Locale,
+    jira,
+    report::{Block, Section},
+};
+
+/// A planned item from the `--goals` file.
+pub struct Goal {
+    pub title: String,
+    /// The ticket ids of the goal, e.g. `PROJ-123` or `#42`.
+    pub tickets: Vec<String>,
+}
+
+/// Reads a goals file: one goal per line with its ticket ids anywhere in
+/// the line, as in `PROJ-12: OAuth login` or a Markdown task list such as
+/// `- [ ] OAuth login (#42)`. Blank lines and Markdown headings are skipped.
+pub fn load(path: &Path) -> Result<Vec<Goal>> {
+    let content = fs::read_to_string(path).with_context(ReportError::io, || {
+        format!("Failed to read goals file {}", path.display())
+    })?;
+    parse(&content).map_err(|line| {
+        ReportError::parse(format!(
+            "Goal on line {} of {} has no ticket id (e.g. PROJ-123 or #42)",
+            line,
+            path.display()
+        ))
+    })
+}
+
+/// Parses the lines of a goals file, or returns the number of the first
+/// goal line without a ticket id.
+pub fn parse(content: &str) -> Result<Vec<Goal>, usize> {
+    let mut goals = Vec::new();
+    for (number, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with("# ") || line.starts_with("##") {
+            continue;
+        }
+        let tickets = tickets(line);
+        if tickets.is_empty() {
+            return Err(number + 1);
+        }
+        goals.push(Goal {
+            title: title(line, &tickets),
+            tickets,
+        });
+    }
+    Ok(goals)
+}
+
+/// The Jira-style keys and `#123` references in a text.
+fn tickets(text: &str) -> Vec<String> {
+    let mut tickets = jira::extract_keys(text, &[]);
+    for word in text.split_whitespace() {
+        let word = word
+            .trim_start_matches(['(', '['])
+            .trim_end_matches([',', '.', ';', ':', ')', ']']);
+        let Some(number) = word.strip_prefix('#') else {
+            continue;
+        };
+        if !number.is_empty()
+            && number.bytes().all(|b| b.is_ascii_digit())
+            && !tickets.iter().any(|ticket| ticket == word)
+        {
+            tickets.push(word.to_string());
+        }
+    }
+    tickets
+}
+
+/// The goal line without its list marker, check box and ticket ids.
+fn title(line: &str, tickets: &[String]) -> String {
+    let mut title = line
+        .trim_start_matches(['-', '*', '+'])
+        .trim_start()
+        .trim_start_matches(|c: char| c.is_ascii_digit())
+        .trim_start_matches(['.', ')'])
+        .trim_start();
+    for check_box in ["[ ]", "[x]", "[X]"] {
+        title = title.strip_prefix(check_box).unwrap_or(title);
+    }
+    let mut title = title.to_string();
+    // Longest first, so `PROJ-1` does not cut into `PROJ-12`.
+    let mut tickets_by_length: Vec<&String> = tickets.iter().collect();
+    tickets_by_length.sort_by_key(|ticket| std::cmp::Reverse(ticket.len()));
+    for ticket in tickets_by_length {
+        title = title.replace(ticket.as_str(), "");
+    }
+    let title = title.replace("()", "").replace("[]", "");
+    let title = title
+        .trim_matches(|c: char| c.is_whitespace() || matches!(c, ':' | '-' | ',' | '|'))
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if title.is_empty() {
+        tickets.join(", ")
+    } else {
+        title
+    }
+}
+
+/// Planned vs actual: which goals saw commits (a commit counts for a goal
+/// when its subject or body mentions one of the goal's ticket ids), which
+/// did not, and the commits that belong to no goal.
+pub fn goals_section(goals: &[Goal], commits: &[Commit], locale: &Locale) -> Section {
+    let mut worked_on: Vec<Vec<&Commit>> = vec![Vec::new(); goals.len()];
+    let mut unplanned = Vec::new();
+    for commit in commits {
+        let mentioned = tickets(&format!("{}\n{}", commit.subject, commit.body));
+        let mut planned = false;
+        for (goal, goal_commits) in goals.iter().zip(&mut worked_on) {
+            if goal.tickets.iter().any(|ticket| mentioned.contains(ticket)) {
+                goal_commits.push(commit);
+                planned = true;
+            }
+        }
+        if !planned {
+            unplanned.push(commit);
+        }
+    }
+
+    let started = worked_on.iter().filter(|c| !c.is_empty()).count();
+    let line = |commit: &&Commit| format!("{} {}", &commit.hash[..8], commit.subject);
+    let label = |key: &str| locale.text(key).to_string();
+    let mut section =
+        Section::new("goals", locale.text("planned-vs-actual")).with_block(Block::Fields(vec![
+            (label("goals-planned"), goals.len().to_string()),
+            (label("goals-with-commits"), started.to_string()),
+            (
+                label("goals-without-commits"),
+                (goals.len() - started).to_string(),
+            ),
+            (label("unplanned-commits"), unplanned.len().to_string()),
+        ]));
+
+    if !goals.is_empty() {
+        section = section.with_block(Block::Table {
+            headers: ["goal", "tickets", "commits", "status"].map(label).to_vec(),
+            rows: goals
+                .iter()
+                .zip(&worked_on)
+                .map(|(goal, commits)| {
+                    vec![
+                        goal.title.clone(),
+                        goal.tickets.join(", "),
+                        commits.len().to_string(),
+                        label(if commits.is_empty() {
+                            "goal-no-commits"
+                        } else {
+                            "goal-worked-on"
+                        }),
+                    ]
+                })
+                .collect(),
+        });
+    }
+    for (goal, commits) in goals.iter().zip(&worked_on) {
+        if !commits.is_empty() {
+            section = section.with_block(Block::List {
+                title: format!("{} ({})", goal.title, goal.tickets.join(", ")),
+                items: commits.iter().map(line).collect(),
+            });
+        }
+    }
+    if !unplanned.is_empty() {
+        section = section.with_block(Block::List {
+            title: label("unplanned-work"),
+            items: unplanned.iter().map(line).collect(),
+        });
+    }
+    section
+}
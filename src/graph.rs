@@ -0,0 +1,49 @@
+use clap::ValueEnum;
+
+use crate::{
+    i18n::Locale,
+    report::{Block, Section},
+};
+
+/// The characters the commit graph is drawn with.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphStyle {
+    /// `*`, `|`, `/` and `\` as printed by `git log --graph`.
+    Ascii,
+    /// Box-drawing lines and `●` commits, for terminals and fonts that have them.
+    Unicode,
+}
+
+/// Redraws one line of `git log --graph` output in `style`. Only the graph
+/// in front of the hash is redrawn, so subjects keep their own characters.
+fn redraw(line: &str, style: GraphStyle) -> String {
+    if style == GraphStyle::Ascii {
+        return line.to_string();
+    }
+    let graph_end = line
+        .find(|c: char| !"*|/\\_-. ".contains(c))
+        .unwrap_or(line.len());
+    let (graph, commit) = line.split_at(graph_end);
+    let graph: String = graph
+        .chars()
+        .map(|c| match c {
+            '*' => '●',
+            '|' => '│',
+            '/' => '╱',
+            '\\' => '╲',
+            '_' | '-' => '─',
+            other => other,
+        })
+        .collect();
+    format!("{}{}", graph, commit)
+}
+
+/// The branching and merges of the range as drawn by `git log --graph`,
+/// newest commit first; see [`crate::git::graph`].
+pub fn graph_section(graph: &str, style: GraphStyle, locale: &Locale) -> Section {
+    let drawing: Vec<String> = graph
+        .lines()
+        .map(|line| redraw(line.trim_end(), style))
+        .collect();
+    Section::new("graph", locale.text("commit-graph")).with_block(Block::Code(drawing.join("\n")))
+}
@@ -0,0 +1,138 @@
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use crate::error::{Context, ReportError, Result};
+
+/// Catalogs compiled into the binary, by language code.
+const BUILT_IN: &[(&str, &str)] = &[
+    ("en", include_str!("locales/en.ftl")),
+    ("de", include_str!("locales/de.ftl")),
+];
+
+const DEFAULT_LOCALES_DIR: &str = ".git-report/locales";
+
+/// The static report strings (headings and labels) in one language. Catalogs
+/// are written in a subset of the Fluent syntax: `key = value` messages,
+/// indented continuation lines, `#` comments and `{ $name }` placeholders.
+/// Messages a catalog lacks fall back to English.
+#[derive(Debug, Clone)]
+pub struct Locale {
+    code: String,
+    messages: HashMap<String, String>,
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self {
+            code: "en".to_string(),
+            messages: parse(BUILT_IN[0].1),
+        }
+    }
+}
+
+/// Resolves the directory of contributed catalogs: `GIT_REPORT_LOCALES` when
+/// set, otherwise `.git-report/locales` in the repository root.
+pub fn locales_dir(repo_path: &str) -> PathBuf {
+    env::var("GIT_REPORT_LOCALES")
+        .map(Into::into)
+        .unwrap_or_else(|_| Path::new(repo_path).join(DEFAULT_LOCALES_DIR))
+}
+
+/// The language codes with a built-in catalog.
+pub fn built_in() -> impl Iterator<Item = &'static str> {
+    BUILT_IN.iter().map(|(code, _)| *code)
+}
+
+impl Locale {
+    /// Loads the catalog for `code`: `<dir>/<code>.ftl` when it exists, whose
+    /// messages take precedence over the built-in ones, or the built-in
+    /// catalog alone.
+    pub fn load(code: &str, dir: &Path) -> Result<Self> {
+        let mut locale = Self {
+            code: code.to_string(),
+            ..Self::default()
+        };
+        if let Some((_, catalog)) = BUILT_IN.iter().find(|(built_in, _)| *built_in == code) {
+            locale.messages.extend(parse(catalog));
+        }
+
+        let path = dir.join(format!("{}.ftl", code));
+        if path.exists() {
+            let catalog = fs::read_to_string(&path).with_context(ReportError::io, || {
+                format!("Failed to read translations {}", path.display())
+            })?;
+            locale.messages.extend(parse(&catalog));
+        } else if !built_in().any(|built_in| built_in == code) {
+            return Err(ReportError::parse(format!(
+                "No translations for language '{}'; built in: {}. Add {} to contribute them",
+                code,
+                built_in().collect::<Vec<_>>().join(", "),
+                path.display()
+            )));
+        }
+
+        Ok(locale)
+    }
+
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    /// The message for `key`, or the key itself when no catalog has it.
+    pub fn text<'a>(&'a self, key: &'a str) -> &'a str {
+        self.messages.get(key).map_or(key, String::as_str)
+    }
+
+    /// The message for `key` with its `{ $name }` placeholders filled in.
+    pub fn format(&self, key: &str, args: &[(&str, &str)]) -> String {
+        let mut text = self.text(key).to_string();
+        for (name, value) in args {
+            text = text
+                .replace(&format!("{{ ${} }}", name), value)
+                .replace(&format!("{{${}}}", name), value);
+        }
+        text
+    }
+
+    /// The English name of the language, for telling the AI backend which
+    /// language to write in.
+    pub fn language_name(&self) -> &str {
+        self.text("language-name")
+    }
+
+    pub fn is_english(&self) -> bool {
+        self.code == "en"
+    }
+}
+
+/// Reads `key = value` messages. A line indented under a message continues
+/// its value on a new line.
+fn parse(catalog: &str) -> HashMap<String, String> {
+    let mut messages: HashMap<String, String> = HashMap::new();
+    let mut current: Option<String> = None;
+    for line in catalog.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            current = None;
+            continue;
+        }
+        if line.starts_with([' ', '\t']) {
+            if let Some(value) = current.as_ref().and_then(|key| messages.get_mut(key)) {
+                if !value.is_empty() {
+                    value.push('\n');
+                }
+                value.push_str(trimmed);
+            }
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            let key = key.trim().to_string();
+            messages.insert(key.clone(), value.trim().to_string());
+            current = Some(key);
+        }
+    }
+    messages
+}
@@ -0,0 +1,71 @@
+use globset::GlobSet;
+use std::collections::HashMap;
+
+use crate::{
+    classify::{self, Category},
+    error::Result,
+    exclude,
+    git::Commit,
+};
+
+/// Scores are capped here so they read as a rough 0-100 scale.
+const MAX_SCORE: f64 = 100.0;
+
+/// Heuristic 0-100 score of how much a commit deserves a reviewer's attention.
+/// Larger commits score higher, on a log scale so one huge commit does not
+/// flatten the rest. Changes to core paths weigh more, code changes without
+/// any test changes weigh more, and docs- or test-only commits weigh less.
+pub struct ImpactScorer {
+    core: GlobSet,
+}
+
+impl ImpactScorer {
+    /// `core_paths` are `.gitignore`-style patterns, e.g. `src/engine/**`.
+    pub fn new(core_paths: &[String]) -> Result<Self> {
+        Ok(Self {
+            core: exclude::glob_set(core_paths, "core path")?,
+        })
+    }
+
+    pub fn score(&self, commit: &Commit) -> u32 {
+        let files = commit.files_changed.len() as f64;
+        let lines: u64 = commit
+            .file_stats
+            .iter()
+            .map(|stat| stat.additions + stat.deletions)
+            .sum();
+        let mut score = 12.0 * files.ln_1p() + 6.0 * (lines as f64).ln_1p();
+
+        if !self.core.is_empty() && files > 0.0 {
+            let core = commit
+                .files_changed
+                .iter()
+                .filter(|path| self.core.is_match(path.as_str()))
+                .count() as f64;
+            score *= 1.0 + core / files;
+        }
+
+        let categories: Vec<Option<Category>> = commit
+            .files_changed
+            .iter()
+            .map(|path| classify::path_category(path))
+            .collect();
+        let touches_code = categories.iter().any(Option::is_none);
+        let touches_tests = categories.contains(&Some(Category::Test));
+        if touches_code && !touches_tests {
+            score *= 1.25;
+        } else if !touches_code {
+            score *= 0.5;
+        }
+
+        score.round().min(MAX_SCORE) as u32
+    }
+
+    /// Scores every commit, keyed by hash.
+    pub fn scores(&self, commits: &[Commit]) -> HashMap<String, u32> {
+        commits
+            .iter()
+            .map(|commit| (commit.hash.clone(), self.score(commit)))
+            .collect()
+    }
+}
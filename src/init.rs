@@ -0,0 +1,260 @@
+use clap::ValueEnum;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    error::{Context, ReportError, Result},
+    git,
+};
+
+/// Git hook `init --hook` installs.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hook {
+    /// Writes a report of the commits about to be pushed to .git/git-report/
+    PrePush,
+}
+
+/// CI service `init --ci` writes a report job for.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ci {
+    /// A workflow in .github/workflows/git-report.yml
+    Github,
+    /// A job in .gitlab/git-report.yml, to include from .gitlab-ci.yml
+    Gitlab,
+}
+
+/// Every config section with its settings commented out, so the file
+/// changes nothing until a setting is uncommented.
+const CONFIG: &str = r#"# git-report configuration. Settings are commented out; uncomment and edit
+# the ones you need. Environment variables override the [email] settings.
+
+[stats]
+# Metric sections added to every report, in order: hotspots, authors, domains.
+# metrics = ["authors", "hotspots"]
+# Author email domains counted as employees by the domains metric.
+# company_domains = ["example.com"]
+
+[notes]
+# Notes refs shown as annotations; commits is refs/notes/commits.
+# refs = ["commits"]
+
+[impact]
+# Changes under these paths raise a commit's impact score (--impact).
+# core_paths = ["src/core/**"]
+
+[check]
+# Paths `git-report check --assert-no-sensitive-paths` fails on (default:
+# .env files, private keys, key stores, credential files, Terraform state).
+# sensitive_paths = [".env", "*.pem", "config/secrets.yml"]
+
+[monorepo]
+# Directories whose subdirectories are projects for --monorepo.
+# roots = ["packages"]
+
+[monorepo.projects]
+# Named projects and the patterns of their files, matched before roots.
+# docs = ["docs/**", "*.md"]
+
+[community]
+# Names, emails or handles whose commits --community leaves out.
+# maintainers = ["Jane Doe", "ops@example.com", "@jdoe"]
+
+[community.handles]
+# Handles to thank authors by, keyed by author name or email.
+# "John Smith" = "jsmith"
+
+[authors.aliases]
+# Identities authors are counted as, keyed by author name or email, on top
+# of the repository's .mailmap.
+# "jdoe@home.example.net" = "Jane Doe <jane@example.com>"
+
+[acknowledgments]
+# Sponsors and funding links closing --community release notes.
+# message = "This release was made possible by our sponsors."
+# sponsors = ["Acme Corp", { name = "Example Inc", url = "https://example.com" }]
+# funding = [{ name = "GitHub Sponsors", url = "https://github.com/sponsors/example" }]
+
+[archive]
+# Store every report with its commit dataset (same as --archive).
+# enabled = true
+# dir = ".git-report/archive"
+# keep_last = 50
+# keep_days = 90
+
+[email]
+# smtp_host = "smtp.example.com"
+# smtp_port = 587
+# username = "reports@example.com"
+# password = "app-password"
+# from = "Git Report <reports@example.com>"
+# tls = "starttls"
+
+[filters]
+# Saved filters for --filter-name.
+# backend = 'files ~ "server/**" && !merge'
+
+[banner]
+# Text added above and below every report; {repository}, {from}, {to},
+# {date}, {title}, {author} and {project} are filled in.
+# header = "Acme Corp - {repository}"
+# footer = "CONFIDENTIAL: for internal use only."
+
+[branding]
+# Report title instead of "Git Commit Report", and the author and project
+# listed in the summary and given to the AI backend.
+# title = "Acme Portal: Sprint Report"
+# author = "Platform Team"
+# project = "Acme Portal"
+
+# [[labels]]
+# Labels attached to commits matching the pattern or changing these files.
+# name = "security"
+# pattern = "(?i)cve-|xss"
+# paths = ["src/auth/**"]
+
+[commit_links]
+# Links shown with every commit, by label; {hash} and {short} are filled in.
+# Build = "https://ci.example.com/builds/{hash}"
+"#;
+
+const PRE_PUSH: &str = r#"#!/bin/sh
+# Written by `git-report init --hook pre-push`: reports on the commits each
+# push sends, into .git/git-report/. Never blocks the push.
+zero=$(git hash-object --stdin </dev/null | tr '0-9a-f' '0')
+dir="$(git rev-parse --git-dir)/git-report"
+mkdir -p "$dir"
+
+while read -r local_ref local_sha remote_ref remote_sha; do
+    # Skip deletions and new branches, which have no previous tip to start from.
+    if [ "$local_sha" = "$zero" ] || [ "$remote_sha" = "$zero" ]; then
+        continue
+    fi
+    name=$(echo "${remote_ref#refs/heads/}" | tr '/' '-')
+    git-report --from "$remote_sha" --to "$local_sha" --format md \
+        --output "$dir/pre-push-$name.md" >/dev/null 2>&1 ||
+        echo "git-report: could not report on $remote_ref" >&2
+done
+exit 0
+"#;
+
+const GITHUB_WORKFLOW: &str = r#"# Written by `git-report init --ci github`: attaches a report of the commits
+# since the previous tag to every pushed tag.
+name: git-report
+
+on:
+  push:
+    tags: ["*"]
+
+jobs:
+  report:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+        with:
+          fetch-depth: 0
+      - run: cargo install git-report
+      - name: Generate report
+        run: git-report --ci github-actions --format md,html,json --output git-report
+      - uses: actions/upload-artifact@v4
+        with:
+          name: git-report
+          path: git-report.*
+"#;
+
+const GITLAB_JOB: &str = r#"# Written by `git-report init --ci gitlab`. Include it from .gitlab-ci.yml:
+#
+#   include:
+#     - local: .gitlab/git-report.yml
+#
+# It keeps a report of the commits since the previous tag for every tag.
+git-report:
+  image: rust:latest
+  rules:
+    - if: $CI_COMMIT_TAG
+  variables:
+    GIT_DEPTH: 0
+  script:
+    - cargo install git-report
+    - git-report --ci gitlab --format md,html,json --output git-report
+  artifacts:
+    paths:
+      - git-report.*
+    reports:
+      dotenv: git-report.env
+"#;
+
+/// What `init` wrote and what it left alone.
+#[derive(Default)]
+pub struct InitResult {
+    pub written: Vec<PathBuf>,
+    /// Files that already existed and were kept; `--force` replaces them.
+    pub skipped: Vec<PathBuf>,
+}
+
+fn write_file(path: &Path, content: &str, force: bool, result: &mut InitResult) -> Result<()> {
+    if path.exists() && !force {
+        result.skipped.push(path.to_path_buf());
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).with_context(ReportError::io, || {
+            format!("Failed to create directory {}", parent.display())
+        })?;
+    }
+    fs::write(path, content).with_context(ReportError::io, || {
+        format!("Failed to write {}", path.display())
+    })?;
+    result.written.push(path.to_path_buf());
+    Ok(())
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755))
+        .with_context(ReportError::io, || {
+            format!("Failed to make {} executable", path.display())
+        })
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+/// Writes a commented `.git-report.toml` to the repository root, and the
+/// chosen hook and CI job. Existing files are kept unless `force` is set.
+pub fn run(repo_path: &str, hook: Option<Hook>, ci: Option<Ci>, force: bool) -> Result<InitResult> {
+    let root = Path::new(repo_path);
+    let mut result = InitResult::default();
+
+    write_file(&root.join(".git-report.toml"), CONFIG, force, &mut result)?;
+
+    if let Some(Hook::PrePush) = hook {
+        let path = git::hooks_dir()?.join("pre-push");
+        write_file(&path, PRE_PUSH, force, &mut result)?;
+        if result.written.contains(&path) {
+            make_executable(&path)?;
+        }
+    }
+
+    match ci {
+        Some(Ci::Github) => write_file(
+            &root.join(".github/workflows/git-report.yml"),
+            GITHUB_WORKFLOW,
+            force,
+            &mut result,
+        )?,
+        Some(Ci::Gitlab) => write_file(
+            &root.join(".gitlab/git-report.yml"),
+            GITLAB_JOB,
+            force,
+            &mut result,
+        )?,
+        None => {}
+    }
+
+    Ok(result)
+}
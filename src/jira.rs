@@ -0,0 +1,272 @@
+#[cfg(feature = "jira")]
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+#[cfg(feature = "jira")]
+use crate::{
+    diagnostics::{self, IssueKind},
+    error::{Context, ReportError},
+    warnings,
+};
+use crate::{
+    error::Result,
+    git::Commit,
+    i18n::Locale,
+    report::{Block, Section},
+};
+
+/// Prefixes that look like ticket keys but are usually standards or
+/// algorithms (`UTF-8`, `SHA-256`, `RFC-3339`).
+const IGNORED_PREFIXES: &[&str] = &["UTF", "SHA", "ISO", "RFC", "CVE", "TLS", "SSL", "HTTP"];
+
+/// Details fetched from the Jira REST API for one ticket.
+pub struct TicketInfo {
+    pub title: String,
+    pub status: String,
+}
+
+/// Finds Jira-style keys (`PROJ-123`) in text. When `projects` is not empty
+/// only keys of those projects are returned.
+pub fn extract_keys(text: &str, projects: &[String]) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut keys: Vec<String> = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let starts_word = i == 0 || !chars[i - 1].is_ascii_alphanumeric();
+        if !starts_word || !chars[i].is_ascii_uppercase() {
+            i += 1;
+            continue;
+        }
+
+        let mut j = i;
+        while j < chars.len() && (chars[j].is_ascii_uppercase() || chars[j].is_ascii_digit()) {
+            j += 1;
+        }
+        let project: String = chars[i..j].iter().collect();
+
+        let mut k = j + 1;
+        while k < chars.len() && chars[k].is_ascii_digit() {
+            k += 1;
+        }
+        let has_number = j < chars.len() && chars[j] == '-' && k > j + 1;
+        let ends_word = k >= chars.len() || !chars[k].is_ascii_alphanumeric();
+
+        if has_number && ends_word && project.len() >= 2 {
+            let wanted = if projects.is_empty() {
+                !IGNORED_PREFIXES.contains(&project.as_str())
+            } else {
+                projects.iter().any(|p| p == &project)
+            };
+            let key: String = chars[i..k].iter().collect();
+            if wanted && !keys.contains(&key) {
+                keys.push(key);
+            }
+            i = k;
+        } else {
+            i = j.max(i + 1);
+        }
+    }
+
+    keys
+}
+
+/// Groups commits by the ticket keys mentioned in their subject or body.
+/// Commits mentioning several tickets appear under each of them.
+pub fn group_by_ticket<'a>(
+    commits: &'a [Commit],
+    projects: &[String],
+) -> (BTreeMap<String, Vec<&'a Commit>>, Vec<&'a Commit>) {
+    let mut tickets: BTreeMap<String, Vec<&Commit>> = BTreeMap::new();
+    let mut unlinked = Vec::new();
+
+    for commit in commits {
+        let keys = extract_keys(&format!("{}\n{}", commit.subject, commit.body), projects);
+        if keys.is_empty() {
+            unlinked.push(commit);
+        }
+        for key in keys {
+            tickets.entry(key).or_default().push(commit);
+        }
+    }
+
+    (tickets, unlinked)
+}
+
+/// Fetches a ticket's summary and status. A `user` selects basic auth as used
+/// by Jira Cloud API tokens; otherwise the token is sent as a bearer token.
+#[cfg(feature = "jira")]
+pub fn fetch_ticket(
+    client: &reqwest::blocking::Client,
+    base_url: &str,
+    user: Option<&str>,
+    token: Option<&str>,
+    key: &str,
+) -> Result<TicketInfo> {
+    let url = format!(
+        "{}/rest/api/2/issue/{}?fields=summary,status",
+        base_url.trim_end_matches('/'),
+        key
+    );
+
+    let request = client.get(&url).header("Accept", "application/json");
+    let request = match (user, token) {
+        (Some(user), token) => request.basic_auth(user, token),
+        (None, Some(token)) => request.bearer_auth(token),
+        (None, None) => request,
+    };
+
+    let response = request.send().with_context(ReportError::delivery, || {
+        format!("Failed to connect to Jira at {}", base_url)
+    })?;
+
+    if !response.status().is_success() {
+        return Err(ReportError::delivery(format!(
+            "Jira returned status {} for {}",
+            response.status(),
+            key
+        )));
+    }
+
+    let issue: Value = response.json().with_context(ReportError::parse, || {
+        format!("Failed to parse Jira response for {}", key)
+    })?;
+
+    Ok(TicketInfo {
+        title: issue["fields"]["summary"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+        status: issue["fields"]["status"]["name"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+    })
+}
+
+/// Looks up every ticket; tickets that cannot be fetched are reported as a
+/// warning and left out of the result.
+#[cfg(feature = "jira")]
+fn fetch_tickets<'k>(
+    keys: impl Iterator<Item = &'k String>,
+    url: &str,
+    options: &JiraOptions<'_>,
+) -> Result<HashMap<&'k str, TicketInfo>> {
+    let client = reqwest::blocking::Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .context(ReportError::delivery, "Failed to create HTTP client")?;
+
+    let mut infos = HashMap::new();
+    for key in keys {
+        match fetch_ticket(&client, url, options.user, options.token, key) {
+            Ok(info) => {
+                infos.insert(key.as_str(), info);
+            }
+            Err(e) => {
+                warnings::warn(format!("could not fetch {}: {:#}", key, e));
+                diagnostics::record(
+                    IssueKind::LookupFailed,
+                    None,
+                    format!("Jira ticket {}: {:#}", key, e),
+                );
+            }
+        }
+    }
+
+    Ok(infos)
+}
+
+#[cfg(not(feature = "jira"))]
+fn fetch_tickets<'k>(
+    _keys: impl Iterator<Item = &'k String>,
+    _url: &str,
+    _options: &JiraOptions<'_>,
+) -> Result<HashMap<&'k str, TicketInfo>> {
+    Err(crate::features::unavailable("Jira ticket lookups", "jira"))
+}
+
+#[cfg_attr(not(feature = "jira"), allow(dead_code))]
+pub struct JiraOptions<'a> {
+    pub url: Option<&'a str>,
+    pub user: Option<&'a str>,
+    pub token: Option<&'a str>,
+    pub projects: &'a [String],
+}
+
+pub fn tickets_section(
+    commits: &[Commit],
+    options: &JiraOptions<'_>,
+    locale: &Locale,
+) -> Result<Section> {
+    let (tickets, unlinked) = group_by_ticket(commits, options.projects);
+
+    let mut infos = match options.url {
+        Some(url) => fetch_tickets(tickets.keys(), url, options)?,
+        None => HashMap::new(),
+    };
+
+    let mut rows = Vec::new();
+    for (key, ticket_commits) in &tickets {
+        let info = infos.remove(key.as_str());
+
+        let link = match options.url {
+            Some(url) => format!("{}/browse/{}", url.trim_end_matches('/'), key),
+            None => String::new(),
+        };
+
+        let mut row = vec![key.clone()];
+        if options.url.is_some() {
+            let (title, status) = info
+                .map(|info| (info.title, info.status))
+                .unwrap_or_else(|| ("(unavailable)".to_string(), String::new()));
+            row.extend([title, status, link]);
+        }
+        row.push(
+            ticket_commits
+                .iter()
+                .map(|c| format!("{} {}", &c.hash[..8], c.subject))
+                .collect::<Vec<_>>()
+                .join("; "),
+        );
+        rows.push(row);
+    }
+
+    let mut headers = vec!["ticket"];
+    if options.url.is_some() {
+        headers.extend(["title", "status", "link"]);
+    }
+    headers.push("commits");
+    let headers = headers
+        .into_iter()
+        .map(|key| locale.text(key).to_string())
+        .collect();
+
+    let mut section =
+        Section::new("jira", locale.text("jira-tickets")).with_block(Block::Fields(vec![
+            (
+                locale.text("tickets-referenced").to_string(),
+                tickets.len().to_string(),
+            ),
+            (
+                locale.text("commits-without-ticket").to_string(),
+                unlinked.len().to_string(),
+            ),
+        ]));
+
+    if !rows.is_empty() {
+        section = section.with_block(Block::Table { headers, rows });
+    }
+
+    if !unlinked.is_empty() {
+        section = section.with_block(Block::List {
+            title: locale.text("unplanned-work-no-ticket").to_string(),
+            items: unlinked
+                .iter()
+                .map(|c| format!("{} {}", &c.hash[..8], c.subject))
+                .collect(),
+        });
+    }
+
+    Ok(section)
+}
@@ -0,0 +1,108 @@
+use globset::GlobSet;
+use regex_automata::meta::Regex;
+use std::collections::BTreeMap;
+
+use crate::{
+    config::LabelRule,
+    error::{ReportError, Result},
+    exclude, filter,
+    git::Commit,
+    i18n::Locale,
+    report::{Block, Section},
+};
+
+/// Attaches the `[[labels]]` of the config to commits: a label applies when
+/// its `pattern` matches the commit message or one of its `paths` matches a
+/// changed file.
+pub struct Labeler {
+    rules: Vec<(String, Option<Regex>, GlobSet)>,
+}
+
+impl Labeler {
+    pub fn new(rules: &[LabelRule]) -> Result<Self> {
+        let rules = rules
+            .iter()
+            .map(|rule| {
+                if rule.pattern.is_none() && rule.paths.is_empty() {
+                    return Err(ReportError::parse(format!(
+                        "Label '{}' needs a pattern or paths",
+                        rule.name
+                    )));
+                }
+                let kind = format!("label '{}'", rule.name);
+                let pattern = rule
+                    .pattern
+                    .as_deref()
+                    .map(|pattern| filter::compile_regex(pattern, &kind, false))
+                    .transpose()?;
+                let paths = exclude::glob_set(&rule.paths, &kind)?;
+                Ok((rule.name.clone(), pattern, paths))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { rules })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// The labels of `commit`, in config order.
+    pub fn labels(&self, commit: &Commit) -> Vec<String> {
+        let message = format!("{}\n\n{}", commit.subject, commit.body);
+        let mut labels: Vec<String> = Vec::new();
+        for (name, pattern, paths) in &self.rules {
+            let matches = pattern.as_ref().is_some_and(|p| p.is_match(&message))
+                || commit.files_changed.iter().any(|path| paths.is_match(path));
+            if matches && !labels.contains(name) {
+                labels.push(name.clone());
+            }
+        }
+        labels
+    }
+
+    pub fn apply(&self, commits: &mut [Commit]) {
+        for commit in commits {
+            commit.labels = self.labels(commit);
+        }
+    }
+}
+
+/// `--group-by label`: the commits of every label, alphabetically, with the
+/// unlabeled ones last. A commit with several labels is listed under each.
+pub fn labels_section(commits: &[Commit], locale: &Locale) -> Section {
+    let mut groups: BTreeMap<&str, Vec<&Commit>> = BTreeMap::new();
+    let mut unlabeled = Vec::new();
+    for commit in commits {
+        if commit.labels.is_empty() {
+            unlabeled.push(commit);
+        }
+        for label in &commit.labels {
+            groups.entry(label).or_default().push(commit);
+        }
+    }
+
+    let line = |commit: &&Commit| format!("{} {}", &commit.hash[..8], commit.subject);
+    let mut section =
+        Section::new("labels", locale.text("commits-by-label")).with_block(Block::Table {
+            headers: ["label", "commits"]
+                .map(|key| locale.text(key).to_string())
+                .to_vec(),
+            rows: groups
+                .iter()
+                .map(|(label, commits)| vec![label.to_string(), commits.len().to_string()])
+                .collect(),
+        });
+    for (label, commits) in &groups {
+        section = section.with_block(Block::List {
+            title: label.to_string(),
+            items: commits.iter().map(line).collect(),
+        });
+    }
+    if !unlabeled.is_empty() {
+        section = section.with_block(Block::List {
+            title: locale.text("unlabeled").to_string(),
+            items: unlabeled.iter().map(line).collect(),
+        });
+    }
+    section
+}
@@ -0,0 +1,366 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    git::Commit,
+    i18n::Locale,
+    report::{Block, Section},
+};
+
+/// A language a commit message can be written in, named in itself so the
+/// name reads the same in a report of any language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Language {
+    /// ISO 639-1 code, as used by `--lang`.
+    pub code: &'static str,
+    pub name: &'static str,
+}
+
+const fn language(code: &'static str, name: &'static str) -> Language {
+    Language { code, name }
+}
+
+/// Languages in the Latin script with their most common short words,
+/// including those typical of commit messages, and letters they use that
+/// English does not.
+const LATIN: &[(Language, &[&str], &str)] = &[
+    (
+        language("en", "English"),
+        &[
+            "the", "and", "to", "of", "for", "with", "in", "is", "on", "this", "that", "from",
+            "when", "not", "it", "be", "add", "fix", "use", "remove", "update", "now", "instead",
+        ],
+        "",
+    ),
+    (
+        language("de", "Deutsch"),
+        &[
+            "der",
+            "die",
+            "das",
+            "und",
+            "nicht",
+            "mit",
+            "für",
+            "von",
+            "ist",
+            "ein",
+            "eine",
+            "bei",
+            "auf",
+            "zu",
+            "wird",
+            "werden",
+            "im",
+            "den",
+            "dem",
+            "des",
+            "auch",
+            "noch",
+            "jetzt",
+            "hinzugefügt",
+            "behoben",
+            "entfernt",
+            "korrigiert",
+            "aktualisiert",
+        ],
+        "äöüß",
+    ),
+    (
+        language("fr", "Français"),
+        &[
+            "le",
+            "la",
+            "les",
+            "des",
+            "et",
+            "pour",
+            "avec",
+            "dans",
+            "une",
+            "un",
+            "est",
+            "pas",
+            "du",
+            "sur",
+            "au",
+            "aux",
+            "qui",
+            "que",
+            "ne",
+            "ajout",
+            "ajoute",
+            "correction",
+            "corrige",
+            "suppression",
+        ],
+        "éèêàçœù",
+    ),
+    (
+        language("es", "Español"),
+        &[
+            "el", "los", "las", "y", "para", "con", "en", "una", "es", "del", "por", "que", "se",
+            "al", "añadir", "añade", "corregir", "corrige", "arreglo", "eliminar",
+        ],
+        "ñ¿¡áíó",
+    ),
+    (
+        language("it", "Italiano"),
+        &[
+            "il",
+            "lo",
+            "gli",
+            "e",
+            "per",
+            "con",
+            "una",
+            "non",
+            "della",
+            "che",
+            "di",
+            "nel",
+            "aggiunto",
+            "aggiunta",
+            "corretto",
+            "rimosso",
+            "aggiornato",
+        ],
+        "àèìòù",
+    ),
+    (
+        language("pt", "Português"),
+        &[
+            "o",
+            "os",
+            "as",
+            "e",
+            "para",
+            "com",
+            "em",
+            "uma",
+            "um",
+            "não",
+            "do",
+            "da",
+            "que",
+            "de",
+            "adicionado",
+            "adiciona",
+            "corrigido",
+            "corrige",
+            "removido",
+        ],
+        "ãõçáéê",
+    ),
+    (
+        language("nl", "Nederlands"),
+        &[
+            "de",
+            "het",
+            "een",
+            "en",
+            "voor",
+            "met",
+            "van",
+            "niet",
+            "op",
+            "bij",
+            "naar",
+            "ook",
+            "toegevoegd",
+            "opgelost",
+            "verwijderd",
+            "bijgewerkt",
+        ],
+        "",
+    ),
+    (
+        language("pl", "Polski"),
+        &[
+            "i",
+            "w",
+            "na",
+            "z",
+            "do",
+            "nie",
+            "się",
+            "dla",
+            "jest",
+            "oraz",
+            "że",
+            "dodano",
+            "poprawiono",
+            "usunięto",
+            "poprawka",
+        ],
+        "ąęłńśźż",
+    ),
+    (
+        language("sv", "Svenska"),
+        &[
+            "och", "att", "för", "med", "på", "är", "inte", "ett", "av", "som", "till", "lagt",
+            "fixat", "tagit",
+        ],
+        "åäö",
+    ),
+    (
+        language("tr", "Türkçe"),
+        &[
+            "ve",
+            "bir",
+            "için",
+            "ile",
+            "bu",
+            "değil",
+            "eklendi",
+            "düzeltildi",
+            "kaldırıldı",
+        ],
+        "ğşı",
+    ),
+];
+
+/// A commit message translated into the report language by the AI backend.
+#[derive(Debug, Clone)]
+pub struct Translation {
+    /// The language the message was written in.
+    pub language: Language,
+    /// The subject and body in the report language.
+    pub text: String,
+}
+
+/// Detects the language of a commit message: by its script for Cyrillic,
+/// Greek, Arabic, Hebrew, Devanagari, Thai, Japanese, Korean and Chinese,
+/// otherwise by the common words and letters of the Latin-script languages
+/// above. `None` when the text gives too little to go by, as in `Bump deps`.
+pub fn detect(text: &str) -> Option<Language> {
+    let letters: Vec<char> = text.chars().filter(|c| c.is_alphabetic()).collect();
+    if letters.is_empty() {
+        return None;
+    }
+
+    let mut scripts: BTreeMap<Language, usize> = BTreeMap::new();
+    let mut kana = false;
+    for &c in &letters {
+        let script = match u32::from(c) {
+            0x0400..=0x04FF => Some(if "іїєґІЇЄҐ".contains(c) {
+                language("uk", "Українська")
+            } else {
+                language("ru", "Русский")
+            }),
+            0x0370..=0x03FF => Some(language("el", "Ελληνικά")),
+            0x0590..=0x05FF => Some(language("he", "עברית")),
+            0x0600..=0x06FF => Some(language("ar", "العربية")),
+            0x0900..=0x097F => Some(language("hi", "हिन्दी")),
+            0x0E00..=0x0E7F => Some(language("th", "ไทย")),
+            0x3040..=0x30FF => {
+                kana = true;
+                Some(language("ja", "日本語"))
+            }
+            0x1100..=0x11FF | 0xAC00..=0xD7AF => Some(language("ko", "한국어")),
+            0x4E00..=0x9FFF => Some(language("zh", "中文")),
+            _ => None,
+        };
+        if let Some(script) = script {
+            *scripts.entry(script).or_default() += 1;
+        }
+    }
+    // Japanese mixes kanji with kana; Cyrillic letters shared by Russian and
+    // Ukrainian count for Ukrainian once one of its own letters shows up.
+    if kana {
+        merge(&mut scripts, "zh", language("ja", "日本語"));
+    }
+    if scripts.keys().any(|l| l.code == "uk") {
+        merge(&mut scripts, "ru", language("uk", "Українська"));
+    }
+    if let Some((&script, &count)) = scripts.iter().max_by_key(|(_, &count)| count) {
+        if count * 10 >= letters.len() * 3 {
+            return Some(script);
+        }
+    }
+
+    let lowercase = text.to_lowercase();
+    let words: Vec<&str> = lowercase
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|word| !word.is_empty())
+        .collect();
+    let mut scores: Vec<(Language, usize)> = LATIN
+        .iter()
+        .map(|(language, common, letters)| {
+            let hits = words.iter().filter(|word| common.contains(word)).count();
+            let marks = letters.chars().filter(|&c| lowercase.contains(c)).count();
+            (*language, hits + 2 * marks)
+        })
+        .collect();
+    scores.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+    match scores[..] {
+        [(best, score), (_, second), ..] if score >= 2 && score > second => Some(best),
+        _ => None,
+    }
+}
+
+fn merge(scripts: &mut BTreeMap<Language, usize>, from: &str, into: Language) {
+    if let Some((&from, &count)) = scripts.iter().find(|(l, _)| l.code == from) {
+        scripts.remove(&from);
+        *scripts.entry(into).or_default() += count;
+    }
+}
+
+/// The language of a commit's subject and body.
+pub fn commit_language(commit: &Commit) -> Option<Language> {
+    detect(&format!("{}\n{}", commit.subject, commit.body))
+}
+
+/// The commits whose message is detected to be in another language than
+/// `report_language`, with that language.
+pub fn foreign_commits<'a>(
+    commits: &'a [Commit],
+    report_language: &str,
+) -> Vec<(&'a Commit, Language)> {
+    commits
+        .iter()
+        .filter_map(|commit| Some((commit, commit_language(commit)?)))
+        .filter(|(_, language)| language.code != report_language)
+        .collect()
+}
+
+/// Commits per detected message language, and the commits written in
+/// another language than the report.
+pub fn languages_section(commits: &[Commit], locale: &Locale) -> Section {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for commit in commits {
+        let name = commit_language(commit)
+            .map_or(locale.text("unknown-language"), |language| language.name);
+        *counts.entry(name).or_default() += 1;
+    }
+    let mut rows: Vec<(&str, usize)> = counts.into_iter().collect();
+    rows.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+
+    let mut section =
+        Section::new("languages", locale.text("commit-languages")).with_block(Block::Table {
+            headers: ["language", "commits"]
+                .map(|key| locale.text(key).to_string())
+                .to_vec(),
+            rows: rows
+                .into_iter()
+                .map(|(name, count)| vec![name.to_string(), count.to_string()])
+                .collect(),
+        });
+    let foreign = foreign_commits(commits, locale.code());
+    if !foreign.is_empty() {
+        section = section.with_block(Block::List {
+            title: locale.text("in-other-languages").to_string(),
+            items: foreign
+                .iter()
+                .map(|(commit, language)| {
+                    format!(
+                        "{} {} ({})",
+                        &commit.hash[..8],
+                        commit.subject,
+                        language.name
+                    )
+                })
+                .collect(),
+        });
+    }
+    section
+}
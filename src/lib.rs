@@ -0,0 +1,108 @@
+//! Commit collection, report building and rendering behind the `git-report`
+//! command. Everything here is synchronous: no async runtime is started unless
+//! an AI backend or network integration actually makes a request. Fallible
+//! functions return [`ReportError`], which callers can match on by kind, and
+//! warnings along the way go to the handler set in [`warnings`].
+//!
+//! ```no_run
+//! use git_report::{git, render, report::{Part, ReportData}};
+//!
+//! let from = git::get_commit("v1.0.0")?;
+//! let to = git::get_commit("HEAD")?;
+//! let commits = git::get_commits_in_range(&from.hash, &to.hash, false)?;
+//!
+//! let data = ReportData {
+//!     repo_path: git::check_git_repository()?,
+//!     generated_at: chrono::Utc::now(),
+//!     from_commit: from,
+//!     to_commit: to,
+//!     commits,
+//!     ai_summary: None,
+//!     convert_ai_summary: false,
+//!     commit_summaries: Default::default(),
+//!     translations: Default::default(),
+//!     impact: Default::default(),
+//!     order: Default::default(),
+//!     sections: Vec::new(),
+//!     dates: Default::default(),
+//!     parts: Part::ALL.to_vec(),
+//!     banner: Default::default(),
+//!     branding: Default::default(),
+//!     locale: Default::default(),
+//!     commit_links: Default::default(),
+//!     theme: Default::default(),
+//!     mermaid_cdn: false,
+//!     front_matter: false,
+//! };
+//! let markdown = render::render(render::Format::Md, &data)?;
+//! # Ok::<(), git_report::ReportError>(())
+//! ```
+
+pub mod ai;
+pub mod aliases;
+#[cfg(feature = "ai")]
+pub mod anthropic;
+pub mod archive;
+pub mod badge;
+pub mod bundle;
+pub mod cache;
+pub mod changelog;
+pub mod check;
+pub mod ci;
+pub mod classify;
+pub mod cluster;
+pub mod community;
+pub mod config;
+#[cfg(feature = "confluence")]
+pub mod confluence;
+pub mod dates;
+pub mod diagnostics;
+#[cfg(feature = "email")]
+pub mod email;
+pub mod error;
+pub mod exclude;
+pub mod features;
+pub mod filter;
+pub mod forge;
+pub mod git;
+pub mod goals;
+pub mod graph;
+pub mod i18n;
+pub mod impact;
+pub mod init;
+pub mod jira;
+pub mod labels;
+pub mod language;
+pub mod mermaid;
+pub mod monorepo;
+pub mod notes;
+#[cfg(feature = "ai")]
+pub mod ollama;
+#[cfg(feature = "ai")]
+pub mod openai;
+pub mod patches;
+pub mod postprocess;
+pub mod progress;
+pub mod range_diff;
+pub mod render;
+pub mod report;
+pub mod report_diff;
+pub mod rollup;
+pub mod run_summary;
+pub mod search;
+pub mod sign;
+pub mod signatures;
+#[cfg(feature = "slack")]
+pub mod slack;
+pub mod squash;
+pub mod state;
+pub mod stats;
+#[cfg(feature = "ai")]
+pub mod transcript;
+pub mod translate;
+pub mod verify;
+pub mod version;
+pub mod warnings;
+pub mod worktree;
+
+pub use error::{ReportError, Result};
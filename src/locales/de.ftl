@@ -0,0 +1,157 @@
+# German report strings.
+
+language-name = German
+
+report-title = Git-Commit-Bericht
+project = Projekt
+prepared-by = Erstellt von
+repository = Repository
+generated = Erstellt
+commit-range = Commit-Bereich
+total-commits = Commits gesamt
+summary = Zusammenfassung
+from = Von
+to = Bis
+date-range = Zeitraum
+date-range-value = { $start } bis { $end }
+lines-changed = Geänderte Zeilen
+lines-changed-value = +{ $added } -{ $deleted } (Code +{ $code_added } -{ $code_deleted }, Tests +{ $test_added } -{ $test_deleted })
+releases = Releases
+commit-types = Commit-Arten
+type = Art
+commits = Commits
+share = Anteil
+timeline = Zeitleiste
+timeline-help = Zum Zoomen scrollen, zum Verschieben ziehen, Doppelklick setzt zurück, für Details über einen Commit fahren oder ihn mit Tab auswählen.
+timeline-label = Commit-Zeitleiste: { $count } Commits von { $start } bis { $end }
+calendar-summary = { $count } Commits von { $authors }
+calendar-more = und { $count } weitere
+detailed-commits = Commits im Detail
+hash = Hash
+author = Autor
+date = Datum
+tags = Tags
+branches = Branches
+signature = Signatur
+labels = Labels
+impact = Auswirkung
+ai-summary = KI-Zusammenfassung
+translation = Übersetzung aus { $language }
+description = Beschreibung
+files-changed = Geänderte Dateien
+
+file = Datei
+files = Dateien
+added = Hinzugefügt
+deleted = Entfernt
+authors = Autoren
+lines = Zeilen
+title = Titel
+state = Zustand
+status = Status
+link = Link
+number = Nummer
+key = Schlüssel
+label = Label
+language = Sprache
+
+file-hotspots = Datei-Hotspots
+churn = Änderungsvolumen
+no-file-changes = Keine Dateiänderungen im Bereich
+author-activity = Aktivität der Autoren
+no-commits = Keine Commits im Bereich
+email-domains = Beiträge nach E-Mail-Domain
+domain = Domain
+company = Unternehmen
+external = Extern
+company-domain = Unternehmen
+external-domain = extern
+domain-share = { $commits } Commits ({ $share } %) von { $authors } Autoren
+
+projects = Projekte
+cross-project-commits = Commits über mehrere Projekte
+project-title = Projekt: { $name }
+
+commit-graph = Commit-Graph
+commit-timeline = Commit-Zeitleiste
+timeline-title = Commits im Zeitverlauf
+branches-and-merges = Branches und Merges
+git-graph-too-large = Der Bereich hat { $count } Commits; der Graph wird nur für bis zu { $max } gezeichnet.
+
+pull-request = Pull-Request
+opened = Eröffnet
+merged = Gemergt
+reviews = Reviews
+draft-state = { $state } (Entwurf)
+pull-requests-and-issues = Pull-Requests und Issues
+merge-requests-and-issues = Merge-Requests und Issues
+pull-requests-referenced = Referenzierte Pull-Requests
+merge-requests-referenced = Referenzierte Merge-Requests
+issues = Issues
+commits-without-reference = Commits ohne Referenz
+reference = Referenz
+jira-tickets = Jira-Tickets
+ticket = Ticket
+tickets = Tickets
+tickets-referenced = Referenzierte Tickets
+commits-without-ticket = Commits ohne Ticket
+unplanned-work-no-ticket = Ungeplante Arbeit (ohne Ticket)
+
+planned-vs-actual = Geplant und umgesetzt
+goals-planned = Geplante Ziele
+goals-with-commits = Ziele mit Commits
+goals-without-commits = Ziele ohne Commits
+unplanned-commits = Ungeplante Commits
+goal = Ziel
+goal-no-commits = Keine Commits
+goal-worked-on = In Arbeit
+unplanned-work = Ungeplante Arbeit
+themes = Themen
+theme = Thema
+commits-by-label = Commits nach Label
+unlabeled = Ohne Label
+commit-languages = Sprachen der Commits
+unknown-language = Unbekannt
+in-other-languages = In anderen Sprachen
+
+version-recommendation = Versionsempfehlung
+recommended-bump = Empfohlene Erhöhung
+current-version = Aktuelle Version
+next-version = Nächste Version
+reasoning = Begründung
+
+commit-signatures = Commit-Signaturen
+signed = Signiert
+unsigned = Nicht signiert
+signed-value = { $signed } von { $total } ({ $share } %)
+signer = Unterzeichner
+without-good-signature = Commits ohne gültige Signatur
+
+community-contributions = Beiträge der Community
+no-community-contributions = Keine Beiträge von außerhalb der Maintainer im Bereich
+community-thanks = Danke an { $names } für die Beiträge zu diesem Release!
+whats-changed = Was sich geändert hat
+contribution = { $subject } von { $author } in { $hash }
+new-contributors = Neue Mitwirkende
+first-contribution = { $author } hat in { $hash } zum ersten Mal beigetragen
+acknowledgments = Danksagungen
+sponsors = Sponsoren
+funding = Finanzierung
+
+annotations = Anmerkungen
+working-tree = Arbeitsverzeichnis
+worktree-clean = Das Arbeitsverzeichnis ist sauber
+staged-files = Vorgemerkte Dateien
+unstaged-files = Nicht vorgemerkte Dateien
+untracked-files = Unversionierte Dateien
+staged-changes = Vorgemerkte Änderungen
+unstaged-changes = Nicht vorgemerkte Änderungen
+untracked = Unversioniert
+binary = binär
+data-quality = Datenqualität
+issue-invalid-date = Nicht lesbare Daten
+issue-invalid-utf8 = Ersetzter Nicht-UTF-8-Inhalt
+issue-missing-details = Fehlende Commit-Details
+issue-truncated = Gekürzter Inhalt
+issue-lookup-failed = Fehlgeschlagene Abfragen
+check-findings = Prüfergebnisse
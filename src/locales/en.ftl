@@ -0,0 +1,169 @@
+# English report strings, and the fallback for every other language.
+# Copy this file to .git-report/locales/<code>.ftl and translate the values
+# to add a language; keys missing there are taken from here.
+
+language-name = English
+
+report-title = Git Commit Report
+project = Project
+prepared-by = Prepared By
+repository = Repository
+generated = Generated
+commit-range = Commit Range
+total-commits = Total Commits
+summary = Summary
+from = From
+to = To
+date-range = Date Range
+date-range-value = { $start } to { $end }
+lines-changed = Lines Changed
+lines-changed-value = +{ $added } -{ $deleted } (code +{ $code_added } -{ $code_deleted }, tests +{ $test_added } -{ $test_deleted })
+releases = Releases
+commit-types = Commit Types
+type = Type
+commits = Commits
+share = Share
+timeline = Timeline
+timeline-help = Scroll to zoom, drag to pan, double-click to reset, hover or tab to a commit for details.
+timeline-label = Commit timeline: { $count } commits from { $start } to { $end }
+calendar-summary = { $count } commits by { $authors }
+calendar-more = and { $count } more
+detailed-commits = Detailed Commits
+hash = Hash
+author = Author
+date = Date
+tags = Tags
+branches = Branches
+signature = Signature
+labels = Labels
+impact = Impact
+ai-summary = AI Summary
+translation = Translation from { $language }
+description = Description
+files-changed = Files Changed
+
+# Column headers and labels shared by the sections below.
+file = File
+files = Files
+added = Added
+deleted = Deleted
+authors = Authors
+lines = Lines
+title = Title
+state = State
+status = Status
+link = Link
+number = Number
+key = Key
+label = Label
+language = Language
+
+# Statistics sections.
+file-hotspots = File Hotspots
+churn = Churn
+no-file-changes = No file changes in range
+author-activity = Author Activity
+no-commits = No commits in range
+email-domains = Contributions by Email Domain
+domain = Domain
+company = Company
+external = External
+company-domain = company
+external-domain = external
+domain-share = { $commits } commits ({ $share }%) by { $authors } authors
+
+# Monorepo projects.
+projects = Projects
+cross-project-commits = Commits touching several projects
+project-title = Project: { $name }
+
+# Commit graph and diagrams.
+commit-graph = Commit Graph
+commit-timeline = Commit Timeline
+timeline-title = Commits over Time
+branches-and-merges = Branches and Merges
+git-graph-too-large = The range has { $count } commits; the graph is only drawn for up to { $max }.
+
+# Pull requests, merge requests and tickets.
+pull-request = Pull Request
+opened = Opened
+merged = Merged
+reviews = Reviews
+draft-state = { $state } (draft)
+pull-requests-and-issues = Pull Requests and Issues
+merge-requests-and-issues = Merge Requests and Issues
+pull-requests-referenced = Pull Requests Referenced
+merge-requests-referenced = Merge Requests Referenced
+issues = Issues
+commits-without-reference = Commits Without Reference
+reference = Reference
+jira-tickets = Jira Tickets
+ticket = Ticket
+tickets = Tickets
+tickets-referenced = Tickets Referenced
+commits-without-ticket = Commits Without Ticket
+unplanned-work-no-ticket = Unplanned Work (no ticket)
+
+# Goals, themes, labels and languages.
+planned-vs-actual = Planned vs Actual
+goals-planned = Goals Planned
+goals-with-commits = Goals With Commits
+goals-without-commits = Goals Without Commits
+unplanned-commits = Unplanned Commits
+goal = Goal
+goal-no-commits = No commits
+goal-worked-on = Worked on
+unplanned-work = Unplanned Work
+themes = Themes
+theme = Theme
+commits-by-label = Commits by Label
+unlabeled = Unlabeled
+commit-languages = Commit Languages
+unknown-language = Unknown
+in-other-languages = In Other Languages
+
+# Version recommendation.
+version-recommendation = Version Recommendation
+recommended-bump = Recommended Bump
+current-version = Current Version
+next-version = Next Version
+reasoning = Reasoning
+
+# Signatures.
+commit-signatures = Commit Signatures
+signed = Signed
+unsigned = Unsigned
+signed-value = { $signed } of { $total } ({ $share }%)
+signer = Signer
+without-good-signature = Commits without a good signature
+
+# Community and acknowledgments.
+community-contributions = Community Contributions
+no-community-contributions = No contributions from outside the maintainers in range
+community-thanks = Thanks to { $names } for contributing to this release!
+whats-changed = What's Changed
+contribution = { $subject } by { $author } in { $hash }
+new-contributors = New Contributors
+first-contribution = { $author } made their first contribution in { $hash }
+acknowledgments = Acknowledgments
+sponsors = Sponsors
+funding = Funding
+
+# Notes, working tree, data quality and check findings.
+annotations = Annotations
+working-tree = Working Tree
+worktree-clean = Working tree is clean
+staged-files = Staged files
+unstaged-files = Unstaged files
+untracked-files = Untracked files
+staged-changes = Staged changes
+unstaged-changes = Unstaged changes
+untracked = Untracked
+binary = binary
+data-quality = Data Quality
+issue-invalid-date = Unparseable dates
+issue-invalid-utf8 = Non-UTF-8 content replaced
+issue-missing-details = Missing commit details
+issue-truncated = Truncated content
+issue-lookup-failed = Failed lookups
+check-findings = Check Findings
@@ -1,21 +1,57 @@
 use anyhow::{Context, Result};
-use chrono::{DateTime, Utc};
-use clap::Parser;
+use chrono::Utc;
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
 use colored::*;
+#[cfg(feature = "tui")]
 use console::Term;
+#[cfg(feature = "tui")]
 use dialoguer::Select;
-use serde_json::{json, Value};
-use std::{fs::File, io::Write, process::Command};
-
-#[derive(Parser, Debug)]
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+mod completions;
+#[cfg(feature = "tui")]
+mod picker;
+mod serve;
+mod watch;
+
+#[cfg(feature = "confluence")]
+use git_report::confluence;
+#[cfg(feature = "email")]
+use git_report::email;
+#[cfg(feature = "slack")]
+use git_report::slack;
+use git_report::{
+    ai, aliases, archive, badge, bundle, cache, changelog, check, ci, classify, cluster, community,
+    config,
+    dates::{self, DateStyle},
+    diagnostics, exclude, features, filter, forge,
+    git::{self, Commit},
+    goals, graph,
+    i18n::{self, Locale},
+    impact, init, jira, labels, language, mermaid, monorepo, notes, patches, postprocess,
+    progress::Progress,
+    range_diff, render,
+    report::{self, Branding, CommitOrder, Part, ReportData, Theme},
+    report_diff, rollup, run_summary, search, sign, signatures, squash, state, stats, translate,
+    verify, version, warnings, worktree,
+};
+
+#[derive(Parser, Debug, Clone)]
 #[command(name = "git-report")]
 #[command(about = "Generate detailed commit reports from git repository")]
 #[command(version)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Commands>,
     #[arg(
         short,
         long,
-        help = "Output file path (default: git-report-{timestamp}.txt)"
+        help = "Output file path (default: git-report-{timestamp}.{format}); with several formats the extension is replaced per format"
     )]
     output: Option<String>,
     #[arg(short, long, help = "From commit hash or reference")]
@@ -25,566 +61,3405 @@ struct Args {
     #[arg(
         short,
         long,
-        default_value = "50",
-        help = "Number of commits to show in selection"
+        default_value = "1000",
+        help = "Most commits the interactive picker loads as you scroll or search"
     )]
     limit: usize,
-    #[arg(long, help = "Generate AI-enhanced report using local Ollama")]
+    #[arg(
+        long,
+        conflicts_with = "limit",
+        help = "Let the interactive picker browse and search the complete history"
+    )]
+    all: bool,
+    #[arg(long, help = "Generate AI-enhanced report")]
     ai: bool,
     #[arg(
         long,
-        default_value = "gemma3",
-        help = "Ollama model to use for AI generation"
+        requires = "ai",
+        help = "Also write the plain report without AI content next to the AI one, with -raw and -ai name suffixes"
     )]
-    model: String,
-}
-
-#[derive(Debug, Clone)]
-struct Commit {
-    hash: String,
-    author: String,
-    date: DateTime<Utc>,
-    subject: String,
-    body: String,
-    files_changed: Vec<String>,
-}
-
-fn check_git_repository() -> Result<String> {
-    let output = Command::new("git")
-        .args(["rev-parse", "--show-toplevel"])
-        .output()
-        .context("Failed to execute git command. Make sure you're in a git repository.")?;
-
-    if !output.status.success() {
-        anyhow::bail!("Not in a git repository");
-    }
-
-    let repo_path = String::from_utf8(output.stdout)?.trim().to_string();
-
-    Ok(repo_path)
-}
-
-fn get_commit_list(limit: usize) -> Result<Vec<Commit>> {
-    let output = Command::new("git")
-        .args([
-            "log",
-            "--pretty=format:%H|%an|%ad|%s",
-            "--date=iso",
-            &format!("-{}", limit),
-        ])
-        .output()
-        .context("Failed to get commit list")?;
-
-    if !output.status.success() {
-        anyhow::bail!("Failed to get commit list");
-    }
-
-    let commits_str = String::from_utf8(output.stdout)?;
-    let mut commits = Vec::new();
-
-    for line in commits_str.lines() {
-        let parts: Vec<&str> = line.split('|').collect();
-        if parts.len() >= 4 {
-            let hash = parts[0].to_string();
-            let author = parts[1].to_string();
-            let date_str = parts[2];
-            let subject = parts[3].to_string();
-
-            let date = DateTime::parse_from_rfc3339(date_str)
-                .unwrap_or_else(|_| Utc::now().into())
-                .with_timezone(&Utc);
-
-            let (body, files_changed) = get_commit_details(&hash)?;
-
-            commits.push(Commit {
-                hash,
-                author,
-                date,
-                subject,
-                body,
-                files_changed,
-            });
-        }
+    both: bool,
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        default_value_t = ai::Provider::Ollama,
+        help = "Service that runs the AI model"
+    )]
+    provider: ai::Provider,
+    #[arg(
+        long,
+        global = true,
+        help = "Model to use for AI generation [default: gemma3 for ollama, claude-sonnet-4-5 for anthropic]"
+    )]
+    model: Option<String>,
+    #[arg(
+        long,
+        global = true,
+        default_value_t = 4096,
+        value_parser = clap::value_parser!(u32).range(1..),
+        help = "Maximum number of tokens in each AI response (anthropic)"
+    )]
+    max_tokens: u32,
+    #[arg(
+        long,
+        global = true,
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..),
+        help = "Largest AI report prompt in estimated tokens; over it, diffs, commit bodies and file lists are left out in that order [default: 6000 for ollama, 150000 for anthropic]"
+    )]
+    max_prompt_tokens: Option<usize>,
+    #[arg(
+        long,
+        global = true,
+        default_value_t = 3,
+        help = "Times to retry an AI request that failed to connect, timed out or hit a rate limit or server error"
+    )]
+    ai_retries: u32,
+    #[arg(
+        long,
+        global = true,
+        default_value = "2s",
+        value_parser = watch::parse_interval,
+        help = "Wait before the first AI retry, doubled for each next one, e.g. 2s or 1m"
+    )]
+    ai_retry_delay: Duration,
+    #[arg(
+        long,
+        help = "Email the generated report to this address (SMTP settings from config/env)"
+    )]
+    email: Option<String>,
+    #[arg(
+        long,
+        requires = "email",
+        help = "Send the report as an attachment instead of the email body"
+    )]
+    email_attach: bool,
+    #[arg(
+        long,
+        help = "Post the generated report to a Slack incoming webhook URL"
+    )]
+    slack_webhook: Option<String>,
+    #[arg(
+        long,
+        value_name = "KEY",
+        requires = "confluence_url",
+        help = "Upload the report as a page to this Confluence space, updating the page of the same title"
+    )]
+    confluence_space: Option<String>,
+    #[arg(
+        long,
+        value_name = "PAGE_ID",
+        requires = "confluence_space",
+        help = "Id of the Confluence page to create the report page under"
+    )]
+    confluence_parent: Option<String>,
+    #[arg(
+        long,
+        help = "Confluence base URL, e.g. https://example.atlassian.net/wiki"
+    )]
+    confluence_url: Option<String>,
+    #[arg(
+        long,
+        help = "Confluence user for basic auth (Confluence Cloud API tokens)"
+    )]
+    confluence_user: Option<String>,
+    #[arg(
+        long,
+        help = "Confluence API token (default: CONFLUENCE_TOKEN environment variable)"
+    )]
+    confluence_token: Option<String>,
+    #[arg(
+        long,
+        help = "Store the report and its commit dataset in the archive directory"
+    )]
+    archive: bool,
+    #[arg(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        default_value = "messages,filenames,authors",
+        help = "Commit data sent to the AI backend besides subjects"
+    )]
+    ai_include: Vec<AiInclude>,
+    #[arg(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        default_value = "preamble,headings",
+        help = "Clean-up of the AI-written text: drop openers like \"Here is your report:\", shift its headings, convert its Markdown to the output format (none keeps the text as written)"
+    )]
+    ai_postprocess: Vec<postprocess::Step>,
+    #[arg(
+        long,
+        value_name = "LEVEL",
+        default_value_t = 3,
+        value_parser = clap::value_parser!(u8).range(1..=6),
+        help = "Heading level the top heading of the AI text is moved to"
+    )]
+    ai_heading_level: u8,
+    #[arg(
+        long,
+        value_name = "LEVEL",
+        default_value_t = 6,
+        value_parser = clap::value_parser!(u8).range(1..=6),
+        help = "Deepest heading level in the AI text; deeper headings are raised to it"
+    )]
+    ai_max_heading: u8,
+    #[arg(
+        long,
+        global = true,
+        help = "Save the exact AI prompts and raw responses to this directory"
+    )]
+    save_prompts: Option<PathBuf>,
+    #[arg(
+        long,
+        help = "Recommend the next semantic version from conventional commits in the range"
+    )]
+    suggest_version: bool,
+    #[arg(
+        long,
+        help = "Add a section with the most frequently changed files, their churn and authors"
+    )]
+    hotspots: bool,
+    #[arg(
+        long,
+        help = "Add a section with contributions by author email domain, company versus external"
+    )]
+    domains: bool,
+    #[arg(
+        long,
+        help = "Group the changes by sub-project (top-level directory, or [monorepo] roots and projects) with a section per project"
+    )]
+    monorepo: bool,
+    #[arg(
+        long,
+        help = "Add a section thanking contributors outside [community] maintainers, like GitHub's generated release notes"
+    )]
+    community: bool,
+    #[arg(
+        long,
+        help = "Add the [acknowledgments] sponsors and funding section (implied by --community)"
+    )]
+    acknowledgments: bool,
+    #[arg(
+        long,
+        help = "Score each commit's impact from its size, core paths and whether tests changed"
+    )]
+    impact: bool,
+    #[arg(
+        long,
+        value_enum,
+        default_value = "date",
+        help = "Order of the detailed commit list; impact implies --impact"
+    )]
+    sort: CommitOrder,
+    #[arg(
+        long,
+        value_enum,
+        value_name = "KEY",
+        help = "Add a section listing the commits grouped by KEY"
+    )]
+    group_by: Option<GroupBy>,
+    #[arg(
+        long,
+        help = "Group the commits into themes by the similarity of their messages and list them theme by theme (needs an embedding model)"
+    )]
+    cluster: bool,
+    #[arg(
+        long,
+        value_name = "N",
+        requires = "cluster",
+        value_parser = clap::value_parser!(u16).range(1..),
+        help = "Number of themes for --cluster (default: about the square root of half the commits, at most 12)"
+    )]
+    clusters: Option<u16>,
+    #[arg(
+        long,
+        value_name = "MODEL",
+        help = "Embedding model for --cluster (default: nomic-embed-text on Ollama, text-embedding-3-small with --embedding-url)"
+    )]
+    embedding_model: Option<String>,
+    #[arg(
+        long,
+        value_name = "URL",
+        help = "OpenAI-compatible API for the embeddings, e.g. https://api.openai.com/v1 (key from OPENAI_API_KEY; default: the local Ollama server)"
+    )]
+    embedding_url: Option<String>,
+    #[arg(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        default_value = "txt",
+        help = "Output formats, rendered from a single collection pass"
+    )]
+    format: Vec<render::Format>,
+    #[arg(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        default_value = "summary,stats,details,files",
+        help = "Report parts to include, e.g. summary for a short overview"
+    )]
+    sections: Vec<Part>,
+    #[arg(
+        long,
+        help = "Also write an SVG badge with the range's commit and author counts to this file"
+    )]
+    badge: Option<PathBuf>,
+    #[arg(
+        long,
+        help = "Also write the run summary (commits, authors, churn, output files, durations, AI tokens) as JSON to this file"
+    )]
+    stats_json: Option<PathBuf>,
+    #[arg(
+        long,
+        help = "Write a SHA-256 digest of every report file to <report>.sha256, checkable with sha256sum -c"
+    )]
+    sign: bool,
+    #[arg(
+        long,
+        value_enum,
+        requires = "sign",
+        help = "Also make a detached OpenPGP signature <report>.asc with this tool"
+    )]
+    sign_with: Option<sign::Signer>,
+    #[arg(
+        long,
+        requires = "sign_with",
+        help = "Key to sign with: a key ID, fingerprint or user ID (gpg --local-user; required for sq)"
+    )]
+    sign_key: Option<String>,
+    #[arg(
+        long,
+        help = "Group commits by the Jira ticket keys (e.g. PROJ-123) they mention"
+    )]
+    jira: bool,
+    #[arg(
+        long,
+        help = "Jira base URL used to fetch ticket titles and status (implies --jira)"
+    )]
+    jira_url: Option<String>,
+    #[arg(long, help = "Jira user for basic auth (Jira Cloud API tokens)")]
+    jira_user: Option<String>,
+    #[arg(
+        long,
+        help = "Jira API token (default: JIRA_TOKEN environment variable)"
+    )]
+    jira_token: Option<String>,
+    #[arg(
+        long,
+        value_delimiter = ',',
+        help = "Only treat keys of these Jira projects as tickets"
+    )]
+    jira_projects: Vec<String>,
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "Compare the range with the planned items in this file, one per line with its ticket ids (PROJ-123, #42): goals with and without commits, and unplanned work"
+    )]
+    goals: Option<PathBuf>,
+    #[arg(
+        long,
+        help = "List the merge or pull requests (!123, #123) and issues closed with 'Closes #12' that commits mention, with titles from the GitLab or GitHub API"
+    )]
+    link_requests: bool,
+    #[arg(
+        long,
+        help = "Post the report summary as a comment on the pull or merge request the CI job runs for, updating the previous one"
+    )]
+    post_pr_comment: bool,
+    #[arg(
+        long,
+        default_value = "origin",
+        help = "Remote of the GitHub or GitLab project for --link-requests and --post-pr-comment"
+    )]
+    forge_remote: String,
+    #[arg(
+        long,
+        value_enum,
+        help = "Forge type for --link-requests and --post-pr-comment, for self-hosted instances whose host name does not tell"
+    )]
+    forge_kind: Option<forge::ForgeKind>,
+    #[arg(
+        long,
+        help = "Include the FROM commit itself in the report (from^..to instead of from..to)"
+    )]
+    inclusive: bool,
+    #[arg(
+        long,
+        help = "Swap FROM and TO with a warning when FROM is newer than TO, instead of stopping"
+    )]
+    swap_reversed: bool,
+    #[arg(
+        long,
+        value_enum,
+        help = "Take the range from the CI job and report back to it (summary, outputs, annotations); detected inside CI when no range is given"
+    )]
+    ci: Option<ci::CiProvider>,
+    #[arg(
+        long,
+        help = "Start from the tip of the previous --since-last report and remember this one's tip (TO defaults to HEAD, --from overrides the stored commit)"
+    )]
+    since_last: bool,
+    #[arg(
+        long,
+        value_name = "BASE",
+        num_args = 0..=1,
+        help = "Report the commits of HEAD not yet in origin/BASE, for review before merging; without BASE, the upstream of the current branch"
+    )]
+    unmerged: Option<Option<String>>,
+    #[arg(
+        long,
+        help = "Add a section with uncommitted staged, unstaged and untracked changes"
+    )]
+    include_worktree: bool,
+    #[arg(
+        long,
+        value_enum,
+        value_name = "STYLE",
+        num_args = 0..=1,
+        default_missing_value = "unicode",
+        help = "Add a section with the branch and merge graph of the range, as drawn by git log --graph, in box-drawing characters unless STYLE is ascii"
+    )]
+    graph: Option<graph::GraphStyle>,
+    #[arg(
+        long,
+        value_enum,
+        value_name = "DIAGRAM",
+        value_delimiter = ',',
+        help = "Add Mermaid diagrams, drawn by GitHub, GitLab and HTML reports: timeline (commits over time per author), git-graph (branches and merges)"
+    )]
+    mermaid: Vec<mermaid::MermaidDiagram>,
+    #[arg(
+        long,
+        help = "Let HTML reports load Mermaid 11.4.1 from jsDelivr to draw their diagrams; without it they show the diagram sources and fetch nothing"
+    )]
+    mermaid_cdn: bool,
+    #[arg(
+        long,
+        help = "Add a short AI-written summary under each commit of the standard report"
+    )]
+    ai_per_commit: bool,
+    #[arg(
+        long,
+        help = "List the languages the commit messages are written in, with the commits not in the report language (--lang)"
+    )]
+    detect_languages: bool,
+    #[arg(
+        long,
+        help = "Translate the commit messages written in another language than the report (--lang) with the AI backend, shown after the original"
+    )]
+    translate_messages: bool,
+    #[arg(
+        long,
+        help = "Ask the AI backend again for every commit summary instead of reusing those of earlier runs"
+    )]
+    no_cache: bool,
+    #[arg(
+        long,
+        default_value = "utc",
+        allow_hyphen_values = true,
+        help = "Timezone of report dates: utc, local, author (each commit's own offset), an offset like +02:00 or an IANA name like Europe/Berlin"
+    )]
+    timezone: dates::Timezone,
+    #[arg(
+        long,
+        default_value = dates::DEFAULT_DATE_FORMAT,
+        value_parser = dates::parse_date_format,
+        help = "strftime format of report dates"
+    )]
+    date_format: String,
+    #[arg(
+        long,
+        value_name = "CODE",
+        default_value = "en",
+        help = "Language of the report headings and labels, and of the AI summaries (built in: en, de; more from .git-report/locales/<CODE>.ftl)"
+    )]
+    lang: String,
+    #[arg(
+        long,
+        help = "Use the high-contrast theme for HTML reports (white on black, yellow links and focus outlines)"
+    )]
+    high_contrast: bool,
+    #[arg(
+        long,
+        help = "Start Markdown and HTML reports with YAML front matter (title, repository, range, commit count, generator, date) for static site generators"
+    )]
+    front_matter: bool,
+    #[arg(
+        long,
+        help = "Report heading instead of \"Git Commit Report\", also used for the page title and front matter"
+    )]
+    title: Option<String>,
+    #[arg(
+        long,
+        value_name = "NAME",
+        help = "Person or team the report is prepared by, listed in the summary and given to the AI backend"
+    )]
+    report_author: Option<String>,
+    #[arg(
+        long,
+        value_name = "NAME",
+        help = "Project or client name listed in the summary and given to the AI backend"
+    )]
+    project_name: Option<String>,
+    #[arg(
+        long,
+        help = "Follow only the first parent of merges, listing each merged branch as its merge commit like GitHub's history of main"
+    )]
+    first_parent: bool,
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Stop reading a range once it has more than N commits; the picker's --limit does not cap ranges"
+    )]
+    max_commits: Option<usize>,
+    #[arg(
+        long,
+        help = "Ignore refs/replace and grafts so the report reflects the real object graph"
+    )]
+    no_replace_objects: bool,
+    #[arg(
+        long,
+        help = "Also show notes from this notes ref as annotations (repeatable, besides [notes] refs from the config)"
+    )]
+    notes_ref: Vec<String>,
+    #[arg(
+        long,
+        value_parser = exclude::parse_pattern,
+        help = "Drop files matching this glob from file lists and stats, e.g. Cargo.lock or 'dist/**' (repeatable)"
+    )]
+    exclude: Vec<String>,
+    #[arg(
+        long,
+        help = "Only include commits whose subject or body matches this extended regex, e.g. security (repeatable, any may match)"
+    )]
+    grep: Vec<String>,
+    #[arg(
+        long,
+        requires = "grep",
+        help = "Only include commits matching none of the --grep patterns"
+    )]
+    invert_grep: bool,
+    #[arg(
+        long,
+        value_parser = filter::parse_expression,
+        help = "Only include commits matching this expression, e.g. 'author =~ \"alice\" && files ~ \"src/**\" && !merge'"
+    )]
+    filter: Option<String>,
+    #[arg(
+        long,
+        help = "Apply a filter saved under this name in [filters] of the config (repeatable)"
+    )]
+    filter_name: Vec<String>,
+    #[arg(
+        long,
+        value_parser = filter::parse_author_pattern,
+        help = "Drop commits whose author name matches this case-insensitive regex, e.g. 'jenkins|release-bot' (repeatable)"
+    )]
+    ignore_authors: Vec<String>,
+    #[arg(
+        long,
+        help = "Drop commits by bots and automation accounts such as dependabot, renovate and github-actions"
+    )]
+    no_bots: bool,
+    #[arg(
+        long,
+        help = "Check the GPG/SSH signature of every commit and show it with the key, plus a count of signed and unsigned commits"
+    )]
+    signatures: bool,
+    #[arg(
+        long,
+        help = "Show every merged branch as the single commit a squash merge makes of it, with a combined message (written by the model with --ai)"
+    )]
+    as_squashed: bool,
+    #[arg(
+        long,
+        help = "Print the range, commit, author and file counts and the AI prompt size, then exit without writing or sending anything"
+    )]
+    dry_run: bool,
+    #[arg(
+        long,
+        help = "Run git fetch before collecting, for the remotes named by --from/--to (e.g. upstream/main) or all remotes"
+    )]
+    fetch: bool,
+    #[arg(
+        long,
+        visible_alias = "repo-path",
+        help = "Repository to report on: a directory (bare or with a work tree), or a .bundle file that is cloned to a temporary directory"
+    )]
+    repo: Option<PathBuf>,
+    #[arg(
+        long,
+        conflicts_with = "repo",
+        help = "Git directory to read, like git --git-dir, e.g. a bare repository on a server"
+    )]
+    git_dir: Option<PathBuf>,
+}
+
+/// Commit data that may be sent to the AI backend. Subjects are always sent.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum AiInclude {
+    Messages,
+    Diffs,
+    Filenames,
+    Authors,
+}
+
+/// What `--group-by` groups commits by.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum GroupBy {
+    /// The `[[labels]]` of the config
+    Label,
+}
+
+/// Report options are global: they can be given before or after any
+/// subcommand, and `git-report` without one runs `generate`.
+#[derive(Subcommand, Debug, Clone)]
+enum Commands {
+    /// Generate a report for a range (the default without a subcommand)
+    Generate,
+    /// Write a Markdown changelog entry for a range, grouped by change type
+    #[command(
+        after_help = "The entry is printed unless --output is given; an existing file keeps its title and gets the entry above its first \"## \" heading."
+    )]
+    Changelog,
+    /// Print statistics for a range in the terminal instead of writing a report
+    Stats {
+        #[arg(
+            long,
+            value_delimiter = ',',
+            help = "Metrics to show, e.g. authors,hotspots (default: all of them)"
+        )]
+        metrics: Vec<String>,
+    },
+    /// Check a range against assertions and fail with the findings, e.g. to gate a release in CI
+    Check {
+        #[arg(
+            long,
+            value_name = "N",
+            help = "Fail when the range has more than N commits"
+        )]
+        assert_max_commits: Option<usize>,
+        #[arg(
+            long,
+            help = "Fail when a commit lacks a good GPG, SSH or X.509 signature"
+        )]
+        assert_all_signed: bool,
+        #[arg(
+            long,
+            help = "Fail when a commit adds or changes a sensitive file such as .env or a private key ([check] sensitive_paths)"
+        )]
+        assert_no_sensitive_paths: bool,
+    },
+    /// Print a shell completion script
+    Completions {
+        #[arg(value_enum, help = "Shell to complete in")]
+        shell: completions::Shell,
+    },
+    /// Translate an existing report with the AI backend, keeping its structure intact
+    #[command(after_help = "--output defaults to <report>.<language>.<ext>.")]
+    Translate {
+        #[arg(help = "Report file to translate")]
+        report_file: String,
+        #[arg(short = 'L', long, help = "Target language code (e.g. de, fr, ja)")]
+        language: String,
+    },
+    /// Summarize archived reports for a whole week, month, quarter or year
+    #[command(
+        after_help = "--ai adds a high-level summary; --output defaults to git-report-rollup-{period}.txt."
+    )]
+    Rollup {
+        #[arg(
+            long,
+            value_enum,
+            default_value = "quarter",
+            help = "Period to summarize"
+        )]
+        period: rollup::Period,
+        #[arg(long, help = "Any date inside the period, YYYY-MM-DD (default: today)")]
+        date: Option<chrono::NaiveDate>,
+    },
+    /// Keep running and report everything new since the previous run at a fixed interval
+    Watch {
+        #[arg(
+            long,
+            default_value = "1w",
+            value_parser = watch::parse_interval,
+            help = "Time between reports, e.g. 30m, 12h, 1d or 1w"
+        )]
+        interval: Duration,
+        #[arg(
+            long,
+            default_value = ".",
+            help = "Directory the timestamped reports are written to"
+        )]
+        output_dir: PathBuf,
+    },
+    /// Compare the commits of a saved dataset's range with what the repository has now
+    VerifyRange {
+        #[arg(long, help = "Dataset JSON written by --archive")]
+        baseline: PathBuf,
+    },
+    /// Compare two JSON reports and list the commits added, removed or changed between them
+    #[command(
+        after_help = "Takes reports written with --format json, or archived datasets, e.g. of the same range before and after a rebase. --output writes the comparison to a file instead of the terminal."
+    )]
+    Diff {
+        #[arg(help = "The earlier report")]
+        old_report: PathBuf,
+        #[arg(help = "The later report")]
+        new_report: PathBuf,
+    },
+    /// Explain which patches were added, dropped or modified between two versions of a series
+    #[command(
+        after_help = "--ai summarizes the differences; --output writes the explanation to a file instead of the terminal."
+    )]
+    RangeDiff {
+        #[arg(help = "Old version of the series, e.g. main..topic-v1")]
+        old_range: String,
+        #[arg(help = "New version of the series, e.g. main..topic-v2")]
+        new_range: String,
+    },
+    /// Report on the commits of a GitHub pull request or GitLab merge request, with its reviews
+    Pr {
+        #[arg(help = "Pull request or merge request number")]
+        number: u64,
+        #[arg(
+            long,
+            default_value = "origin",
+            help = "Remote of the GitHub or GitLab project"
+        )]
+        remote: String,
+        #[arg(
+            long,
+            value_enum,
+            help = "Forge type, for self-hosted instances whose host name does not tell"
+        )]
+        forge: Option<forge::ForgeKind>,
+    },
+    /// Write a commented .git-report.toml and optionally a git hook or CI job into the repository
+    Init {
+        #[arg(long, value_enum, help = "Also install this git hook")]
+        hook: Option<init::Hook>,
+        #[arg(long, value_enum, help = "Also write a report job for this CI service")]
+        ci: Option<init::Ci>,
+        #[arg(long, help = "Replace files that already exist")]
+        force: bool,
+    },
+    /// Serve a web page for picking a range and viewing or downloading its report
+    #[command(
+        after_help = "Reports are rendered on request with the options given to git-report, so e.g. `git-report --hotspots serve` adds hotspots to every report. Bind to 0.0.0.0 to share the viewer with your team."
+    )]
+    Serve {
+        #[arg(
+            long,
+            default_value = "127.0.0.1:8080",
+            help = "Address and port to listen on"
+        )]
+        listen: String,
+    },
+    /// Build a report from a format-patch series or mailing-list mbox instead of the repository
+    FromPatches {
+        #[arg(help = "Directory of .patch files or an mbox file")]
+        input: PathBuf,
+    },
+    /// Find the commits whose subject, body or changed paths mention a text
+    #[command(
+        after_help = "Searches all history reachable from --to (default HEAD), or only --from..--to. --grep, --filter, --exclude and the author filters narrow the search; the matches are printed unless --output is given."
+    )]
+    Search {
+        #[arg(help = "Text to look for; case is ignored unless --case-sensitive is given")]
+        query: String,
+        #[arg(long, help = "Treat the query as a regex")]
+        regex: bool,
+        #[arg(long, help = "Match case exactly")]
+        case_sensitive: bool,
+        #[arg(
+            long = "in",
+            value_enum,
+            value_delimiter = ',',
+            value_name = "FIELDS",
+            help = "Where to look, e.g. subject,path (default: subject,body,path)"
+        )]
+        fields: Vec<search::Field>,
+    },
+}
+
+/// HEAD's history for choosing endpoints, loaded page by page as the picker
+/// needs it, up to `limit` commits (all of history when `None`).
+struct CommitHistory {
+    commits: Vec<Commit>,
+    limit: Option<usize>,
+}
+
+impl CommitHistory {
+    /// Loads up to `count` more commits and returns them; fewer once history
+    /// or the limit runs out.
+    fn load(&mut self, count: usize) -> Result<&[Commit]> {
+        let start = self.commits.len();
+        let count = match self.limit {
+            Some(limit) => count.min(limit.saturating_sub(start)),
+            None => count,
+        };
+        if count > 0 {
+            let max_count = (count != usize::MAX).then_some(count);
+            self.commits
+                .extend(git::get_commit_headers(start, max_count)?);
+        }
+        Ok(&self.commits[start..])
+    }
+}
+
+/// Finds a commit by hash prefix among the loaded commits, falling back to
+/// git for references and commits that are not loaded (e.g. the root).
+fn find_commit(commits: &[Commit], reference: &str) -> Result<Commit> {
+    if let Some(commit) = commits.iter().find(|c| c.hash.starts_with(reference)) {
+        return Ok(git::get_commit(&commit.hash)?);
+    }
+
+    git::get_commit(reference).or_else(|error| {
+        let remotes = git::remotes()?;
+        match git::remote_of(reference, &remotes) {
+            Some(remote) => Err(anyhow::Error::new(error).context(format!(
+                "Remote-tracking ref '{}' not found. Check the branch name or pass --fetch to update '{}' first",
+                reference, remote
+            ))),
+            None => Err(error.into()),
+        }
+    })
+}
+
+/// Explains, when FROM is not an ancestor of TO, why `FROM..TO` would not be
+/// the range the user meant, with options that would work instead.
+fn ancestry_problem(
+    from_commit: &Commit,
+    to_commit: &Commit,
+    from_label: &str,
+    to_label: &str,
+) -> Result<Option<String>> {
+    if git::is_ancestor(&from_commit.hash, &to_commit.hash)? {
+        return Ok(None);
+    }
+
+    if git::is_ancestor(&to_commit.hash, &from_commit.hash)? {
+        return Ok(Some(format!(
+            "FROM {} is newer than TO {}: TO comes before FROM in history, so the range is empty.\n\
+            Swap them: --from {} --to {}, or pass --swap-reversed to have them swapped",
+            from_label, to_label, to_label, from_label
+        )));
+    }
+
+    Ok(Some(match git::common_ancestor(&from_commit.hash, &to_commit.hash)? {
+        Some(base) => format!(
+            "FROM {} is not an ancestor of TO {}: they are on branches that diverged at {}, so the range would silently skip FROM's side.\n\
+            To report TO's branch since it diverged (TO's side of git log FROM...TO): --from {} --to {}\n\
+            To report a branch that contains both, name it as TO, e.g. --from {} --to main",
+            from_label,
+            to_label,
+            &base[..8],
+            &base[..8],
+            to_label,
+            from_label
+        ),
+        None => format!(
+            "FROM {} and TO {} share no history, so the range would be all of TO's history.\n\
+            Pick both commits from the same branch, or report TO's history with --from <root commit> --inclusive",
+            from_label, to_label
+        ),
+    }))
+}
+
+/// Whether FROM and TO were given newest first: TO is an ancestor of FROM,
+/// and not the same commit.
+fn is_reversed(from_commit: &Commit, to_commit: &Commit) -> Result<bool> {
+    Ok(from_commit.hash != to_commit.hash && git::is_ancestor(&to_commit.hash, &from_commit.hash)?)
+}
+
+/// The branch `--unmerged` compares HEAD with: `origin/<base>` when it
+/// exists, else `<base>` itself; without a base, the upstream of the current
+/// branch.
+fn unmerged_base(base: Option<&str>) -> Result<String> {
+    let Some(base) = base else {
+        return git::upstream()?.context(
+            "The current branch has no upstream (@{upstream}); name the base branch, e.g. --unmerged main",
+        );
+    };
+
+    let remotes = git::remotes()?;
+    if git::remote_of(base, &remotes).is_none() && remotes.iter().any(|r| r == "origin") {
+        let tracking = format!("origin/{}", base);
+        if git::ref_exists(&tracking)? {
+            return Ok(tracking);
+        }
+        println!(
+            "{}",
+            format!(
+                "Warning: {} not found, comparing with the local branch {}",
+                tracking, base
+            )
+            .yellow()
+        );
+    }
+    if !git::ref_exists(base)? {
+        anyhow::bail!("Base branch '{}' not found", base);
+    }
+    Ok(base.to_string())
+}
+
+/// Runs `git fetch` for `--fetch`: only the remotes whose tracking refs are
+/// named by `--from`/`--to`, or every remote when neither names one.
+fn fetch_remotes(args: &Args) -> Result<()> {
+    let remotes = git::remotes()?;
+    if remotes.is_empty() {
+        println!(
+            "{}",
+            "Warning: --fetch given but the repository has no remotes".yellow()
+        );
+        return Ok(());
+    }
+
+    let mut named = Vec::new();
+    for reference in [&args.from, &args.to].into_iter().flatten() {
+        if let Some(remote) = git::remote_of(reference, &remotes) {
+            if !named.contains(&remote) {
+                named.push(remote);
+            }
+        }
+    }
+
+    if named.is_empty() {
+        println!("{}", "Fetching all remotes...".blue());
+        git::fetch(None)?;
+    } else {
+        for remote in named {
+            println!("{}", format!("Fetching {}...", remote).blue());
+            git::fetch(Some(remote))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Fails early, before any history is collected, when an option needs a Cargo
+/// feature this binary was built without.
+fn check_features(args: &Args) -> Result<()> {
+    let translate = matches!(args.command, Some(Commands::Translate { .. }));
+    let required = [
+        (
+            args.ai || args.ai_per_commit || translate,
+            cfg!(feature = "ai"),
+            "AI reports",
+            "ai",
+        ),
+        (
+            args.translate_messages,
+            cfg!(feature = "ai"),
+            "Commit message translations",
+            "ai",
+        ),
+        (
+            args.cluster,
+            cfg!(feature = "ai"),
+            "Clustered reports",
+            "ai",
+        ),
+        (
+            args.jira_url.is_some(),
+            cfg!(feature = "jira"),
+            "Jira ticket lookups",
+            "jira",
+        ),
+        (
+            args.slack_webhook.is_some(),
+            cfg!(feature = "slack"),
+            "Slack deliveries",
+            "slack",
+        ),
+        (
+            args.confluence_space.is_some(),
+            cfg!(feature = "confluence"),
+            "Confluence uploads",
+            "confluence",
+        ),
+        (
+            args.email.is_some(),
+            cfg!(feature = "email"),
+            "Email deliveries",
+            "email",
+        ),
+        (
+            args.format.contains(&render::Format::Pdf),
+            cfg!(feature = "pdf"),
+            "PDF reports",
+            "pdf",
+        ),
+        (
+            args.format.contains(&render::Format::Xlsx),
+            cfg!(feature = "xlsx"),
+            "Excel reports",
+            "xlsx",
+        ),
+        (
+            matches!(args.command, Some(Commands::Pr { .. })),
+            cfg!(feature = "forge"),
+            "Pull request reports",
+            "forge",
+        ),
+        (
+            args.link_requests,
+            cfg!(feature = "forge"),
+            "Merge request and issue lookups",
+            "forge",
+        ),
+        (
+            args.post_pr_comment,
+            cfg!(feature = "forge"),
+            "Pull request comments",
+            "forge",
+        ),
+    ];
+
+    for (used, enabled, what, feature) in required {
+        if used && !enabled {
+            return Err(features::unavailable(what, feature).into());
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(not(feature = "tui"))]
+fn select_commit(
+    _history: &mut CommitHistory,
+    _prompt: &str,
+    _dates: &DateStyle,
+) -> Result<Commit> {
+    Err(features::unavailable(
+        "Interactive commit selection (pass --from and --to instead)",
+        "tui",
+    )
+    .into())
+}
+
+#[cfg(feature = "tui")]
+fn select_commit(history: &mut CommitHistory, prompt: &str, dates: &DateStyle) -> Result<Commit> {
+    let term = Term::stdout();
+    term.clear_screen()?;
+
+    println!("{}", prompt.bright_blue());
+    println!("Select a commit (newest first); type part of a subject, hash or author to filter:\n");
+
+    let label = |i: usize, c: &Commit| {
+        format!(
+            "{}. {} - {} ({}, {})",
+            i + 1,
+            &c.hash[..8],
+            c.subject,
+            c.author,
+            dates.format_with(&c.date, "%Y-%m-%d")
+        )
+    };
+    let options: Vec<String> = history
+        .commits
+        .iter()
+        .enumerate()
+        .map(|(i, c)| label(i, c))
+        .collect();
+
+    let selection = picker::pick("Commit", options, |count| {
+        let start = history.commits.len();
+        Ok(history
+            .load(count)?
+            .iter()
+            .enumerate()
+            .map(|(i, c)| label(start + i, c))
+            .collect())
+    })?;
+
+    Ok(git::get_commit(&history.commits[selection].hash)?)
+}
+
+/// Without the `tui` feature endpoints always come from the command line, so
+/// there is nothing to confirm.
+#[cfg(not(feature = "tui"))]
+fn confirm_range(
+    _from_commit: &Commit,
+    _to_commit: &Commit,
+    _inclusive: bool,
+    _dates: &DateStyle,
+) -> Result<bool> {
+    Ok(true)
+}
+
+/// Number of authors listed in the range preview.
+#[cfg(feature = "tui")]
+const PREVIEW_AUTHORS: usize = 3;
+
+/// Shows a cheap preview of the selected range and asks whether to go ahead.
+/// Returns `false` when the user wants to pick the endpoints again.
+#[cfg(feature = "tui")]
+fn confirm_range(
+    from_commit: &Commit,
+    to_commit: &Commit,
+    inclusive: bool,
+    dates: &DateStyle,
+) -> Result<bool> {
+    let summary = git::range_summary(&from_commit.hash, &to_commit.hash, inclusive)?;
+
+    println!("\n{}", "Range preview:".blue());
+    println!("  Commits: {}", summary.count);
+    if let (Some(first), Some(last)) = (summary.first_date, summary.last_date) {
+        println!(
+            "  Dates: {} to {} ({} days)",
+            dates.format_with(&first, "%Y-%m-%d"),
+            dates.format_with(&last, "%Y-%m-%d"),
+            (last - first).num_days()
+        );
+    }
+    if !summary.authors.is_empty() {
+        let top: Vec<String> = summary
+            .authors
+            .iter()
+            .take(PREVIEW_AUTHORS)
+            .map(|(name, count)| format!("{} ({})", name, count))
+            .collect();
+        println!("  Top authors: {}", top.join(", "));
+    }
+    if summary.count == 0 {
+        println!(
+            "{}",
+            "  The range is empty. Use --inclusive to include the FROM commit itself.".yellow()
+        );
+    }
+    println!();
+
+    let choice = Select::new()
+        .with_prompt("Generate the report for this range?")
+        .items(&["Generate report", "Reselect commits", "Cancel"])
+        .default(0)
+        .interact()
+        .context("Failed to get user selection")?;
+
+    match choice {
+        0 => Ok(true),
+        1 => Ok(false),
+        _ => anyhow::bail!("Cancelled"),
+    }
+}
+
+/// The catalog of `--lang`, with the contributed translations of the
+/// repository.
+fn locale(args: &Args, repo_path: &str) -> Result<Locale> {
+    Ok(Locale::load(&args.lang, &i18n::locales_dir(repo_path))?)
+}
+
+/// The sentence asking the model to write in the report language, empty
+/// for English.
+fn language_instruction(locale: &Locale) -> String {
+    if locale.is_english() {
+        String::new()
+    } else {
+        format!("\n\nWrite your reply in {}.", locale.language_name())
+    }
+}
+
+/// `--title`, `--report-author` and `--project-name`, each falling back to
+/// the `[branding]` config section.
+fn branding(args: &Args, config: &config::Config) -> Branding {
+    let configured = &config.branding;
+    Branding {
+        title: args.title.clone().or_else(|| configured.title.clone()),
+        author: args
+            .report_author
+            .clone()
+            .or_else(|| configured.author.clone()),
+        project: args
+            .project_name
+            .clone()
+            .or_else(|| configured.project.clone()),
+    }
+}
+
+fn html_theme(args: &Args) -> Theme {
+    if args.high_contrast {
+        Theme::HighContrast
+    } else {
+        Theme::Default
+    }
+}
+
+fn date_style(args: &Args) -> DateStyle {
+    DateStyle {
+        timezone: args.timezone,
+        format: args.date_format.clone(),
+    }
+}
+
+fn ai_backend(args: &Args) -> Result<Box<dyn ai::Backend>> {
+    Ok(ai::backend(
+        args.provider,
+        args.model.as_deref(),
+        args.max_tokens,
+        prompt_budget(args),
+        ai::RetryPolicy {
+            retries: args.ai_retries,
+            delay: args.ai_retry_delay,
+        },
+        args.save_prompts.clone(),
+    )?)
+}
+
+/// Re-collects the range recorded in a dataset and fails when the commits
+/// differ. `--from`/`--to` override the recorded ends, e.g. to check that a
+/// release branch still contains exactly the commits it had.
+fn verify_range(args: &Args, baseline: &Path) -> Result<()> {
+    let dataset = archive::load_dataset(baseline)?;
+    let from = args.from.as_deref().unwrap_or(&dataset.from);
+    let to = args.to.as_deref().unwrap_or(&dataset.to);
+
+    let from_commit = git::get_commit(from)
+        .with_context(|| format!("FROM commit {} is missing from this repository", from))?;
+    let to_commit = git::get_commit(to)
+        .with_context(|| format!("TO commit {} is missing from this repository", to))?;
+    println!(
+        "Verifying {}{}..{} against {}",
+        &from_commit.hash[..8],
+        if dataset.inclusive {
+            " (inclusive)"
+        } else {
+            ""
+        },
+        &to_commit.hash[..8],
+        baseline.display()
+    );
+
+    let commits = git::get_commits_in_range(&from_commit.hash, &to_commit.hash, dataset.inclusive)?;
+    println!(
+        "Baseline commits: {}, current commits: {}",
+        dataset.commits.len(),
+        commits.len()
+    );
+
+    let diff = verify::compare(&dataset.commits, &commits);
+    if diff.is_empty() {
+        println!("{}", "The range matches the baseline".green());
+        return Ok(());
+    }
+
+    print!("{}", verify::render(&diff));
+    anyhow::bail!(
+        "The range differs from the baseline: {} added, {} removed, {} changed",
+        diff.added.len(),
+        diff.removed.len(),
+        diff.changed.len()
+    )
+}
+
+/// `translate`: writes a translated copy of a report file, warning when
+/// hashes or numbers of the original are missing from it.
+fn translate_file(
+    args: &Args,
+    report_file: &str,
+    language: &str,
+    ai: &dyn ai::Backend,
+) -> Result<()> {
+    let content = fs::read_to_string(report_file)
+        .with_context(|| format!("Failed to read report file {}", report_file))?;
+
+    println!(
+        "{}",
+        format!("Checking {} and model '{}'...", ai.name(), ai.model()).blue()
+    );
+    ai.check()?;
+    println!(
+        "{}",
+        format!(
+            "Translating {} to '{}' using model '{}'...",
+            report_file,
+            language,
+            ai.model()
+        )
+        .blue()
+    );
+
+    let translated = translate::translate_report(&content, language, ai)?;
+
+    let missing = translate::missing_verbatim_tokens(&content, &translated);
+    if !missing.is_empty() {
+        println!(
+            "{}",
+            format!(
+                "Warning: {} hashes/numbers from the original were not found in the translation: {}",
+                missing.len(),
+                missing.join(", ")
+            )
+            .yellow()
+        );
+    }
+
+    let output_file = args
+        .output
+        .clone()
+        .unwrap_or_else(|| translate::default_output_path(report_file, language));
+    fs::write(&output_file, translated)
+        .with_context(|| format!("Failed to write translated report {}", output_file))?;
+
+    println!("Translated report saved to: {}", output_file.bright_blue());
+    Ok(())
+}
+
+/// `diff`: compares the commits of two JSON reports or datasets.
+fn diff_reports(args: &Args, old_path: &Path, new_path: &Path) -> Result<()> {
+    let old = report_diff::load(old_path)?;
+    let new = report_diff::load(new_path)?;
+
+    match &args.output {
+        Some(output_file) => {
+            // A file gets the markers without terminal colors.
+            colored::control::set_override(false);
+            let content = report_diff::explain(old_path, &old, new_path, &new);
+            colored::control::unset_override();
+            fs::write(output_file, &content)
+                .with_context(|| format!("Failed to write report diff {}", output_file))?;
+            println!("Report diff saved to: {}", output_file.bright_blue());
+        }
+        None => print!("{}", report_diff::explain(old_path, &old, new_path, &new)),
+    }
+    Ok(())
+}
+
+/// `rollup`: summarizes the archived reports of a period.
+fn rollup_report(
+    args: &Args,
+    repo_path: &str,
+    archive_dir: &Path,
+    period: rollup::Period,
+    date: Option<chrono::NaiveDate>,
+    ai: Option<&dyn ai::Backend>,
+) -> Result<()> {
+    let date = date.unwrap_or_else(|| Utc::now().date_naive());
+    let range = rollup::period_containing(period, date);
+
+    let datasets = archive::load_datasets(archive_dir)?;
+    let data = rollup::collect(&datasets, &range);
+    println!(
+        "Found {} commits from {} archived reports for {}",
+        data.commits.len(),
+        data.reports,
+        range.label
+    );
+
+    if data.commits.is_empty() {
+        anyhow::bail!(
+            "No archived commits found for {} in {}. Generate reports with --archive first.",
+            range.label,
+            archive_dir.display()
+        );
+    }
+
+    let mut report_content = rollup::generate_rollup_report(repo_path, &range, &data);
+    if let Some(ai) = ai {
+        println!(
+            "{}",
+            format!("Checking {} and model '{}'...", ai.name(), ai.model()).blue()
+        );
+        ai.check()?;
+        println!(
+            "{}",
+            format!(
+                "Generating AI rollup summary with model '{}'...",
+                ai.model()
+            )
+            .blue()
+        );
+        report_content = rollup::generate_ai_rollup(repo_path, &range, &report_content, ai)?;
+    }
+
+    let output_file = args.output.clone().unwrap_or_else(|| {
+        let suffix = if ai.is_some() { "-ai" } else { "" };
+        format!("git-report-rollup{}-{}.txt", suffix, range.label)
+    });
+    fs::write(&output_file, report_content)
+        .with_context(|| format!("Failed to write rollup report {}", output_file))?;
+
+    println!("Rollup report saved to: {}", output_file.bright_blue());
+    Ok(())
+}
+
+/// `range-diff`: explains how a patch series changed between two versions.
+fn range_diff_report(
+    args: &Args,
+    old_range: &str,
+    new_range: &str,
+    ai: Option<&dyn ai::Backend>,
+) -> Result<()> {
+    let pairs = range_diff::parse(&git::range_diff(old_range, new_range)?);
+    if pairs.is_empty() {
+        anyhow::bail!(
+            "git range-diff found no patches in {} or {}",
+            old_range,
+            new_range
+        );
+    }
+
+    let mut report_content = range_diff::explain(old_range, new_range, &pairs);
+    if let Some(ai) = ai {
+        println!(
+            "{}",
+            format!("Checking {} and model '{}'...", ai.name(), ai.model()).blue()
+        );
+        ai.check()?;
+        println!(
+            "{}",
+            format!("Summarizing the range diff with model '{}'...", ai.model()).blue()
+        );
+        let summary = range_diff::generate_ai_summary(&report_content, ai)?;
+        report_content.push_str(&format!("\nAI Summary\n==========\n\n{}\n", summary.trim()));
+    }
+
+    match &args.output {
+        Some(output_file) => {
+            fs::write(output_file, &report_content)
+                .with_context(|| format!("Failed to write range diff report {}", output_file))?;
+            println!("Range diff saved to: {}", output_file.bright_blue());
+        }
+        None => print!("{}", report_content),
+    }
+    Ok(())
+}
+
+/// Reports on a pull or merge request: its head and base branch are fetched
+/// from the remote and the range starts where the head branched off the base.
+fn pull_request_report(
+    args: &Args,
+    config: &config::Config,
+    repo_path: &str,
+    number: u64,
+    remote: &str,
+    kind: Option<forge::ForgeKind>,
+) -> Result<()> {
+    let forge = forge::Forge::from_remote_url(&git::remote_url(remote)?, kind)?;
+    println!(
+        "{}",
+        format!(
+            "Fetching {} #{} from {}...",
+            forge.request_name(),
+            number,
+            forge.host
+        )
+        .blue()
+    );
+    let pr = forge.pull_request(number)?;
+    println!(
+        "{} #{}: {}",
+        forge.request_name(),
+        number,
+        pr.title.bright_blue()
+    );
+
+    git::fetch_refs(
+        remote,
+        &[
+            &forge.head_ref(number),
+            &format!("refs/heads/{}", pr.base_branch),
+        ],
+    )?;
+    let base = git::merge_base(&pr.base_sha, &pr.head_sha)?;
+    let from_commit = git::get_commit(&base)?;
+    let to_commit = git::get_commit(&pr.head_sha)?;
+    let started = Instant::now();
+    let commits = git::get_matching_commits_in_range(
+        &from_commit.hash,
+        &to_commit.hash,
+        false,
+        &message_filter(args),
+    )?;
+    println!(
+        "Found {} commits on {} since {}",
+        commits.len(),
+        pr.head_branch,
+        &from_commit.hash[..8]
+    );
+
+    let range = ReportRange {
+        from_commit,
+        to_commit,
+        inclusive: false,
+        commits,
+        sections: vec![forge::pull_request_section(
+            &pr,
+            &date_style(args),
+            &locale(args, repo_path)?,
+        )],
+        collect_time: started.elapsed(),
+    };
+    generate_report(args, config, repo_path, range, None)
+}
+
+/// Points git at `--git-dir` or `--repo`. A bundle file is cloned to a temporary repository
+/// that lives as long as the returned value; options that keep state in the
+/// repository are rejected for it, since the clone is removed afterwards.
+fn open_repository(args: &Args) -> Result<Option<bundle::BundleClone>> {
+    if let Some(git_dir) = &args.git_dir {
+        if !git_dir.is_dir() {
+            anyhow::bail!("--git-dir {} is not a directory", git_dir.display());
+        }
+        git::set_git_dir(git_dir.clone());
+        return Ok(None);
+    }
+    let Some(repo) = &args.repo else {
+        return Ok(None);
+    };
+    if repo.is_dir() {
+        git::set_repository(repo.clone());
+        return Ok(None);
+    }
+
+    let stateful = [
+        (args.since_last, "--since-last"),
+        (args.archive, "--archive"),
+        (args.include_worktree, "--include-worktree"),
+        (
+            matches!(args.command, Some(Commands::Rollup { .. })),
+            "rollup",
+        ),
+        (
+            matches!(args.command, Some(Commands::Watch { .. })),
+            "watch",
+        ),
+    ];
+    if let Some((_, option)) = stateful.iter().find(|(used, _)| *used) {
+        anyhow::bail!("{} cannot be used with a bundle passed to --repo", option);
+    }
+
+    println!("{}", format!("Cloning bundle {}...", repo.display()).blue());
+    let clone = bundle::BundleClone::new(repo)?;
+    git::set_repository(clone.path().to_path_buf());
+    Ok(Some(clone))
+}
+
+/// The repository name shown in reports: the bundle file for `--repo
+/// <bundle>`, otherwise the work tree root.
+fn repository_label(args: &Args, repo_path: &str) -> String {
+    match &args.repo {
+        Some(repo) if repo.is_file() => repo.display().to_string(),
+        _ => repo_path.to_string(),
+    }
+}
+
+/// Reports on a patch series. Options that read the repository are rejected,
+/// since the series may not exist as commits anywhere.
+fn from_patches(args: &Args, input: &Path) -> Result<()> {
+    let needs_repository = [
+        (args.suggest_version, "--suggest-version"),
+        (args.include_worktree, "--include-worktree"),
+        (args.graph.is_some(), "--graph"),
+        (
+            args.mermaid.contains(&mermaid::MermaidDiagram::GitGraph),
+            "--mermaid git-graph",
+        ),
+        (args.since_last, "--since-last"),
+        (args.archive, "--archive"),
+        (args.fetch, "--fetch"),
+        (!args.notes_ref.is_empty(), "--notes-ref"),
+        (args.repo.is_some(), "--repo"),
+        (args.git_dir.is_some(), "--git-dir"),
+        (
+            args.ai && args.ai_include.contains(&AiInclude::Diffs),
+            "--ai-include diffs",
+        ),
+    ];
+    if let Some((_, option)) = needs_repository.iter().find(|(used, _)| *used) {
+        anyhow::bail!(
+            "{} needs a repository and cannot be used with from-patches",
+            option
+        );
+    }
+
+    let started = Instant::now();
+    let commits = patches::load(input)?;
+    println!(
+        "Read {} patches from {}",
+        commits.len(),
+        input.display().to_string().bright_blue()
+    );
+
+    let mut config = config::load_config(".")?;
+    config.archive.enabled = false;
+    config.notes.refs.clear();
+    let mut args = args.clone();
+    apply_named_filters(&mut args, &config)?;
+
+    let range = ReportRange {
+        from_commit: commits[0].clone(),
+        to_commit: commits[commits.len() - 1].clone(),
+        inclusive: true,
+        commits,
+        sections: Vec::new(),
+        collect_time: started.elapsed(),
+    };
+    generate_report(&args, &config, &input.display().to_string(), range, None)
+}
+
+/// Sends the prompt from [`fit_ai_prompt`], warning about the details it
+/// had to leave out.
+fn generate_ai_report(
+    args: &Args,
+    prompt: AiPrompt,
+    ai: &dyn ai::Backend,
+    locale: &Locale,
+) -> Result<String> {
+    let budget = prompt_budget(args);
+    let AiPrompt {
+        mut prompt,
+        estimate,
+        dropped,
+    } = prompt;
+    if !dropped.is_empty() {
+        println!(
+            "{}",
+            format!(
+                "Warning: the prompt is about {} tokens, over the {} of --max-prompt-tokens; left out the {}",
+                estimate,
+                budget,
+                dropped.join(", ")
+            )
+            .yellow()
+        );
+        diagnostics::record(
+            diagnostics::IssueKind::Truncated,
+            None,
+            format!(
+                "AI prompt over --max-prompt-tokens; left out the {}",
+                dropped.join(", ")
+            ),
+        );
+    }
+    let trimmed = ai::estimate_tokens(&prompt);
+    if trimmed > budget {
+        println!(
+            "{}",
+            format!(
+                "Warning: the prompt is still about {} tokens, over the {} of --max-prompt-tokens; the model may not see all commits. Report a shorter range or raise --max-prompt-tokens.",
+                trimmed, budget
+            )
+            .yellow()
+        );
+    }
+
+    prompt.push_str(&language_instruction(locale));
+    let progress = Progress::spinner(&format!("Waiting for {}", ai.model()));
+    let response = ai.generate(&prompt, 0.7);
+    progress.finish();
+
+    Ok(response?)
+}
+
+/// The report prompt as it will be sent, with the estimated size of the
+/// full prompt before details were left out to fit.
+struct AiPrompt {
+    prompt: String,
+    estimate: usize,
+    dropped: Vec<&'static str>,
+}
+
+/// Builds the report prompt with the `--ai-include` details, leaving them out
+/// from the largest to the smallest until it fits `--max-prompt-tokens`.
+fn fit_ai_prompt(
+    args: &Args,
+    repo_path: &str,
+    from_commit: &Commit,
+    to_commit: &Commit,
+    commits: &[Commit],
+    branding: &Branding,
+) -> Result<AiPrompt> {
+    let dates = date_style(args);
+    let budget = prompt_budget(args);
+    let mut include = args.ai_include.clone();
+    let diffs = if include.contains(&AiInclude::Diffs) {
+        commits
+            .iter()
+            .map(|commit| git::get_commit_diff(&commit.hash))
+            .collect::<Result<Vec<_>, _>>()?
+    } else {
+        Vec::new()
+    };
+
+    let mut context = String::new();
+    for (label, value) in [
+        ("Project", &branding.project),
+        ("Report title", &branding.title),
+        ("Prepared by", &branding.author),
+    ] {
+        if let Some(value) = value {
+            context.push_str(&format!("{}: {}\n", label, value));
+        }
+    }
+    context.push_str(&format!("Path: {}\n", repo_path));
+
+    let mut prompt = ai_report_prompt(
+        &context,
+        from_commit,
+        to_commit,
+        commits,
+        &include,
+        &diffs,
+        &dates,
+    );
+    let estimate = ai::estimate_tokens(&prompt);
+    let mut dropped = Vec::new();
+    for (detail, name) in [
+        (AiInclude::Diffs, "diffs"),
+        (AiInclude::Messages, "commit bodies"),
+        (AiInclude::Filenames, "file lists"),
+    ] {
+        if ai::estimate_tokens(&prompt) <= budget {
+            break;
+        }
+        if include.contains(&detail) {
+            include.retain(|included| *included != detail);
+            dropped.push(name);
+            prompt = ai_report_prompt(
+                &context,
+                from_commit,
+                to_commit,
+                commits,
+                &include,
+                &diffs,
+                &dates,
+            );
+        }
+    }
+
+    Ok(AiPrompt {
+        prompt,
+        estimate,
+        dropped,
+    })
+}
+
+/// The largest prompt to send, in estimated tokens.
+fn prompt_budget(args: &Args) -> usize {
+    args.max_prompt_tokens
+        .unwrap_or(args.provider.default_prompt_tokens())
+}
+
+/// The report prompt with the commit details `include` allows; `diffs` holds
+/// one diff per commit when diffs are included. `context` names the project
+/// and repository, one `Label: value` line each.
+fn ai_report_prompt(
+    context: &str,
+    from_commit: &Commit,
+    to_commit: &Commit,
+    commits: &[Commit],
+    include: &[AiInclude],
+    diffs: &[String],
+    dates: &DateStyle,
+) -> String {
+    let mut commit_details = String::new();
+    for (i, commit) in commits.iter().enumerate() {
+        commit_details.push_str(&format!("Commit {}:\n", i + 1));
+        commit_details.push_str(&format!("  Hash: {}\n", commit.hash));
+        if include.contains(&AiInclude::Authors) {
+            commit_details.push_str(&format!("  Author: {}\n", commit.author));
+        }
+        commit_details.push_str(&format!("  Date: {}\n", dates.format(&commit.date)));
+        commit_details.push_str(&format!("  Subject: {}\n", commit.subject));
+        if include.contains(&AiInclude::Messages) && !commit.body.trim().is_empty() {
+            commit_details.push_str(&format!("  Description: {}\n", commit.body.trim()));
+        }
+        if include.contains(&AiInclude::Filenames) && !commit.files_changed.is_empty() {
+            commit_details.push_str("  Files Changed:\n");
+            for file in &commit.files_changed {
+                commit_details.push_str(&format!("    - {}\n", file));
+            }
+        }
+        if include.contains(&AiInclude::Diffs) {
+            let diff = diffs.get(i).map(String::as_str).unwrap_or_default();
+            if !diff.trim().is_empty() {
+                commit_details.push_str("  Diff:\n");
+                for line in diff.lines() {
+                    commit_details.push_str(&format!("    {}\n", line));
+                }
+            }
+        }
+        commit_details.push('\n');
+    }
+
+    format!(
+        "You are an assistant who writes concise and clear commit summaries for sharing directly in Telegram personal messages.\n\
+        Please create a informative update for a colleague who needs to know what changed in the repository.\n\
+        Format:\n\
+        1. Start with a brief summary of what changed overall (1-2 sentences).\n\
+        2. Then, list the key changes as bullet points (plain text, each on a new line, no markdown).\n\
+        3. If there are important difficult technical details, explain them in simple terms.\n\
+        4. Do not use markdown, do not add headings, do not mention 'report' or 'repository' — just the essential changes.\n\
+        5. Avoid repeating phrases or adding unnecessary formalities.\n\
+        6. Write so the text can be copied and sent directly in TELEGRAM.\n\
+        7. Make sure you do not lose ANY of the information or changes. You MUST tell about ALL changes.\n\
+        \n\
+        Data for the summary:\n\
+        {}\
+        Commit range: {} -> {}\n\
+        Number of commits: {}\n\
+        Generated at: {}\n\
+        \n\
+        Commits:\n\
+        {}",
+        context,
+        from_commit.hash,
+        to_commit.hash,
+        commits.len(),
+        dates.format(&Utc::now()),
+        commit_details
+    )
+}
+
+/// The prompt asking for a one-to-two sentence summary of a single commit
+/// from its message and diffstat, limited to the data allowed by
+/// `--ai-include`.
+fn commit_summary_prompt(commit: &Commit, include: &[AiInclude], locale: &Locale) -> String {
+    let mut details = format!("Subject: {}\n", commit.subject);
+    if include.contains(&AiInclude::Messages) && !commit.body.trim().is_empty() {
+        details.push_str(&format!("Message:\n{}\n", commit.body.trim()));
+    }
+    if include.contains(&AiInclude::Filenames) && !commit.file_stats.is_empty() {
+        details.push_str("Files changed (lines added/deleted):\n");
+        for stat in &commit.file_stats {
+            if stat.binary {
+                details.push_str(&format!("  {}\n", stat.label()));
+            } else {
+                details.push_str(&format!(
+                    "  {} +{} -{}\n",
+                    stat.label(),
+                    stat.additions,
+                    stat.deletions
+                ));
+            }
+        }
+    }
+
+    format!(
+        "Summarize what the following git commit changes in one or two plain-language sentences.\n\
+        Write for a reader who does not know the code base. Do not use markdown, headings or lists, \
+        and reply with the summary only.{}\n\
+        \n\
+        {}",
+        language_instruction(locale),
+        details
+    )
+}
+
+/// `--translate-messages`: translates the messages detected to be in another
+/// language than the report, reusing earlier translations like the commit
+/// summaries. Bodies are only sent when `--ai-include` has `messages`.
+fn translate_messages(
+    args: &Args,
+    repo_path: &str,
+    commits: &[Commit],
+    ai: &dyn ai::Backend,
+    locale: &Locale,
+) -> Result<HashMap<String, language::Translation>> {
+    let foreign = language::foreign_commits(commits, locale.code());
+    let cache_path = cache::cache_path(repo_path);
+    let mut cache = if args.no_cache {
+        None
+    } else {
+        Some(cache::SummaryCache::load(&cache_path)?)
+    };
+
+    let mut translations = HashMap::new();
+    let progress = Progress::bar(foreign.len(), "Translating commit messages");
+    for (commit, language) in foreign {
+        let mut message = commit.subject.clone();
+        if args.ai_include.contains(&AiInclude::Messages) && !commit.body.trim().is_empty() {
+            message.push_str(&format!("\n\n{}", commit.body.trim()));
+        }
+        let prompt = format!(
+            "Translate the following git commit message from {} into {}.\n\
+            Keep the first line as the subject line and the line breaks of the rest. Leave code, \
+            identifiers, file paths, issue references and trailers such as Signed-off-by unchanged, \
+            and reply with the translation only.\n\
+            \n\
+            {}",
+            language.name,
+            locale.language_name(),
+            message
+        );
+        let key = cache::SummaryCache::key(ai.name(), ai.model(), &prompt);
+        let text = match cache.as_mut().and_then(|cache| cache.get(&key)) {
+            Some(text) => text,
+            None => {
+                let text = ai.generate(&prompt, 0.2)?.trim().to_string();
+                if let Some(cache) = cache.as_mut() {
+                    cache.insert(key, &commit.hash, &text);
+                }
+                text
+            }
+        };
+        translations.insert(
+            commit.hash.clone(),
+            language::Translation { language, text },
+        );
+        progress.inc();
+    }
+    progress.finish();
+
+    if let Some(cache) = cache.as_mut() {
+        cache.save(&cache_path)?;
+    }
+    Ok(translations)
+}
+
+/// `--ai-per-commit`: a summary of every commit, reusing the ones earlier
+/// runs got for the same prompt and model unless `--no-cache` is given.
+/// Returns the summaries and how many came from the cache.
+fn summarize_commits(
+    args: &Args,
+    repo_path: &str,
+    commits: &[Commit],
+    ai: &dyn ai::Backend,
+    locale: &Locale,
+) -> Result<(HashMap<String, String>, usize)> {
+    let cache_path = cache::cache_path(repo_path);
+    let mut cache = if args.no_cache {
+        None
+    } else {
+        Some(cache::SummaryCache::load(&cache_path)?)
+    };
+
+    let mut summaries = HashMap::new();
+    let mut reused = 0;
+    let progress = Progress::bar(commits.len(), "Summarizing commits");
+    for commit in commits {
+        let prompt = commit_summary_prompt(commit, &args.ai_include, locale);
+        let key = cache::SummaryCache::key(ai.name(), ai.model(), &prompt);
+        let summary = match cache.as_mut().and_then(|cache| cache.get(&key)) {
+            Some(summary) => {
+                reused += 1;
+                summary
+            }
+            None => {
+                let summary = match ai.generate(&prompt, 0.3) {
+                    Ok(summary) => summary.trim().to_string(),
+                    Err(error) => {
+                        // Keep the summaries this run already paid for.
+                        progress.finish();
+                        if let Some(cache) = cache.as_mut() {
+                            cache.save(&cache_path)?;
+                        }
+                        return Err(error.into());
+                    }
+                };
+                if let Some(cache) = cache.as_mut() {
+                    cache.insert(key, &commit.hash, &summary);
+                }
+                summary
+            }
+        };
+        summaries.insert(commit.hash.clone(), summary);
+        progress.inc();
+    }
+    progress.finish();
+
+    if let Some(cache) = cache.as_mut() {
+        cache.save(&cache_path)?;
+        println!(
+            "Reused {} of {} commit summaries from earlier runs",
+            reused,
+            commits.len()
+        );
+    }
+    Ok((summaries, reused))
+}
+
+/// Warns when replace refs, grafts or a shallow clone make the reported
+/// history differ from the commit objects.
+fn warn_history_rewrites(args: &Args) -> Result<()> {
+    let rewrites = git::history_rewrites()?;
+
+    if rewrites.replace_refs > 0 {
+        let message = if args.no_replace_objects {
+            format!(
+                "Note: ignoring {} replace ref(s) (--no-replace-objects).",
+                rewrites.replace_refs
+            )
+        } else {
+            format!(
+                "Warning: {} replace ref(s) in refs/replace rewrite the reported history. Use --no-replace-objects to report the real object graph.",
+                rewrites.replace_refs
+            )
+        };
+        println!("{}", message.yellow());
+    }
+    if rewrites.grafts {
+        let message = if args.no_replace_objects {
+            "Note: ignoring the grafts file (--no-replace-objects)."
+        } else {
+            "Warning: info/grafts rewrites commit parents, so the reported history may differ from the real object graph. Use --no-replace-objects to ignore it."
+        };
+        println!("{}", message.yellow());
+    }
+    if rewrites.shallow {
+        println!(
+            "{}",
+            "Warning: this is a shallow clone. History before the shallow boundary is missing from the report."
+                .yellow()
+        );
+    }
+
+    Ok(())
+}
+
+/// Parses the command line with every report option made global, so
+/// `git-report generate --from v1` and `git-report --from v1 generate` both
+/// work.
+fn command() -> clap::Command {
+    Args::command().mut_args(|arg| arg.global(true))
+}
+
+fn parse_args() -> Args {
+    let mut matches = command().get_matches();
+    Args::from_arg_matches_mut(&mut matches).unwrap_or_else(|error| error.exit())
+}
+
+fn main() -> Result<()> {
+    let mut args = parse_args();
+    if let Some(Commands::Completions { shell }) = &args.command {
+        print!("{}", completions::script(*shell, &mut command()));
+        return Ok(());
+    }
+    check_features(&args)?;
+    if args.sign_with == Some(sign::Signer::Sq) && args.sign_key.is_none() {
+        anyhow::bail!("--sign-with sq needs the signing key, pass --sign-key");
+    }
+    // Library warnings go to stderr, so they stay out of piped reports.
+    warnings::set_handler(|message| {
+        eprintln!("{}", format!("Warning: {}", message).yellow());
+    });
+    git::set_no_replace_objects(args.no_replace_objects);
+    git::set_first_parent(args.first_parent);
+    git::set_max_commits(args.max_commits);
+
+    if let Some(Commands::Translate {
+        report_file,
+        language,
+    }) = &args.command
+    {
+        let ai = ai_backend(&args)?;
+        return translate_file(&args, report_file, language, ai.as_ref());
+    }
+
+    println!("{}", "Git Report Generator".bright_green().bold());
+
+    if let Some(Commands::Diff {
+        old_report,
+        new_report,
+    }) = &args.command
+    {
+        return diff_reports(&args, old_report, new_report);
+    }
+
+    if let Some(Commands::FromPatches { input }) = &args.command {
+        return from_patches(&args, input);
+    }
+
+    let bundle = open_repository(&args)?;
+    let repo_path = git::check_git_repository()?;
+    println!(
+        "Repository: {}",
+        repository_label(&args, &repo_path).bright_blue()
+    );
+    if args.include_worktree && git::is_bare_repository()? {
+        anyhow::bail!(
+            "--include-worktree needs a work tree, but {} is a bare repository",
+            repo_path
+        );
+    }
+
+    // A bundle's temporary clone has no config file; read the one next to the caller instead.
+    let mut config = config::load_config(if bundle.is_some() { "." } else { &repo_path })?;
+    if bundle.is_some() && config.archive.enabled {
+        println!(
+            "{}",
+            "Note: archiving is skipped for bundles, the temporary clone is removed afterwards."
+                .yellow()
+        );
+        config.archive.enabled = false;
+    }
+    apply_named_filters(&mut args, &config)?;
+
+    if let Some(Commands::Init { hook, ci, force }) = &args.command {
+        if bundle.is_some() {
+            anyhow::bail!("init writes into the repository, which a bundle does not have");
+        }
+        let result = init::run(&repo_path, *hook, *ci, *force)?;
+        for path in &result.written {
+            println!("Wrote {}", path.display().to_string().bright_blue());
+        }
+        for path in &result.skipped {
+            println!(
+                "{}",
+                format!("Kept existing {} (--force replaces it)", path.display()).yellow()
+            );
+        }
+        return Ok(());
+    }
+
+    if let Some(Commands::Rollup { period, date }) = &args.command {
+        let archive_dir = archive::archive_dir(&repo_path, &config.archive);
+        let ai = if args.ai {
+            Some(ai_backend(&args)?)
+        } else {
+            None
+        };
+        return rollup_report(
+            &args,
+            &repo_path,
+            &archive_dir,
+            *period,
+            *date,
+            ai.as_deref(),
+        );
+    }
+
+    if !git::has_commits()? {
+        anyhow::bail!("Repository has no commits yet, so there is nothing to report");
+    }
+    warn_history_rewrites(&args)?;
+
+    if args.fetch {
+        fetch_remotes(&args)?;
+    }
+
+    if let Some(Commands::VerifyRange { baseline }) = &args.command {
+        return verify_range(&args, baseline);
+    }
+
+    if let Some(Commands::Search {
+        query,
+        regex,
+        case_sensitive,
+        fields,
+    }) = &args.command
+    {
+        let search = search::Search::new(query, *regex, *case_sensitive, fields)?;
+        return search_history(&args, &config, query, &search);
+    }
+
+    if let Some(Commands::Pr {
+        number,
+        remote,
+        forge,
+    }) = &args.command
+    {
+        return pull_request_report(&args, &config, &repo_path, *number, remote, *forge);
+    }
+
+    if let Some(Commands::RangeDiff {
+        old_range,
+        new_range,
+    }) = &args.command
+    {
+        let ai = if args.ai {
+            Some(ai_backend(&args)?)
+        } else {
+            None
+        };
+        return range_diff_report(&args, old_range, new_range, ai.as_deref());
+    }
+
+    if let Some(Commands::Watch {
+        interval,
+        output_dir,
+    }) = &args.command
+    {
+        if args.dry_run {
+            anyhow::bail!("--dry-run cannot be used with watch, which keeps generating reports");
+        }
+        return watch::run(&args, &config, &repo_path, *interval, output_dir);
+    }
+
+    if let Some(Commands::Serve { listen }) = &args.command {
+        // The page renders on request; AI calls would hold up every visitor.
+        let unsupported = [
+            (args.dry_run, "--dry-run"),
+            (args.ai, "--ai"),
+            (args.ai_per_commit, "--ai-per-commit"),
+            (args.translate_messages, "--translate-messages"),
+            (args.both, "--both"),
+        ];
+        if let Some((_, option)) = unsupported.iter().find(|(used, _)| *used) {
+            anyhow::bail!("{} cannot be used with serve", option);
+        }
+        return serve::run(&args, &config, &repo_path, listen);
+    }
+
+    if let Some(base) = &args.unmerged {
+        if args.from.is_some() || args.to.is_some() {
+            anyhow::bail!("--unmerged chooses the range itself; leave out --from and --to");
+        }
+        let base = unmerged_base(base.as_deref())?;
+        let fork = git::merge_base(&base, "HEAD")?;
+        println!(
+            "Reporting the commits of HEAD not yet in {} (branched off at {})",
+            base,
+            &fork[..8]
+        );
+        args.from = Some(fork);
+        args.to = Some("HEAD".to_string());
+    }
+
+    let state_path = state::state_path(&repo_path);
+    let last_reported = if args.since_last && args.from.is_none() {
+        let last = state::last_commit(&state_path, &repo_path)?;
+        match &last {
+            Some(hash) => println!("Continuing from the previous report at {}", &hash[..8]),
+            None => println!(
+                "{}",
+                "No previous report recorded, select the starting commit".yellow()
+            ),
+        }
+        last
+    } else {
+        None
+    };
+    // In a CI job there is nobody to pick the range; take it from the job.
+    if args.ci.is_none() && args.from.is_none() && args.to.is_none() && !args.since_last {
+        args.ci = ci::CiProvider::detect();
+        if let Some(provider) = args.ci {
+            println!(
+                "Running in {}, taking the range from the job",
+                provider.name()
+            );
+        }
+    }
+    let ci_range = match args.ci {
+        Some(provider) if args.from.is_none() || args.to.is_none() => {
+            let range = ci::range(provider)?;
+            println!(
+                "Reporting on the {} ({}..{})",
+                range.origin,
+                &range.from[..range.from.len().min(8)],
+                &range.to[..range.to.len().min(8)]
+            );
+            Some(range)
+        }
+        _ => None,
+    };
+    let from_arg = args
+        .from
+        .clone()
+        .or(last_reported)
+        .or_else(|| ci_range.as_ref().map(|range| range.from.clone()));
+    let to_arg = args
+        .to
+        .clone()
+        .or_else(|| ci_range.map(|range| range.to))
+        .or_else(|| (args.since_last && from_arg.is_some()).then(|| "HEAD".to_string()));
+
+    let mut history = CommitHistory {
+        commits: Vec::new(),
+        limit: (!args.all).then_some(args.limit),
+    };
+    // Two commits tell a single-commit repository apart; the picker loads
+    // the rest as it needs them.
+    history.load(2)?;
+
+    let dates = date_style(&args);
+    let single_commit = history.commits.len() == 1 && from_arg.is_none() && to_arg.is_none();
+    if single_commit {
+        println!("Repository has a single commit, reporting it on its own");
+    }
+
+    let inclusive = args.inclusive || single_commit;
+    let interactive = from_arg.is_none() && to_arg.is_none() && !single_commit;
+
+    let (from_commit, to_commit) = loop {
+        let from_commit = if let Some(from) = &from_arg {
+            find_commit(&history.commits, from)?
+        } else if single_commit {
+            git::get_commit(&history.commits[0].hash)?
+        } else {
+            select_commit(&mut history, "Select FROM commit (older commit)", &dates)?
+        };
+
+        let to_commit = if let Some(to) = &to_arg {
+            find_commit(&history.commits, to)?
+        } else if single_commit {
+            from_commit.clone()
+        } else {
+            select_commit(&mut history, "Select TO commit (newer commit)", &dates)?
+        };
+
+        let from_label = from_arg
+            .clone()
+            .unwrap_or_else(|| from_commit.hash[..8].to_string());
+        let to_label = to_arg
+            .clone()
+            .unwrap_or_else(|| to_commit.hash[..8].to_string());
+        let (from_commit, to_commit, from_label, to_label) =
+            if args.swap_reversed && is_reversed(&from_commit, &to_commit)? {
+                println!(
+                    "{}",
+                    format!(
+                        "Warning: FROM {} is newer than TO {}, swapping them",
+                        from_label, to_label
+                    )
+                    .yellow()
+                );
+                (to_commit, from_commit, to_label, from_label)
+            } else {
+                (from_commit, to_commit, from_label, to_label)
+            };
+        if let Some(mut problem) =
+            ancestry_problem(&from_commit, &to_commit, &from_label, &to_label)?
+        {
+            if interactive {
+                println!("\n{}\n", problem.yellow());
+                continue;
+            }
+            if args.since_last && args.from.is_none() {
+                problem.push_str("\nThe previous report's tip is no longer in the history of TO; if the branch was rewritten, pass --from to choose a new starting point.");
+            }
+            anyhow::bail!(problem);
+        }
+
+        if !interactive || confirm_range(&from_commit, &to_commit, inclusive, &dates)? {
+            break (from_commit, to_commit);
+        }
+    };
+
+    println!("Range: {} -> {}", from_commit.subject, to_commit.subject);
+
+    let filter = message_filter(&args);
+    let started = Instant::now();
+    let range_commits =
+        git::get_matching_commits_in_range(&from_commit.hash, &to_commit.hash, inclusive, &filter)?;
+    if filter.is_empty() {
+        println!("Found {} commits in range", range_commits.len());
+    } else {
+        println!(
+            "Found {} commits in range {} --grep",
+            range_commits.len(),
+            if args.invert_grep {
+                "not matching"
+            } else {
+                "matching"
+            }
+        );
+    }
+    if range_commits.is_empty() && !filter.is_empty() {
+        println!(
+            "{}",
+            "Warning: no commits in the range match the --grep filter.".yellow()
+        );
+    } else if range_commits.is_empty() && args.since_last && from_commit.hash == to_commit.hash {
+        println!(
+            "{}",
+            "Warning: no new commits since the previous report.".yellow()
+        );
+    } else if range_commits.is_empty() {
+        println!(
+            "{}",
+            "Warning: the range is empty. Use --inclusive to include the FROM commit itself."
+                .yellow()
+        );
+    }
+
+    let range = ReportRange {
+        from_commit,
+        to_commit: to_commit.clone(),
+        inclusive,
+        commits: range_commits,
+        sections: Vec::new(),
+        collect_time: started.elapsed(),
+    };
+    match &args.command {
+        // Statistics are a look at the range, not a report to continue from.
+        Some(Commands::Stats { metrics }) => {
+            return print_stats(&args, &config, &repo_path, range, metrics)
+        }
+        Some(Commands::Check {
+            assert_max_commits,
+            assert_all_signed,
+            assert_no_sensitive_paths,
+        }) => {
+            let sensitive_paths = assert_no_sensitive_paths.then(|| {
+                if config.check.sensitive_paths.is_empty() {
+                    check::SENSITIVE_PATHS
+                        .iter()
+                        .map(|p| p.to_string())
+                        .collect()
+                } else {
+                    config.check.sensitive_paths.clone()
+                }
+            });
+            let assertions = check::Assertions {
+                max_commits: *assert_max_commits,
+                all_signed: *assert_all_signed,
+                sensitive_paths,
+            };
+            return run_checks(&args, &config, &repo_path, range, &assertions);
+        }
+        _ if args.dry_run => return print_plan(&args, &config, &repo_path, range),
+        Some(Commands::Changelog) => {
+            let title = to_arg.unwrap_or_else(|| to_commit.hash[..8].to_string());
+            write_changelog(&args, &config, range, &title)?
+        }
+        _ => generate_report(&args, &config, &repo_path, range, None)?,
+    }
+
+    if args.since_last {
+        state::record_last_commit(&state_path, &repo_path, &to_commit.hash)?;
+    }
+
+    Ok(())
+}
+
+/// Merges the `[filters]` entries named by `--filter-name` into the flags:
+/// their expressions are and-ed with `--filter`, their grep patterns and
+/// exclusions added to those of the command line.
+fn apply_named_filters(args: &mut Args, config: &config::Config) -> Result<()> {
+    for name in args.filter_name.clone() {
+        let Some(named) = config.filters.get(&name) else {
+            if config.filters.is_empty() {
+                anyhow::bail!("No filter named '{}': the config has no [filters]", name);
+            }
+            anyhow::bail!(
+                "No filter named '{}' in [filters] (saved filters: {})",
+                name,
+                config
+                    .filters
+                    .keys()
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        };
+
+        let options = named.options();
+        if let Some(expr) = options.filter {
+            filter::Filter::parse(&expr)
+                .with_context(|| format!("Invalid expression in saved filter '{}'", name))?;
+            args.filter = Some(match args.filter.take() {
+                Some(own) => format!("({}) && ({})", own, expr),
+                None => expr,
+            });
+        }
+        args.grep.extend(options.grep);
+        args.invert_grep |= options.invert_grep;
+        args.exclude.extend(options.exclude);
+        args.ignore_authors.extend(options.ignore_authors);
+        args.no_bots |= options.no_bots;
+    }
+
+    Ok(())
+}
+
+/// The `--grep`/`--invert-grep` message filter for collecting ranges.
+fn message_filter(args: &Args) -> git::MessageFilter {
+    git::MessageFilter {
+        patterns: args.grep.clone(),
+        invert: args.invert_grep,
+    }
+}
+
+/// The commits of one report, plus sections known before collection (e.g.
+/// the pull request the range belongs to), which come first in the report.
+struct ReportRange {
+    from_commit: Commit,
+    to_commit: Commit,
+    /// Whether FROM itself belongs to the range: `--inclusive`, or the only
+    /// commit of a single-commit repository.
+    inclusive: bool,
+    commits: Vec<Commit>,
+    sections: Vec<report::Section>,
+    /// How long collecting the commits took, for the run summary.
+    collect_time: Duration,
+}
+
+/// Applies `--exclude`, `--ignore-authors`/`--no-bots` and `--filter` to the
+/// collected commits.
+fn filter_commits(args: &Args, config: &config::Config, commits: &mut Vec<Commit>) -> Result<()> {
+    aliases::AuthorAliases::new(&config.authors.aliases).apply(commits);
+    exclude::PathExclusions::new(&args.exclude)?.apply(commits);
+    if args.no_bots || !args.ignore_authors.is_empty() {
+        let removed =
+            filter::AuthorExclusions::new(&args.ignore_authors, args.no_bots)?.apply(commits);
+        println!(
+            "Dropped {} commits by ignored authors, {} left",
+            removed,
+            commits.len()
+        );
+    }
+    if let Some(expr) = &args.filter {
+        let filter = filter::Filter::parse(expr)?;
+        let total = commits.len();
+        commits.retain(|commit| filter.matches(commit));
+        println!(
+            "Kept {} of {} commits matching --filter",
+            commits.len(),
+            total
+        );
+    }
+    Ok(())
+}
+
+/// `search`: collects the history to search with the usual filters and
+/// prints the matching commits, newest first, or writes them to `--output`.
+fn search_history(
+    args: &Args,
+    config: &config::Config,
+    query: &str,
+    search: &search::Search,
+) -> Result<()> {
+    let to = args.to.as_deref().unwrap_or("HEAD");
+    let mut commits = git::get_history(args.from.as_deref(), to, &message_filter(args))?;
+    filter_commits(args, config, &mut commits)?;
+    let hits: Vec<search::Hit> = commits.iter().filter_map(|c| search.hit(c)).collect();
+    let output = search::render(query, &hits, commits.len(), &date_style(args));
+
+    match &args.output {
+        Some(path) => {
+            fs::write(path, output).with_context(|| format!("Failed to write {}", path))?;
+            println!("Search results saved to: {}", path.bright_blue());
+        }
+        None => print!("\n{}", output),
+    }
+    Ok(())
+}
+
+/// `changelog`: prints the range's changelog entry or adds it to `--output`.
+fn write_changelog(
+    args: &Args,
+    config: &config::Config,
+    range: ReportRange,
+    title: &str,
+) -> Result<()> {
+    let mut commits = range.commits;
+    filter_commits(args, config, &mut commits)?;
+    // Changelog headings carry the day only, unless `--date-format` asks
+    // for something else.
+    let dates = date_style(args);
+    let date = if args.date_format == dates::DEFAULT_DATE_FORMAT {
+        dates.format_with(&range.to_commit.date, "%Y-%m-%d")
+    } else {
+        dates.format(&range.to_commit.date)
+    };
+    let entry = changelog::render(&commits, title, &date);
+
+    match &args.output {
+        Some(path) => {
+            changelog::write(Path::new(path), &entry)?;
+            println!("Changelog saved to: {}", path.bright_blue());
+        }
+        None => println!("\n{}", entry),
+    }
+    Ok(())
+}
+
+/// The built-in metrics, with `[stats] company_domains` for `domains`.
+fn metric_registry(config: &config::Config) -> stats::MetricRegistry {
+    let mut registry = stats::MetricRegistry::with_defaults();
+    registry.register(stats::EmailDomains::new(
+        config.stats.company_domains.clone(),
+    ));
+    registry
+}
+
+/// The `--mermaid` section of a diagram.
+fn mermaid_section(
+    diagram: mermaid::MermaidDiagram,
+    args: &Args,
+    from_commit: &Commit,
+    to_commit: &Commit,
+    inclusive: bool,
+    commits: &[Commit],
+    locale: &Locale,
+) -> Result<report::Section> {
+    Ok(match diagram {
+        mermaid::MermaidDiagram::Timeline => {
+            mermaid::timeline_section(commits, &date_style(args), locale)
+        }
+        mermaid::MermaidDiagram::GitGraph => mermaid::git_graph_section(
+            &git::range_headers(&from_commit.hash, &to_commit.hash, inclusive)?,
+            locale,
+        ),
+    })
+}
+
+/// The metric sections of a report: `[stats] metrics`, with `--hotspots`
+/// and `--domains` added.
+fn report_metrics(args: &Args, config: &config::Config) -> Vec<String> {
+    let mut metrics = config.stats.metrics.clone();
+    if args.hotspots && !metrics.iter().any(|name| name == "hotspots") {
+        metrics.insert(0, "hotspots".to_string());
+    }
+    if args.domains && !metrics.iter().any(|name| name == "domains") {
+        metrics.push("domains".to_string());
+    }
+    metrics
+}
+
+/// Attaches the `[[labels]]` of the config to the commits.
+fn label_commits(args: &Args, config: &config::Config, commits: &mut [Commit]) -> Result<()> {
+    let labeler = labels::Labeler::new(&config.labels)?;
+    if labeler.is_empty() && args.group_by == Some(GroupBy::Label) {
+        println!(
+            "{}",
+            "Warning: --group-by label given but the config defines no [[labels]]".yellow()
+        );
+    }
+    labeler.apply(commits);
+    Ok(())
+}
+
+/// `stats`: prints the commit type breakdown and the metric sections.
+fn print_stats(
+    args: &Args,
+    config: &config::Config,
+    repo_path: &str,
+    range: ReportRange,
+    names: &[String],
+) -> Result<()> {
+    let locale = locale(args, repo_path)?;
+    let mut commits = range.commits;
+    filter_commits(args, config, &mut commits)?;
+
+    let registry = metric_registry(config);
+    let names = if names.is_empty() {
+        registry.names().into_iter().map(String::from).collect()
+    } else {
+        names.to_vec()
+    };
+
+    let types = report::Section::new("types", locale.text("commit-types"))
+        .with_block(classify::breakdown(&commits, &locale));
+    println!("\n{}", render::text::render_section(&types));
+    for section in registry.sections(&names, &commits, &locale)? {
+        println!("{}", render::text::render_section(&section));
+    }
+    Ok(())
+}
+
+/// `check`: runs the assertions on the range, printing the findings (and
+/// writing them to `--output` when given) and failing when there are any.
+fn run_checks(
+    args: &Args,
+    config: &config::Config,
+    repo_path: &str,
+    range: ReportRange,
+    assertions: &check::Assertions,
+) -> Result<()> {
+    if assertions.max_commits.is_none()
+        && !assertions.all_signed
+        && assertions.sensitive_paths.is_none()
+    {
+        anyhow::bail!(
+            "check needs an assertion: --assert-max-commits, --assert-all-signed or --assert-no-sensitive-paths"
+        );
+    }
+    let mut commits = range.commits;
+    filter_commits(args, config, &mut commits)?;
+    if assertions.all_signed {
+        let progress = Progress::spinner("Checking commit signatures");
+        let checked = git::signatures(&mut commits);
+        progress.finish();
+        checked?;
+    }
+
+    let findings = check::check(assertions, &commits)?;
+    if findings.is_empty() {
+        println!(
+            "{}",
+            format!("All checks passed for {} commit(s)", commits.len()).green()
+        );
+        return Ok(());
     }
 
-    Ok(commits)
+    let locale = locale(args, repo_path)?;
+    let report = render::text::render_section(&check::findings_section(&findings, &locale));
+    println!("\n{}", report);
+    if let Some(output) = &args.output {
+        fs::write(output, &report)
+            .with_context(|| format!("Failed to write findings to {}", output))?;
+        println!("Findings saved to: {}", output.bright_blue());
+    }
+    anyhow::bail!("{} check(s) failed", findings.len())
 }
 
-fn get_commit_details(hash: &str) -> Result<(String, Vec<String>)> {
-    let body_output = Command::new("git")
-        .args(["show", "--no-patch", "--format=%B", hash])
-        .output()
-        .context("Failed to get commit body")?;
-
-    let body = String::from_utf8(body_output.stdout)?
-        .lines()
-        .skip(1)
-        .collect::<Vec<_>>()
-        .join("\n");
-
-    let files_output = Command::new("git")
-        .args(["show", "--name-only", "--format=", hash])
-        .output()
-        .context("Failed to get files changed")?;
+/// `--post-pr-comment`: posts the summary and statistics, and the AI
+/// summary when there is one, to the pull request of `pr` or of the CI job.
+/// Jobs that do not run for a pull request skip the comment.
+fn post_pr_comment(args: &Args, data: &mut ReportData) -> Result<()> {
+    let (number, remote, kind) = match &args.command {
+        Some(Commands::Pr {
+            number,
+            remote,
+            forge,
+        }) => (Some(*number), remote.as_str(), *forge),
+        _ => (
+            match args.ci {
+                Some(provider) => ci::request_number(provider)?,
+                None => None,
+            },
+            args.forge_remote.as_str(),
+            args.forge_kind,
+        ),
+    };
+    let Some(number) = number else {
+        println!(
+            "{}",
+            "Warning: --post-pr-comment skipped, the job does not run for a pull request".yellow()
+        );
+        return Ok(());
+    };
 
-    let files_str = String::from_utf8(files_output.stdout)?;
-    let files_changed: Vec<String> = files_str
-        .lines()
-        .filter(|line| !line.trim().is_empty() && !line.starts_with("commit"))
-        .map(|s| s.to_string())
-        .collect();
+    let forge = forge::Forge::from_remote_url(&git::remote_url(remote)?, kind)?;
+    // The commit list would bury the overview; the AI summary is its short form.
+    let mut parts = vec![Part::Summary, Part::Stats];
+    if data.ai_summary.is_some() {
+        parts.push(Part::Details);
+    }
+    let parts = std::mem::replace(&mut data.parts, parts);
+    let summary = render::markdown::render(data);
+    data.parts = parts;
 
-    Ok((body, files_changed))
+    println!(
+        "{}",
+        format!("Commenting on {} #{}...", forge.request_name(), number).blue()
+    );
+    let url = forge.post_comment(number, &forge::comment_body(&summary))?;
+    println!("Report summary posted to: {}", url.bright_blue());
+    Ok(())
 }
 
-fn select_commit<'a>(commits: &'a [Commit], prompt: &str) -> Result<&'a Commit> {
-    let term = Term::stdout();
-    term.clear_screen()?;
+/// `report.md` with `-ai` becomes `report-ai.md`.
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(extension) => format!("{}{}.{}", stem, suffix, extension.to_string_lossy()),
+        None => format!("{}{}", stem, suffix),
+    };
+    path.with_file_name(name)
+}
 
-    println!("{}", prompt.bright_blue());
-    println!("Select a commit (commits are shown in chronological order, newest first):\n");
+/// The embedding model of `--cluster`.
+fn embedder(args: &Args) -> Result<Box<dyn ai::Embedder>> {
+    Ok(ai::embedder(
+        args.embedding_model.as_deref(),
+        args.embedding_url.as_deref(),
+        ai::RetryPolicy {
+            retries: args.ai_retries,
+            delay: args.ai_retry_delay,
+        },
+    )?)
+}
 
-    let options: Vec<String> = commits
+/// `--cluster`: groups the commits into themes and reorders them theme by
+/// theme, so the detailed list follows the themes section.
+fn cluster_commits(
+    args: &Args,
+    commits: &mut Vec<Commit>,
+    locale: &Locale,
+) -> Result<report::Section> {
+    let themes = cluster::themes(
+        commits,
+        embedder(args)?.as_ref(),
+        args.clusters.map(usize::from),
+    )?;
+    *commits = themes
         .iter()
-        .enumerate()
-        .map(|(i, c)| {
-            format!(
-                "{}. {} - {} ({})",
-                i + 1,
-                c.hash[..8].to_string(),
-                c.subject,
-                c.date.format("%Y-%m-%d")
-            )
-        })
+        .flat_map(|theme| theme.commits.iter().cloned())
         .collect();
+    Ok(cluster::themes_section(&themes, locale))
+}
 
-    let selection = Select::new()
-        .items(&options)
-        .default(0)
-        .interact()
-        .context("Failed to get user selection")?;
-
-    Ok(&commits[selection])
-}
-
-fn get_commits_in_range(from_hash: &str, to_hash: &str) -> Result<Vec<Commit>> {
-    let mut commits = Vec::new();
-
-    let from_commit_details = get_commit_details(from_hash)?;
-    let from_commit_output = Command::new("git")
-        .args([
-            "log",
-            "--pretty=format:%H|%an|%ad|%s",
-            "--date=iso",
-            "-1",
-            from_hash,
-        ])
-        .output()
-        .context("Failed to get from commit details")?;
-
-    if from_commit_output.status.success() {
-        let from_line = String::from_utf8(from_commit_output.stdout)?;
-        if let Some(line) = from_line.lines().next() {
-            let parts: Vec<&str> = line.split('|').collect();
-            if parts.len() >= 4 {
-                let hash = parts[0].to_string();
-                let author = parts[1].to_string();
-                let date_str = parts[2];
-                let subject = parts[3].to_string();
-
-                let date = DateTime::parse_from_rfc3339(date_str)
-                    .unwrap_or_else(|_| Utc::now().into())
-                    .with_timezone(&Utc);
-
-                commits.push(Commit {
-                    hash,
-                    author,
-                    date,
-                    subject,
-                    body: from_commit_details.0,
-                    files_changed: from_commit_details.1,
-                });
-            }
-        }
-    }
-
-    let output = Command::new("git")
-        .args([
-            "log",
-            "--pretty=format:%H|%an|%ad|%s",
-            "--date=iso",
-            &format!("{}..{}", from_hash, to_hash),
-        ])
-        .output()
-        .context("Failed to get commits in range")?;
-
-    if !output.status.success() {
-        anyhow::bail!("Failed to get commits in range");
+/// `--dry-run`: prints what a report on the range would cover and send to
+/// the AI backend, without contacting it or writing anything.
+fn print_plan(
+    args: &Args,
+    config: &config::Config,
+    repo_path: &str,
+    range: ReportRange,
+) -> Result<()> {
+    let ReportRange {
+        from_commit,
+        to_commit,
+        inclusive,
+        mut commits,
+        ..
+    } = range;
+    if args.as_squashed {
+        commits = squash::squash(commits, &to_commit.hash, None)?;
     }
+    filter_commits(args, config, &mut commits)?;
 
-    let commits_str = String::from_utf8(output.stdout)?;
-
-    for line in commits_str.lines() {
-        let parts: Vec<&str> = line.split('|').collect();
-        if parts.len() >= 4 {
-            let hash = parts[0].to_string();
-            let author = parts[1].to_string();
-            let date_str = parts[2];
-            let subject = parts[3].to_string();
-
-            let date = DateTime::parse_from_rfc3339(date_str)
-                .unwrap_or_else(|_| Utc::now().into())
-                .with_timezone(&Utc);
-
-            let (body, files_changed) = get_commit_details(&hash)?;
-
-            commits.push(Commit {
-                hash,
-                author,
-                date,
-                subject,
-                body,
-                files_changed,
-            });
+    let authors: HashSet<&str> = commits.iter().map(|c| c.author.as_str()).collect();
+    let files: HashSet<&str> = commits
+        .iter()
+        .flat_map(|c| &c.files_changed)
+        .map(String::as_str)
+        .collect();
+    let (added, deleted) = commits
+        .iter()
+        .flat_map(|c| &c.file_stats)
+        .fold((0, 0), |(added, deleted), stat| {
+            (added + stat.additions, deleted + stat.deletions)
+        });
+    let formats: Vec<&str> = args.format.iter().map(|f| f.extension()).collect();
+
+    println!(
+        "\n{}",
+        "Dry run: nothing is written, sent or recorded".bold()
+    );
+    println!(
+        "Range: {}..{}{}",
+        &from_commit.hash[..8],
+        &to_commit.hash[..8],
+        if inclusive { " (inclusive)" } else { "" }
+    );
+    println!("Commits: {}", commits.len());
+    println!("Authors: {}", authors.len());
+    println!("Files: {} (+{} -{})", files.len(), added, deleted);
+    println!("Formats: {}", formats.join(", "));
+
+    let model = args
+        .model
+        .as_deref()
+        .unwrap_or(args.provider.default_model());
+    let budget = prompt_budget(args);
+    if args.ai {
+        let prompt = fit_ai_prompt(
+            args,
+            repo_path,
+            &from_commit,
+            &to_commit,
+            &commits,
+            &branding(args, config),
+        )?;
+        let tokens = ai::estimate_tokens(&prompt.prompt);
+        println!(
+            "AI prompt: about {} tokens for '{}' (--max-prompt-tokens {})",
+            tokens, model, budget
+        );
+        if !prompt.dropped.is_empty() {
+            println!(
+                "{}",
+                format!(
+                    "  The full prompt is about {} tokens; the {} would be left out",
+                    prompt.estimate,
+                    prompt.dropped.join(", ")
+                )
+                .yellow()
+            );
         }
-    }
-
-    if from_hash != to_hash {
-        let to_commit_already_included = commits.iter().any(|c| c.hash == to_hash);
-        if !to_commit_already_included {
-            let to_commit_details = get_commit_details(to_hash)?;
-            let to_commit_output = Command::new("git")
-                .args([
-                    "log",
-                    "--pretty=format:%H|%an|%ad|%s",
-                    "--date=iso",
-                    "-1",
-                    to_hash,
-                ])
-                .output()
-                .context("Failed to get to commit details")?;
-
-            if to_commit_output.status.success() {
-                let to_line = String::from_utf8(to_commit_output.stdout)?;
-                if let Some(line) = to_line.lines().next() {
-                    let parts: Vec<&str> = line.split('|').collect();
-                    if parts.len() >= 4 {
-                        let hash = parts[0].to_string();
-                        let author = parts[1].to_string();
-                        let date_str = parts[2];
-                        let subject = parts[3].to_string();
-
-                        let date = DateTime::parse_from_rfc3339(date_str)
-                            .unwrap_or_else(|_| Utc::now().into())
-                            .with_timezone(&Utc);
-
-                        commits.push(Commit {
-                            hash,
-                            author,
-                            date,
-                            subject,
-                            body: to_commit_details.0,
-                            files_changed: to_commit_details.1,
-                        });
-                    }
-                }
-            }
+        if tokens > budget {
+            println!(
+                "{}",
+                "  Still over the limit: the model may not see all commits".yellow()
+            );
         }
     }
-
-    commits.sort_by(|a, b| a.date.cmp(&b.date));
-
-    Ok(commits)
+    if args.ai_per_commit && !commits.is_empty() {
+        let locale = locale(args, repo_path)?;
+        let tokens: usize = commits
+            .iter()
+            .map(|c| ai::estimate_tokens(&commit_summary_prompt(c, &args.ai_include, &locale)))
+            .sum();
+        println!(
+            "Commit summaries: {} requests to '{}', about {} prompt tokens in total (fewer when cached)",
+            commits.len(),
+            model,
+            tokens
+        );
+    }
+    if args.translate_messages {
+        let foreign = language::foreign_commits(&commits, locale(args, repo_path)?.code());
+        println!(
+            "Translations: {} messages in other languages, one request each to '{}'",
+            foreign.len(),
+            model
+        );
+    }
+    Ok(())
 }
 
-fn generate_report(
-    repo_path: &str,
+/// The optional sections of a report on the range, in report order, for the
+/// report files and `serve` alike. Signatures must already be checked when
+/// the report shows them; `--cluster` reorders `commits` theme by theme.
+fn report_sections(
+    args: &Args,
+    config: &config::Config,
+    locale: &Locale,
     from_commit: &Commit,
     to_commit: &Commit,
-    commits: &[Commit],
-) -> String {
-    let mut report = String::new();
-
-    report.push_str(&format!("Git Commit Report\n"));
-    report.push_str(&format!("================\n\n"));
-    report.push_str(&format!("Repository: {}\n", repo_path));
-    report.push_str(&format!(
-        "Generated: {}\n",
-        Utc::now().format("%Y-%m-%d %H:%M:%S UTC")
-    ));
-    report.push_str(&format!(
-        "Commit Range: {} -> {}\n",
-        from_commit.hash, to_commit.hash
-    ));
-    report.push_str(&format!("Total Commits: {}\n\n", commits.len()));
-
-    report.push_str(&format!("Summary\n"));
-    report.push_str(&format!("-------\n"));
-    report.push_str(&format!(
-        "From: {} ({})\n",
-        from_commit.subject, from_commit.hash
-    ));
-    report.push_str(&format!("To: {} ({})\n", to_commit.subject, to_commit.hash));
-    report.push_str(&format!(
-        "Date Range: {} to {}\n\n",
-        from_commit.date.format("%Y-%m-%d %H:%M:%S"),
-        to_commit.date.format("%Y-%m-%d %H:%M:%S")
-    ));
+    inclusive: bool,
+    commits: &mut Vec<Commit>,
+) -> Result<Vec<report::Section>> {
+    let mut sections =
+        metric_registry(config).sections(&report_metrics(args, config), commits, locale)?;
+    if args.monorepo {
+        sections.extend(monorepo::project_sections(
+            commits,
+            &config.monorepo,
+            locale,
+        )?);
+    }
+    if args.group_by == Some(GroupBy::Label) {
+        sections.push(labels::labels_section(commits, locale));
+    }
+    if args.cluster {
+        sections.push(cluster_commits(args, commits, locale)?);
+    }
 
-    report.push_str(&format!("Detailed Commits\n"));
-    report.push_str(&format!("================\n\n"));
+    if args.jira || args.jira_url.is_some() {
+        let jira_token = args
+            .jira_token
+            .clone()
+            .or_else(|| std::env::var("JIRA_TOKEN").ok());
+        let options = jira::JiraOptions {
+            url: args.jira_url.as_deref(),
+            user: args.jira_user.as_deref(),
+            token: jira_token.as_deref(),
+            projects: &args.jira_projects,
+        };
+        sections.push(jira::tickets_section(commits, &options, locale)?);
+    }
+    if let Some(path) = &args.goals {
+        sections.push(goals::goals_section(&goals::load(path)?, commits, locale));
+    }
+    if args.detect_languages {
+        sections.push(language::languages_section(commits, locale));
+    }
 
-    for (i, commit) in commits.iter().enumerate() {
-        report.push_str(&format!("{}. {}\n", i + 1, commit.subject));
-        report.push_str(&format!("   Hash: {}\n", commit.hash));
-        report.push_str(&format!("   Author: {}\n", commit.author));
-        report.push_str(&format!(
-            "   Date: {}\n",
-            commit.date.format("%Y-%m-%d %H:%M:%S")
-        ));
+    if args.link_requests {
+        sections.push(forge::links_section(&link_forge(args)?, commits, locale)?);
+    }
 
-        if !commit.body.trim().is_empty() {
-            report.push_str(&format!("   Description:\n"));
-            for line in commit.body.lines() {
-                report.push_str(&format!("     {}\n", line));
+    if args.community {
+        // Patch series have no history to find earlier contributions in.
+        let previous = match args.command {
+            Some(Commands::FromPatches { .. }) => None,
+            _ => {
+                let mut previous = git::authors_before(&from_commit.hash, inclusive)?;
+                aliases::AuthorAliases::new(&config.authors.aliases).extend_known(&mut previous);
+                Some(previous)
             }
-        }
+        };
+        sections.push(community::community_section(
+            commits,
+            &config.community,
+            previous.as_ref(),
+            locale,
+        ));
+    }
 
-        if !commit.files_changed.is_empty() {
-            report.push_str(&format!("   Files Changed:\n"));
-            for file in &commit.files_changed {
-                report.push_str(&format!("     - {}\n", file));
-            }
+    let mut notes_refs = config.notes.refs.clone();
+    for notes_ref in &args.notes_ref {
+        if !notes_refs.contains(notes_ref) {
+            notes_refs.push(notes_ref.clone());
         }
-
-        report.push_str("\n");
+    }
+    if let Some(section) = notes::annotations_section(commits, &notes_refs, locale)? {
+        sections.push(section);
     }
 
-    report
-}
+    if args.suggest_version {
+        // The release the range starts from; a tag on TO is the release
+        // being reported, not the one to bump. An inclusive range reports
+        // FROM as well, so its release is the one before FROM, or none when
+        // FROM is a root commit and `FROM^` does not resolve.
+        let base = if inclusive {
+            format!("{}^", from_commit.hash)
+        } else {
+            from_commit.hash.clone()
+        };
+        let current_tag = git::latest_tag(&base)?;
+        let suggestion = version::suggest(commits, current_tag.as_deref());
+        sections.push(version::version_section(&suggestion, locale));
+    }
 
-async fn check_ollama_server(model: &str) -> Result<()> {
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(60 * 2))
-        .build()
-        .context("Failed to create HTTP client")?;
+    if checks_signatures(args) {
+        sections.push(signatures::signatures_section(commits, locale));
+    }
 
-    let health_response = client.get("http://localhost:11434/api/tags").send().await;
+    if args.include_worktree {
+        sections.push(worktree::worktree_section(locale)?);
+    }
 
-    match health_response {
-        Ok(response) if response.status().is_success() => {
-            let models_response = response
-                .json::<Value>()
-                .await
-                .context("Failed to parse Ollama models response")?;
+    if let Some(style) = args.graph {
+        let drawing = git::graph(&from_commit.hash, &to_commit.hash, inclusive)?;
+        sections.push(graph::graph_section(&drawing, style, locale));
+    }
+    for diagram in &args.mermaid {
+        sections.push(mermaid_section(
+            *diagram,
+            args,
+            from_commit,
+            to_commit,
+            inclusive,
+            commits,
+            locale,
+        )?);
+    }
 
-            let models = models_response["models"]
-                .as_array()
-                .ok_or_else(|| anyhow::anyhow!("Invalid models response format"))?;
+    // Acknowledgments close the release notes, after every other section.
+    if args.community || args.acknowledgments {
+        sections.extend(community::acknowledgments_section(
+            &config.acknowledgments,
+            locale,
+        ));
+    }
 
-            let model_available = models.iter().any(|m| {
-                m["name"].as_str().map_or(false, |name| {
-                    name == model || name.starts_with(&format!("{}:", model))
-                })
-            });
+    Ok(sections)
+}
 
-            if !model_available {
-                anyhow::bail!(
-                    "Model '{}' is not available. Available models: {}",
-                    model,
-                    models
-                        .iter()
-                        .filter_map(|m| m["name"].as_str())
-                        .collect::<Vec<_>>()
-                        .join(", ")
-                );
-            }
+/// The forge `--link-requests` fetches requests and issues from.
+fn link_forge(args: &Args) -> Result<forge::Forge> {
+    Ok(forge::Forge::from_remote_url(
+        &git::remote_url(&args.forge_remote)?,
+        args.forge_kind,
+    )?)
+}
 
-            Ok(())
-        }
-        Ok(response) => {
-            anyhow::bail!("Ollama server returned error status: {}", response.status());
-        }
-        Err(e) if e.is_timeout() => {
-            anyhow::bail!("Ollama server is not responding. Please make sure Ollama is running on localhost:11434");
-        }
-        Err(e) => {
-            anyhow::bail!("Failed to connect to Ollama server: {}. Please make sure Ollama is running on localhost:11434", e);
-        }
+/// Adds the data quality issues noted since the last call as a section
+/// before the acknowledgments, which still close the report, and returns
+/// how many there were.
+fn add_diagnostics(sections: &mut Vec<report::Section>, locale: &Locale) -> usize {
+    let issues = diagnostics::take();
+    if !issues.is_empty() {
+        let at = sections
+            .iter()
+            .position(|section| section.id == "acknowledgments")
+            .unwrap_or(sections.len());
+        sections.insert(at, diagnostics::diagnostics_section(&issues, locale));
     }
+    issues.len()
+}
+
+/// Whether the report checks and lists signatures: patches carry none.
+fn checks_signatures(args: &Args) -> bool {
+    args.signatures && !matches!(args.command, Some(Commands::FromPatches { .. }))
 }
 
-async fn generate_ai_report(
+/// Collects the optional sections, renders every requested format and runs
+/// the configured deliveries for one range. With `output_dir` set, files get
+/// their default timestamped names inside it instead of `--output`.
+fn generate_report(
+    args: &Args,
+    config: &config::Config,
     repo_path: &str,
-    from_commit: &Commit,
-    to_commit: &Commit,
-    commits: &[Commit],
-    model: &str,
-) -> Result<String> {
-    let mut commit_details = String::new();
-    for (i, commit) in commits.iter().enumerate() {
-        commit_details.push_str(&format!("Commit {}:\n", i + 1));
-        commit_details.push_str(&format!("  Hash: {}\n", commit.hash));
-        commit_details.push_str(&format!("  Author: {}\n", commit.author));
-        commit_details.push_str(&format!(
-            "  Date: {}\n",
-            commit.date.format("%Y-%m-%d %H:%M:%S")
-        ));
-        commit_details.push_str(&format!("  Subject: {}\n", commit.subject));
-        if !commit.body.trim().is_empty() {
-            commit_details.push_str(&format!("  Description: {}\n", commit.body.trim()));
-        }
-        if !commit.files_changed.is_empty() {
-            commit_details.push_str(&format!("  Files Changed:\n"));
-            for file in &commit.files_changed {
-                commit_details.push_str(&format!("    - {}\n", file));
-            }
-        }
-        commit_details.push_str("\n");
+    range: ReportRange,
+    output_dir: Option<&Path>,
+) -> Result<()> {
+    if args.dry_run {
+        return print_plan(args, config, repo_path, range);
     }
+    let ReportRange {
+        from_commit,
+        to_commit,
+        inclusive,
+        mut commits,
+        mut sections,
+        collect_time,
+    } = range;
+    let started = Instant::now();
+    let locale = locale(args, repo_path)?;
+    let branding = branding(args, config);
+    let translates =
+        args.translate_messages && !language::foreign_commits(&commits, locale.code()).is_empty();
+    let ai = if args.ai || (args.ai_per_commit && !commits.is_empty()) || translates {
+        let ai = ai_backend(args)?;
+        println!(
+            "{}",
+            format!("Checking {} and model '{}'...", ai.name(), ai.model()).blue()
+        );
+        ai.check()?;
+        Some(ai)
+    } else {
+        None
+    };
 
-    let prompt = format!(
-        "You are an assistant who writes concise and clear commit summaries for sharing directly in Telegram personal messages.\n\
-        Please create a informative update for a colleague who needs to know what changed in the repository.\n\
-        Format:\n\
-        1. Start with a brief summary of what changed overall (1-2 sentences).\n\
-        2. Then, list the key changes as bullet points (plain text, each on a new line, no markdown).\n\
-        3. If there are important difficult technical details, explain them in simple terms.\n\
-        4. Do not use markdown, do not add headings, do not mention 'report' or 'repository' — just the essential changes.\n\
-        5. Avoid repeating phrases or adding unnecessary formalities.\n\
-        6. Write so the text can be copied and sent directly in TELEGRAM.\n\
-        7. Make sure you do not lose ANY of the information or changes. You MUST tell about ALL changes.\n\
-        \n\
-        Data for the summary:\n\
-        Path: {}\n\
-        Commit range: {} -> {}\n\
-        Number of commits: {}\n\
-        Generated at: {}\n\
-        \n\
-        Commits:\n\
-        {}",
-        repo_path,
-        from_commit.hash,
-        to_commit.hash,
-        commits.len(),
-        Utc::now().format("%Y-%m-%d %H:%M:%S UTC"),
-        commit_details
-    );
-
-    let payload = json!({
-        "model": model,
-        "prompt": prompt,
-        "stream": false,
-        "options": {
-            "temperature": 0.7,
-            "top_p": 0.9,
-            "max_tokens": 4000
+    let squash_started = Instant::now();
+    let mut squash_time = Duration::ZERO;
+    if args.as_squashed {
+        let total = commits.len();
+        let writer = ai.as_deref().filter(|_| args.ai);
+        commits = squash::squash(commits, &to_commit.hash, writer)?;
+        println!("Squashed {} commits into {} entries", total, commits.len());
+        if writer.is_some() {
+            squash_time = squash_started.elapsed();
         }
-    });
-
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(120))
-        .build()
-        .context("Failed to create HTTP client")?;
-
-    let response = client
-        .post("http://localhost:11434/api/generate")
-        .json(&payload)
-        .send()
-        .await
-        .context(format!("Failed to connect to Ollama with model '{}'. Make sure Ollama is running on localhost:11434", model))?;
-
-    if !response.status().is_success() {
-        anyhow::bail!(
-            "Ollama API request failed with status: {} for model '{}'",
-            response.status(),
-            model
+    }
+    filter_commits(args, config, &mut commits)?;
+    label_commits(args, config, &mut commits)?;
+    let signatures = checks_signatures(args);
+    if signatures {
+        let progress = Progress::spinner("Checking commit signatures");
+        let checked = git::signatures(&mut commits);
+        progress.finish();
+        checked?;
+    } else if args.signatures {
+        println!(
+            "{}",
+            "Warning: --signatures needs commits from a repository; patches carry no signatures"
+                .yellow()
         );
     }
 
-    let response_json: Value = response
-        .json()
-        .await
-        .context("Failed to parse Ollama response")?;
+    let mut output_files = Vec::new();
 
-    let ai_report = response_json["response"].as_str().ok_or_else(|| {
-        anyhow::anyhow!("Invalid response format from Ollama for model '{}'", model)
-    })?;
+    // Progress is told here rather than in `report_sections`, which `serve`
+    // runs for every request.
+    if args.cluster {
+        let embedder = embedder(args)?;
+        println!(
+            "{}",
+            format!(
+                "Clustering commits with {} and model '{}'...",
+                embedder.name(),
+                embedder.model()
+            )
+            .blue()
+        );
+    }
+    if args.jira_url.is_some() {
+        println!("{}", "Fetching Jira ticket details...".blue());
+    }
+    if args.link_requests {
+        println!(
+            "{}",
+            format!(
+                "Fetching referenced requests and issues from {}...",
+                link_forge(args)?.host
+            )
+            .blue()
+        );
+    }
+    let optional = report_sections(
+        args,
+        config,
+        &locale,
+        &from_commit,
+        &to_commit,
+        inclusive,
+        &mut commits,
+    )?;
+    for section in &optional {
+        match (section.id.as_str(), section.blocks.first()) {
+            ("themes", Some(report::Block::Table { rows, .. })) => println!(
+                "Grouped {} commits into {} themes",
+                commits.len(),
+                rows.len()
+            ),
+            ("version", _) => println!("\n{}", render::text::render_section(section)),
+            _ => {}
+        }
+    }
+    if args.acknowledgments
+        && !optional
+            .iter()
+            .any(|section| section.id == "acknowledgments")
+    {
+        println!(
+            "{}",
+            "Warning: --acknowledgments given but [acknowledgments] is empty".yellow()
+        );
+    }
+    sections.extend(optional);
 
-    Ok(ai_report.to_string())
-}
+    let impact = if args.impact || args.sort == CommitOrder::Impact {
+        impact::ImpactScorer::new(&config.impact.core_paths)?.scores(&commits)
+    } else {
+        HashMap::new()
+    };
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    let args = Args::parse();
+    let dates = date_style(args);
+    let ai_started = Instant::now();
+
+    let ai_summary = match &ai {
+        Some(ai) if args.ai => {
+            println!(
+                "{}",
+                format!(
+                    "Generating AI-enhanced report using {} with model '{}'...",
+                    ai.name(),
+                    ai.model()
+                )
+                .blue()
+            );
+            let prompt = fit_ai_prompt(
+                args,
+                &repository_label(args, repo_path),
+                &from_commit,
+                &to_commit,
+                &commits,
+                &branding,
+            )?;
+            Some(generate_ai_report(args, prompt, ai.as_ref(), &locale)?)
+        }
+        _ => None,
+    };
 
-    println!("{}", "Git Report Generator".bright_green().bold());
+    let (mut commit_summaries, reused_summaries) = match &ai {
+        Some(ai) if args.ai_per_commit => {
+            summarize_commits(args, repo_path, &commits, ai.as_ref(), &locale)?
+        }
+        _ => (HashMap::new(), 0),
+    };
+    let mut translations = match &ai {
+        Some(ai) if args.translate_messages => {
+            translate_messages(args, repo_path, &commits, ai.as_ref(), &locale)?
+        }
+        _ => HashMap::new(),
+    };
+    let ai_time = squash_time + ai_started.elapsed();
 
-    let repo_path = check_git_repository()?;
-    println!("Repository: {}", repo_path.bright_blue());
+    let postprocess = postprocess::PostProcess {
+        steps: args.ai_postprocess.clone(),
+        heading_level: args.ai_heading_level,
+        max_heading: args.ai_max_heading,
+    };
+    let ai_summary = ai_summary.map(|summary| postprocess.apply(&summary));
+    for summary in commit_summaries.values_mut() {
+        *summary = postprocess.apply(summary);
+    }
+    for translation in translations.values_mut() {
+        translation.text = postprocess.apply(&translation.text);
+    }
 
-    let commits = get_commit_list(args.limit)?;
-    println!("Found {} commits", commits.len());
+    // Noted last, so the gaps of the AI prompt are in; acknowledgments still
+    // close the report.
+    let issues = add_diagnostics(&mut sections, &locale);
+    if issues > 0 {
+        println!(
+            "{}",
+            format!(
+                "Warning: {} data quality issue(s) found, listed in the Data Quality section",
+                issues
+            )
+            .yellow()
+        );
+    }
 
-    let from_commit = if let Some(from) = args.from {
-        commits
-            .iter()
-            .find(|c| c.hash.starts_with(&from))
-            .ok_or_else(|| anyhow::anyhow!("Commit '{}' not found", from))?
-    } else {
-        select_commit(&commits, "Select FROM commit (older commit)")?
+    let mut data = ReportData {
+        repo_path: repository_label(args, repo_path),
+        generated_at: Utc::now(),
+        from_commit: from_commit.clone(),
+        to_commit: to_commit.clone(),
+        commits,
+        ai_summary,
+        convert_ai_summary: postprocess.converts(),
+        commit_summaries,
+        translations,
+        impact,
+        order: args.sort,
+        sections,
+        dates,
+        parts: args.sections.clone(),
+        banner: config.banner.clone(),
+        branding,
+        locale,
+        commit_links: config.commit_links.clone(),
+        theme: html_theme(args),
+        mermaid_cdn: args.mermaid_cdn,
+        front_matter: args.front_matter,
     };
 
-    let to_commit = if let Some(to) = args.to {
-        commits
+    let timestamp = data.generated_at.format("%Y%m%d_%H%M%S").to_string();
+    let suffix = if args.ai { "-ai" } else { "" };
+
+    let output_path = |suffix: &str, format: render::Format| -> PathBuf {
+        let default_name = format!("git-report{}-{}.{}", suffix, timestamp, format.extension());
+        let output = args.output.as_ref().map(|output| {
+            let output = Path::new(output);
+            let output = if args.format.len() == 1 {
+                output.to_path_buf()
+            } else {
+                output.with_extension(format.extension())
+            };
+            if args.both {
+                with_suffix(&output, suffix)
+            } else {
+                output
+            }
+        });
+        match (output_dir, output) {
+            (Some(dir), _) => dir.join(default_name),
+            (None, Some(output)) => output,
+            (None, None) => PathBuf::from(default_name),
+        }
+    };
+    let outputs = |suffix: &str| -> Vec<(render::Format, PathBuf)> {
+        args.format
             .iter()
-            .find(|c| c.hash.starts_with(&to))
-            .ok_or_else(|| anyhow::anyhow!("Commit '{}' not found", to))?
-    } else {
-        select_commit(&commits, "Select TO commit (newer commit)")?
+            .map(|&format| (format, output_path(suffix, format)))
+            .collect()
     };
 
-    println!("Range: {} -> {}", from_commit.subject, to_commit.subject);
-
-    let range_commits = get_commits_in_range(&from_commit.hash, &to_commit.hash)?;
-    println!("Found {} commits in range", range_commits.len());
+    // Reports are streamed to their files rather than rendered in memory.
+    let render_started = Instant::now();
+    let progress = Progress::spinner("Rendering report");
+    let mut reports = vec![outputs(suffix)];
+    let written = render::write_all(&reports[0], &data);
+    let raw = args.both.then(|| {
+        // The raw report is the same data without anything the model wrote.
+        let raw = outputs("-raw");
+        let ai_summary = data.ai_summary.take();
+        let commit_summaries = std::mem::take(&mut data.commit_summaries);
+        let written = render::write_all(&raw, &data);
+        data.ai_summary = ai_summary;
+        data.commit_summaries = commit_summaries;
+        written.map(|()| raw)
+    });
+    progress.finish();
+    written?;
+    if let Some(raw) = raw {
+        reports.push(raw?);
+    }
+    let render_time = render_started.elapsed();
 
-    let report_content = if args.ai {
+    for (_, path) in reports.into_iter().flatten() {
         println!(
-            "{}",
-            format!("Checking Ollama server and model '{}'...", args.model).blue()
+            "Report saved to: {}",
+            path.display().to_string().bright_blue()
         );
-        check_ollama_server(&args.model).await?;
+        if args.sign {
+            let digest = sign::write_digest(&path)?;
+            println!("SHA-256: {}", digest);
+            if let Some(signer) = args.sign_with {
+                let signature = sign::sign_file(&path, signer, args.sign_key.as_deref())?;
+                println!(
+                    "Signature saved to: {}",
+                    signature.display().to_string().bright_blue()
+                );
+            }
+        }
+        output_files.push(path.to_string_lossy().to_string());
+    }
+
+    if let Some(badge_path) = &args.badge {
+        fs::write(badge_path, badge::range_badge(&data.commits))
+            .with_context(|| format!("Failed to write badge {}", badge_path.display()))?;
         println!(
-            "{}",
-            format!(
-                "Generating AI-enhanced report using Ollama with model '{}'...",
-                args.model
-            )
-            .blue()
+            "Badge saved to: {}",
+            badge_path.display().to_string().bright_blue()
         );
-        generate_ai_report(
-            &repo_path,
-            from_commit,
-            to_commit,
-            &range_commits,
-            &args.model,
-        )
-        .await?
-    } else {
-        generate_report(&repo_path, from_commit, to_commit, &range_commits)
+    }
+
+    // Deliveries need text; use the first text format requested, or plain
+    // text. It is only rendered in memory when something is delivered.
+    let report_content = || -> Result<String> {
+        Ok(match args.format.iter().find(|f| f.is_text()) {
+            Some(&format) => String::from_utf8(render::render(format, &data)?)?,
+            None => render::text::render(&data),
+        })
     };
 
-    let output_file = args.output.unwrap_or_else(|| {
-        let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
-        let suffix = if args.ai { "-ai" } else { "" };
-        format!("git-report{}-{}.txt", suffix, timestamp)
-    });
+    if args.archive || config.archive.enabled {
+        let dataset = archive::Dataset {
+            generated_at: Utc::now(),
+            repository: repo_path.to_string(),
+            from: from_commit.hash.clone(),
+            to: to_commit.hash.clone(),
+            inclusive,
+            ai: args.ai,
+            commits: data.commits.clone(),
+        };
+        let archive_dir = archive::archive_dir(repo_path, &config.archive);
+        let dataset_path = archive::save(&archive_dir, &dataset, &report_content()?)?;
+        println!(
+            "Report archived to: {}",
+            dataset_path.display().to_string().bright_blue()
+        );
+
+        let removed = archive::prune(&archive_dir, &config.archive)?;
+        if removed > 0 {
+            println!("Removed {} archived report(s) past retention", removed);
+        }
+    }
+
+    #[cfg(feature = "email")]
+    if let Some(to) = &args.email {
+        println!("{}", format!("Sending report to {}...", to).blue());
+        let subject = format!(
+            "Git report: {} -> {}",
+            &from_commit.hash[..8],
+            &to_commit.hash[..8]
+        );
+        email::send_report(
+            &config.email,
+            to,
+            &subject,
+            &output_files[0],
+            &report_content()?,
+            args.email_attach,
+        )?;
+        println!("Report emailed to: {}", to.bright_blue());
+    }
+
+    #[cfg(feature = "slack")]
+    if let Some(webhook_url) = &args.slack_webhook {
+        println!("{}", "Posting report to Slack...".blue());
+        let parts = slack::post_report(webhook_url, &report_content()?)?;
+        println!("Report posted to Slack ({} message(s))", parts);
+    }
+
+    #[cfg(feature = "confluence")]
+    if let (Some(space), Some(url)) = (&args.confluence_space, &args.confluence_url) {
+        println!("{}", "Uploading report to Confluence...".blue());
+        let token = args
+            .confluence_token
+            .clone()
+            .or_else(|| std::env::var("CONFLUENCE_TOKEN").ok());
+        let options = confluence::ConfluenceOptions {
+            url,
+            space,
+            parent: args.confluence_parent.as_deref(),
+            user: args.confluence_user.as_deref(),
+            token: token.as_deref(),
+        };
+        let page = confluence::publish(
+            &options,
+            &confluence::page_title(&data),
+            &confluence::to_storage(&data),
+        )?;
+        let action = if page.created { "created" } else { "updated" };
+        match page.url {
+            Some(url) => println!("Confluence page {}: {}", action, url.bright_blue()),
+            None => println!("Confluence page {} in space {}", action, space),
+        }
+    }
+
+    if args.post_pr_comment {
+        post_pr_comment(args, &mut data)?;
+    }
 
-    let mut file = File::create(&output_file)?;
-    file.write_all(report_content.as_bytes())?;
+    if let Some(provider) = args.ci {
+        ci::publish(provider, &data, &output_files)?;
+    }
 
-    println!("Report saved to: {}", output_file.bright_blue());
+    let mut summary = run_summary::RunSummary::new(&data.commits);
+    summary.output_files = output_files;
+    summary.set_durations(
+        collect_time,
+        ai_time,
+        render_time,
+        collect_time + started.elapsed(),
+    );
+    summary.ai = ai.map(|ai| run_summary::AiUsage {
+        provider: ai.name().to_string(),
+        model: ai.model().to_string(),
+        usage: ai.usage(),
+        reused_summaries,
+    });
+    eprintln!("{} {}", "Run summary:".dimmed(), summary.line().dimmed());
+    if let Some(path) = &args.stats_json {
+        summary.write_json(path)?;
+    }
 
     Ok(())
 }
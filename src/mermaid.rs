@@ -0,0 +1,243 @@
+use clap::ValueEnum;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{
+    dates::DateStyle,
+    git::Commit,
+    i18n::Locale,
+    report::{Block, Section},
+};
+
+/// Larger histories make diagrams GitHub and GitLab refuse to draw.
+const MAX_GRAPH_COMMITS: usize = 300;
+/// Periods of the timeline; days become weeks, then months, beyond it.
+const MAX_PERIODS: usize = 31;
+
+/// A Mermaid diagram `--mermaid` adds to the report.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MermaidDiagram {
+    /// Commits per day, week or month with the authors who made them.
+    Timeline,
+    /// The branches and merges of the range as a Mermaid gitGraph.
+    GitGraph,
+}
+
+/// Blanks out what Mermaid would read as syntax: `:` separates timeline
+/// events, `#` and `;` start entities and statements, `"` ends labels.
+fn label(text: &str) -> String {
+    text.replace([':', ';', '#', '"'], " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The commits per period and, within each, per author, most active first.
+/// Periods are days while there are at most [`MAX_PERIODS`] of them, then ISO
+/// weeks, then months, in the report timezone.
+pub fn timeline(commits: &[Commit], dates: &DateStyle, locale: &Locale) -> String {
+    let per_period = |format: &str| {
+        let mut periods: BTreeMap<String, BTreeMap<&str, usize>> = BTreeMap::new();
+        for commit in commits {
+            *periods
+                .entry(dates.format_with(&commit.date, format))
+                .or_default()
+                .entry(commit.author.as_str())
+                .or_default() += 1;
+        }
+        periods
+    };
+    let periods = ["%Y-%m-%d", "%G-W%V", "%Y-%m"]
+        .iter()
+        .map(|format| per_period(format))
+        .find(|periods| periods.len() <= MAX_PERIODS)
+        .unwrap_or_else(|| per_period("%Y"));
+
+    let mut diagram = format!(
+        "timeline\n    title {}\n",
+        label(locale.text("timeline-title"))
+    );
+    for (period, authors) in periods {
+        let mut authors: Vec<(&str, usize)> = authors.into_iter().collect();
+        authors.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        diagram.push_str(&format!("    {}", period));
+        for (author, count) in authors {
+            diagram.push_str(&format!(" : {} ({})", label(author), count));
+        }
+        diagram.push('\n');
+    }
+    diagram
+}
+
+/// The name of the branch a merge brought in, from subjects like `Merge
+/// branch 'topic'` or `Merge pull request #12 from user/topic`.
+fn merged_branch(subject: &str) -> Option<&str> {
+    if let Some(rest) = subject.strip_prefix("Merge branch '") {
+        return rest.split('\'').next();
+    }
+    let rest = subject.strip_prefix("Merge pull request ")?;
+    let (_, source) = rest.split_once(" from ")?;
+    let source = source.split_whitespace().next()?;
+    Some(source.split_once('/').map_or(source, |(_, branch)| branch))
+}
+
+/// Mermaid branch names may not contain spaces or quotes.
+fn branch_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_alphanumeric() || "-_/.".contains(c) {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+/// Adds a branch, numbered when its name is taken, and returns its index.
+fn add_lane(lanes: &mut Vec<String>, name: &str) -> usize {
+    let mut name = branch_name(name);
+    if lanes.contains(&name) {
+        name = format!("{}-{}", name, lanes.len() + 1);
+    }
+    lanes.push(name);
+    lanes.len() - 1
+}
+
+/// Draws the history of the range, oldest commit first and in topological
+/// order as [`crate::git::range_headers`] lists it, as a Mermaid gitGraph.
+/// The first-parent chain of TO is the main branch; the commits a merge
+/// brought in form a branch named after the merged branch where the merge
+/// subject tells it. Branches forked before FROM start at their first commit.
+pub fn git_graph(history: &[Commit]) -> String {
+    let position: HashMap<&str, usize> = history
+        .iter()
+        .enumerate()
+        .map(|(i, commit)| (commit.hash.as_str(), i))
+        .collect();
+    let parent = |commit: &Commit, n: usize| {
+        commit
+            .parents
+            .get(n)
+            .and_then(|hash| position.get(hash.as_str()).copied())
+    };
+
+    // Assign every commit a lane by following first parents, starting with
+    // TO, then the side of each merge not yet assigned, newest merge first.
+    let mut lanes: Vec<String> = Vec::new();
+    let mut lane: Vec<Option<usize>> = vec![None; history.len()];
+    let follow = |lane: &mut [Option<usize>], tip: usize, index: usize| {
+        let mut commit = Some(tip);
+        while let Some(i) = commit.filter(|&i| lane[i].is_none()) {
+            lane[i] = Some(index);
+            commit = parent(&history[i], 0);
+        }
+    };
+    let Some(to) = history.last() else {
+        return String::from("gitGraph\n");
+    };
+    add_lane(
+        &mut lanes,
+        to.branches.first().map_or("main", String::as_str),
+    );
+    follow(&mut lane, history.len() - 1, 0);
+    for i in (0..history.len()).rev() {
+        let commit = &history[i];
+        for n in 1..commit.parents.len() {
+            if let Some(side) = parent(commit, n).filter(|&side| lane[side].is_none()) {
+                let fallback = format!("branch-{}", lanes.len());
+                let name = merged_branch(&commit.subject)
+                    .or_else(|| history[side].branches.first().map(String::as_str))
+                    .unwrap_or(&fallback);
+                let index = add_lane(&mut lanes, name);
+                follow(&mut lane, side, index);
+            }
+        }
+    }
+
+    let mut diagram = format!(
+        "%%{{init: {{ 'gitGraph': {{ 'mainBranchName': '{}' }} }} }}%%\ngitGraph\n",
+        lanes[0]
+    );
+    let mut created = vec![false; lanes.len()];
+    created[0] = true;
+    let mut drawn = vec![false; lanes.len()];
+    let mut current = 0;
+    for (i, commit) in history.iter().enumerate() {
+        let on = lane[i].unwrap_or(0);
+        if !created[on] {
+            // Forked before FROM: the branch starts where main is now.
+            if current != 0 {
+                diagram.push_str(&format!("    checkout {}\n", lanes[0]));
+            }
+            diagram.push_str(&format!("    branch {}\n", lanes[on]));
+            created[on] = true;
+            current = on;
+        }
+        if current != on {
+            diagram.push_str(&format!("    checkout {}\n", lanes[on]));
+            current = on;
+        }
+
+        let mut attributes = format!(
+            "id: \"{} {}\"",
+            &commit.hash[..7],
+            label(&commit.subject.chars().take(40).collect::<String>())
+        );
+        if let Some(tag) = commit.tags.first() {
+            attributes.push_str(&format!(" tag: \"{}\"", label(tag)));
+        }
+        // Mermaid refuses merges into a branch without commits, as when
+        // FROM is the first parent of the merge; those become commits.
+        match (1..commit.parents.len())
+            .filter_map(|n| lane[parent(commit, n)?])
+            .find(|&side| side != on && drawn[side] && drawn[on])
+        {
+            Some(side) => diagram.push_str(&format!("    merge {} {}\n", lanes[side], attributes)),
+            None => diagram.push_str(&format!("    commit {}\n", attributes)),
+        }
+        drawn[on] = true;
+
+        // Branches forking here are created now, so they start at this
+        // commit even when main moves on before their first commit.
+        // `branch` checks the new branch out, so the lane of this commit is
+        // only checked out again before another fork or its next commit.
+        let forks: Vec<usize> = history
+            .iter()
+            .enumerate()
+            .filter(|(_, child)| parent(child, 0) == Some(i))
+            .filter_map(|(j, _)| lane[j])
+            .filter(|&forked| forked != on && !created[forked])
+            .collect();
+        for forked in forks {
+            if current != on {
+                diagram.push_str(&format!("    checkout {}\n", lanes[on]));
+            }
+            diagram.push_str(&format!("    branch {}\n", lanes[forked]));
+            created[forked] = true;
+            current = forked;
+        }
+    }
+    diagram
+}
+
+/// The timeline as a report section.
+pub fn timeline_section(commits: &[Commit], dates: &DateStyle, locale: &Locale) -> Section {
+    Section::new("mermaid-timeline", locale.text("commit-timeline"))
+        .with_block(Block::Mermaid(timeline(commits, dates, locale)))
+}
+
+/// The gitGraph as a report section, or a note when the range is too large
+/// to be drawn.
+pub fn git_graph_section(history: &[Commit], locale: &Locale) -> Section {
+    let section = Section::new("mermaid-git-graph", locale.text("branches-and-merges"));
+    if history.len() > MAX_GRAPH_COMMITS {
+        return section.with_block(Block::Text(locale.format(
+            "git-graph-too-large",
+            &[
+                ("count", &history.len().to_string()),
+                ("max", &MAX_GRAPH_COMMITS.to_string()),
+            ],
+        )));
+    }
+    section.with_block(Block::Mermaid(git_graph(history)))
+}
@@ -0,0 +1,169 @@
+use globset::GlobSet;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{
+    classify,
+    config::MonorepoConfig,
+    error::Result,
+    exclude,
+    git::Commit,
+    i18n::Locale,
+    report::{Block, Section},
+};
+
+/// Project of the files at the repository root, outside any directory.
+const ROOT_PROJECT: &str = "(root)";
+
+/// Maps changed files to the sub-projects of a monorepo: the first
+/// `[monorepo] projects` entry whose patterns match, else the directory
+/// under one of the `[monorepo] roots` (`packages/web` for
+/// `packages/web/src/app.ts`), else the top-level directory.
+pub struct ProjectMap {
+    projects: Vec<(String, GlobSet)>,
+    roots: Vec<String>,
+}
+
+impl ProjectMap {
+    pub fn new(config: &MonorepoConfig) -> Result<Self> {
+        let projects = config
+            .projects
+            .iter()
+            .map(|(name, patterns)| Ok((name.clone(), exclude::glob_set(patterns, "project")?)))
+            .collect::<Result<_>>()?;
+        let roots = config
+            .roots
+            .iter()
+            .map(|root| root.trim_matches('/').to_string())
+            .filter(|root| !root.is_empty())
+            .collect();
+        Ok(Self { projects, roots })
+    }
+
+    pub fn project(&self, path: &str) -> String {
+        if let Some((name, _)) = self.projects.iter().find(|(_, set)| set.is_match(path)) {
+            return name.clone();
+        }
+        for root in &self.roots {
+            let nested = path
+                .strip_prefix(root.as_str())
+                .and_then(|rest| rest.strip_prefix('/'))
+                .and_then(|rest| rest.split_once('/'));
+            if let Some((name, _)) = nested {
+                return format!("{}/{}", root, name);
+            }
+        }
+        match path.split_once('/') {
+            Some((dir, _)) => dir.to_string(),
+            None => ROOT_PROJECT.to_string(),
+        }
+    }
+}
+
+/// The commits and changes of one project. A commit touching several
+/// projects counts in each, with the lines it changed there.
+#[derive(Default)]
+struct Project<'a> {
+    commits: Vec<&'a Commit>,
+    authors: BTreeSet<&'a str>,
+    files: BTreeSet<&'a str>,
+    additions: u64,
+    deletions: u64,
+}
+
+/// `--monorepo`: an overview of the projects the range touched, then a
+/// section per project with its own numbers, commit types and commits,
+/// busiest project first.
+pub fn project_sections(
+    commits: &[Commit],
+    config: &MonorepoConfig,
+    locale: &Locale,
+) -> Result<Vec<Section>> {
+    let map = ProjectMap::new(config)?;
+    let mut projects: BTreeMap<String, Project> = BTreeMap::new();
+    let mut cross_project = 0;
+    for commit in commits {
+        let mut touched = BTreeSet::new();
+        for path in &commit.files_changed {
+            let name = map.project(path);
+            let project = projects.entry(name.clone()).or_default();
+            project.files.insert(path);
+            if touched.insert(name) {
+                project.commits.push(commit);
+                project.authors.insert(&commit.author);
+            }
+        }
+        for stat in &commit.file_stats {
+            let project = projects.entry(map.project(&stat.path)).or_default();
+            project.additions += stat.additions;
+            project.deletions += stat.deletions;
+        }
+        if touched.len() > 1 {
+            cross_project += 1;
+        }
+    }
+
+    let mut projects: Vec<(String, Project)> = projects.into_iter().collect();
+    projects.sort_by_key(|(_, project)| std::cmp::Reverse(project.commits.len()));
+
+    let overview = Section::new("projects", locale.text("projects"))
+        .with_block(Block::Fields(vec![
+            (
+                locale.text("projects").to_string(),
+                projects.len().to_string(),
+            ),
+            (
+                locale.text("cross-project-commits").to_string(),
+                cross_project.to_string(),
+            ),
+        ]))
+        .with_block(Block::Table {
+            headers: ["project", "commits", "authors", "files", "added", "deleted"]
+                .map(|key| locale.text(key).to_string())
+                .to_vec(),
+            rows: projects
+                .iter()
+                .map(|(name, project)| {
+                    vec![
+                        name.clone(),
+                        project.commits.len().to_string(),
+                        project.authors.len().to_string(),
+                        project.files.len().to_string(),
+                        project.additions.to_string(),
+                        project.deletions.to_string(),
+                    ]
+                })
+                .collect(),
+        });
+
+    let mut sections = vec![overview];
+    for (name, project) in &projects {
+        let own: Vec<Commit> = project.commits.iter().map(|&c| c.clone()).collect();
+        let authors: Vec<&str> = project.authors.iter().copied().collect();
+        let section = Section::new(
+            &format!("project-{}", name),
+            &locale.format("project-title", &[("name", name)]),
+        )
+        .with_block(Block::Fields(vec![
+            (locale.text("commits").to_string(), own.len().to_string()),
+            (locale.text("authors").to_string(), authors.join(", ")),
+            (
+                locale.text("files-changed").to_string(),
+                project.files.len().to_string(),
+            ),
+            (
+                locale.text("lines").to_string(),
+                format!("+{} -{}", project.additions, project.deletions),
+            ),
+        ]))
+        .with_block(classify::breakdown(&own, locale))
+        .with_block(Block::List {
+            title: locale.text("commits").to_string(),
+            items: own
+                .iter()
+                .map(|commit| format!("{} {}", &commit.hash[..8], commit.subject))
+                .collect(),
+        });
+        sections.push(section);
+    }
+    Ok(sections)
+}
@@ -0,0 +1,47 @@
+use crate::{
+    error::Result,
+    git::{self, Commit},
+    i18n::Locale,
+    report::{Block, Section},
+};
+
+/// Lists the git notes of every annotated commit, one block per commit. Each
+/// note line is prefixed with its namespace, e.g. `review: approved by Ana`.
+/// Returns `None` when no commit in the range has a note.
+pub fn annotations_section(
+    commits: &[Commit],
+    namespaces: &[String],
+    locale: &Locale,
+) -> Result<Option<Section>> {
+    let mut notes_by_namespace = Vec::new();
+    for namespace in namespaces {
+        notes_by_namespace.push((display_name(namespace), git::notes(namespace, commits)?));
+    }
+
+    let mut section = Section::new("annotations", locale.text("annotations"));
+    for commit in commits {
+        let items: Vec<String> = notes_by_namespace
+            .iter()
+            .filter_map(|(namespace, notes)| Some((namespace, notes.get(&commit.hash)?)))
+            .flat_map(|(namespace, note)| {
+                note.lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .map(move |line| format!("{}: {}", namespace, line))
+            })
+            .collect();
+
+        if !items.is_empty() {
+            section = section.with_block(Block::List {
+                title: format!("{} {}", &commit.hash[..8], commit.subject),
+                items,
+            });
+        }
+    }
+
+    Ok((!section.blocks.is_empty()).then_some(section))
+}
+
+fn display_name(namespace: &str) -> &str {
+    namespace.strip_prefix("refs/notes/").unwrap_or(namespace)
+}
@@ -0,0 +1,270 @@
+use chrono::Utc;
+use serde_json::{json, Value};
+use std::{cell::Cell, path::PathBuf};
+
+use crate::{
+    ai::{request_error, status_error, Backend, Embedder, TokenUsage},
+    error::{Context, ReportError, Result},
+    transcript::TranscriptLog,
+};
+
+const GENERATE_URL: &str = "http://localhost:11434/api/generate";
+const TAGS_URL: &str = "http://localhost:11434/api/tags";
+const EMBED_URL: &str = "http://localhost:11434/api/embed";
+
+/// The embedding model used when `--embedding-model` is not given.
+pub const DEFAULT_EMBEDDING_MODEL: &str = "nomic-embed-text";
+
+/// Room left in the context window for the response.
+const RESPONSE_TOKENS: usize = 4000;
+
+/// A local Ollama model, optionally recording every exchange as a transcript.
+pub struct Ollama {
+    pub model: String,
+    /// Context window requested with every prompt (`num_ctx`). Ollama's
+    /// default is small and it drops the start of longer prompts without an
+    /// error.
+    context_tokens: usize,
+    transcripts: TranscriptLog,
+    usage: Cell<TokenUsage>,
+}
+
+impl Ollama {
+    pub fn new(model: &str, prompt_tokens: usize, transcript_dir: Option<PathBuf>) -> Self {
+        Self {
+            model: model.to_string(),
+            context_tokens: prompt_tokens + RESPONSE_TOKENS,
+            transcripts: TranscriptLog::new(transcript_dir),
+            usage: Cell::default(),
+        }
+    }
+}
+
+impl Backend for Ollama {
+    fn name(&self) -> &str {
+        "Ollama"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn check(&self) -> Result<()> {
+        check_ollama_server(&self.model)
+    }
+
+    /// Sends a single non-streaming completion request.
+    fn generate(&self, prompt: &str, temperature: f64) -> Result<String> {
+        let model = self.model.as_str();
+        let payload = json!({
+            "model": model,
+            "prompt": prompt,
+            "stream": false,
+            "options": {
+                "temperature": temperature,
+                "top_p": 0.9,
+                "max_tokens": RESPONSE_TOKENS,
+                "num_ctx": self.context_tokens
+            }
+        });
+
+        let client = reqwest::blocking::Client::builder()
+            .timeout(std::time::Duration::from_secs(120))
+            .build()
+            .context(ReportError::ai, "Failed to create HTTP client")?;
+
+        let sent_at = Utc::now();
+        let response = client
+            .post(GENERATE_URL)
+            .json(&payload)
+            .send()
+            .map_err(|e| unreachable(GENERATE_URL, e))?;
+
+        let status = response.status();
+        let raw_response = response
+            .text()
+            .context(ReportError::ai, "Failed to read Ollama response")?;
+
+        self.transcripts.save(
+            GENERATE_URL,
+            model,
+            &payload,
+            sent_at,
+            status.as_u16(),
+            &raw_response,
+        )?;
+
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Err(ReportError::ai(not_installed(model)));
+        }
+        if !status.is_success() {
+            return Err(status_error(
+                status,
+                format!(
+                    "Ollama request failed with status {} for model '{}': {}",
+                    status,
+                    model,
+                    error_message(&raw_response)
+                ),
+            ));
+        }
+
+        let response_json: Value = serde_json::from_str(&raw_response)
+            .context(ReportError::parse, "Failed to parse Ollama response")?;
+
+        let text = response_json["response"].as_str().ok_or_else(|| {
+            ReportError::parse(format!(
+                "Invalid response format from Ollama for model '{}'",
+                model
+            ))
+        })?;
+        self.usage.set(self.usage.get().with_request(
+            response_json["prompt_eval_count"].as_u64().unwrap_or(0),
+            response_json["eval_count"].as_u64().unwrap_or(0),
+        ));
+
+        Ok(text.to_string())
+    }
+
+    fn usage(&self) -> TokenUsage {
+        self.usage.get()
+    }
+}
+
+/// An embedding model served by the local Ollama server.
+pub struct OllamaEmbeddings {
+    pub model: String,
+}
+
+impl OllamaEmbeddings {
+    pub fn new(model: &str) -> Self {
+        Self {
+            model: model.to_string(),
+        }
+    }
+}
+
+impl Embedder for OllamaEmbeddings {
+    fn name(&self) -> &str {
+        "Ollama"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let client = reqwest::blocking::Client::builder()
+            .timeout(std::time::Duration::from_secs(120))
+            .build()
+            .context(ReportError::ai, "Failed to create HTTP client")?;
+        let response = client
+            .post(EMBED_URL)
+            .json(&json!({ "model": self.model, "input": texts }))
+            .send()
+            .map_err(|e| unreachable(EMBED_URL, e))?;
+
+        let status = response.status();
+        let body = response
+            .text()
+            .context(ReportError::ai, "Failed to read Ollama response")?;
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Err(ReportError::ai(not_installed(&self.model)));
+        }
+        if !status.is_success() {
+            return Err(status_error(
+                status,
+                format!(
+                    "Ollama embedding request failed with status {} for model '{}': {}",
+                    status,
+                    self.model,
+                    error_message(&body)
+                ),
+            ));
+        }
+
+        let response: Value = serde_json::from_str(&body)
+            .context(ReportError::parse, "Failed to parse Ollama response")?;
+        serde_json::from_value(response["embeddings"].clone())
+            .with_context(ReportError::parse, || {
+                format!("Invalid embeddings from Ollama for model '{}'", self.model)
+            })
+    }
+}
+
+/// A request that got no response, with a hint when nothing listens on the
+/// port at all.
+fn unreachable(url: &str, error: reqwest::Error) -> ReportError {
+    let error = request_error("Ollama", url, error);
+    match error {
+        ReportError::AiUnavailable { message, source } if message.starts_with("Cannot connect") => {
+            ReportError::AiUnavailable {
+                message: format!("{} (is `ollama serve` running?)", message),
+                source,
+            }
+        }
+        error => error,
+    }
+}
+
+fn not_installed(model: &str) -> String {
+    format!(
+        "Model '{}' is not installed in Ollama; run `ollama pull {}`",
+        model, model
+    )
+}
+
+/// Extracts the message of an Ollama error body, falling back to the raw body.
+fn error_message(body: &str) -> String {
+    serde_json::from_str::<Value>(body)
+        .ok()
+        .and_then(|value| value["error"].as_str().map(String::from))
+        .unwrap_or_else(|| body.trim().to_string())
+}
+
+fn check_ollama_server(model: &str) -> Result<()> {
+    let client = reqwest::blocking::Client::builder()
+        .timeout(std::time::Duration::from_secs(60 * 2))
+        .build()
+        .context(ReportError::ai, "Failed to create HTTP client")?;
+
+    let response = client
+        .get(TAGS_URL)
+        .send()
+        .map_err(|e| unreachable(TAGS_URL, e))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(status_error(
+            status,
+            format!("Ollama server returned error status: {}", status),
+        ));
+    }
+
+    let models_response = response
+        .json::<Value>()
+        .context(ReportError::parse, "Failed to parse Ollama models response")?;
+
+    let models = models_response["models"]
+        .as_array()
+        .ok_or_else(|| ReportError::parse("Invalid models response format".to_string()))?;
+
+    let model_available = models.iter().any(|m| {
+        m["name"]
+            .as_str()
+            .is_some_and(|name| name == model || name.starts_with(&format!("{}:", model)))
+    });
+
+    if !model_available {
+        return Err(ReportError::ai(format!(
+            "{}. Available models: {}",
+            not_installed(model),
+            models
+                .iter()
+                .filter_map(|m| m["name"].as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )));
+    }
+
+    Ok(())
+}
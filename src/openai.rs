@@ -0,0 +1,103 @@
+use serde_json::{json, Value};
+use std::{env, time::Duration};
+
+use crate::{
+    ai::{request_error, status_error, Embedder},
+    error::{Context, ReportError, Result},
+};
+
+/// The embedding model used when `--embedding-model` is not given.
+pub const DEFAULT_EMBEDDING_MODEL: &str = "text-embedding-3-small";
+
+/// An embedding model behind an OpenAI-compatible `/embeddings` endpoint,
+/// such as OpenAI itself, vLLM, LocalAI or LM Studio. The API key is read
+/// from `OPENAI_API_KEY` and may be left unset for servers without one.
+pub struct OpenAiEmbeddings {
+    pub model: String,
+    base_url: String,
+}
+
+impl OpenAiEmbeddings {
+    /// `base_url` is the API root, e.g. `https://api.openai.com/v1`.
+    pub fn new(model: &str, base_url: &str) -> Self {
+        Self {
+            model: model.to_string(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+}
+
+/// Extracts the message of an API error body, falling back to the raw body.
+fn error_message(body: &str) -> String {
+    serde_json::from_str::<Value>(body)
+        .ok()
+        .and_then(|value| value["error"]["message"].as_str().map(String::from))
+        .unwrap_or_else(|| body.trim().to_string())
+}
+
+impl Embedder for OpenAiEmbeddings {
+    fn name(&self) -> &str {
+        "the embeddings API"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let endpoint = format!("{}/embeddings", self.base_url);
+        let client = reqwest::blocking::Client::builder()
+            .timeout(Duration::from_secs(120))
+            .build()
+            .context(ReportError::ai, "Failed to create HTTP client")?;
+        let mut request = client
+            .post(&endpoint)
+            .json(&json!({ "model": self.model, "input": texts }));
+        if let Some(key) = env::var("OPENAI_API_KEY")
+            .ok()
+            .filter(|key| !key.trim().is_empty())
+        {
+            request = request.bearer_auth(key);
+        }
+        let response = request
+            .send()
+            .map_err(|e| request_error("the embeddings API", &endpoint, e))?;
+
+        let status = response.status();
+        let body = response
+            .text()
+            .context(ReportError::ai, "Failed to read the embeddings response")?;
+        if !status.is_success() {
+            return Err(status_error(
+                status,
+                format!(
+                    "Embeddings API at {} returned status {} for model '{}': {}",
+                    endpoint,
+                    status,
+                    self.model,
+                    error_message(&body)
+                ),
+            ));
+        }
+
+        // Each item carries its input's index; servers need not keep the order.
+        let response: Value = serde_json::from_str(&body).context(
+            ReportError::parse,
+            "Failed to parse the embeddings response",
+        )?;
+        let mut items: Vec<(usize, Vec<f32>)> = response["data"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .enumerate()
+            .map(|(position, item)| {
+                let index = item["index"].as_u64().map_or(position, |i| i as usize);
+                let vector = serde_json::from_value(item["embedding"].clone())
+                    .context(ReportError::parse, "Invalid embedding in the response")?;
+                Ok((index, vector))
+            })
+            .collect::<Result<_>>()?;
+        items.sort_by_key(|(index, _)| *index);
+        Ok(items.into_iter().map(|(_, vector)| vector).collect())
+    }
+}
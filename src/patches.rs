@@ -0,0 +1,616 @@
+use chrono::{DateTime, FixedOffset};
+use std::{fs, path::Path};
+
+use crate::{
+    error::{Context, ReportError, Result},
+    git::{Commit, FileStat, FileStatus},
+};
+
+/// Reads a patch series written by `git format-patch`, either a directory of
+/// `.patch` files (in file name order) or a single mbox holding one or more
+/// messages. Cover letters (`[PATCH 0/N]`) and messages without a diff, such as
+/// mailing-list replies, are skipped.
+pub fn load(path: &Path) -> Result<Vec<Commit>> {
+    let mut sources = Vec::new();
+    if path.is_dir() {
+        let entries = fs::read_dir(path).with_context(ReportError::io, || {
+            format!("Failed to read patch directory {}", path.display())
+        })?;
+        for entry in entries {
+            let entry = entry.with_context(ReportError::io, || {
+                format!("Failed to read patch directory {}", path.display())
+            })?;
+            let file = entry.path();
+            let is_patch = file
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| matches!(ext, "patch" | "diff" | "eml" | "mbox"));
+            if is_patch {
+                sources.push(file);
+            }
+        }
+        sources.sort();
+    } else {
+        sources.push(path.to_path_buf());
+    }
+
+    let mut commits = Vec::new();
+    for source in &sources {
+        let content = fs::read(source).with_context(ReportError::io, || {
+            format!("Failed to read patch file {}", source.display())
+        })?;
+        let content = String::from_utf8_lossy(&content);
+        for message in split_mbox(&content) {
+            if let Some(commit) = parse_message(message, commits.len())
+                .map_err(|error| ReportError::parse(format!("{} in {}", error, source.display())))?
+            {
+                commits.push(commit);
+            }
+        }
+    }
+
+    if commits.is_empty() {
+        return Err(ReportError::parse(format!(
+            "No patches found in {}",
+            path.display()
+        )));
+    }
+
+    Ok(commits)
+}
+
+/// Splits an mbox at its `From ` separator lines. Text without separators is
+/// treated as a single message.
+fn split_mbox(content: &str) -> Vec<&str> {
+    let mut starts = Vec::new();
+    let mut offset = 0;
+    let mut previous_blank = true;
+    for line in content.split_inclusive('\n') {
+        if previous_blank && line.starts_with("From ") {
+            starts.push(offset);
+        }
+        previous_blank = line.trim().is_empty();
+        offset += line.len();
+    }
+
+    if starts.is_empty() {
+        return vec![content];
+    }
+    starts.push(content.len());
+    starts
+        .windows(2)
+        .map(|range| &content[range[0]..range[1]])
+        .collect()
+}
+
+/// Builds a commit from one patch email, or `None` for cover letters and
+/// messages without a diff.
+fn parse_message(message: &str, index: usize) -> std::result::Result<Option<Commit>, String> {
+    let mut lines = message.lines().peekable();
+
+    // `From <hash> Mon Sep 17 00:00:00 2001` carries the original commit hash.
+    let mut hash = None;
+    if let Some(separator) = lines.next_if(|line| line.starts_with("From ")) {
+        hash = separator
+            .split_whitespace()
+            .nth(1)
+            .filter(|id| id.len() == 40 && id.chars().all(|c| c.is_ascii_hexdigit()))
+            .map(str::to_string);
+    }
+
+    let mut headers: Vec<(String, String)> = Vec::new();
+    for line in lines.by_ref() {
+        if line.is_empty() {
+            break;
+        }
+        match headers.last_mut() {
+            Some((_, value)) if line.starts_with([' ', '\t']) => {
+                value.push(' ');
+                value.push_str(line.trim());
+            }
+            _ => {
+                if let Some((name, value)) = line.split_once(':') {
+                    headers.push((name.trim().to_lowercase(), value.trim().to_string()));
+                }
+            }
+        }
+    }
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| decode_words(value))
+    };
+
+    let rest: Vec<&str> = lines.collect();
+    if !rest.iter().any(|line| line.starts_with("diff --git ")) {
+        return Ok(None);
+    }
+
+    let raw_subject = header("subject").ok_or("Patch without a Subject header")?;
+    let Some(subject) = strip_subject_tags(&raw_subject) else {
+        return Ok(None);
+    };
+
+    // `git am` takes a `From:` line at the start of the body as the real author.
+    let mut body_start = 0;
+    let mut author = header("from");
+    if let Some(from) = rest.first().and_then(|line| line.strip_prefix("From: ")) {
+        author = Some(decode_words(from));
+        body_start = 1;
+    }
+    let from = author.unwrap_or_default();
+    let (author, email) = (author_name(&from), author_email(&from));
+
+    let date = header("date").ok_or("Patch without a Date header")?;
+    let date: DateTime<FixedOffset> = DateTime::parse_from_rfc2822(&date)
+        .map_err(|error| format!("Invalid Date header '{}': {}", date, error))?;
+
+    let body_end = rest
+        .iter()
+        .position(|line| *line == "---" || line.starts_with("diff --git "))
+        .unwrap_or(rest.len());
+    let body = rest[body_start..body_end.max(body_start)]
+        .join("\n")
+        .trim()
+        .to_string();
+
+    let file_stats = parse_diff(&rest[body_end..]);
+
+    Ok(Some(Commit {
+        // Patches sent without a `From <hash>` line get a placeholder id.
+        hash: hash.unwrap_or_else(|| format!("{:040x}", index + 1)),
+        author,
+        email,
+        date,
+        subject,
+        body,
+        files_changed: file_stats.iter().map(|stat| stat.path.clone()).collect(),
+        file_stats,
+        parents: Vec::new(),
+        signature: None,
+        labels: Vec::new(),
+        tags: Vec::new(),
+        branches: Vec::new(),
+    }))
+}
+
+/// Removes leading `[PATCH v2 3/7]`-style tags. Returns `None` for a cover
+/// letter, numbered `0/N`.
+fn strip_subject_tags(subject: &str) -> Option<String> {
+    let mut subject = subject.trim();
+    while let Some(rest) = subject.strip_prefix('[') {
+        let Some((tag, rest)) = rest.split_once(']') else {
+            break;
+        };
+        if tag
+            .split_whitespace()
+            .any(|part| part.starts_with("0/") || part.starts_with("00/"))
+        {
+            return None;
+        }
+        subject = rest.trim_start();
+    }
+    Some(subject.to_string())
+}
+
+/// `Jane Doe <jane@example.com>` -> `Jane Doe`; a bare address is kept as is.
+fn author_name(from: &str) -> String {
+    match from.split_once('<') {
+        Some((name, _)) if !name.trim().is_empty() => name.trim().trim_matches('"').to_string(),
+        _ => from.trim().trim_matches(['<', '>']).to_string(),
+    }
+}
+
+/// `Jane Doe <jane@example.com>` -> `jane@example.com`; empty when there is no
+/// address.
+fn author_email(from: &str) -> String {
+    match from.split_once('<') {
+        Some((_, rest)) => rest
+            .split('>')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+        None if from.contains('@') => from.trim().to_string(),
+        None => String::new(),
+    }
+}
+
+/// Decodes RFC 2047 `=?charset?Q?...?=` words as written by `git format-patch`
+/// for non-ASCII names and subjects, and the `=?charset?B?...?=` (base64)
+/// words some mailers use instead. Text in charsets other than UTF-8 and
+/// ISO-8859-1 is decoded as UTF-8.
+fn decode_words(value: &str) -> String {
+    let mut output = String::new();
+    let mut rest = value;
+    let mut after_encoded = false;
+    while let Some(start) = rest.find("=?") {
+        let (before, word) = rest.split_at(start);
+        let parts: Vec<&str> = word[2..].splitn(3, '?').collect();
+        let decoded = match parts.as_slice() {
+            [charset, encoding, text] => text.find("?=").and_then(|end| {
+                let bytes = match encoding {
+                    e if e.eq_ignore_ascii_case("q") => Some(decode_q(&text[..end])),
+                    e if e.eq_ignore_ascii_case("b") => decode_b(&text[..end]),
+                    _ => None,
+                }?;
+                Some((decode_charset(charset, bytes), &text[end + 2..]))
+            }),
+            _ => None,
+        };
+
+        match decoded {
+            Some((text, remaining)) => {
+                // Whitespace between two encoded words is not part of the text.
+                if !(after_encoded && before.trim().is_empty()) {
+                    output.push_str(before);
+                }
+                output.push_str(&text);
+                rest = remaining;
+                after_encoded = true;
+            }
+            None => {
+                output.push_str(before);
+                output.push_str("=?");
+                rest = &word[2..];
+                after_encoded = false;
+            }
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+fn decode_q(text: &str) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    let mut input = text.bytes();
+    while let Some(byte) = input.next() {
+        match byte {
+            b'_' => bytes.push(b' '),
+            b'=' => {
+                let hex: Vec<u8> = input.by_ref().take(2).collect();
+                match std::str::from_utf8(&hex)
+                    .ok()
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                {
+                    Some(decoded) => bytes.push(decoded),
+                    None => {
+                        bytes.push(b'=');
+                        bytes.extend(hex);
+                    }
+                }
+            }
+            _ => bytes.push(byte),
+        }
+    }
+    bytes
+}
+
+/// Decodes base64, or `None` when `text` is not valid base64.
+fn decode_b(text: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+    let mut bits = 0u32;
+    let mut count = 0;
+    for byte in text.trim_end_matches('=').bytes() {
+        let value = match byte {
+            b'A'..=b'Z' => byte - b'A',
+            b'a'..=b'z' => byte - b'a' + 26,
+            b'0'..=b'9' => byte - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        };
+        bits = (bits << 6) | u32::from(value);
+        count += 6;
+        if count >= 8 {
+            count -= 8;
+            bytes.push((bits >> count) as u8);
+        }
+    }
+    Some(bytes)
+}
+
+fn decode_charset(charset: &str, bytes: Vec<u8>) -> String {
+    // RFC 2231 allows a language after the charset, as in `utf-8*en`.
+    let charset = charset.split('*').next().unwrap_or_default();
+    if charset.eq_ignore_ascii_case("iso-8859-1") || charset.eq_ignore_ascii_case("latin1") {
+        bytes.into_iter().map(char::from).collect()
+    } else {
+        String::from_utf8_lossy(&bytes).into_owned()
+    }
+}
+
+/// Counts added and deleted lines per file of a unified git diff.
+fn parse_diff(lines: &[&str]) -> Vec<FileStat> {
+    let mut stats: Vec<FileStat> = Vec::new();
+    let mut in_hunk = false;
+
+    for line in lines {
+        if let Some(paths) = line.strip_prefix("diff --git ") {
+            let path = paths
+                .rsplit_once(" b/")
+                .map(|(_, path)| path)
+                .unwrap_or(paths)
+                .trim_matches('"');
+            stats.push(FileStat::new(path, 0, 0, false));
+            in_hunk = false;
+            continue;
+        }
+        // The signature that ends the email: "-- " followed by the git version.
+        if *line == "-- " {
+            break;
+        }
+        let Some(stat) = stats.last_mut() else {
+            continue;
+        };
+
+        if line.starts_with("@@") {
+            in_hunk = true;
+            continue;
+        }
+        if in_hunk {
+            match line.chars().next() {
+                Some('+') => stat.additions += 1,
+                Some('-') => stat.deletions += 1,
+                Some(' ') | Some('\\') => {}
+                _ => in_hunk = false,
+            }
+            if in_hunk {
+                continue;
+            }
+        }
+
+        if let Some(path) = line
+            .strip_prefix("rename to ")
+            .or_else(|| line.strip_prefix("copy to "))
+        {
+            stat.path = path.to_string();
+        } else if let Some(path) = line.strip_prefix("rename from ") {
+            stat.status = FileStatus::Renamed;
+            stat.old_path = Some(path.to_string());
+        } else if let Some(path) = line.strip_prefix("copy from ") {
+            stat.status = FileStatus::Copied;
+            stat.old_path = Some(path.to_string());
+        } else if line.starts_with("new file mode ") {
+            stat.status = FileStatus::Added;
+        } else if line.starts_with("deleted file mode ") {
+            stat.status = FileStatus::Deleted;
+        } else if line.starts_with("Binary files ") || *line == "GIT binary patch" {
+            stat.binary = true;
+        }
+    }
+
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A series as `git format-patch --cover-letter --stdout` writes it.
+    const SERIES: &str = "\
+From 0000000000000000000000000000000000000000 Mon Sep 17 00:00:00 2001
+From: Jane Doe <jane@example.com>
+Date: Tue, 5 Mar 2024 10:00:00 +0100
+Subject: [PATCH 0/2] Parser fixes
+
+Two fixes for the parser.
+
+Jane Doe (2):
+  parser: accept empty input
+  parser: report columns
+
+-- 
+2.44.0
+
+From 4f3c2a1b9e8d7c6b5a4f3e2d1c0b9a8f7e6d5c4b Mon Sep 17 00:00:00 2001
+From: Jane Doe <jane@example.com>
+Date: Tue, 5 Mar 2024 10:00:00 +0100
+Subject: [PATCH 1/2] parser: accept empty input
+
+Empty input used to panic.
+---
+ src/parser.rs | 3 ++-
+ 1 file changed, 2 insertions(+), 1 deletion(-)
+
+diff --git a/src/parser.rs b/src/parser.rs
+index 1111111..2222222 100644
+--- a/src/parser.rs
++++ b/src/parser.rs
+@@ -1,3 +1,4 @@
+ fn parse() {
+-    todo!()
++    if input.is_empty() {
++    }
+ }
+-- 
+2.44.0
+
+From 9e8d7c6b5a4f3e2d1c0b9a8f7e6d5c4b4f3c2a1b Mon Sep 17 00:00:00 2001
+From: =?UTF-8?q?J=C3=B6rg=20M=C3=BCller?= <joerg@example.com>
+Date: Tue, 5 Mar 2024 11:00:00 +0100
+Subject: [PATCH 2/2] parser: report columns in errors that span a
+ very long subject line
+X-Mailer: git-send-email 2.44.0
+
+diff --git a/src/old.rs b/src/new.rs
+similarity index 90%
+rename from src/old.rs
+rename to src/new.rs
+--- a/src/old.rs
++++ b/src/new.rs
+@@ -1 +1 @@
+-a
++b
+diff --git a/logo.png b/logo.png
+new file mode 100644
+Binary files /dev/null and b/logo.png differ
+-- 
+2.44.0
+";
+
+    fn parse(content: &str) -> Vec<Commit> {
+        let mut commits = Vec::new();
+        for message in split_mbox(content) {
+            if let Some(commit) = parse_message(message, commits.len()).unwrap() {
+                commits.push(commit);
+            }
+        }
+        commits
+    }
+
+    #[test]
+    fn cover_letter_is_skipped() {
+        let commits = parse(SERIES);
+        assert_eq!(split_mbox(SERIES).len(), 3);
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].subject, "parser: accept empty input");
+        assert_eq!(commits[0].hash, "4f3c2a1b9e8d7c6b5a4f3e2d1c0b9a8f7e6d5c4b");
+        assert_eq!(commits[0].author, "Jane Doe");
+        assert_eq!(commits[0].email, "jane@example.com");
+        assert_eq!(commits[0].body, "Empty input used to panic.");
+        assert_eq!(commits[0].date.to_rfc3339(), "2024-03-05T10:00:00+01:00");
+        assert_eq!(commits[0].files_changed, ["src/parser.rs"]);
+        assert_eq!(commits[0].file_stats[0].additions, 2);
+        assert_eq!(commits[0].file_stats[0].deletions, 1);
+    }
+
+    #[test]
+    fn folded_headers_are_joined() {
+        let commits = parse(SERIES);
+        assert_eq!(
+            commits[1].subject,
+            "parser: report columns in errors that span a very long subject line"
+        );
+        assert_eq!(commits[1].author, "Jörg Müller");
+        assert_eq!(commits[1].body, "");
+    }
+
+    #[test]
+    fn diff_statuses() {
+        let stats = &parse(SERIES)[1].file_stats;
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].path, "src/new.rs");
+        assert_eq!(stats[0].old_path.as_deref(), Some("src/old.rs"));
+        assert!(matches!(stats[0].status, FileStatus::Renamed));
+        assert_eq!((stats[0].additions, stats[0].deletions), (1, 1));
+        assert_eq!(stats[1].path, "logo.png");
+        assert!(matches!(stats[1].status, FileStatus::Added));
+        assert!(stats[1].binary);
+    }
+
+    #[test]
+    fn patch_without_a_from_line_gets_a_placeholder_hash() {
+        // A patch saved from a mail client: no mbox separator, and the real
+        // author in a `From:` line at the start of the body.
+        let patch = "\
+From: Mailing List <list@example.org>
+Date: Wed, 6 Mar 2024 09:30:00 -0800
+Subject: [PATCH v3] docs: fix typo
+
+From: Ana Lima <ana@example.com>
+
+Spotted in review.
+---
+diff --git a/README.md b/README.md
+--- a/README.md
++++ b/README.md
+@@ -1 +1 @@
+-teh
++the
+";
+        let commits = parse(patch);
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].hash, format!("{:040x}", 1));
+        assert_eq!(commits[0].subject, "docs: fix typo");
+        assert_eq!(commits[0].author, "Ana Lima");
+        assert_eq!(commits[0].email, "ana@example.com");
+        assert_eq!(commits[0].body, "Spotted in review.");
+
+        // A separator without a hash, as some tools write, also gets one;
+        // placeholders count the patches before, not the messages.
+        let series = format!(
+            "{}\nFrom nobody Mon Sep 17 00:00:00 2001\n{}",
+            SERIES, patch
+        );
+        assert_eq!(parse(&series)[2].hash, format!("{:040x}", 3));
+    }
+
+    #[test]
+    fn message_without_a_diff_is_skipped() {
+        let reply = "\
+From: Bob <bob@example.com>
+Date: Wed, 6 Mar 2024 09:30:00 +0000
+Subject: Re: [PATCH 1/2] parser: accept empty input
+
+Looks good to me.
+";
+        assert!(parse_message(reply, 0).unwrap().is_none());
+    }
+
+    #[test]
+    fn missing_headers_are_errors() {
+        let patch = "Subject: [PATCH] x\n\ndiff --git a/x b/x\n";
+        assert_eq!(
+            parse_message(patch, 0).unwrap_err(),
+            "Patch without a Date header"
+        );
+        let patch = "Date: Wed, 6 Mar 2024 09:30:00 +0000\n\ndiff --git a/x b/x\n";
+        assert_eq!(
+            parse_message(patch, 0).unwrap_err(),
+            "Patch without a Subject header"
+        );
+    }
+
+    #[test]
+    fn q_encoded_words() {
+        assert_eq!(
+            decode_words("=?UTF-8?q?[PATCH]=20caf=C3=A9_au_lait?="),
+            "[PATCH] café au lait"
+        );
+        // Whitespace between encoded words, as left by folding, is dropped.
+        assert_eq!(
+            decode_words("=?UTF-8?q?Gr=C3=BC?= =?UTF-8?q?=C3=9Fe?= aus Wien"),
+            "Grüße aus Wien"
+        );
+        assert_eq!(decode_words("=?iso-8859-1?Q?Andr=E9?="), "André");
+        // Malformed escapes and words are kept.
+        assert_eq!(decode_words("=?UTF-8?q?100=ZZ?="), "100=ZZ");
+        assert_eq!(decode_words("a =? b"), "a =? b");
+        assert_eq!(
+            decode_words("=?UTF-8?q?unterminated"),
+            "=?UTF-8?q?unterminated"
+        );
+    }
+
+    #[test]
+    fn b_encoded_words() {
+        // "Jörg Müller" and "日本語" in base64.
+        assert_eq!(
+            decode_words("=?UTF-8?B?SsO2cmcgTcO8bGxlcg==?="),
+            "Jörg Müller"
+        );
+        assert_eq!(
+            decode_words("=?utf-8?b?5pel5pys6Kqe?= patch"),
+            "日本語 patch"
+        );
+        assert_eq!(
+            decode_words("=?UTF-8?B?not base64?="),
+            "=?UTF-8?B?not base64?="
+        );
+    }
+
+    #[test]
+    fn subject_tags() {
+        assert_eq!(
+            strip_subject_tags("[PATCH v2 03/10] [RFC] fix").as_deref(),
+            Some("fix")
+        );
+        assert_eq!(strip_subject_tags("[PATCH v2 00/10] series"), None);
+        assert_eq!(
+            strip_subject_tags("[unclosed tag").as_deref(),
+            Some("[unclosed tag")
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use anyhow::{Context, Result};
+use console::{style, truncate_str, Key, Term};
+
+/// Most items shown at once; the list scrolls past this.
+const MAX_ROWS: usize = 15;
+
+/// Scores `item` against `query` as a case-insensitive subsequence, or
+/// `None` when not every query character appears in order. Runs of
+/// consecutive characters and matches at word starts score higher, so
+/// `fixlog` ranks "fix login" above "fix parsing of the changelog".
+pub fn fuzzy_score(item: &str, query: &str) -> Option<i64> {
+    let query: Vec<char> = query.to_lowercase().chars().collect();
+    let mut next = 0;
+    let mut score = 0;
+    let mut previous: Option<char> = None;
+    let mut previous_matched = false;
+
+    for c in item.to_lowercase().chars() {
+        if next == query.len() {
+            break;
+        }
+        if c == query[next] {
+            score += 1;
+            if previous_matched {
+                score += 5;
+            }
+            if previous.is_none_or(|p| !p.is_alphanumeric()) {
+                score += 3;
+            }
+            next += 1;
+            previous_matched = true;
+        } else {
+            previous_matched = false;
+        }
+        previous = Some(c);
+    }
+
+    (next == query.len()).then_some(score)
+}
+
+/// Lets the user type part of an item to narrow the list, move with the
+/// arrow keys and pick with Enter. Returns the index into the items; Esc
+/// cancels. With an empty query the list keeps its order.
+///
+/// `items` can be a first page: `more(n)` is asked for up to `n` further
+/// items when the selection nears the end of the list, and for all the rest
+/// once the user types a filter (`n` is then `usize::MAX`). Returning fewer
+/// than asked for means there are no more.
+pub fn pick(
+    prompt: &str,
+    mut items: Vec<String>,
+    mut more: impl FnMut(usize) -> Result<Vec<String>>,
+) -> Result<usize> {
+    let term = Term::stderr();
+    if !term.is_term() {
+        anyhow::bail!("Interactive selection needs a terminal; pass --from and --to instead");
+    }
+
+    let mut query = String::new();
+    let mut selected = 0;
+    let mut offset = 0;
+    let mut complete = false;
+
+    loop {
+        let (height, width) = term.size();
+        let rows = (height as usize).saturating_sub(4).clamp(3, MAX_ROWS);
+
+        let wanted = if !query.is_empty() {
+            usize::MAX
+        } else if selected + 2 * rows >= items.len() {
+            2 * rows
+        } else {
+            0
+        };
+        if !complete && wanted > 0 {
+            let loaded = more(wanted)?;
+            complete = loaded.len() < wanted;
+            items.extend(loaded);
+        }
+
+        let mut matches: Vec<(usize, i64)> = items
+            .iter()
+            .enumerate()
+            .filter_map(|(i, item)| fuzzy_score(item, &query).map(|score| (i, score)))
+            .collect();
+        // A stable sort keeps equally good matches in list order.
+        matches.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+
+        selected = selected.min(matches.len().saturating_sub(1));
+        if selected < offset {
+            offset = selected;
+        } else if selected >= offset + rows {
+            offset = selected + 1 - rows;
+        }
+
+        let mut lines = vec![format!(
+            "{} {}",
+            style(format!("{}:", prompt)).bold(),
+            query
+        )];
+        for (row, (index, _)) in matches.iter().enumerate().skip(offset).take(rows) {
+            let item = truncate_str(&items[*index], (width as usize).saturating_sub(3), "…");
+            lines.push(if row == selected {
+                format!("{} {}", style(">").cyan(), style(item).cyan())
+            } else {
+                format!("  {}", item)
+            });
+        }
+        lines.push(
+            style(format!(
+                "{}/{}{} shown, type to filter, arrows to move, Enter to select, Esc to cancel",
+                matches.len(),
+                items.len(),
+                if complete { "" } else { "+" }
+            ))
+            .dim()
+            .to_string(),
+        );
+        for line in &lines {
+            term.write_line(line)?;
+        }
+
+        let key = term.read_key().context("Failed to get user selection")?;
+        term.clear_last_lines(lines.len())?;
+
+        match key {
+            Key::Enter => {
+                if let Some((index, _)) = matches.get(selected) {
+                    term.write_line(&format!(
+                        "{} {}",
+                        style(format!("{}:", prompt)).bold(),
+                        items[*index]
+                    ))?;
+                    return Ok(*index);
+                }
+            }
+            Key::Escape | Key::CtrlC => anyhow::bail!("Cancelled"),
+            Key::ArrowUp => selected = selected.saturating_sub(1),
+            Key::ArrowDown => selected += 1,
+            Key::PageUp => selected = selected.saturating_sub(rows),
+            Key::PageDown => selected += rows,
+            Key::Home => selected = 0,
+            Key::End => selected = matches.len().saturating_sub(1),
+            Key::Backspace => {
+                query.pop();
+                selected = 0;
+            }
+            Key::Char(c) if !c.is_control() => {
+                query.push(c);
+                selected = 0;
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,616 @@
+use clap::ValueEnum;
+
+/// A step of `--ai-postprocess`, applied to AI-written text before it is
+/// put into a report.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    /// Drop openers like "Here is your report:" and closers like "Let me know
+    /// if you need anything else", and unwrap a reply fenced as a whole
+    Preamble,
+    /// Shift the headings so the top one is at --ai-heading-level and none
+    /// is deeper than --ai-max-heading
+    Headings,
+    /// Turn the Markdown into HTML, AsciiDoc or plain text for those formats
+    /// instead of showing it verbatim
+    Convert,
+    /// Leave the text as the model wrote it
+    #[value(name = "none")]
+    Off,
+}
+
+/// Opening words of a reply that introduce the answer instead of being it.
+const PREAMBLE_STARTS: &[&str] = &[
+    "here is",
+    "here's",
+    "here are",
+    "sure",
+    "certainly",
+    "of course",
+    "okay",
+    "ok,",
+    "absolutely",
+    "below is",
+    "the following is",
+];
+
+/// Opening words of a closing remark after the answer.
+const CLOSER_STARTS: &[&str] = &[
+    "let me know",
+    "i hope this",
+    "hope this helps",
+    "feel free to",
+    "would you like me",
+];
+
+/// The `--ai-postprocess` steps and heading limits.
+#[derive(Debug, Clone)]
+pub struct PostProcess {
+    pub steps: Vec<Step>,
+    /// Level the shallowest heading is moved to.
+    pub heading_level: u8,
+    /// Deepest heading level; deeper headings are raised to it.
+    pub max_heading: u8,
+}
+
+impl PostProcess {
+    fn has(&self, step: Step) -> bool {
+        self.steps.contains(&step) && !self.steps.contains(&Step::Off)
+    }
+
+    /// Whether renderers should convert the Markdown to their format.
+    pub fn converts(&self) -> bool {
+        self.has(Step::Convert)
+    }
+
+    /// Applies the text steps, [`Step::Preamble`] and [`Step::Headings`].
+    pub fn apply(&self, text: &str) -> String {
+        let mut text = text.to_string();
+        if self.has(Step::Preamble) {
+            text = strip_preamble(&text);
+        }
+        if self.has(Step::Headings) {
+            text = normalize_headings(&text, self.heading_level, self.max_heading);
+        }
+        text
+    }
+}
+
+fn starts_with_any(line: &str, starts: &[&str]) -> bool {
+    let line = line.trim_start_matches(['*', '_', '#', ' ']).to_lowercase();
+    starts.iter().any(|start| line.starts_with(start))
+}
+
+/// Removes a model's introduction and sign-off around the answer, and the
+/// code fence some models put around a whole Markdown reply.
+pub fn strip_preamble(text: &str) -> String {
+    let mut lines: Vec<&str> = text.trim().lines().collect();
+
+    // An introduction is one line that leads into the answer, not a first
+    // sentence of it.
+    if let Some(first) = lines.first() {
+        let first = first.trim();
+        let leads_in = (first.ends_with(':') && first.len() <= 120) || first.len() <= 60;
+        let more = lines[1..].iter().any(|line| !line.trim().is_empty());
+        if starts_with_any(first, PREAMBLE_STARTS) && leads_in && more {
+            lines.remove(0);
+        }
+    }
+    while lines.first().is_some_and(|line| line.trim().is_empty()) {
+        lines.remove(0);
+    }
+
+    while let Some(last) = lines.last() {
+        let last = last.trim();
+        if last.is_empty() || last == "---" || starts_with_any(last, CLOSER_STARTS) {
+            lines.pop();
+        } else {
+            break;
+        }
+    }
+
+    if let (Some(first), Some(last)) = (lines.first(), lines.last()) {
+        let opener = first.trim();
+        let fenced = opener.starts_with("```")
+            && matches!(
+                opener.trim_start_matches('`').trim(),
+                "" | "markdown" | "md" | "text"
+            );
+        if fenced && lines.len() > 1 && last.trim() == "```" {
+            lines.remove(0);
+            lines.pop();
+        }
+    }
+
+    lines.join("\n").trim().to_string()
+}
+
+/// The level and text of an ATX heading line such as `## Changes ##`.
+fn atx_heading(line: &str) -> Option<(usize, &str)> {
+    let trimmed = line.trim_start();
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let level = trimmed.chars().take_while(|&c| c == '#').count();
+    let rest = &trimmed[level..];
+    if !(1..=6).contains(&level) || !(rest.is_empty() || rest.starts_with([' ', '\t'])) {
+        return None;
+    }
+    Some((level, rest.trim().trim_end_matches('#').trim_end()))
+}
+
+fn is_fence(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with("```") || line.starts_with("~~~")
+}
+
+/// Rewrites the headings as ATX headings (`Title` underlined with `===` or
+/// `---` included) shifted so the shallowest is at `top`, and raises the
+/// ones deeper than `max` to `max`. Every heading gets a blank line before
+/// and after it. Fenced code is left alone.
+pub fn normalize_headings(text: &str, top: u8, max: u8) -> String {
+    let (top, max) = (usize::from(top.clamp(1, 6)), usize::from(max.clamp(1, 6)));
+    let lines: Vec<&str> = text.lines().collect();
+
+    // Headings by line, with setext underlines marked for removal.
+    let mut headings: Vec<Option<(usize, String)>> = vec![None; lines.len()];
+    let mut underlines = vec![false; lines.len()];
+    let mut in_fence = false;
+    for (i, line) in lines.iter().enumerate() {
+        if is_fence(line) {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence || underlines[i] {
+            continue;
+        }
+        if let Some((level, title)) = atx_heading(line) {
+            headings[i] = Some((level, title.to_string()));
+            continue;
+        }
+        let next = lines.get(i + 1).map(|next| next.trim()).unwrap_or_default();
+        let setext = match next.chars().next() {
+            Some(c @ ('=' | '-')) if next.chars().all(|n| n == c) => {
+                Some(if c == '=' { 1 } else { 2 })
+            }
+            _ => None,
+        };
+        let paragraph = !line.trim().is_empty()
+            && !line.trim_start().starts_with(['-', '*', '+', '>', '|'])
+            && (i == 0 || lines[i - 1].trim().is_empty());
+        if let (Some(level), true) = (setext, paragraph) {
+            headings[i] = Some((level, line.trim().to_string()));
+            underlines[i + 1] = true;
+        }
+    }
+
+    let shallowest = headings.iter().flatten().map(|(level, _)| *level).min();
+    let mut output: Vec<String> = Vec::with_capacity(lines.len());
+    for (i, line) in lines.iter().enumerate() {
+        if underlines[i] {
+            continue;
+        }
+        match (&headings[i], shallowest) {
+            (Some((level, title)), Some(shallowest)) => {
+                let level = (level - shallowest + top).min(max);
+                if output.last().is_some_and(|last| !last.is_empty()) {
+                    output.push(String::new());
+                }
+                output.push(format!("{} {}", "#".repeat(level), title));
+                let next = if underlines.get(i + 1) == Some(&true) {
+                    lines.get(i + 2)
+                } else {
+                    lines.get(i + 1)
+                };
+                if next.is_some_and(|next| !next.trim().is_empty()) {
+                    output.push(String::new());
+                }
+            }
+            _ => output.push(line.to_string()),
+        }
+    }
+    output.join("\n")
+}
+
+/// An output format the Markdown of an AI reply can be converted to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Markup {
+    Html,
+    AsciiDoc,
+    Org,
+    Text,
+}
+
+/// A block of the Markdown subset models write.
+enum Markdown<'a> {
+    Heading(usize, &'a str),
+    Paragraph(Vec<&'a str>),
+    List { ordered: bool, items: Vec<&'a str> },
+    Code(Vec<&'a str>),
+}
+
+fn list_item(line: &str) -> Option<(bool, &str)> {
+    let line = line.trim_start();
+    if let Some(item) = line
+        .strip_prefix("- ")
+        .or_else(|| line.strip_prefix("* "))
+        .or_else(|| line.strip_prefix("+ "))
+    {
+        return Some((false, item.trim()));
+    }
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    let rest = &line[digits..];
+    if digits > 0 && (rest.starts_with(". ") || rest.starts_with(") ")) {
+        return Some((true, rest[2..].trim()));
+    }
+    None
+}
+
+fn blocks(text: &str) -> Vec<Markdown<'_>> {
+    let mut blocks = Vec::new();
+    let mut lines = text.lines().peekable();
+    while let Some(line) = lines.next() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if is_fence(line) {
+            let mut code = Vec::new();
+            for line in lines.by_ref() {
+                if is_fence(line) {
+                    break;
+                }
+                code.push(line);
+            }
+            blocks.push(Markdown::Code(code));
+        } else if let Some((level, title)) = atx_heading(line) {
+            blocks.push(Markdown::Heading(level, title));
+        } else if let Some((ordered, item)) = list_item(line) {
+            let mut items = vec![item];
+            while let Some((same, item)) = lines.peek().and_then(|next| list_item(next)) {
+                if same != ordered {
+                    break;
+                }
+                items.push(item);
+                lines.next();
+            }
+            blocks.push(Markdown::List { ordered, items });
+        } else {
+            let mut paragraph = vec![line.trim()];
+            while let Some(next) = lines.peek() {
+                if next.trim().is_empty()
+                    || is_fence(next)
+                    || atx_heading(next).is_some()
+                    || list_item(next).is_some()
+                {
+                    break;
+                }
+                paragraph.push(next.trim());
+                lines.next();
+            }
+            blocks.push(Markdown::Paragraph(paragraph));
+        }
+    }
+    blocks
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Where the emphasis opened by `marker` ends in `text`, skipping doubled
+/// markers of a strong span inside it and, for `_`, underscores within a
+/// word.
+fn emphasis_end(text: &str, marker: &str) -> Option<usize> {
+    let mut offset = 0;
+    while let Some(found) = text[offset..].find(marker) {
+        let end = offset + found;
+        let after = &text[end + 1..];
+        if after.starts_with(marker) {
+            offset = end + 2;
+        } else if marker == "_" && after.starts_with(|c: char| c.is_alphanumeric()) {
+            offset = end + 1;
+        } else {
+            return Some(end);
+        }
+    }
+    None
+}
+
+/// Converts the inline markup of one line: `code`, **bold**, *emphasis*
+/// and [links](url).
+fn inline(text: &str, markup: Markup) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    let plain = |text: &str| match markup {
+        Markup::Html => escape_html(text),
+        Markup::AsciiDoc | Markup::Org | Markup::Text => text.to_string(),
+    };
+    while !rest.is_empty() {
+        let Some(start) = rest.find(['`', '*', '_', '[']) else {
+            output.push_str(&plain(rest));
+            break;
+        };
+        output.push_str(&plain(&rest[..start]));
+        rest = &rest[start..];
+        let position = text.len() - rest.len();
+
+        if let Some(code) = rest.strip_prefix('`') {
+            if let Some(end) = code.find('`') {
+                let code = &code[..end];
+                output.push_str(&match markup {
+                    Markup::Html => format!("<code>{}</code>", escape_html(code)),
+                    Markup::AsciiDoc => format!("`+{}+`", code),
+                    Markup::Org => format!("~{}~", code),
+                    Markup::Text => code.to_string(),
+                });
+                rest = &rest[end + 2..];
+                continue;
+            }
+        }
+        if let Some(strong) = rest.strip_prefix("**").or_else(|| rest.strip_prefix("__")) {
+            let marker = &rest[..2];
+            if let Some(end) = strong.find(marker).filter(|&end| end > 0) {
+                let inner = inline(&strong[..end], markup);
+                output.push_str(&match markup {
+                    Markup::Html => format!("<strong>{}</strong>", inner),
+                    Markup::AsciiDoc | Markup::Org => format!("*{}*", inner),
+                    Markup::Text => inner,
+                });
+                rest = &strong[end + 2..];
+                continue;
+            }
+        }
+        let marker = &rest[..1];
+        // `_` only marks emphasis at the start of a word, not in snake_case.
+        let word_start = !text[..position].ends_with(|c: char| c.is_alphanumeric());
+        if (marker == "*" || (marker == "_" && word_start)) && rest.len() > 1 {
+            let emphasis = &rest[1..];
+            if let Some(end) = emphasis_end(emphasis, marker)
+                .filter(|_| !emphasis.starts_with(' ') && !emphasis.starts_with(marker))
+            {
+                let inner = inline(&emphasis[..end], markup);
+                output.push_str(&match markup {
+                    Markup::Html => format!("<em>{}</em>", inner),
+                    Markup::AsciiDoc => format!("_{}_", inner),
+                    Markup::Org => format!("/{}/", inner),
+                    Markup::Text => inner,
+                });
+                rest = &emphasis[end + 1..];
+                continue;
+            }
+        }
+        if marker == "[" {
+            let link = rest[1..].find(']').and_then(|label_end| {
+                let url_start = label_end + 3;
+                if !rest[label_end + 2..].starts_with('(') {
+                    return None;
+                }
+                rest[url_start..].find(')').map(|url_end| {
+                    (
+                        &rest[1..label_end + 1],
+                        &rest[url_start..url_start + url_end],
+                    )
+                })
+            });
+            if let Some((label, url)) = link {
+                let label_text = inline(label, markup);
+                output.push_str(&match markup {
+                    Markup::Html => {
+                        format!("<a href=\"{}\">{}</a>", escape_html(url), label_text)
+                    }
+                    Markup::AsciiDoc => format!("{}[{}]", url, label_text.replace(']', "\\]")),
+                    Markup::Org => format!("[[{}][{}]]", url, label_text),
+                    Markup::Text => format!("{} ({})", label_text, url),
+                });
+                rest = &rest[label.len() + url.len() + 4..];
+                continue;
+            }
+        }
+        output.push_str(&plain(marker));
+        rest = &rest[1..];
+    }
+    output
+}
+
+/// Converts the Markdown of an AI reply to `markup`: headings, paragraphs,
+/// bullet and numbered lists, fenced code and inline code, emphasis and
+/// links. Other Markdown is kept as text.
+pub fn convert(text: &str, markup: Markup) -> String {
+    let mut output = String::new();
+    for block in blocks(text) {
+        match (block, markup) {
+            (Markdown::Heading(level, title), Markup::Html) => {
+                output.push_str(&format!("<h{0}>{1}</h{0}>\n", level, inline(title, markup)))
+            }
+            (Markdown::Heading(level, title), Markup::AsciiDoc) => output.push_str(&format!(
+                "{} {}\n\n",
+                "=".repeat(level),
+                inline(title, markup)
+            )),
+            // One level below the headline the summary is written under.
+            (Markdown::Heading(level, title), Markup::Org) => output.push_str(&format!(
+                "{} {}\n\n",
+                "*".repeat(level + 1),
+                inline(title, markup)
+            )),
+            (Markdown::Heading(level, title), Markup::Text) => {
+                let title = inline(title, markup);
+                let underline = if level <= 2 { '=' } else { '-' };
+                output.push_str(&format!(
+                    "{}\n{}\n\n",
+                    title,
+                    underline.to_string().repeat(title.chars().count())
+                ));
+            }
+            (Markdown::Paragraph(lines), Markup::Html) => {
+                let lines: Vec<String> = lines.iter().map(|l| inline(l, markup)).collect();
+                output.push_str(&format!("<p>{}</p>\n", lines.join("\n")));
+            }
+            (Markdown::Paragraph(lines), _) => {
+                let lines: Vec<String> = lines.iter().map(|l| inline(l, markup)).collect();
+                output.push_str(&format!("{}\n\n", lines.join("\n")));
+            }
+            (Markdown::List { ordered, items }, Markup::Html) => {
+                let tag = if ordered { "ol" } else { "ul" };
+                output.push_str(&format!("<{}>\n", tag));
+                for item in items {
+                    output.push_str(&format!("<li>{}</li>\n", inline(item, markup)));
+                }
+                output.push_str(&format!("</{}>\n", tag));
+            }
+            (Markdown::List { ordered, items }, _) => {
+                for (i, item) in items.iter().enumerate() {
+                    let bullet = match (markup, ordered) {
+                        (Markup::AsciiDoc, true) => ".".to_string(),
+                        (Markup::AsciiDoc, false) => "*".to_string(),
+                        (_, true) => format!("{}.", i + 1),
+                        (_, false) => "-".to_string(),
+                    };
+                    output.push_str(&format!("{} {}\n", bullet, inline(item, markup)));
+                }
+                output.push('\n');
+            }
+            (Markdown::Code(lines), Markup::Html) => output.push_str(&format!(
+                "<pre><code>{}</code></pre>\n",
+                escape_html(&lines.join("\n"))
+            )),
+            (Markdown::Code(lines), Markup::AsciiDoc) => {
+                output.push_str(&format!("----\n{}\n----\n\n", lines.join("\n")))
+            }
+            (Markdown::Code(lines), Markup::Org) => {
+                output.push_str(&format!("#+begin_src\n{}\n#+end_src\n\n", lines.join("\n")))
+            }
+            (Markdown::Code(lines), Markup::Text) => {
+                for line in lines {
+                    output.push_str(&format!("    {}\n", line));
+                }
+                output.push('\n');
+            }
+        }
+    }
+    output.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested_emphasis() {
+        assert_eq!(
+            inline("**bold *and* more**", Markup::Html),
+            "<strong>bold <em>and</em> more</strong>"
+        );
+        assert_eq!(
+            inline("*emphasis **with** bold*", Markup::Html),
+            "<em>emphasis <strong>with</strong> bold</em>"
+        );
+        assert_eq!(
+            inline("__bold `code` here__", Markup::Org),
+            "*bold ~code~ here*"
+        );
+        assert_eq!(
+            inline("**bold _and_ more**", Markup::AsciiDoc),
+            "*bold _and_ more*"
+        );
+        assert_eq!(inline("**bold *and* more**", Markup::Text), "bold and more");
+    }
+
+    #[test]
+    fn unterminated_markers_are_kept() {
+        for text in [
+            "**bold",
+            "*emphasis",
+            "_emphasis",
+            "`code",
+            "[label](url",
+            "*a**",
+        ] {
+            assert_eq!(inline(text, Markup::Text), text);
+        }
+        assert_eq!(inline("a * b * c", Markup::Html), "a * b * c");
+        assert_eq!(inline("**** and ____", Markup::Text), "**** and ____");
+        assert_eq!(inline("x < `y", Markup::Html), "x &lt; `y");
+    }
+
+    #[test]
+    fn links() {
+        let text = "see [the *docs*](https://example.com/a?b=1&c=2) now";
+        assert_eq!(
+            inline(text, Markup::Html),
+            "see <a href=\"https://example.com/a?b=1&amp;c=2\">the <em>docs</em></a> now"
+        );
+        assert_eq!(
+            inline("[a](u) and [b](v)", Markup::Org),
+            "[[u][a]] and [[v][b]]"
+        );
+        assert_eq!(inline("[a](u)", Markup::AsciiDoc), "u[a]");
+        assert_eq!(inline("[a](u)", Markup::Text), "a (u)");
+        // A bracket that is not a link does not swallow the next one.
+        assert_eq!(inline("[a] b [c](d)", Markup::Text), "[a] b c (d)");
+        assert_eq!(inline("[a]", Markup::Html), "[a]");
+    }
+
+    #[test]
+    fn underscores_inside_words_are_not_emphasis() {
+        assert_eq!(
+            inline("call snake_case_name()", Markup::Html),
+            "call snake_case_name()"
+        );
+        assert_eq!(
+            inline("_use snake_case_ now", Markup::Html),
+            "<em>use snake_case</em> now"
+        );
+        assert_eq!(inline("__init__", Markup::Text), "init");
+        // `*` still marks emphasis inside a word.
+        assert_eq!(
+            inline("un*frigging*believable", Markup::Html),
+            "un<em>frigging</em>believable"
+        );
+    }
+
+    #[test]
+    fn non_ascii_text() {
+        assert_eq!(
+            inline("café *très* bien — `naïve`", Markup::Html),
+            "café <em>très</em> bien — <code>naïve</code>"
+        );
+        assert_eq!(inline("größe_über_alles", Markup::Text), "größe_über_alles");
+        assert_eq!(
+            inline("[日本語](https://例え.jp)", Markup::Text),
+            "日本語 (https://例え.jp)"
+        );
+        assert_eq!(convert("## Größe", Markup::Text), "Größe\n=====");
+    }
+
+    #[test]
+    fn convert_blocks() {
+        let text = "# Summary\n\nFirst line\nsecond *line*\n\n- one\n- two\n\n1. first\n2) second\n\n```\nlet x = a < b;\n```";
+        assert_eq!(
+            convert(text, Markup::Html),
+            "<h1>Summary</h1>\n<p>First line\nsecond <em>line</em></p>\n<ul>\n<li>one</li>\n<li>two</li>\n</ul>\n<ol>\n<li>first</li>\n<li>second</li>\n</ol>\n<pre><code>let x = a &lt; b;</code></pre>"
+        );
+        assert_eq!(
+            convert(text, Markup::AsciiDoc),
+            "= Summary\n\nFirst line\nsecond _line_\n\n* one\n* two\n\n. first\n. second\n\n----\nlet x = a < b;\n----"
+        );
+        assert_eq!(
+            convert(text, Markup::Org),
+            "** Summary\n\nFirst line\nsecond /line/\n\n- one\n- two\n\n1. first\n2. second\n\n#+begin_src\nlet x = a < b;\n#+end_src"
+        );
+        assert_eq!(
+            convert(text, Markup::Text),
+            "Summary\n=======\n\nFirst line\nsecond line\n\n- one\n- two\n\n1. first\n2. second\n\n    let x = a < b;"
+        );
+    }
+
+    #[test]
+    fn unterminated_fence_keeps_the_code() {
+        assert_eq!(
+            convert("```\n*not* emphasis", Markup::Html),
+            "<pre><code>*not* emphasis</code></pre>"
+        );
+    }
+}
@@ -0,0 +1,84 @@
+//! Progress bars and spinners for slow steps. They draw to stderr and are
+//! hidden automatically when it is not a terminal; without the `tui` feature
+//! they do nothing.
+
+#[cfg(feature = "tui")]
+use indicatif::{ProgressBar, ProgressStyle};
+#[cfg(feature = "tui")]
+use std::time::Duration;
+
+pub struct Progress {
+    #[cfg(feature = "tui")]
+    bar: ProgressBar,
+}
+
+impl Progress {
+    /// A bar with `len` steps and an ETA, e.g. one step per commit.
+    pub fn bar(len: usize, message: &str) -> Self {
+        #[cfg(feature = "tui")]
+        {
+            let bar = ProgressBar::new(len as u64).with_message(message.to_string());
+            bar.set_style(
+                ProgressStyle::with_template("{msg} [{bar:40}] {pos}/{len} (ETA {eta})")
+                    .unwrap_or_else(|_| ProgressStyle::default_bar())
+                    .progress_chars("=> "),
+            );
+            Self { bar }
+        }
+        #[cfg(not(feature = "tui"))]
+        {
+            let _ = (len, message);
+            Self {}
+        }
+    }
+
+    /// A spinner with the elapsed time, for steps without a known length.
+    pub fn spinner(message: &str) -> Self {
+        #[cfg(feature = "tui")]
+        {
+            let bar = ProgressBar::new_spinner().with_message(message.to_string());
+            bar.set_style(
+                ProgressStyle::with_template("{spinner} {msg} ({elapsed})")
+                    .unwrap_or_else(|_| ProgressStyle::default_spinner()),
+            );
+            bar.enable_steady_tick(Duration::from_millis(100));
+            Self { bar }
+        }
+        #[cfg(not(feature = "tui"))]
+        {
+            let _ = message;
+            Self {}
+        }
+    }
+
+    /// A spinner with the number of steps so far, for counting steps without
+    /// a known total, e.g. commits as git lists them.
+    pub fn counter(message: &str) -> Self {
+        #[cfg(feature = "tui")]
+        {
+            let bar = ProgressBar::new_spinner().with_message(message.to_string());
+            bar.set_style(
+                ProgressStyle::with_template("{spinner} {msg}: {pos} ({elapsed})")
+                    .unwrap_or_else(|_| ProgressStyle::default_spinner()),
+            );
+            bar.enable_steady_tick(Duration::from_millis(100));
+            Self { bar }
+        }
+        #[cfg(not(feature = "tui"))]
+        {
+            let _ = message;
+            Self {}
+        }
+    }
+
+    pub fn inc(&self) {
+        #[cfg(feature = "tui")]
+        self.bar.inc(1);
+    }
+
+    /// Removes the bar, so regular output continues on a clean line.
+    pub fn finish(&self) {
+        #[cfg(feature = "tui")]
+        self.bar.finish_and_clear();
+    }
+}
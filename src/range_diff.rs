@@ -0,0 +1,197 @@
+use crate::{ai::Backend, error::Result, progress::Progress};
+
+/// How a patch changed between the two versions of a series.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    Unchanged,
+    Modified,
+    Dropped,
+    Added,
+}
+
+/// A patch's position in one version of the series and its abbreviated hash.
+pub struct Side {
+    pub position: usize,
+    pub hash: String,
+}
+
+/// One line of `git range-diff` output: a patch pairing and, for modified
+/// patches, the diff between the two versions of the patch.
+pub struct PatchPair {
+    pub old: Option<Side>,
+    pub new: Option<Side>,
+    pub change: Change,
+    pub subject: String,
+    /// Parts of the patch that differ, e.g. `Commit message` or `src/lib.rs`.
+    pub areas: Vec<String>,
+    /// The diff of the two patch versions, without git's indentation.
+    pub diff: Vec<String>,
+}
+
+/// Parses `git range-diff --no-color` output, whose pairing lines look like
+/// `2:  03847ad ! 2:  5e10590 Subject`, each followed by indented diff lines
+/// when the patch was modified.
+pub fn parse(output: &str) -> Vec<PatchPair> {
+    let mut pairs: Vec<PatchPair> = Vec::new();
+    let mut area: Option<String> = None;
+
+    for line in output.lines() {
+        if let Some(diff_line) = line.strip_prefix("    ") {
+            let Some(pair) = pairs.last_mut() else {
+                continue;
+            };
+            pair.diff.push(diff_line.to_string());
+
+            // Inner hunks are named after their section (`@@ Commit message`)
+            // and `## path ##` lines start each file of the patch.
+            let content = diff_line.get(1..).unwrap_or("").trim();
+            if let Some(name) = diff_line.strip_prefix("@@ ") {
+                area = Some(name.trim().to_string());
+            } else if let Some(name) = content
+                .strip_prefix("## ")
+                .and_then(|name| name.strip_suffix(" ##"))
+            {
+                area = Some(name.to_string());
+            }
+            if diff_line.starts_with(['+', '-']) {
+                if let Some(name) = &area {
+                    if !pair.areas.contains(name) {
+                        pair.areas.push(name.clone());
+                    }
+                }
+            }
+            continue;
+        }
+
+        if let Some(pair) = parse_pair(line) {
+            pairs.push(pair);
+            area = None;
+        }
+    }
+
+    pairs
+}
+
+fn parse_pair(line: &str) -> Option<PatchPair> {
+    let mut words = line.split_whitespace();
+    let old = side(words.next()?, words.next()?);
+    let change = match words.next()? {
+        "=" => Change::Unchanged,
+        "!" => Change::Modified,
+        "<" => Change::Dropped,
+        ">" => Change::Added,
+        _ => return None,
+    };
+    let new = side(words.next()?, words.next()?);
+    let subject = words.collect::<Vec<_>>().join(" ");
+
+    Some(PatchPair {
+        old,
+        new,
+        change,
+        subject,
+        areas: Vec::new(),
+        diff: Vec::new(),
+    })
+}
+
+/// `3:` and `27ad26d` become a side; git writes `-:  -------` for a patch
+/// missing from that version.
+fn side(position: &str, hash: &str) -> Option<Side> {
+    let position = position.strip_suffix(':')?.parse().ok()?;
+    Some(Side {
+        position,
+        hash: hash.to_string(),
+    })
+}
+
+fn label(side: &Option<Side>) -> String {
+    match side {
+        Some(side) => format!("{}: {}", side.position, side.hash),
+        None => "-".to_string(),
+    }
+}
+
+/// Explains the differences in plain text: counts first, then the added,
+/// dropped and modified patches with what changed in each.
+pub fn explain(old_range: &str, new_range: &str, pairs: &[PatchPair]) -> String {
+    let count = |change: Change| pairs.iter().filter(|p| p.change == change).count();
+    let old_count = pairs.iter().filter(|p| p.old.is_some()).count();
+    let new_count = pairs.iter().filter(|p| p.new.is_some()).count();
+
+    let mut report = String::new();
+    report.push_str("Range Diff\n");
+    report.push_str("==========\n\n");
+    report.push_str(&format!("Old: {} ({} patches)\n", old_range, old_count));
+    report.push_str(&format!("New: {} ({} patches)\n", new_range, new_count));
+    report.push_str(&format!(
+        "Unchanged: {}, modified: {}, dropped: {}, added: {}\n",
+        count(Change::Unchanged),
+        count(Change::Modified),
+        count(Change::Dropped),
+        count(Change::Added)
+    ));
+
+    let groups = [
+        (Change::Added, "Added patches"),
+        (Change::Dropped, "Dropped patches"),
+        (Change::Modified, "Modified patches"),
+    ];
+    for (change, title) in groups {
+        let group: Vec<&PatchPair> = pairs.iter().filter(|p| p.change == change).collect();
+        if group.is_empty() {
+            continue;
+        }
+
+        report.push_str(&format!("\n{} ({}):\n", title, group.len()));
+        for pair in group {
+            match change {
+                Change::Added => {
+                    report.push_str(&format!("  + {} {}\n", label(&pair.new), pair.subject))
+                }
+                Change::Dropped => {
+                    report.push_str(&format!("  - {} {}\n", label(&pair.old), pair.subject))
+                }
+                _ => {
+                    report.push_str(&format!(
+                        "  ~ {} -> {} {}\n",
+                        label(&pair.old),
+                        label(&pair.new),
+                        pair.subject
+                    ));
+                    if !pair.areas.is_empty() {
+                        report.push_str(&format!("    Changed: {}\n", pair.areas.join(", ")));
+                    }
+                    for line in &pair.diff {
+                        report.push_str(&format!("      {}\n", line));
+                    }
+                }
+            }
+        }
+    }
+
+    report
+}
+
+pub fn generate_ai_summary(explanation: &str, ai: &dyn Backend) -> Result<String> {
+    let prompt = format!(
+        "You are an assistant who reviews new versions of patch series.\n\
+        Below is a comparison of two versions of the same series made with git range-diff. \
+        Modified patches include the diff between their old and new version: lines starting \
+        with '-' were in the old version, lines starting with '+' are in the new one.\n\
+        Write a short changelog of the new version for reviewers:\n\
+        1. Start with one or two sentences on how the series changed overall.\n\
+        2. List added and dropped patches and, if it is apparent, why.\n\
+        3. For each modified patch, describe in one sentence what changed in it.\n\
+        4. Do not describe unchanged patches.\n\
+        \n\
+        {}",
+        explanation
+    );
+
+    let progress = Progress::spinner(&format!("Waiting for {}", ai.model()));
+    let response = ai.generate(&prompt, 0.3);
+    progress.finish();
+
+    response
+}
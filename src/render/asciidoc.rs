@@ -0,0 +1,353 @@
+use std::io::{self, Write};
+
+use crate::{
+    classify,
+    error::Result,
+    git::Commit,
+    postprocess::{self, Markup},
+    render::{written, Format, ReportRenderer},
+    report::{Block, Part, ReportData, Section},
+};
+
+pub fn render(data: &ReportData) -> String {
+    super::collect(|out| write(data, out))
+}
+
+/// AsciiDoc reports.
+pub struct Asciidoc;
+
+impl ReportRenderer for Asciidoc {
+    fn format(&self) -> Format {
+        Format::Asciidoc
+    }
+
+    fn write(&self, data: &ReportData, out: &mut dyn Write) -> Result<()> {
+        written(Format::Asciidoc, write(data, out))
+    }
+}
+
+/// Writes the document to `out` piece by piece, one commit at a time.
+pub fn write(data: &ReportData, out: &mut dyn Write) -> io::Result<()> {
+    let (from_commit, to_commit) = (&data.from_commit, &data.to_commit);
+
+    writeln!(out, "= {}", data.title())?;
+    if let Some(author) = &data.branding.author {
+        writeln!(out, ":author: {}", attribute(author))?;
+    }
+    writeln!(
+        out,
+        ":revdate: {}",
+        data.dates.format_with(&data.generated_at, "%Y-%m-%d")
+    )?;
+    writeln!(out, ":repository: {}", attribute(&data.repo_path))?;
+    writeln!(out, ":commit-from: {}", from_commit.hash)?;
+    writeln!(out, ":commit-to: {}", to_commit.hash)?;
+    writeln!(out, ":commit-count: {}", data.commits.len())?;
+    write!(out, ":toc:\n\n")?;
+    if let Some(header) = data.header() {
+        write!(out, "{}\n\n", banner(&header))?;
+    }
+
+    if data.shows(Part::Summary) {
+        out.write_all(summary(data).as_bytes())?;
+    }
+    if data.shows(Part::Stats) {
+        out.write_all(commit_types(data).as_bytes())?;
+    }
+    if let Some(summary) = data.visible_ai_summary() {
+        write!(out, "== {}\n\n", data.locale.text("ai-summary"))?;
+        if data.convert_ai_summary {
+            write!(
+                out,
+                "{}\n\n",
+                postprocess::convert(summary, Markup::AsciiDoc)
+            )?;
+        } else {
+            write!(out, "....\n{}\n....\n\n", summary.trim())?;
+        }
+    } else if data.lists_commits() {
+        commits(data, out)?;
+    }
+    if data.shows(Part::Stats) {
+        for section in &data.sections {
+            out.write_all(render_section(section).as_bytes())?;
+        }
+    }
+    if let Some(footer) = data.footer() {
+        write!(out, "'''\n\n{}\n", banner(&footer))?;
+    }
+
+    Ok(())
+}
+
+/// Banner text as a paragraph with hard line breaks.
+fn banner(text: &str) -> String {
+    text.lines()
+        .map(|line| inline(line.trim_end()))
+        .collect::<Vec<_>>()
+        .join(" +\n")
+}
+
+fn summary(data: &ReportData) -> String {
+    let (from_commit, to_commit) = (&data.from_commit, &data.to_commit);
+    let mut doc = format!("== {}\n\n", data.locale.text("summary"));
+
+    doc.push_str("[cols=\"1h,3\"]\n|===\n");
+    for (label, value) in data.branding_fields() {
+        doc.push_str(&format!("|{} |{}\n", label, cell(value)));
+    }
+    doc.push_str(&format!(
+        "|{} |{}\n",
+        data.locale.text("repository"),
+        cell(&data.repo_path)
+    ));
+    doc.push_str(&format!(
+        "|{} |{}\n",
+        data.locale.text("generated"),
+        data.dates.format(&data.generated_at)
+    ));
+    doc.push_str(&format!(
+        "|{} |{} (`{}`)\n",
+        data.locale.text("from"),
+        cell(&from_commit.subject),
+        from_commit.hash
+    ));
+    doc.push_str(&format!(
+        "|{} |{} (`{}`)\n",
+        data.locale.text("to"),
+        cell(&to_commit.subject),
+        to_commit.hash
+    ));
+    doc.push_str(&format!(
+        "|{} |{}\n",
+        data.locale.text("date-range"),
+        data.date_range()
+    ));
+    if let Some(releases) = data.releases() {
+        doc.push_str(&format!(
+            "|{} |{}\n",
+            data.locale.text("releases"),
+            cell(&releases)
+        ));
+    }
+    doc.push_str(&format!(
+        "|{} |{}\n",
+        data.locale.text("lines-changed"),
+        data.lines_changed()
+    ));
+    doc.push_str(&format!(
+        "|{} |{}\n",
+        data.locale.text("total-commits"),
+        data.commits.len()
+    ));
+    doc.push_str("|===\n\n");
+
+    doc
+}
+
+fn commit_types(data: &ReportData) -> String {
+    if data.commits.is_empty() {
+        return String::new();
+    }
+
+    let mut doc = format!(".{}\n", data.locale.text("commit-types"));
+    doc.push_str(&render_block(&classify::breakdown(
+        &data.commits,
+        &data.locale,
+    )));
+    doc
+}
+
+fn commits(data: &ReportData, out: &mut dyn Write) -> io::Result<()> {
+    write!(out, "== {}\n\n", data.locale.text("detailed-commits"))?;
+
+    // Each commit is wrapped in tag markers so other documents can pull
+    // in a single entry with `include::report.adoc[tag=commit-<hash>]`.
+    for (i, commit) in data.detailed_commits().into_iter().enumerate() {
+        let short_hash = &commit.hash[..8];
+        let mut entry = format!("// tag::commit-{}[]\n", short_hash);
+        entry.push_str(&format!("[[commit-{}]]\n", short_hash));
+        entry.push_str(&format!("=== {}. {}\n\n", i + 1, inline(&commit.subject)));
+        if data.shows(Part::Details) {
+            entry.push_str(&details(data, commit));
+        }
+        if data.shows(Part::Files) {
+            entry.push_str(&files(data, commit));
+        }
+        entry.push_str(&format!("// end::commit-{}[]\n\n", short_hash));
+        out.write_all(entry.as_bytes())?;
+    }
+
+    Ok(())
+}
+
+fn details(data: &ReportData, commit: &Commit) -> String {
+    let mut doc = String::new();
+
+    doc.push_str(&format!(
+        "{}:: `{}`\n",
+        data.locale.text("hash"),
+        commit.hash
+    ));
+    doc.push_str(&format!(
+        "{}:: {}\n",
+        data.locale.text("author"),
+        inline(&commit.author)
+    ));
+    doc.push_str(&format!(
+        "{}:: {}\n",
+        data.locale.text("date"),
+        data.dates.format(&commit.date)
+    ));
+    for (key, refs) in [("tags", &commit.tags), ("branches", &commit.branches)] {
+        if !refs.is_empty() {
+            let names: Vec<String> = refs.iter().map(|name| inline(name)).collect();
+            doc.push_str(&format!(
+                "{}:: {}\n",
+                data.locale.text(key),
+                names.join(", ")
+            ));
+        }
+    }
+    if !commit.labels.is_empty() {
+        let chips: Vec<String> = commit
+            .labels
+            .iter()
+            .map(|label| format!("[.label]#{}#", inline(label)))
+            .collect();
+        doc.push_str(&format!(
+            "{}:: {}\n",
+            data.locale.text("labels"),
+            chips.join(" ")
+        ));
+    }
+    if let Some(signature) = &commit.signature {
+        doc.push_str(&format!(
+            "{}:: {}\n",
+            data.locale.text("signature"),
+            inline(&signature.describe())
+        ));
+    }
+    if let Some(score) = data.impact.get(&commit.hash) {
+        doc.push_str(&format!("{}:: {}\n", data.locale.text("impact"), score));
+    }
+    for (label, url) in data.links(commit) {
+        doc.push_str(&format!("{}:: {}[]\n", inline(label), url));
+    }
+    if let Some(summary) = data.commit_summaries.get(&commit.hash) {
+        doc.push_str(&format!(
+            "{}:: {}\n",
+            data.locale.text("ai-summary"),
+            inline(summary)
+        ));
+    }
+    doc.push('\n');
+
+    if !commit.body.trim().is_empty() {
+        doc.push_str(&format!("....\n{}\n....\n\n", commit.body.trim()));
+    }
+    if let Some(translation) = data.translations.get(&commit.hash) {
+        doc.push_str(&format!(
+            ".{}\n....\n{}\n....\n\n",
+            data.translation_label(translation),
+            translation.text.trim()
+        ));
+    }
+
+    doc
+}
+
+fn files(data: &ReportData, commit: &Commit) -> String {
+    let mut doc = String::new();
+
+    if !commit.file_stats.is_empty() {
+        doc.push_str(&format!(".{}\n", data.locale.text("files-changed")));
+        for stat in &commit.file_stats {
+            match &stat.old_path {
+                Some(old_path) => doc.push_str(&format!("* `+{}+` -> `+{}+`", old_path, stat.path)),
+                None => doc.push_str(&format!("* `+{}+`", stat.path)),
+            }
+            for marker in stat.markers() {
+                doc.push_str(&format!(" [{}]", marker));
+            }
+            doc.push('\n');
+        }
+        doc.push('\n');
+    }
+
+    doc
+}
+
+pub fn render_section(section: &Section) -> String {
+    let mut doc = format!("[[{}]]\n== {}\n\n", section.id, inline(&section.title));
+
+    for block in &section.blocks {
+        doc.push_str(&render_block(block));
+    }
+
+    doc
+}
+
+fn render_block(block: &Block) -> String {
+    let mut doc = String::new();
+    match block {
+        Block::Fields(fields) => {
+            for (label, value) in fields {
+                doc.push_str(&format!("{}:: {}\n", label, inline(value)));
+            }
+            doc.push('\n');
+        }
+        Block::List { title, items } => {
+            doc.push_str(&format!(".{}\n", title));
+            for item in items {
+                doc.push_str(&format!("* {}\n", inline(item)));
+            }
+            doc.push('\n');
+        }
+        Block::Table { headers, rows } => {
+            doc.push_str("[%header]\n|===\n");
+            let header: Vec<String> = headers.iter().map(|h| cell(h)).collect();
+            doc.push_str(&format!("|{}\n", header.join(" |")));
+            for row in rows {
+                let cells: Vec<String> = row.iter().map(|c| cell(c)).collect();
+                doc.push_str(&format!("|{}\n", cells.join(" |")));
+            }
+            doc.push_str("|===\n\n");
+        }
+        Block::Text(text) => {
+            doc.push_str(&inline(text.trim()));
+            doc.push_str("\n\n");
+        }
+        Block::Code(code) => {
+            doc.push_str(&format!("....\n{}\n....\n\n", code.trim_end()));
+        }
+        // Drawn by asciidoctor-diagram, shown as a literal block without it.
+        Block::Mermaid(diagram) => {
+            doc.push_str(&format!(
+                "[mermaid]\n....\n{}\n....\n\n",
+                diagram.trim_end()
+            ));
+        }
+    }
+
+    doc
+}
+
+/// Wraps text containing characters like `*`, `_` or `#` in a passthrough so
+/// commit subjects are not interpreted as AsciiDoc formatting.
+fn inline(text: &str) -> String {
+    if text.contains(['*', '_', '`', '#', '^', '~', '+', '{', '[', ']']) {
+        format!("pass:c[{}]", text.replace(']', "\\]"))
+    } else {
+        text.to_string()
+    }
+}
+
+fn cell(text: &str) -> String {
+    inline(text).replace('|', "\\|")
+}
+
+/// Attribute values must stay on one line.
+fn attribute(text: &str) -> String {
+    text.replace('\n', " ")
+}
@@ -0,0 +1,501 @@
+use std::io::{self, Write};
+
+use serde_json::{json, Value};
+
+use crate::{
+    classify,
+    error::Result,
+    git::Commit,
+    postprocess::{self, Markup},
+    render::{written, Format, ReportRenderer},
+    report::{Block, Part, ReportData, Section, Theme},
+    version,
+};
+
+const STYLE: &str = "body{font-family:-apple-system,Segoe UI,Helvetica,Arial,sans-serif;max-width:960px;margin:2rem auto;padding:0 1rem;color:#1f2328;line-height:1.5}\
+h1,h2{border-bottom:1px solid #d0d7de;padding-bottom:.3rem}\
+code,pre{font-family:ui-monospace,Menlo,Consolas,monospace;font-size:.9em}\
+pre{background:#f6f8fa;padding:.75rem;border-radius:6px;white-space:pre-wrap}\
+table{border-collapse:collapse;margin:1rem 0}\
+th,td{border:1px solid #d0d7de;padding:.3rem .6rem;text-align:left}\
+th{background:#f6f8fa}\
+dl{display:grid;grid-template-columns:max-content auto;gap:.2rem 1rem}\
+dt{font-weight:600}\
+dd{margin:0}\
+.banner{color:#57606a;font-size:.9em;border:1px solid #d0d7de;border-radius:6px;padding:.5rem .75rem;margin:1rem 0}\
+.file-marker{font-size:.8em;color:#57606a;border:1px solid #d0d7de;border-radius:1em;padding:0 .4em}\
+.label{font-size:.85em;font-weight:600;color:#0550ae;background:#ddf4ff;border:1px solid #54aeff;border-radius:1em;padding:0 .5em}\
+#timeline{width:100%;height:auto;border:1px solid #d0d7de;border-radius:6px;cursor:grab;user-select:none}\
+.tl-axis{stroke:#8c959f}\
+.tl-label{font-size:11px;fill:#57606a}\
+.tl-commit{fill:#0969da;fill-opacity:.75;stroke:#fff}\
+.tl-release{stroke:#bf8700;stroke-dasharray:3 2}\
+.tl-release-label{font-size:11px;font-weight:600;fill:#9a6700}\
+.tl-commit:hover,.tl-commit:focus{fill:#cf222e;fill-opacity:1;stroke:#1f2328;stroke-width:2;outline:none}\
+:focus-visible{outline:2px solid #0969da;outline-offset:2px}\
+.tl-help{color:#57606a;font-size:.85em;margin:.2rem 0}\
+#timeline-tip{position:absolute;display:none;max-width:360px;background:#fff;border:1px solid #d0d7de;border-radius:6px;padding:.4rem .6rem;box-shadow:0 3px 12px rgba(0,0,0,.15);font-size:.85em;pointer-events:none}";
+
+/// Overrides of [`STYLE`] for `--high-contrast`, also applied when the
+/// reader's system prefers more contrast.
+const HIGH_CONTRAST: &str = "body{background:#000;color:#fff}\
+h1,h2,th,td,#timeline{border-color:#fff}\
+pre,th{background:#1a1a1a;color:#fff}\
+a{color:#ff0}\
+.banner,.file-marker,.tl-help{color:#fff;border-color:#fff}\
+.label{color:#000;background:#ff0;border-color:#ff0}\
+.tl-axis{stroke:#fff}\
+.tl-label{fill:#fff}\
+.tl-commit{fill:#0ff;fill-opacity:1;stroke:#000}\
+.tl-release{stroke:#ff0}\
+.tl-release-label{fill:#ff0}\
+.tl-commit:hover,.tl-commit:focus{fill:#ff0;stroke:#fff}\
+:focus-visible{outline:3px solid #ff0}\
+#timeline-tip{background:#000;color:#fff;border:2px solid #fff}";
+
+/// Banner text with its line breaks kept.
+fn banner(text: &str) -> String {
+    text.lines().map(escape).collect::<Vec<_>>().join("<br>\n")
+}
+
+/// Draws the commit timeline from the JSON written by [`timeline`].
+const TIMELINE_SCRIPT: &str = include_str!("timeline.js");
+
+/// Where `<pre class="mermaid">` diagrams are drawn from: an exact release
+/// in its single-file build, so the page always runs the same script.
+const MERMAID_URL: &str = "https://cdn.jsdelivr.net/npm/mermaid@11.4.1/dist/mermaid.min.js";
+
+pub fn render(data: &ReportData) -> String {
+    super::collect(|out| write(data, out))
+}
+
+/// A standalone HTML page reports.
+pub struct Html;
+
+impl ReportRenderer for Html {
+    fn format(&self) -> Format {
+        Format::Html
+    }
+
+    fn write(&self, data: &ReportData, out: &mut dyn Write) -> Result<()> {
+        written(Format::Html, write(data, out))
+    }
+}
+
+/// Writes the page to `out` piece by piece, one commit at a time.
+pub fn write(data: &ReportData, out: &mut dyn Write) -> io::Result<()> {
+    if let Some(front_matter) = data.front_matter() {
+        out.write_all(front_matter.as_bytes())?;
+    }
+
+    write!(
+        out,
+        "<!DOCTYPE html>\n<html lang=\"{}\">\n<head>\n<meta charset=\"utf-8\">\n",
+        escape(data.locale.code())
+    )?;
+    writeln!(out, "<title>{}</title>", escape(data.title()))?;
+    if let Some(author) = &data.branding.author {
+        writeln!(out, "<meta name=\"author\" content=\"{}\">", escape(author))?;
+    }
+    write!(out, "<style>{}", STYLE)?;
+    match data.theme {
+        Theme::Default => write!(out, "@media (prefers-contrast:more){{{}}}", HIGH_CONTRAST)?,
+        Theme::HighContrast => out.write_all(HIGH_CONTRAST.as_bytes())?,
+    }
+    write!(out, "</style>\n</head>\n<body>\n")?;
+    if let Some(header) = data.header() {
+        writeln!(out, "<header class=\"banner\">{}</header>", banner(&header))?;
+    }
+    write!(out, "<main>\n<h1>{}</h1>\n", escape(data.title()))?;
+
+    if data.shows(Part::Summary) {
+        out.write_all(summary(data).as_bytes())?;
+        out.write_all(timeline(data).as_bytes())?;
+    }
+    if data.shows(Part::Stats) {
+        out.write_all(commit_types(data).as_bytes())?;
+    }
+    if let Some(summary) = data.visible_ai_summary() {
+        writeln!(out, "<h2>{}</h2>", escape(data.locale.text("ai-summary")))?;
+        if data.convert_ai_summary {
+            writeln!(out, "{}", postprocess::convert(summary, Markup::Html))?;
+        } else {
+            writeln!(out, "<pre>{}</pre>", escape(summary.trim()))?;
+        }
+    } else if data.lists_commits() {
+        commits(data, out)?;
+    }
+    if data.shows(Part::Stats) {
+        for section in &data.sections {
+            out.write_all(render_section(section).as_bytes())?;
+        }
+        if data.mermaid_cdn
+            && data
+                .sections
+                .iter()
+                .flat_map(|section| &section.blocks)
+                .any(|block| matches!(block, Block::Mermaid(_)))
+        {
+            out.write_all(mermaid_script(data.theme).as_bytes())?;
+        }
+    }
+
+    writeln!(out, "</main>")?;
+
+    if let Some(footer) = data.footer() {
+        writeln!(out, "<footer class=\"banner\">{}</footer>", banner(&footer))?;
+    }
+    write!(out, "</body>\n</html>\n")
+}
+
+fn summary(data: &ReportData) -> String {
+    let (from_commit, to_commit) = (&data.from_commit, &data.to_commit);
+    let mut html = String::from("<dl>\n");
+    for (label, value) in data.branding_fields() {
+        html.push_str(&field(label, &escape(value)));
+    }
+
+    html.push_str(&field(
+        data.locale.text("repository"),
+        &escape(&data.repo_path),
+    ));
+    html.push_str(&field(
+        data.locale.text("generated"),
+        &escape(&data.dates.format(&data.generated_at)),
+    ));
+    html.push_str(&field(
+        data.locale.text("commit-range"),
+        &format!(
+            "<code>{}</code> &rarr; <code>{}</code>",
+            from_commit.hash, to_commit.hash
+        ),
+    ));
+    html.push_str(&field(
+        data.locale.text("total-commits"),
+        &data.commits.len().to_string(),
+    ));
+    html.push_str("</dl>\n");
+
+    html.push_str(&format!(
+        "<h2>{}</h2>\n<dl>\n",
+        escape(data.locale.text("summary"))
+    ));
+    html.push_str(&field(
+        data.locale.text("from"),
+        &format!(
+            "{} (<code>{}</code>)",
+            escape(&from_commit.subject),
+            from_commit.hash
+        ),
+    ));
+    html.push_str(&field(
+        data.locale.text("to"),
+        &format!(
+            "{} (<code>{}</code>)",
+            escape(&to_commit.subject),
+            to_commit.hash
+        ),
+    ));
+    html.push_str(&field(
+        data.locale.text("date-range"),
+        &escape(&data.date_range()),
+    ));
+    if let Some(releases) = data.releases() {
+        html.push_str(&field(data.locale.text("releases"), &escape(&releases)));
+    }
+    html.push_str(&field(
+        data.locale.text("lines-changed"),
+        &escape(&data.lines_changed()),
+    ));
+    html.push_str("</dl>\n");
+
+    html
+}
+
+/// Loads Mermaid to draw the diagrams of the page (`--mermaid-cdn`). It is the
+/// only script the page fetches, so without network access the diagram
+/// sources stay visible. It is fetched without cookies or a referrer, so the
+/// CDN does not learn which report is opened.
+fn mermaid_script(theme: Theme) -> String {
+    let theme = match theme {
+        Theme::Default => "default",
+        Theme::HighContrast => "dark",
+    };
+    format!(
+        "<script src=\"{}\" crossorigin=\"anonymous\" referrerpolicy=\"no-referrer\"></script>\n<script>\nmermaid.initialize({{ startOnLoad: true, theme: \"{}\" }});\n</script>\n",
+        MERMAID_URL, theme
+    )
+}
+
+/// A zoomable timeline of the range with one dot per commit, sized by its
+/// changed lines, and a marker with the tag at every release. The commits
+/// are embedded as JSON for the script; the chart is labelled with the range
+/// for screen readers, and every dot with its commit.
+fn timeline(data: &ReportData) -> String {
+    if data.commits.is_empty() {
+        return String::new();
+    }
+
+    let points: Vec<Value> = data
+        .commits
+        .iter()
+        .map(|commit| {
+            json!({
+                "time": commit.date.timestamp_millis(),
+                "date": data.dates.format(&commit.date),
+                "short": &commit.hash[..8],
+                "subject": commit.subject,
+                "author": commit.author,
+                "files": commit.files_changed.len(),
+                "added": commit.file_stats.iter().map(|f| f.additions).sum::<u64>(),
+                "deleted": commit.file_stats.iter().map(|f| f.deletions).sum::<u64>(),
+                "tags": commit.tags,
+                "releases": commit.tags.iter().filter(|tag| version::is_release_tag(tag)).collect::<Vec<_>>(),
+            })
+        })
+        .collect();
+    // `<` only occurs inside JSON strings, and escaping it keeps `</script>`
+    // in a subject from closing the element.
+    let points = serde_json::to_string(&points)
+        .expect("timeline is valid JSON")
+        .replace('<', "\\u003c");
+
+    format!(
+        "<h3>{}</h3>\n\
+        <p class=\"tl-help\" id=\"timeline-help\">{}</p>\n\
+        <svg id=\"timeline\" role=\"group\" aria-label=\"{}\" aria-describedby=\"timeline-help\"></svg>\n\
+        <div id=\"timeline-tip\" role=\"tooltip\"></div>\n\
+        <script type=\"application/json\" id=\"timeline-data\">{}</script>\n\
+        <script>\n{}</script>\n",
+        escape(data.locale.text("timeline")),
+        escape(data.locale.text("timeline-help")),
+        escape(&data.locale.format(
+            "timeline-label",
+            &[
+                ("count", &data.commits.len().to_string()),
+                ("start", &data.dates.format(&data.from_commit.date)),
+                ("end", &data.dates.format(&data.to_commit.date)),
+            ]
+        )),
+        points,
+        TIMELINE_SCRIPT
+    )
+}
+
+fn commit_types(data: &ReportData) -> String {
+    if data.commits.is_empty() {
+        return String::new();
+    }
+
+    // A subheading of the summary, or a heading of its own without it.
+    let level = if data.shows(Part::Summary) { 3 } else { 2 };
+    let mut html = format!(
+        "<h{0}>{1}</h{0}>\n",
+        level,
+        escape(data.locale.text("commit-types"))
+    );
+    html.push_str(&render_block(&classify::breakdown(
+        &data.commits,
+        &data.locale,
+    )));
+    html
+}
+
+fn commits(data: &ReportData, out: &mut dyn Write) -> io::Result<()> {
+    writeln!(
+        out,
+        "<h2>{}</h2>",
+        escape(data.locale.text("detailed-commits"))
+    )?;
+
+    for (i, commit) in data.detailed_commits().into_iter().enumerate() {
+        let mut entry = format!(
+            "<section>\n<h3>{}. {}</h3>\n",
+            i + 1,
+            escape(&commit.subject)
+        );
+        if data.shows(Part::Details) {
+            entry.push_str(&details(data, commit));
+        }
+        if data.shows(Part::Files) {
+            entry.push_str(&files(data, commit));
+        }
+        entry.push_str("</section>\n");
+        out.write_all(entry.as_bytes())?;
+    }
+
+    Ok(())
+}
+
+fn details(data: &ReportData, commit: &Commit) -> String {
+    let mut html = String::from("<dl>\n");
+
+    html.push_str(&field(
+        data.locale.text("hash"),
+        &format!("<code>{}</code>", commit.hash),
+    ));
+    html.push_str(&field(data.locale.text("author"), &escape(&commit.author)));
+    html.push_str(&field(
+        data.locale.text("date"),
+        &escape(&data.dates.format(&commit.date)),
+    ));
+    for (key, refs) in [("tags", &commit.tags), ("branches", &commit.branches)] {
+        if !refs.is_empty() {
+            let names: Vec<String> = refs
+                .iter()
+                .map(|name| format!("<code>{}</code>", escape(name)))
+                .collect();
+            html.push_str(&field(data.locale.text(key), &names.join(" ")));
+        }
+    }
+    if !commit.labels.is_empty() {
+        let chips: Vec<String> = commit
+            .labels
+            .iter()
+            .map(|label| format!("<span class=\"label\">{}</span>", escape(label)))
+            .collect();
+        html.push_str(&field(data.locale.text("labels"), &chips.join(" ")));
+    }
+    if let Some(signature) = &commit.signature {
+        html.push_str(&field(
+            data.locale.text("signature"),
+            &escape(&signature.describe()),
+        ));
+    }
+    if let Some(score) = data.impact.get(&commit.hash) {
+        html.push_str(&field(data.locale.text("impact"), &score.to_string()));
+    }
+    for (label, url) in data.links(commit) {
+        html.push_str(&field(
+            &escape(label),
+            &format!("<a href=\"{0}\">{0}</a>", escape(&url)),
+        ));
+    }
+    if let Some(summary) = data.commit_summaries.get(&commit.hash) {
+        html.push_str(&field(data.locale.text("ai-summary"), &escape(summary)));
+    }
+    html.push_str("</dl>\n");
+
+    if !commit.body.trim().is_empty() {
+        html.push_str(&format!("<pre>{}</pre>\n", escape(commit.body.trim())));
+    }
+    if let Some(translation) = data.translations.get(&commit.hash) {
+        html.push_str(&format!(
+            "<p>{}:</p>\n<pre>{}</pre>\n",
+            escape(&data.translation_label(translation)),
+            escape(translation.text.trim())
+        ));
+    }
+
+    html
+}
+
+fn files(data: &ReportData, commit: &Commit) -> String {
+    let mut html = String::new();
+
+    if !commit.file_stats.is_empty() {
+        html.push_str(&format!(
+            "<p>{}:</p>\n<ul>\n",
+            escape(data.locale.text("files-changed"))
+        ));
+        for stat in &commit.file_stats {
+            html.push_str("<li>");
+            if let Some(old_path) = &stat.old_path {
+                html.push_str(&format!("<code>{}</code> -&gt; ", escape(old_path)));
+            }
+            html.push_str(&format!("<code>{}</code>", escape(&stat.path)));
+            for marker in stat.markers() {
+                html.push_str(&format!(" <span class=\"file-marker\">{}</span>", marker));
+            }
+            html.push_str("</li>\n");
+        }
+        html.push_str("</ul>\n");
+    }
+
+    html
+}
+
+pub fn render_section(section: &Section) -> String {
+    let mut html = format!(
+        "<section id=\"{}\">\n<h2>{}</h2>\n",
+        escape(&section.id),
+        escape(&section.title)
+    );
+
+    for block in &section.blocks {
+        html.push_str(&render_block(block));
+    }
+
+    html.push_str("</section>\n");
+
+    html
+}
+
+fn render_block(block: &Block) -> String {
+    let mut html = String::new();
+    match block {
+        Block::Fields(fields) => {
+            html.push_str("<dl>\n");
+            for (label, value) in fields {
+                html.push_str(&field(&escape(label), &escape(value)));
+            }
+            html.push_str("</dl>\n");
+        }
+        Block::List { title, items } => {
+            html.push_str(&format!("<p>{}:</p>\n<ul>\n", escape(title)));
+            for item in items {
+                html.push_str(&format!("<li>{}</li>\n", escape(item)));
+            }
+            html.push_str("</ul>\n");
+        }
+        Block::Table { headers, rows } => {
+            html.push_str("<table>\n<thead>\n<tr>");
+            for header in headers {
+                html.push_str(&format!("<th scope=\"col\">{}</th>", escape(header)));
+            }
+            html.push_str("</tr>\n</thead>\n<tbody>\n");
+            for row in rows {
+                html.push_str("<tr>");
+                for cell in row {
+                    html.push_str(&format!("<td>{}</td>", escape(cell)));
+                }
+                html.push_str("</tr>\n");
+            }
+            html.push_str("</tbody>\n</table>\n");
+        }
+        Block::Text(text) => {
+            html.push_str(&format!("<p>{}</p>\n", escape(text)));
+        }
+        Block::Code(code) => {
+            html.push_str(&format!("<pre>{}</pre>\n", escape(code.trim_end())));
+        }
+        Block::Mermaid(diagram) => {
+            html.push_str(&format!(
+                "<pre class=\"mermaid\">{}</pre>\n",
+                escape(diagram.trim_end())
+            ));
+        }
+    }
+
+    html
+}
+
+/// A `<dt>`/`<dd>` pair; `value` must already be escaped.
+fn field(label: &str, value: &str) -> String {
+    format!("<dt>{}</dt><dd>{}</dd>\n", label, value)
+}
+
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,149 @@
+use chrono::{Duration, NaiveDate};
+use std::{
+    collections::BTreeMap,
+    io::{self, Write},
+};
+
+use crate::{
+    error::Result,
+    git::Commit,
+    render::{written, Format, ReportRenderer},
+    report::ReportData,
+};
+
+/// Subjects listed in an event before the rest are only counted.
+const TOP_SUBJECTS: usize = 5;
+
+pub fn render(data: &ReportData) -> String {
+    super::collect(|out| write(data, out))
+}
+
+/// An iCalendar file reports.
+pub struct Ics;
+
+impl ReportRenderer for Ics {
+    fn format(&self) -> Format {
+        Format::Ics
+    }
+
+    fn write(&self, data: &ReportData, out: &mut dyn Write) -> Result<()> {
+        written(Format::Ics, write(data, out))
+    }
+}
+
+/// Writes an iCalendar file with one all-day event per day with commits,
+/// giving the commit count, the authors (most active first) and the first
+/// subjects. Days follow `--timezone`.
+pub fn write(data: &ReportData, out: &mut dyn Write) -> io::Result<()> {
+    let mut days: BTreeMap<String, Vec<&Commit>> = BTreeMap::new();
+    for commit in &data.commits {
+        let day = data.dates.format_with(&commit.date, "%Y-%m-%d");
+        days.entry(day).or_default().push(commit);
+    }
+    let stamp = data.generated_at.format("%Y%m%dT%H%M%SZ").to_string();
+
+    line(out, "BEGIN:VCALENDAR")?;
+    line(out, "VERSION:2.0")?;
+    line(
+        out,
+        concat!(
+            "PRODID:-//git-report//git-report ",
+            env!("CARGO_PKG_VERSION"),
+            "//EN"
+        ),
+    )?;
+    line(out, "CALSCALE:GREGORIAN")?;
+    line(out, &format!("X-WR-CALNAME:{}", text(&data.repo_path)))?;
+
+    for (day, commits) in &days {
+        let Ok(date) = NaiveDate::parse_from_str(day, "%Y-%m-%d") else {
+            continue;
+        };
+        line(out, "BEGIN:VEVENT")?;
+        line(
+            out,
+            &format!(
+                "UID:{}-{}@git-report",
+                date.format("%Y%m%d"),
+                data.to_commit.hash
+            ),
+        )?;
+        line(out, &format!("DTSTAMP:{}", stamp))?;
+        line(
+            out,
+            &format!("DTSTART;VALUE=DATE:{}", date.format("%Y%m%d")),
+        )?;
+        let end = date + Duration::days(1);
+        line(out, &format!("DTEND;VALUE=DATE:{}", end.format("%Y%m%d")))?;
+        line(out, &format!("SUMMARY:{}", text(&summary(data, commits))))?;
+        line(
+            out,
+            &format!("DESCRIPTION:{}", text(&description(data, commits))),
+        )?;
+        line(out, "TRANSP:TRANSPARENT")?;
+        line(out, "END:VEVENT")?;
+    }
+
+    line(out, "END:VCALENDAR")
+}
+
+/// The commit count and authors of one day.
+fn summary(data: &ReportData, commits: &[&Commit]) -> String {
+    let mut authors: BTreeMap<&str, usize> = BTreeMap::new();
+    for commit in commits {
+        *authors.entry(&commit.author).or_default() += 1;
+    }
+    let mut authors: Vec<(&str, usize)> = authors.into_iter().collect();
+    authors.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    let authors: Vec<&str> = authors.into_iter().map(|(author, _)| author).collect();
+
+    data.locale.format(
+        "calendar-summary",
+        &[
+            ("count", &commits.len().to_string()),
+            ("authors", &authors.join(", ")),
+        ],
+    )
+}
+
+/// The first subjects of one day, with a count of the rest.
+fn description(data: &ReportData, commits: &[&Commit]) -> String {
+    let mut lines: Vec<String> = commits
+        .iter()
+        .take(TOP_SUBJECTS)
+        .map(|commit| format!("- {} ({})", commit.subject, &commit.hash[..8]))
+        .collect();
+    if commits.len() > TOP_SUBJECTS {
+        lines.push(data.locale.format(
+            "calendar-more",
+            &[("count", &(commits.len() - TOP_SUBJECTS).to_string())],
+        ));
+    }
+    lines.join("\n")
+}
+
+/// Escapes a TEXT value (RFC 5545, 3.3.11).
+fn text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Writes a content line, folded after 75 octets without splitting
+/// characters, with the CRLF line ending iCalendar requires.
+fn line(out: &mut dyn Write, content: &str) -> io::Result<()> {
+    let mut folded = String::with_capacity(content.len() + 2);
+    let mut width = 0;
+    for ch in content.chars() {
+        if width + ch.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(ch);
+        width += ch.len_utf8();
+    }
+    folded.push_str("\r\n");
+    out.write_all(folded.as_bytes())
+}
@@ -0,0 +1,217 @@
+use serde_json::{json, Map, Value};
+use std::io::{self, Write};
+
+use crate::{
+    classify,
+    error::Result,
+    render::{written, Format, ReportRenderer},
+    report::{Block, Part, ReportData, Section},
+};
+
+pub fn render(data: &ReportData) -> String {
+    super::collect(|out| write(data, out))
+}
+
+/// JSON reports.
+pub struct Json;
+
+impl ReportRenderer for Json {
+    fn format(&self) -> Format {
+        Format::Json
+    }
+
+    fn write(&self, data: &ReportData, out: &mut dyn Write) -> Result<()> {
+        written(Format::Json, write(data, out))
+    }
+}
+
+/// Writes the selected parts as top-level keys; `repository` and
+/// `generated_at` are always present, `title`, `author`, `project`, `header`
+/// and `footer` when configured.
+pub fn write(data: &ReportData, out: &mut dyn Write) -> io::Result<()> {
+    let mut report = Map::new();
+    report.insert("repository".to_string(), json!(data.repo_path));
+    report.insert(
+        "generated_at".to_string(),
+        json!(data.generated_at.to_rfc3339()),
+    );
+
+    for (key, value) in [
+        ("title", &data.branding.title),
+        ("author", &data.branding.author),
+        ("project", &data.branding.project),
+    ] {
+        if let Some(value) = value {
+            report.insert(key.to_string(), json!(value));
+        }
+    }
+    if let Some(header) = data.header() {
+        report.insert("header".to_string(), json!(header));
+    }
+    if let Some(footer) = data.footer() {
+        report.insert("footer".to_string(), json!(footer));
+    }
+
+    if data.shows(Part::Summary) {
+        report.insert("from".to_string(), json!(data.from_commit.hash));
+        report.insert("to".to_string(), json!(data.to_commit.hash));
+        report.insert("total_commits".to_string(), json!(data.commits.len()));
+        let lines = data.line_changes();
+        report.insert(
+            "lines".to_string(),
+            json!({
+                "added": lines.added,
+                "deleted": lines.deleted,
+                "code": { "added": lines.code_added(), "deleted": lines.code_deleted() },
+                "tests": { "added": lines.test_added, "deleted": lines.test_deleted },
+            }),
+        );
+        let releases: Vec<Value> = data
+            .release_tags()
+            .into_iter()
+            .map(|(tag, commit)| json!({ "tag": tag, "commit": commit.hash }))
+            .collect();
+        if !releases.is_empty() {
+            report.insert("releases".to_string(), Value::Array(releases));
+        }
+    }
+    if data.shows(Part::Stats) {
+        let commit_types: Map<String, Value> = classify::counts(&data.commits)
+            .into_iter()
+            .map(|(category, count)| (category.to_string(), Value::from(count)))
+            .collect();
+        let sections: Map<String, Value> = data
+            .sections
+            .iter()
+            .map(|section| (section.id.clone(), section_to_json(section)))
+            .collect();
+        report.insert("commit_types".to_string(), Value::Object(commit_types));
+        report.insert("sections".to_string(), Value::Object(sections));
+    }
+    if data.shows(Part::Details) {
+        report.insert("ai_summary".to_string(), json!(data.ai_summary));
+        report.insert("commit_summaries".to_string(), json!(data.commit_summaries));
+        let translations: Map<String, Value> = data
+            .translations
+            .iter()
+            .map(|(hash, translation)| {
+                let value = json!({
+                    "language": translation.language.code,
+                    "text": translation.text,
+                });
+                (hash.clone(), value)
+            })
+            .collect();
+        report.insert("translations".to_string(), Value::Object(translations));
+    }
+    if data.shows(Part::Details) || data.shows(Part::Files) {
+        report.insert("commits".to_string(), commits_to_json(data));
+    }
+
+    serde_json::to_writer_pretty(&mut *out, &Value::Object(report))?;
+    writeln!(out)
+}
+
+/// Commits with only the fields of the selected parts: `hash` and `subject`
+/// always, the rest (including `impact` when scored and the `[commit_links]`
+/// as `links`) with [`Part::Details`] and the file lists with [`Part::Files`].
+fn commits_to_json(data: &ReportData) -> Value {
+    let commits = data
+        .detailed_commits()
+        .into_iter()
+        .map(|commit| {
+            let mut value = json!(commit);
+            if let Value::Object(fields) = &mut value {
+                if let Some(score) = data.impact.get(&commit.hash) {
+                    fields.insert("impact".to_string(), json!(score));
+                }
+                let links: Map<String, Value> = data
+                    .links(commit)
+                    .into_iter()
+                    .map(|(label, url)| (label.to_string(), json!(url)))
+                    .collect();
+                if !links.is_empty() {
+                    fields.insert("links".to_string(), Value::Object(links));
+                }
+                if !data.shows(Part::Details) {
+                    fields.retain(|key, _| {
+                        matches!(
+                            key.as_str(),
+                            "hash" | "subject" | "files_changed" | "file_stats"
+                        )
+                    });
+                }
+                if !data.shows(Part::Files) {
+                    fields.remove("files_changed");
+                    fields.remove("file_stats");
+                }
+            }
+            value
+        })
+        .collect();
+
+    Value::Array(commits)
+}
+
+/// Flattens a section's blocks into one object: fields become keys, lists
+/// become arrays under their title, tables become arrays of row objects.
+pub fn section_to_json(section: &Section) -> Value {
+    let mut object = Map::new();
+    object.insert("title".to_string(), json!(section.title));
+
+    for block in &section.blocks {
+        match block {
+            Block::Fields(fields) => {
+                for (label, value) in fields {
+                    object.insert(key(label), json!(value));
+                }
+            }
+            Block::List { title, items } => {
+                object.insert(key(title), json!(items));
+            }
+            Block::Table { headers, rows } => {
+                let rows: Vec<Value> = rows
+                    .iter()
+                    .map(|row| {
+                        let row: Map<String, Value> = headers
+                            .iter()
+                            .zip(row)
+                            .map(|(header, cell)| (key(header), cell_value(cell)))
+                            .collect();
+                        Value::Object(row)
+                    })
+                    .collect();
+                object.insert("rows".to_string(), Value::Array(rows));
+            }
+            Block::Text(text) => {
+                object.insert("text".to_string(), json!(text));
+            }
+            Block::Code(code) => {
+                object.insert("code".to_string(), json!(code));
+            }
+            Block::Mermaid(diagram) => {
+                object.insert("mermaid".to_string(), json!(diagram));
+            }
+        }
+    }
+
+    Value::Object(object)
+}
+
+/// Converts a human-readable label like `Recommended Bump` to `recommended_bump`.
+fn key(label: &str) -> String {
+    label
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| part.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// Keeps numeric table cells as JSON numbers.
+fn cell_value(cell: &str) -> Value {
+    cell.parse::<i64>()
+        .map(Value::from)
+        .or_else(|_| cell.parse::<f64>().map(Value::from))
+        .unwrap_or_else(|_| Value::String(cell.to_string()))
+}
@@ -0,0 +1,335 @@
+use std::io::{self, Write};
+
+use crate::{
+    classify,
+    error::Result,
+    git::Commit,
+    render::{written, Format, ReportRenderer},
+    report::{Block, Part, ReportData, Section},
+};
+
+pub fn render(data: &ReportData) -> String {
+    super::collect(|out| write(data, out))
+}
+
+/// Markdown reports.
+pub struct Markdown;
+
+impl ReportRenderer for Markdown {
+    fn format(&self) -> Format {
+        Format::Md
+    }
+
+    fn write(&self, data: &ReportData, out: &mut dyn Write) -> Result<()> {
+        written(Format::Md, write(data, out))
+    }
+}
+
+/// Writes the report to `out` piece by piece, one commit at a time.
+pub fn write(data: &ReportData, out: &mut dyn Write) -> io::Result<()> {
+    if let Some(front_matter) = data.front_matter() {
+        out.write_all(front_matter.as_bytes())?;
+    }
+    if let Some(header) = data.header() {
+        write!(out, "{}\n\n", banner(&header))?;
+    }
+    write!(out, "# {}\n\n", data.title())?;
+
+    if data.shows(Part::Summary) {
+        out.write_all(summary(data).as_bytes())?;
+    }
+    if data.shows(Part::Stats) {
+        out.write_all(commit_types(data).as_bytes())?;
+    }
+    if let Some(summary) = data.visible_ai_summary() {
+        write!(out, "## {}\n\n", data.locale.text("ai-summary"))?;
+        write!(out, "{}\n\n", summary.trim())?;
+    } else if data.lists_commits() {
+        commits(data, out)?;
+    }
+    if data.shows(Part::Stats) {
+        for section in &data.sections {
+            out.write_all(render_section(section).as_bytes())?;
+        }
+    }
+    if let Some(footer) = data.footer() {
+        write!(out, "---\n\n{}\n", banner(&footer))?;
+    }
+
+    Ok(())
+}
+
+/// Banner text as a paragraph that keeps its line breaks.
+fn banner(text: &str) -> String {
+    text.lines()
+        .map(|line| escape(line.trim_end()))
+        .collect::<Vec<_>>()
+        .join("  \n")
+}
+
+fn summary(data: &ReportData) -> String {
+    let (from_commit, to_commit) = (&data.from_commit, &data.to_commit);
+    let mut report = String::new();
+
+    for (label, value) in data.branding_fields() {
+        report.push_str(&format!("- **{}:** {}\n", label, escape(value)));
+    }
+    report.push_str(&format!(
+        "- **{}:** {}\n",
+        data.locale.text("repository"),
+        data.repo_path
+    ));
+    report.push_str(&format!(
+        "- **{}:** {}\n",
+        data.locale.text("generated"),
+        data.dates.format(&data.generated_at)
+    ));
+    report.push_str(&format!(
+        "- **{}:** `{}` -> `{}`\n",
+        data.locale.text("commit-range"),
+        from_commit.hash,
+        to_commit.hash
+    ));
+    report.push_str(&format!(
+        "- **{}:** {}\n\n",
+        data.locale.text("total-commits"),
+        data.commits.len()
+    ));
+
+    report.push_str(&format!("## {}\n\n", data.locale.text("summary")));
+    report.push_str(&format!(
+        "- **{}:** {} (`{}`)\n",
+        data.locale.text("from"),
+        escape(&from_commit.subject),
+        from_commit.hash
+    ));
+    report.push_str(&format!(
+        "- **{}:** {} (`{}`)\n",
+        data.locale.text("to"),
+        escape(&to_commit.subject),
+        to_commit.hash
+    ));
+    report.push_str(&format!(
+        "- **{}:** {}\n",
+        data.locale.text("date-range"),
+        data.date_range()
+    ));
+    if let Some(releases) = data.releases() {
+        report.push_str(&format!(
+            "- **{}:** {}\n",
+            data.locale.text("releases"),
+            escape(&releases)
+        ));
+    }
+    report.push_str(&format!(
+        "- **{}:** {}\n\n",
+        data.locale.text("lines-changed"),
+        data.lines_changed()
+    ));
+
+    report
+}
+
+fn commit_types(data: &ReportData) -> String {
+    if data.commits.is_empty() {
+        return String::new();
+    }
+
+    let mut report = format!("**{}:**\n\n", data.locale.text("commit-types"));
+    report.push_str(&render_block(&classify::breakdown(
+        &data.commits,
+        &data.locale,
+    )));
+    report
+}
+
+fn commits(data: &ReportData, out: &mut dyn Write) -> io::Result<()> {
+    write!(out, "## {}\n\n", data.locale.text("detailed-commits"))?;
+
+    for (i, commit) in data.detailed_commits().into_iter().enumerate() {
+        let mut entry = format!("### {}. {}\n\n", i + 1, escape(&commit.subject));
+        if data.shows(Part::Details) {
+            entry.push_str(&details(data, commit));
+        }
+        if data.shows(Part::Files) {
+            entry.push_str(&files(data, commit));
+        }
+        out.write_all(entry.as_bytes())?;
+    }
+
+    Ok(())
+}
+
+fn details(data: &ReportData, commit: &Commit) -> String {
+    let mut report = String::new();
+
+    report.push_str(&format!(
+        "- **{}:** `{}`\n",
+        data.locale.text("hash"),
+        commit.hash
+    ));
+    report.push_str(&format!(
+        "- **{}:** {}\n",
+        data.locale.text("author"),
+        escape(&commit.author)
+    ));
+    report.push_str(&format!(
+        "- **{}:** {}\n",
+        data.locale.text("date"),
+        data.dates.format(&commit.date)
+    ));
+    for (key, refs) in [("tags", &commit.tags), ("branches", &commit.branches)] {
+        if !refs.is_empty() {
+            let chips: Vec<String> = refs.iter().map(|name| format!("`{}`", name)).collect();
+            report.push_str(&format!(
+                "- **{}:** {}\n",
+                data.locale.text(key),
+                chips.join(" ")
+            ));
+        }
+    }
+    if !commit.labels.is_empty() {
+        let chips: Vec<String> = commit
+            .labels
+            .iter()
+            .map(|label| format!("`{}`", label))
+            .collect();
+        report.push_str(&format!(
+            "- **{}:** {}\n",
+            data.locale.text("labels"),
+            chips.join(" ")
+        ));
+    }
+    if let Some(signature) = &commit.signature {
+        report.push_str(&format!(
+            "- **{}:** {}\n",
+            data.locale.text("signature"),
+            escape(&signature.describe())
+        ));
+    }
+    if let Some(score) = data.impact.get(&commit.hash) {
+        report.push_str(&format!(
+            "- **{}:** {}\n",
+            data.locale.text("impact"),
+            score
+        ));
+    }
+    for (label, url) in data.links(commit) {
+        report.push_str(&format!("- **{}:** <{}>\n", escape(label), url));
+    }
+    if let Some(summary) = data.commit_summaries.get(&commit.hash) {
+        report.push_str(&format!(
+            "- **{}:** {}\n",
+            data.locale.text("ai-summary"),
+            escape(summary)
+        ));
+    }
+    report.push('\n');
+
+    if !commit.body.trim().is_empty() {
+        for line in commit.body.trim().lines() {
+            report.push_str(&format!("> {}\n", line));
+        }
+        report.push('\n');
+    }
+    if let Some(translation) = data.translations.get(&commit.hash) {
+        report.push_str(&format!("*{}:*\n\n", data.translation_label(translation)));
+        for line in translation.text.trim().lines() {
+            report.push_str(&format!("> {}\n", line));
+        }
+        report.push('\n');
+    }
+
+    report
+}
+
+fn files(data: &ReportData, commit: &Commit) -> String {
+    let mut report = String::new();
+
+    if !commit.file_stats.is_empty() {
+        report.push_str(&format!("**{}:**\n\n", data.locale.text("files-changed")));
+        for stat in &commit.file_stats {
+            match &stat.old_path {
+                Some(old_path) => report.push_str(&format!("- `{}` -> `{}`", old_path, stat.path)),
+                None => report.push_str(&format!("- `{}`", stat.path)),
+            }
+            for marker in stat.markers() {
+                report.push_str(&format!(" [{}]", marker));
+            }
+            report.push('\n');
+        }
+        report.push('\n');
+    }
+
+    report
+}
+
+pub fn render_section(section: &Section) -> String {
+    let mut output = format!("## {}\n\n", escape(&section.title));
+
+    for block in &section.blocks {
+        output.push_str(&render_block(block));
+    }
+
+    output
+}
+
+fn render_block(block: &Block) -> String {
+    let mut output = String::new();
+    match block {
+        Block::Fields(fields) => {
+            for (label, value) in fields {
+                output.push_str(&format!("- **{}:** {}\n", label, escape(value)));
+            }
+            output.push('\n');
+        }
+        Block::List { title, items } => {
+            output.push_str(&format!("**{}:**\n\n", title));
+            for item in items {
+                output.push_str(&format!("- {}\n", escape(item)));
+            }
+            output.push('\n');
+        }
+        Block::Table { headers, rows } => {
+            output.push_str(&table_row(headers));
+            output.push_str(&format!("|{}\n", "---|".repeat(headers.len())));
+            for row in rows {
+                output.push_str(&table_row(row));
+            }
+            output.push('\n');
+        }
+        Block::Text(text) => {
+            output.push_str(text.trim());
+            output.push_str("\n\n");
+        }
+        Block::Code(code) => {
+            output.push_str(&format!("```text\n{}\n```\n\n", code.trim_end()));
+        }
+        Block::Mermaid(diagram) => {
+            output.push_str(&format!("```mermaid\n{}\n```\n\n", diagram.trim_end()));
+        }
+    }
+
+    output
+}
+
+fn table_row(cells: &[String]) -> String {
+    let cells: Vec<String> = cells
+        .iter()
+        .map(|cell| escape(cell).replace('|', "\\|"))
+        .collect();
+    format!("| {} |\n", cells.join(" | "))
+}
+
+/// Escapes characters that would otherwise start Markdown formatting inside
+/// commit subjects and author names.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '`' | '*' | '_' | '<' | '>' | '[' | ']') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
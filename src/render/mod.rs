@@ -0,0 +1,270 @@
+use clap::ValueEnum;
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+    thread,
+};
+
+use crate::{
+    error::{Context, ReportError, Result},
+    report::ReportData,
+};
+
+pub mod asciidoc;
+pub mod html;
+pub mod ics;
+pub mod json;
+pub mod markdown;
+pub mod org;
+#[cfg(feature = "pdf")]
+pub mod pdf;
+pub mod text;
+#[cfg(feature = "xlsx")]
+pub mod xlsx;
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Txt,
+    Md,
+    Html,
+    Json,
+    Pdf,
+    Asciidoc,
+    Xlsx,
+    Ics,
+    Org,
+}
+
+impl Format {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Txt => "txt",
+            Format::Md => "md",
+            Format::Html => "html",
+            Format::Json => "json",
+            Format::Pdf => "pdf",
+            Format::Asciidoc => "adoc",
+            Format::Xlsx => "xlsx",
+            Format::Ics => "ics",
+            Format::Org => "org",
+        }
+    }
+
+    /// Whether the rendered output is human-readable text that can be sent
+    /// as a message body.
+    pub fn is_text(self) -> bool {
+        !matches!(self, Format::Pdf | Format::Xlsx | Format::Ics)
+    }
+}
+
+/// Renders the report in one format. Each format module implements this
+/// for a unit struct, e.g. [`markdown::Markdown`], and registers it in
+/// [`RendererRegistry::with_defaults`], so a new format needs no change to
+/// how the report is collected or written out.
+pub trait ReportRenderer: Send + Sync {
+    /// The format this renderer produces; registering a second renderer for
+    /// it replaces the first.
+    fn format(&self) -> Format;
+
+    /// Writes the report to `out`, a piece at a time where the format allows.
+    fn write(&self, data: &ReportData, out: &mut dyn Write) -> Result<()>;
+
+    /// The report as bytes.
+    fn render(&self, data: &ReportData) -> Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        self.write(data, &mut bytes)?;
+        Ok(bytes)
+    }
+}
+
+/// The renderers reports can be written with, one per format.
+pub struct RendererRegistry {
+    renderers: Vec<Box<dyn ReportRenderer>>,
+}
+
+impl RendererRegistry {
+    /// A registry without any renderer.
+    pub fn new() -> Self {
+        RendererRegistry {
+            renderers: Vec::new(),
+        }
+    }
+
+    /// The built-in renderers; PDF and XLSX only when their features are on.
+    pub fn with_defaults() -> Self {
+        let mut registry = Self::new();
+        registry.register(text::Text);
+        registry.register(markdown::Markdown);
+        registry.register(html::Html);
+        registry.register(json::Json);
+        #[cfg(feature = "pdf")]
+        registry.register(pdf::Pdf);
+        registry.register(asciidoc::Asciidoc);
+        #[cfg(feature = "xlsx")]
+        registry.register(xlsx::Xlsx);
+        registry.register(ics::Ics);
+        registry.register(org::Org);
+        registry
+    }
+
+    /// Adds a renderer, replacing the one registered for the same format.
+    pub fn register(&mut self, renderer: impl ReportRenderer + 'static) {
+        self.renderers
+            .retain(|known| known.format() != renderer.format());
+        self.renderers.push(Box::new(renderer));
+    }
+
+    /// The formats renderers are registered for.
+    pub fn formats(&self) -> Vec<Format> {
+        self.renderers
+            .iter()
+            .map(|renderer| renderer.format())
+            .collect()
+    }
+
+    /// The renderer of a format; fails for PDF and XLSX when their features
+    /// are off.
+    pub fn get(&self, format: Format) -> Result<&dyn ReportRenderer> {
+        if let Some(renderer) = self
+            .renderers
+            .iter()
+            .find(|renderer| renderer.format() == format)
+        {
+            return Ok(renderer.as_ref());
+        }
+        Err(match format {
+            Format::Pdf if !cfg!(feature = "pdf") => {
+                crate::features::unavailable("PDF reports", "pdf")
+            }
+            Format::Xlsx if !cfg!(feature = "xlsx") => {
+                crate::features::unavailable("Excel reports", "xlsx")
+            }
+            _ => ReportError::render(format!(
+                "No renderer is registered for {} reports",
+                format.extension()
+            )),
+        })
+    }
+
+    /// Renders the report in one format.
+    pub fn render(&self, format: Format, data: &ReportData) -> Result<Vec<u8>> {
+        self.get(format)?.render(data)
+    }
+
+    /// Writes the report in one format to `out`.
+    pub fn write(&self, format: Format, data: &ReportData, out: &mut dyn Write) -> Result<()> {
+        self.get(format)?.write(data, out)
+    }
+
+    /// Writes every format to its file through a buffered writer, one thread
+    /// per format, in the manner of [`RendererRegistry::render_all`].
+    pub fn write_all(&self, outputs: &[(Format, PathBuf)], data: &ReportData) -> Result<()> {
+        let write_file = |format: Format, path: &PathBuf| -> Result<()> {
+            let renderer = self.get(format)?;
+            let file = File::create(path).with_context(ReportError::io, || {
+                format!("Failed to create output file {}", path.display())
+            })?;
+            let mut out = BufWriter::new(file);
+            renderer.write(data, &mut out)?;
+            out.flush().with_context(ReportError::io, || {
+                format!("Failed to write output file {}", path.display())
+            })
+        };
+        if let [(format, path)] = outputs {
+            return write_file(*format, path);
+        }
+
+        thread::scope(|scope| {
+            let handles: Vec<_> = outputs
+                .iter()
+                .map(|(format, path)| scope.spawn(move || write_file(*format, path)))
+                .collect();
+
+            handles
+                .into_iter()
+                .zip(outputs)
+                .try_for_each(|(handle, (format, _))| {
+                    handle.join().unwrap_or_else(|_| Err(panicked(*format)))
+                })
+        })
+    }
+
+    /// Renders every requested format from the same data, one thread per
+    /// format.
+    pub fn render_all(
+        &self,
+        formats: &[Format],
+        data: &ReportData,
+    ) -> Result<Vec<(Format, Vec<u8>)>> {
+        if formats.len() == 1 {
+            return Ok(vec![(formats[0], self.render(formats[0], data)?)]);
+        }
+
+        thread::scope(|scope| {
+            let handles: Vec<_> = formats
+                .iter()
+                .map(|&format| {
+                    scope.spawn(move || self.render(format, data).map(|bytes| (format, bytes)))
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .zip(formats)
+                .map(|(handle, &format)| handle.join().unwrap_or_else(|_| Err(panicked(format))))
+                .collect()
+        })
+    }
+}
+
+impl Default for RendererRegistry {
+    fn default() -> Self {
+        Self::with_defaults()
+    }
+}
+
+fn panicked(format: Format) -> ReportError {
+    ReportError::render(format!("The {} renderer panicked", format.extension()))
+}
+
+/// Renders the report in one format with the built-in renderers; fails for
+/// PDF and XLSX when their features are off.
+pub fn render(format: Format, data: &ReportData) -> Result<Vec<u8>> {
+    RendererRegistry::with_defaults().render(format, data)
+}
+
+/// Writes the report in one format to `out` with the built-in renderers.
+/// Text, Markdown, HTML, AsciiDoc, iCalendar and Org are written a piece at
+/// a time, so the whole report is never held in memory; PDF and XLSX are built
+/// in memory first.
+pub fn write(format: Format, data: &ReportData, out: &mut dyn Write) -> Result<()> {
+    RendererRegistry::with_defaults().write(format, data, out)
+}
+
+/// Writes every format to its file with the built-in renderers, see
+/// [`RendererRegistry::write_all`].
+pub fn write_all(outputs: &[(Format, PathBuf)], data: &ReportData) -> Result<()> {
+    RendererRegistry::with_defaults().write_all(outputs, data)
+}
+
+/// Renders every requested format with the built-in renderers, see
+/// [`RendererRegistry::render_all`].
+pub fn render_all(formats: &[Format], data: &ReportData) -> Result<Vec<(Format, Vec<u8>)>> {
+    RendererRegistry::with_defaults().render_all(formats, data)
+}
+
+/// Wraps what a streaming renderer's `write` returns in the error the
+/// [`ReportRenderer`] implementations report.
+pub(crate) fn written(format: Format, written: io::Result<()>) -> Result<()> {
+    written.with_context(ReportError::io, || {
+        format!("Failed to write the {} report", format.extension())
+    })
+}
+
+/// Collects what a streaming renderer writes into a string.
+pub(crate) fn collect(write: impl FnOnce(&mut dyn Write) -> io::Result<()>) -> String {
+    let mut bytes = Vec::new();
+    write(&mut bytes).expect("writing to memory does not fail");
+    String::from_utf8(bytes).expect("renderers write UTF-8")
+}
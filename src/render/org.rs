@@ -0,0 +1,336 @@
+use std::io::{self, Write};
+
+use crate::{
+    classify,
+    error::Result,
+    git::Commit,
+    postprocess::{self, Markup},
+    render::{written, Format, ReportRenderer},
+    report::{Block, Part, ReportData, Section},
+};
+
+pub fn render(data: &ReportData) -> String {
+    super::collect(|out| write(data, out))
+}
+
+/// Emacs Org reports.
+pub struct Org;
+
+impl ReportRenderer for Org {
+    fn format(&self) -> Format {
+        Format::Org
+    }
+
+    fn write(&self, data: &ReportData, out: &mut dyn Write) -> Result<()> {
+        written(Format::Org, write(data, out))
+    }
+}
+
+/// Writes the document to `out` piece by piece, one commit at a time.
+pub fn write(data: &ReportData, out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "#+TITLE: {}", keyword(data.title()))?;
+    if let Some(author) = &data.branding.author {
+        writeln!(out, "#+AUTHOR: {}", keyword(author))?;
+    }
+    writeln!(
+        out,
+        "#+DATE: {}",
+        data.dates.format_with(&data.generated_at, "[%Y-%m-%d %a]")
+    )?;
+    write!(out, "#+OPTIONS: toc:t\n\n")?;
+    if let Some(header) = data.header() {
+        write!(out, "{}\n\n", banner(&header))?;
+    }
+
+    if data.shows(Part::Summary) {
+        out.write_all(summary(data).as_bytes())?;
+    }
+    if data.shows(Part::Stats) {
+        out.write_all(commit_types(data).as_bytes())?;
+    }
+    if let Some(summary) = data.visible_ai_summary() {
+        write!(out, "* {}\n\n", data.locale.text("ai-summary"))?;
+        if data.convert_ai_summary {
+            write!(out, "{}\n\n", postprocess::convert(summary, Markup::Org))?;
+        } else {
+            out.write_all(example(summary).as_bytes())?;
+        }
+    } else if data.lists_commits() {
+        commits(data, out)?;
+    }
+    if data.shows(Part::Stats) {
+        for section in &data.sections {
+            out.write_all(render_section(section).as_bytes())?;
+        }
+    }
+    if let Some(footer) = data.footer() {
+        write!(out, "-----\n\n{}\n", banner(&footer))?;
+    }
+
+    Ok(())
+}
+
+/// Banner text as a paragraph with hard line breaks.
+fn banner(text: &str) -> String {
+    text.lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join(" \\\\\n")
+}
+
+fn summary(data: &ReportData) -> String {
+    let (from_commit, to_commit) = (&data.from_commit, &data.to_commit);
+    let mut doc = format!("* {}\n\n", data.locale.text("summary"));
+
+    for (label, value) in data.branding_fields() {
+        doc.push_str(&item(label, value));
+    }
+    doc.push_str(&item(data.locale.text("repository"), &data.repo_path));
+    doc.push_str(&item(
+        data.locale.text("generated"),
+        &data.dates.format(&data.generated_at),
+    ));
+    doc.push_str(&item(
+        data.locale.text("from"),
+        &format!("{} (={}=)", from_commit.subject, from_commit.hash),
+    ));
+    doc.push_str(&item(
+        data.locale.text("to"),
+        &format!("{} (={}=)", to_commit.subject, to_commit.hash),
+    ));
+    doc.push_str(&item(data.locale.text("date-range"), &data.date_range()));
+    if let Some(releases) = data.releases() {
+        doc.push_str(&item(data.locale.text("releases"), &releases));
+    }
+    doc.push_str(&item(
+        data.locale.text("lines-changed"),
+        &data.lines_changed(),
+    ));
+    doc.push_str(&item(
+        data.locale.text("total-commits"),
+        &data.commits.len().to_string(),
+    ));
+    doc.push('\n');
+
+    doc
+}
+
+fn commit_types(data: &ReportData) -> String {
+    if data.commits.is_empty() {
+        return String::new();
+    }
+
+    let mut doc = format!("#+CAPTION: {}\n", data.locale.text("commit-types"));
+    doc.push_str(&render_block(&classify::breakdown(
+        &data.commits,
+        &data.locale,
+    )));
+    doc
+}
+
+fn commits(data: &ReportData, out: &mut dyn Write) -> io::Result<()> {
+    write!(out, "* {}\n\n", data.locale.text("detailed-commits"))?;
+
+    // The commit type and labels become headline tags, so agenda views and
+    // tag searches can pick out e.g. every `:fix:` of the range.
+    for commit in data.detailed_commits() {
+        let mut entry = format!("** {} {}\n", commit.subject, tags(commit));
+        entry.push_str(&drawer(data, commit));
+        if data.shows(Part::Details) {
+            entry.push_str(&details(data, commit));
+        }
+        if data.shows(Part::Files) {
+            entry.push_str(&files(data, commit));
+        }
+        out.write_all(entry.as_bytes())?;
+    }
+
+    Ok(())
+}
+
+fn tags(commit: &Commit) -> String {
+    let mut tags = vec![classify::classify(commit).to_string()];
+    for label in &commit.labels {
+        let label = tag(label);
+        if !tags.contains(&label) {
+            tags.push(label);
+        }
+    }
+    format!(":{}:", tags.join(":"))
+}
+
+/// Org tags only take letters, digits and `_@#%`.
+fn tag(label: &str) -> String {
+    label
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || "_@#%".contains(c) {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// The property drawer with the hash, author and date of the commit, the
+/// date as an inactive timestamp, and the tags and branches pointing at it.
+fn drawer(data: &ReportData, commit: &Commit) -> String {
+    let mut doc = String::from(":PROPERTIES:\n");
+    doc.push_str(&format!(":HASH: {}\n", commit.hash));
+    doc.push_str(&format!(":AUTHOR: {}\n", keyword(&commit.author)));
+    doc.push_str(&format!(
+        ":DATE: {}\n",
+        data.dates.format_with(&commit.date, "[%Y-%m-%d %a %H:%M]")
+    ));
+    if !commit.tags.is_empty() {
+        doc.push_str(&format!(":GIT_TAGS: {}\n", commit.tags.join(" ")));
+    }
+    if !commit.branches.is_empty() {
+        doc.push_str(&format!(":BRANCHES: {}\n", commit.branches.join(" ")));
+    }
+    doc.push_str(":END:\n\n");
+    doc
+}
+
+fn details(data: &ReportData, commit: &Commit) -> String {
+    let mut doc = String::new();
+
+    if !commit.labels.is_empty() {
+        doc.push_str(&item(data.locale.text("labels"), &commit.labels.join(", ")));
+    }
+    if let Some(signature) = &commit.signature {
+        doc.push_str(&item(data.locale.text("signature"), &signature.describe()));
+    }
+    if let Some(score) = data.impact.get(&commit.hash) {
+        doc.push_str(&item(data.locale.text("impact"), &score.to_string()));
+    }
+    for (label, url) in data.links(commit) {
+        doc.push_str(&item(label, &format!("[[{}]]", url)));
+    }
+    if let Some(summary) = data.commit_summaries.get(&commit.hash) {
+        doc.push_str(&item(data.locale.text("ai-summary"), summary));
+    }
+    if !doc.is_empty() {
+        doc.push('\n');
+    }
+
+    if !commit.body.trim().is_empty() {
+        doc.push_str(&example(&commit.body));
+    }
+    if let Some(translation) = data.translations.get(&commit.hash) {
+        doc.push_str(&format!(
+            "#+CAPTION: {}\n{}",
+            data.translation_label(translation),
+            example(&translation.text)
+        ));
+    }
+
+    doc
+}
+
+fn files(data: &ReportData, commit: &Commit) -> String {
+    let mut doc = String::new();
+
+    if !commit.file_stats.is_empty() {
+        doc.push_str(&format!("{}:\n", data.locale.text("files-changed")));
+        for stat in &commit.file_stats {
+            match &stat.old_path {
+                Some(old_path) => doc.push_str(&format!("- ={}= -> ={}=", old_path, stat.path)),
+                None => doc.push_str(&format!("- ={}=", stat.path)),
+            }
+            for marker in stat.markers() {
+                doc.push_str(&format!(" [{}]", marker));
+            }
+            doc.push('\n');
+        }
+        doc.push('\n');
+    }
+
+    doc
+}
+
+pub fn render_section(section: &Section) -> String {
+    let mut doc = format!(
+        "* {}\n:PROPERTIES:\n:CUSTOM_ID: {}\n:END:\n\n",
+        section.title, section.id
+    );
+
+    for block in &section.blocks {
+        doc.push_str(&render_block(block));
+    }
+
+    doc
+}
+
+fn render_block(block: &Block) -> String {
+    let mut doc = String::new();
+    match block {
+        Block::Fields(fields) => {
+            for (label, value) in fields {
+                doc.push_str(&item(label, value));
+            }
+            doc.push('\n');
+        }
+        Block::List { title, items } => {
+            doc.push_str(&format!("{}:\n", title));
+            for item in items {
+                doc.push_str(&format!("- {}\n", item));
+            }
+            doc.push('\n');
+        }
+        Block::Table { headers, rows } => {
+            let header: Vec<String> = headers.iter().map(|h| cell(h)).collect();
+            doc.push_str(&format!("| {} |\n", header.join(" | ")));
+            doc.push_str(&format!("|{}|\n", vec!["---"; headers.len()].join("+")));
+            for row in rows {
+                let cells: Vec<String> = row.iter().map(|c| cell(c)).collect();
+                doc.push_str(&format!("| {} |\n", cells.join(" | ")));
+            }
+            doc.push('\n');
+        }
+        Block::Text(text) => {
+            doc.push_str(text.trim());
+            doc.push_str("\n\n");
+        }
+        Block::Code(code) => doc.push_str(&example(code)),
+        Block::Mermaid(diagram) => {
+            doc.push_str(&format!(
+                "#+begin_src mermaid\n{}\n#+end_src\n\n",
+                diagram.trim_end()
+            ));
+        }
+    }
+
+    doc
+}
+
+/// A description list item, `- Label :: value`.
+fn item(label: &str, value: &str) -> String {
+    format!("- {} :: {}\n", label, keyword(value))
+}
+
+/// Text in an example block, shown verbatim. Lines that would otherwise
+/// start a headline or a keyword are escaped with a comma, as Org does.
+fn example(text: &str) -> String {
+    let mut doc = String::from("#+begin_example\n");
+    for line in text.trim_start_matches(['\r', '\n']).trim_end().lines() {
+        let content = line.trim_start().trim_start_matches(',');
+        if content.starts_with('*') || content.starts_with("#+") {
+            doc.push(',');
+        }
+        doc.push_str(line);
+        doc.push('\n');
+    }
+    doc.push_str("#+end_example\n\n");
+    doc
+}
+
+fn cell(text: &str) -> String {
+    keyword(text).replace('|', "\\vert{}")
+}
+
+/// Keyword, property and list values must stay on one line.
+fn keyword(text: &str) -> String {
+    text.replace('\n', " ")
+}
@@ -0,0 +1,174 @@
+//! A minimal PDF writer that lays out the plain-text report in a monospaced
+//! font on A4 pages. It only needs the standard Courier font, so no font files
+//! or external crates are required.
+
+use std::io::Write;
+
+use crate::{
+    error::Result,
+    render::{text, written, Format, ReportRenderer},
+    report::ReportData,
+};
+
+const PAGE_WIDTH: u32 = 595;
+const PAGE_HEIGHT: u32 = 842;
+const MARGIN: u32 = 40;
+const FONT_SIZE: u32 = 9;
+const LINE_HEIGHT: u32 = 11;
+/// Courier glyphs are 0.6em wide, so this many characters fit between margins.
+const CHARS_PER_LINE: usize = ((PAGE_WIDTH - 2 * MARGIN) * 10 / (FONT_SIZE * 6)) as usize;
+const LINES_PER_PAGE: usize = ((PAGE_HEIGHT - 2 * MARGIN) / LINE_HEIGHT) as usize;
+
+/// PDF reports.
+pub struct Pdf;
+
+impl ReportRenderer for Pdf {
+    fn format(&self) -> Format {
+        Format::Pdf
+    }
+
+    fn write(&self, data: &ReportData, out: &mut dyn Write) -> Result<()> {
+        written(Format::Pdf, out.write_all(&render(data)))
+    }
+
+    fn render(&self, data: &ReportData) -> Result<Vec<u8>> {
+        Ok(render(data))
+    }
+}
+
+pub fn render(data: &ReportData) -> Vec<u8> {
+    let text = text::render_body(data);
+    let lines = wrap_lines(&text);
+    // The banner is repeated on every page, a blank line apart from the text.
+    let header = data.header().map(|header| wrap_lines(&header));
+    let footer = data.footer().map(|footer| wrap_lines(&footer));
+    let banner_lines = |banner: &Option<Vec<String>>| banner.as_ref().map_or(0, |b| b.len() + 1);
+    let lines_per_page = LINES_PER_PAGE
+        .saturating_sub(banner_lines(&header) + banner_lines(&footer))
+        .max(1);
+    let pages: Vec<&[String]> = if lines.is_empty() {
+        vec![&[]]
+    } else {
+        lines.chunks(lines_per_page).collect()
+    };
+
+    // Object layout: 1 catalog, 2 page tree, 3 font, then a page and a
+    // content stream object for every page.
+    let page_ids: Vec<usize> = (0..pages.len()).map(|i| 4 + i * 2).collect();
+    let mut objects: Vec<Vec<u8>> = vec![
+        b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            page_ids
+                .iter()
+                .map(|id| format!("{} 0 R", id))
+                .collect::<Vec<_>>()
+                .join(" "),
+            pages.len()
+        )
+        .into_bytes(),
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Courier /Encoding /WinAnsiEncoding >>".to_vec(),
+    ];
+
+    for (page, page_id) in pages.iter().zip(&page_ids) {
+        objects.push(
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+                PAGE_WIDTH,
+                PAGE_HEIGHT,
+                page_id + 1
+            )
+            .into_bytes(),
+        );
+
+        let mut content = format!(
+            "BT\n/F1 {} Tf\n{} TL\n{} {} Td\n",
+            FONT_SIZE,
+            LINE_HEIGHT,
+            MARGIN,
+            PAGE_HEIGHT - MARGIN - FONT_SIZE
+        )
+        .into_bytes();
+        let mut page_lines: Vec<&str> = Vec::with_capacity(LINES_PER_PAGE);
+        if let Some(header) = &header {
+            page_lines.extend(header.iter().map(String::as_str));
+            page_lines.push("");
+        }
+        page_lines.extend(page.iter().map(String::as_str));
+        if let Some(footer) = &footer {
+            // Footers sit at the bottom of the page however full it is.
+            page_lines.resize(LINES_PER_PAGE - footer.len(), "");
+            page_lines.extend(footer.iter().map(String::as_str));
+        }
+        for line in page_lines {
+            content.push(b'(');
+            content.extend(encode_pdf_string(line));
+            content.extend_from_slice(b") Tj T*\n");
+        }
+        content.extend_from_slice(b"ET");
+
+        let mut stream = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+        stream.extend(content);
+        stream.extend_from_slice(b"\nendstream");
+        objects.push(stream);
+    }
+
+    let mut pdf = b"%PDF-1.4\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend(format!("{} 0 obj\n", i + 1).into_bytes());
+        pdf.extend(object);
+        pdf.extend_from_slice(b"\nendobj\n");
+    }
+
+    let xref_offset = pdf.len();
+    pdf.extend(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).into_bytes());
+    for offset in offsets {
+        pdf.extend(format!("{:010} 00000 n \n", offset).into_bytes());
+    }
+    pdf.extend(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref_offset
+        )
+        .into_bytes(),
+    );
+
+    pdf
+}
+
+/// Splits text into lines and hard-wraps anything wider than the page.
+fn wrap_lines(text: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    for line in text.lines() {
+        let line = line.replace('\t', "    ");
+        let chars: Vec<char> = line.chars().collect();
+        if chars.is_empty() {
+            lines.push(String::new());
+        }
+        for chunk in chars.chunks(CHARS_PER_LINE) {
+            lines.push(chunk.iter().collect());
+        }
+    }
+    lines
+}
+
+/// Encodes a line as a PDF literal string in WinAnsi (Latin-1 compatible)
+/// encoding. Characters outside of it are replaced with `?`.
+fn encode_pdf_string(line: &str) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(line.len());
+    for c in line.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                bytes.push(b'\\');
+                bytes.push(c as u8);
+            }
+            ' '..='~' => bytes.push(c as u8),
+            '\u{a0}'..='\u{ff}' => bytes.push(c as u32 as u8),
+            _ => bytes.push(b'?'),
+        }
+    }
+    bytes
+}
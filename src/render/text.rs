@@ -0,0 +1,345 @@
+use std::io::{self, Write};
+
+use crate::{
+    classify,
+    error::Result,
+    git::Commit,
+    postprocess::{self, Markup},
+    render::{written, Format, ReportRenderer},
+    report::{Block, Part, ReportData, Section},
+};
+
+pub fn render(data: &ReportData) -> String {
+    super::collect(|out| write(data, out))
+}
+
+/// Plain text reports.
+pub struct Text;
+
+impl ReportRenderer for Text {
+    fn format(&self) -> Format {
+        Format::Txt
+    }
+
+    fn write(&self, data: &ReportData, out: &mut dyn Write) -> Result<()> {
+        written(Format::Txt, write(data, out))
+    }
+}
+
+/// Writes the report to `out` piece by piece, one commit at a time.
+pub fn write(data: &ReportData, out: &mut dyn Write) -> io::Result<()> {
+    if let Some(header) = data.header() {
+        write!(out, "{}\n\n", header)?;
+    }
+    write_body(data, out)?;
+    if let Some(footer) = data.footer() {
+        write!(out, "\n\n{}\n{}\n", "-".repeat(40), footer)?;
+    }
+    Ok(())
+}
+
+/// The report without the banner, for layouts that repeat it on every page.
+#[cfg(feature = "pdf")]
+pub(crate) fn render_body(data: &ReportData) -> String {
+    super::collect(|out| write_body(data, out))
+}
+
+fn write_body(data: &ReportData, out: &mut dyn Write) -> io::Result<()> {
+    // The AI text is a complete report of its own and replaces the overview too.
+    match data.visible_ai_summary() {
+        Some(summary) if data.convert_ai_summary => {
+            out.write_all(postprocess::convert(summary, Markup::Text).as_bytes())?
+        }
+        Some(summary) => out.write_all(summary.as_bytes())?,
+        None => {
+            writeln!(out, "{}", heading(data.title(), '='))?;
+            if data.shows(Part::Summary) {
+                out.write_all(summary(data).as_bytes())?;
+            }
+            if data.shows(Part::Stats) {
+                out.write_all(commit_types(data).as_bytes())?;
+            }
+            if data.lists_commits() {
+                commits(data, out)?;
+            }
+        }
+    }
+
+    if data.shows(Part::Stats) {
+        for section in &data.sections {
+            write!(out, "\n\n{}", render_section(section))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn summary(data: &ReportData) -> String {
+    let (from_commit, to_commit) = (&data.from_commit, &data.to_commit);
+    let mut report = String::new();
+
+    for (label, value) in data.branding_fields() {
+        report.push_str(&format!("{}: {}\n", label, value));
+    }
+    report.push_str(&format!(
+        "{}: {}\n",
+        data.locale.text("repository"),
+        data.repo_path
+    ));
+    report.push_str(&format!(
+        "{}: {}\n",
+        data.locale.text("generated"),
+        data.dates.format(&data.generated_at)
+    ));
+    report.push_str(&format!(
+        "{}: {} -> {}\n",
+        data.locale.text("commit-range"),
+        from_commit.hash,
+        to_commit.hash
+    ));
+    report.push_str(&format!(
+        "{}: {}\n\n",
+        data.locale.text("total-commits"),
+        data.commits.len()
+    ));
+
+    report.push_str(&heading(data.locale.text("summary"), '-'));
+    report.push_str(&format!(
+        "{}: {} ({})\n",
+        data.locale.text("from"),
+        from_commit.subject,
+        from_commit.hash
+    ));
+    report.push_str(&format!(
+        "{}: {} ({})\n",
+        data.locale.text("to"),
+        to_commit.subject,
+        to_commit.hash
+    ));
+    report.push_str(&format!(
+        "{}: {}\n",
+        data.locale.text("date-range"),
+        data.date_range()
+    ));
+    if let Some(releases) = data.releases() {
+        report.push_str(&format!("{}: {}\n", data.locale.text("releases"), releases));
+    }
+    report.push_str(&format!(
+        "{}: {}\n\n",
+        data.locale.text("lines-changed"),
+        data.lines_changed()
+    ));
+
+    report
+}
+
+/// A title underlined to its length.
+fn heading(title: &str, underline: char) -> String {
+    format!(
+        "{}\n{}\n",
+        title,
+        underline.to_string().repeat(title.chars().count())
+    )
+}
+
+fn commit_types(data: &ReportData) -> String {
+    if data.commits.is_empty() {
+        return String::new();
+    }
+
+    let mut report = format!("{}:\n", data.locale.text("commit-types"));
+    report.push_str(&render_block(&classify::breakdown(
+        &data.commits,
+        &data.locale,
+    )));
+    report.push('\n');
+    report
+}
+
+fn commits(data: &ReportData, out: &mut dyn Write) -> io::Result<()> {
+    writeln!(
+        out,
+        "{}",
+        heading(data.locale.text("detailed-commits"), '=')
+    )?;
+
+    for (i, commit) in data.detailed_commits().into_iter().enumerate() {
+        let mut entry = format!("{}. {}\n", i + 1, commit.subject);
+        if data.shows(Part::Details) {
+            entry.push_str(&details(data, commit));
+        }
+        if data.shows(Part::Files) {
+            entry.push_str(&files(data, commit));
+        }
+        entry.push('\n');
+        out.write_all(entry.as_bytes())?;
+    }
+
+    Ok(())
+}
+
+fn details(data: &ReportData, commit: &Commit) -> String {
+    let mut report = String::new();
+
+    report.push_str(&format!(
+        "   {}: {}\n",
+        data.locale.text("hash"),
+        commit.hash
+    ));
+    report.push_str(&format!(
+        "   {}: {}\n",
+        data.locale.text("author"),
+        commit.author
+    ));
+    report.push_str(&format!(
+        "   {}: {}\n",
+        data.locale.text("date"),
+        data.dates.format(&commit.date)
+    ));
+    for (key, refs) in [("tags", &commit.tags), ("branches", &commit.branches)] {
+        if !refs.is_empty() {
+            report.push_str(&format!(
+                "   {}: {}\n",
+                data.locale.text(key),
+                refs.join(", ")
+            ));
+        }
+    }
+    if !commit.labels.is_empty() {
+        report.push_str(&format!(
+            "   {}: {}\n",
+            data.locale.text("labels"),
+            commit.labels.join(", ")
+        ));
+    }
+    if let Some(signature) = &commit.signature {
+        report.push_str(&format!(
+            "   {}: {}\n",
+            data.locale.text("signature"),
+            signature.describe()
+        ));
+    }
+    if let Some(score) = data.impact.get(&commit.hash) {
+        report.push_str(&format!("   {}: {}\n", data.locale.text("impact"), score));
+    }
+    if let Some(summary) = data.commit_summaries.get(&commit.hash) {
+        report.push_str(&format!(
+            "   {}: {}\n",
+            data.locale.text("ai-summary"),
+            summary
+        ));
+    }
+
+    if !commit.body.trim().is_empty() {
+        report.push_str(&format!("   {}:\n", data.locale.text("description")));
+        for line in commit.body.lines() {
+            report.push_str(&format!("     {}\n", line));
+        }
+    }
+    if let Some(translation) = data.translations.get(&commit.hash) {
+        report.push_str(&format!("   {}:\n", data.translation_label(translation)));
+        for line in translation.text.lines() {
+            report.push_str(&format!("     {}\n", line));
+        }
+    }
+
+    report
+}
+
+fn files(data: &ReportData, commit: &Commit) -> String {
+    let mut report = String::new();
+
+    if !commit.file_stats.is_empty() {
+        report.push_str(&format!("   {}:\n", data.locale.text("files-changed")));
+        for stat in &commit.file_stats {
+            report.push_str(&format!("     - {}\n", stat.label()));
+        }
+    }
+
+    report
+}
+
+pub fn render_section(section: &Section) -> String {
+    let mut output = String::new();
+
+    output.push_str(&format!("{}\n", section.title));
+    output.push_str(&format!("{}\n", "-".repeat(section.title.chars().count())));
+
+    for block in &section.blocks {
+        output.push_str(&render_block(block));
+    }
+
+    output
+}
+
+fn render_block(block: &Block) -> String {
+    let mut output = String::new();
+    match block {
+        Block::Fields(fields) => {
+            for (label, value) in fields {
+                output.push_str(&format!("{}: {}\n", label, value));
+            }
+        }
+        Block::List { title, items } => {
+            output.push_str(&format!("{}:\n", title));
+            for item in items {
+                output.push_str(&format!("  - {}\n", item));
+            }
+        }
+        Block::Table { headers, rows } => output.push_str(&render_table(headers, rows)),
+        Block::Text(text) => {
+            output.push_str(text);
+            output.push('\n');
+        }
+        Block::Code(code) | Block::Mermaid(code) => {
+            for line in code.lines() {
+                output.push_str(&format!("  {}\n", line));
+            }
+        }
+    }
+
+    output
+}
+
+/// Renders a table with space-padded columns, numbers right-aligned.
+fn render_table(headers: &[String], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let format_row = |cells: &[String]| {
+        let line = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &width)| {
+                if is_numeric(cell) {
+                    format!("{:>width$}", cell)
+                } else {
+                    format!("{:<width$}", cell)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("  ");
+        format!("{}\n", line.trim_end())
+    };
+
+    let mut output = format_row(headers);
+    let separator: Vec<String> = widths.iter().map(|&width| "-".repeat(width)).collect();
+    output.push_str(&format_row(&separator));
+    for row in rows {
+        output.push_str(&format_row(row));
+    }
+
+    output
+}
+
+fn is_numeric(cell: &str) -> bool {
+    let digits = cell.trim_start_matches(['+', '-']);
+    !digits.is_empty()
+        && digits
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == '.' || c == '%')
+}
@@ -0,0 +1,129 @@
+// Draws the commit timeline of an HTML report from the JSON embedded next to
+// it. Scroll to zoom around the cursor, drag to pan, double-click to reset,
+// hover a commit for its details. Release tags are marked above the commits.
+(function () {
+  var source = document.getElementById("timeline-data");
+  var svg = document.getElementById("timeline");
+  var tip = document.getElementById("timeline-tip");
+  if (!source || !svg || !tip) return;
+
+  var commits = JSON.parse(source.textContent);
+  var ns = "http://www.w3.org/2000/svg";
+  var width = 900, height = 110, pad = 30, axisY = 70;
+  var times = commits.map(function (c) { return c.time; });
+  var first = Math.min.apply(null, times), last = Math.max.apply(null, times);
+  var margin = Math.max((last - first) * 0.05, 3600000);
+  var full = { start: first - margin, end: last + margin };
+  var view = { start: full.start, end: full.end };
+
+  function x(time) {
+    return pad + (time - view.start) / (view.end - view.start) * (width - 2 * pad);
+  }
+
+  function timeAt(clientX) {
+    var box = svg.getBoundingClientRect();
+    var px = (clientX - box.left) / box.width * width;
+    return view.start + (px - pad) / (width - 2 * pad) * (view.end - view.start);
+  }
+
+  function node(name, attrs, text) {
+    var element = document.createElementNS(ns, name);
+    for (var key in attrs) element.setAttribute(key, attrs[key]);
+    if (text !== undefined) element.textContent = text;
+    svg.appendChild(element);
+    return element;
+  }
+
+  function label(time) {
+    var iso = new Date(time).toISOString();
+    return view.end - view.start < 2 * 86400000 ? iso.slice(0, 16).replace("T", " ") : iso.slice(0, 10);
+  }
+
+  function draw() {
+    while (svg.firstChild) svg.removeChild(svg.firstChild);
+    node("line", { x1: pad, x2: width - pad, y1: axisY, y2: axisY, "class": "tl-axis" });
+    for (var i = 0; i <= 4; i++) {
+      var time = view.start + (view.end - view.start) * i / 4;
+      node("line", { x1: x(time), x2: x(time), y1: axisY, y2: axisY + 5, "class": "tl-axis" });
+      node("text", { x: x(time), y: axisY + 20, "text-anchor": "middle", "class": "tl-label", "aria-hidden": "true" }, label(time) + (i === 4 ? " UTC" : ""));
+    }
+    commits.forEach(function (commit) {
+      var cx = x(commit.time);
+      if (!commit.releases.length || cx < pad || cx > width - pad) return;
+      node("line", { x1: cx, x2: cx, y1: 14, y2: axisY, "class": "tl-release" });
+      node("text", { x: cx, y: 10, "text-anchor": "middle", "class": "tl-release-label", "aria-hidden": "true" }, commit.releases.join(", "));
+    });
+    commits.forEach(function (commit, index) {
+      var cx = x(commit.time);
+      if (cx < pad - 10 || cx > width - pad + 10) return;
+      var radius = Math.min(4 + Math.log(1 + commit.added + commit.deleted), 12);
+      var dot = node("circle", {
+        cx: cx, cy: axisY - 20 - (index % 3) * 12, r: radius, "class": "tl-commit", tabindex: 0,
+        role: "img", "aria-label": commit.short + " " + commit.subject + ", " + commit.author + ", " + commit.date +
+          (commit.tags.length ? ", " + commit.tags.join(", ") : "")
+      });
+      dot.addEventListener("mouseenter", function (event) { show(commit, event); });
+      dot.addEventListener("focus", function () { show(commit, null); });
+      dot.addEventListener("mouseleave", hide);
+      dot.addEventListener("blur", hide);
+    });
+  }
+
+  function show(commit, event) {
+    tip.textContent = "";
+    [commit.short + " " + commit.subject, commit.author + ", " + commit.date,
+     commit.files + " files, +" + commit.added + " -" + commit.deleted]
+      .concat(commit.tags.length ? [commit.tags.join(", ")] : []).forEach(function (line, i) {
+      var row = document.createElement(i === 0 ? "strong" : "div");
+      row.textContent = line;
+      tip.appendChild(row);
+    });
+    var box = svg.getBoundingClientRect();
+    var left = event ? event.pageX : box.left + window.scrollX;
+    var top = event ? event.pageY : box.top + window.scrollY;
+    tip.style.left = left + 12 + "px";
+    tip.style.top = top + 12 + "px";
+    tip.style.display = "block";
+  }
+
+  function hide() {
+    tip.style.display = "none";
+  }
+
+  svg.addEventListener("wheel", function (event) {
+    event.preventDefault();
+    var anchor = timeAt(event.clientX);
+    var factor = event.deltaY < 0 ? 0.8 : 1.25;
+    var start = anchor - (anchor - view.start) * factor;
+    var end = anchor + (view.end - anchor) * factor;
+    if (end - start < 60000) return;
+    view.start = Math.max(start, full.start);
+    view.end = Math.min(end, full.end);
+    draw();
+  });
+
+  var drag = null;
+  svg.addEventListener("mousedown", function (event) {
+    drag = { time: timeAt(event.clientX), start: view.start, end: view.end };
+  });
+  window.addEventListener("mousemove", function (event) {
+    if (!drag) return;
+    var span = drag.end - drag.start;
+    var box = svg.getBoundingClientRect();
+    var shift = (event.clientX - box.left) / box.width * width;
+    var offset = drag.time - (drag.start + (shift - pad) / (width - 2 * pad) * span);
+    var start = Math.min(Math.max(drag.start + offset, full.start), full.end - span);
+    view.start = start;
+    view.end = start + span;
+    draw();
+  });
+  window.addEventListener("mouseup", function () { drag = null; });
+  svg.addEventListener("dblclick", function () {
+    view.start = full.start;
+    view.end = full.end;
+    draw();
+  });
+
+  svg.setAttribute("viewBox", "0 0 " + width + " " + height);
+  draw();
+})();
@@ -0,0 +1,161 @@
+//! An Excel workbook with one sheet of commits, one of per-author stats and
+//! one of per-file churn, for spreadsheet users who filter and pivot the data.
+//! The banner becomes the print header and footer of every sheet.
+
+use rust_xlsxwriter::{ExcelDateTime, Format, Workbook, Worksheet, XlsxError};
+
+use crate::{
+    error::{Context, ReportError, Result},
+    render::ReportRenderer,
+    report::ReportData,
+    stats::{self, Metric},
+};
+
+/// Excel workbook reports.
+pub struct Xlsx;
+
+impl ReportRenderer for Xlsx {
+    fn format(&self) -> crate::render::Format {
+        crate::render::Format::Xlsx
+    }
+
+    fn write(&self, data: &ReportData, out: &mut dyn std::io::Write) -> Result<()> {
+        let bytes = self.render(data)?;
+        crate::render::written(self.format(), out.write_all(&bytes))
+    }
+
+    fn render(&self, data: &ReportData) -> Result<Vec<u8>> {
+        render(data)
+    }
+}
+
+pub fn render(data: &ReportData) -> Result<Vec<u8>> {
+    build(data)
+        .and_then(|mut workbook| workbook.save_to_buffer())
+        .context(ReportError::render, "Failed to build the Excel workbook")
+}
+
+fn build(data: &ReportData) -> std::result::Result<Workbook, XlsxError> {
+    let mut workbook = Workbook::new();
+    let header = Format::new().set_bold();
+    let date = Format::new().set_num_format("yyyy-mm-dd hh:mm:ss");
+
+    let sheet = workbook.add_worksheet().set_name("Commits")?;
+    let signatures = data.commits.iter().any(|commit| commit.signature.is_some());
+    let mut headers = vec![
+        "Hash", "Author", "Date", "Subject", "Files", "Added", "Deleted", "Message",
+    ];
+    let tags = data.commits.iter().any(|commit| !commit.tags.is_empty());
+    if tags {
+        headers.push("Tags");
+    }
+    if signatures {
+        headers.push("Signature");
+    }
+    write_header(sheet, &header, &headers)?;
+    for (row, commit) in (1..).zip(&data.commits) {
+        // Dates are written in the report timezone, since Excel has no offsets.
+        let local = data.dates.format_with(&commit.date, "%Y-%m-%dT%H:%M:%S");
+        sheet.write_string(row, 0, &commit.hash)?;
+        sheet.write_string(row, 1, &commit.author)?;
+        sheet.write_datetime_with_format(row, 2, &ExcelDateTime::parse_from_str(&local)?, &date)?;
+        sheet.write_string(row, 3, &commit.subject)?;
+        sheet.write_number(row, 4, commit.files_changed.len() as f64)?;
+        sheet.write_number(
+            row,
+            5,
+            commit.file_stats.iter().map(|f| f.additions).sum::<u64>() as f64,
+        )?;
+        sheet.write_number(
+            row,
+            6,
+            commit.file_stats.iter().map(|f| f.deletions).sum::<u64>() as f64,
+        )?;
+        sheet.write_string(row, 7, commit.body.trim())?;
+        if tags {
+            sheet.write_string(row, 8, commit.tags.join(", "))?;
+        }
+        if let Some(signature) = &commit.signature {
+            sheet.write_string(row, 8 + u16::from(tags), signature.describe())?;
+        }
+    }
+    finish(sheet, data.commits.len(), headers.len() as u16 - 1)?;
+    // Autofit does not measure formatted dates.
+    sheet.set_column_width(2, 20)?;
+
+    let authors = stats::AuthorActivity.compute(&data.commits);
+    let sheet = workbook.add_worksheet().set_name("Authors")?;
+    write_header(sheet, &header, &["Author", "Commits", "Added", "Deleted"])?;
+    for (row, author) in (1..).zip(&authors) {
+        sheet.write_string(row, 0, &author.author)?;
+        sheet.write_number(row, 1, author.commits as f64)?;
+        sheet.write_number(row, 2, author.additions as f64)?;
+        sheet.write_number(row, 3, author.deletions as f64)?;
+    }
+    finish(sheet, authors.len(), 3)?;
+
+    let files = stats::file_hotspots(&data.commits);
+    let sheet = workbook.add_worksheet().set_name("Files")?;
+    write_header(
+        sheet,
+        &header,
+        &["File", "Commits", "Added", "Deleted", "Churn", "Authors"],
+    )?;
+    for (row, file) in (1..).zip(&files) {
+        sheet.write_string(row, 0, &file.path)?;
+        sheet.write_number(row, 1, file.commits as f64)?;
+        sheet.write_number(row, 2, file.additions as f64)?;
+        sheet.write_number(row, 3, file.deletions as f64)?;
+        sheet.write_number(row, 4, file.churn() as f64)?;
+        sheet.write_string(row, 5, file.authors.join(", "))?;
+    }
+    finish(sheet, files.len(), 5)?;
+
+    let header = data.header().map(|text| print_banner(&text));
+    let footer = data.footer().map(|text| print_banner(&text));
+    for sheet in workbook.worksheets_mut() {
+        if let Some(header) = &header {
+            sheet.set_header(header);
+        }
+        if let Some(footer) = &footer {
+            sheet.set_footer(footer);
+        }
+    }
+
+    Ok(workbook)
+}
+
+/// Banner text as a centered print header or footer. `&` starts a control
+/// code there, and Excel rejects headers over 255 characters.
+fn print_banner(text: &str) -> String {
+    let mut banner = String::from("&C");
+    for c in text.chars() {
+        if banner.chars().count() >= 250 {
+            break;
+        }
+        if c == '&' {
+            banner.push('&');
+        }
+        banner.push(c);
+    }
+    banner
+}
+
+fn write_header(
+    sheet: &mut Worksheet,
+    format: &Format,
+    headers: &[&str],
+) -> std::result::Result<(), XlsxError> {
+    for (col, title) in (0..).zip(headers) {
+        sheet.write_string_with_format(0, col, *title, format)?;
+    }
+    Ok(())
+}
+
+/// Freezes the header row, adds filter buttons and sizes the columns.
+fn finish(sheet: &mut Worksheet, rows: usize, last_col: u16) -> std::result::Result<(), XlsxError> {
+    sheet.set_freeze_panes(1, 0)?;
+    sheet.autofilter(0, 0, rows as u32, last_col)?;
+    sheet.autofit();
+    Ok(())
+}
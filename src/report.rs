@@ -0,0 +1,411 @@
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{
+    classify::{self, Category},
+    dates::DateStyle,
+    git::Commit,
+    i18n::Locale,
+    language::Translation,
+    version,
+};
+
+/// Lines added and deleted across a range, in total and in test files.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LineChanges {
+    pub added: u64,
+    pub deleted: u64,
+    pub test_added: u64,
+    pub test_deleted: u64,
+}
+
+impl LineChanges {
+    pub fn code_added(&self) -> u64 {
+        self.added - self.test_added
+    }
+
+    pub fn code_deleted(&self) -> u64 {
+        self.deleted - self.test_deleted
+    }
+}
+
+/// Everything a renderer needs to produce a report in any output format.
+pub struct ReportData {
+    pub repo_path: String,
+    pub generated_at: DateTime<Utc>,
+    pub from_commit: Commit,
+    pub to_commit: Commit,
+    pub commits: Vec<Commit>,
+    /// AI-written report text; when set it replaces the detailed commit list.
+    pub ai_summary: Option<String>,
+    /// Convert the Markdown of the AI summary to the output format instead
+    /// of showing it verbatim (`--ai-postprocess convert`).
+    pub convert_ai_summary: bool,
+    /// Short AI-written summaries of single commits, keyed by commit hash.
+    pub commit_summaries: HashMap<String, String>,
+    /// Translations of the messages written in another language than the
+    /// report, keyed by commit hash; shown after the original message.
+    pub translations: HashMap<String, Translation>,
+    /// Heuristic impact scores keyed by commit hash, shown with each commit.
+    pub impact: HashMap<String, u32>,
+    /// Order of the detailed commit list.
+    pub order: CommitOrder,
+    /// Optional analysis sections appended after the main report.
+    pub sections: Vec<Section>,
+    /// Timezone and format of the dates shown to readers.
+    pub dates: DateStyle,
+    /// Parts of the report to render, e.g. only [`Part::Summary`] for managers.
+    pub parts: Vec<Part>,
+    /// Text put above and below the report in every format.
+    pub banner: Banner,
+    /// Title, author and project name shown instead of the defaults.
+    pub branding: Branding,
+    /// Language of the headings and labels.
+    pub locale: Locale,
+    /// URL templates of the links shown with each commit in the rich formats,
+    /// by label, such as the CI build of the commit. `{hash}` and `{short}`
+    /// are replaced with the commit's full and abbreviated hash.
+    pub commit_links: BTreeMap<String, String>,
+    /// Colour scheme of the HTML report.
+    pub theme: Theme,
+    /// Load the Mermaid script from its CDN, so HTML reports draw their
+    /// diagrams instead of showing their sources.
+    pub mermaid_cdn: bool,
+    /// Start Markdown and HTML reports with YAML front matter.
+    pub front_matter: bool,
+}
+
+/// Fixed text every report starts or ends with, such as the company name, a
+/// confidentiality notice or the distribution list. `{repository}`, `{from}`,
+/// `{to}`, `{date}`, `{title}`, `{author}` and `{project}` are replaced with
+/// the report's values.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Banner {
+    pub header: Option<String>,
+    pub footer: Option<String>,
+}
+
+/// Names a report carries for its readers, from `--title`, `--report-author`
+/// and `--project-name` or the `[branding]` config section. The title
+/// replaces "Git Commit Report"; author and project are listed in the
+/// summary when set.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Branding {
+    pub title: Option<String>,
+    pub author: Option<String>,
+    pub project: Option<String>,
+}
+
+impl ReportData {
+    /// The report heading: the custom title or the localized default.
+    pub fn title(&self) -> &str {
+        self.branding
+            .title
+            .as_deref()
+            .unwrap_or_else(|| self.locale.text("report-title"))
+    }
+
+    /// The project and report author as summary rows, when set.
+    pub fn branding_fields(&self) -> Vec<(&str, &str)> {
+        [
+            ("project", &self.branding.project),
+            ("prepared-by", &self.branding.author),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| Some((self.locale.text(key), value.as_deref()?)))
+        .collect()
+    }
+
+    pub fn shows(&self, part: Part) -> bool {
+        self.parts.contains(&part)
+    }
+
+    /// The AI summary, if there is one and [`Part::Details`] is selected.
+    pub fn visible_ai_summary(&self) -> Option<&str> {
+        self.ai_summary
+            .as_deref()
+            .filter(|_| self.shows(Part::Details))
+    }
+
+    /// The banner header with its placeholders filled in.
+    pub fn header(&self) -> Option<String> {
+        self.banner_text(self.banner.header.as_deref())
+    }
+
+    /// The banner footer with its placeholders filled in.
+    pub fn footer(&self) -> Option<String> {
+        self.banner_text(self.banner.footer.as_deref())
+    }
+
+    fn banner_text(&self, template: Option<&str>) -> Option<String> {
+        let template = template.map(str::trim).filter(|text| !text.is_empty())?;
+        fn short(hash: &str) -> &str {
+            &hash[..hash.len().min(8)]
+        }
+        Some(
+            template
+                .replace("{repository}", &self.repo_path)
+                .replace("{title}", self.title())
+                .replace(
+                    "{author}",
+                    self.branding.author.as_deref().unwrap_or_default(),
+                )
+                .replace(
+                    "{project}",
+                    self.branding.project.as_deref().unwrap_or_default(),
+                )
+                .replace("{from}", short(&self.from_commit.hash))
+                .replace("{to}", short(&self.to_commit.hash))
+                .replace(
+                    "{date}",
+                    &self.dates.format_with(&self.generated_at, "%Y-%m-%d"),
+                ),
+        )
+    }
+
+    /// The heading of a translated message, e.g. `Translation from Deutsch`.
+    pub fn translation_label(&self, translation: &Translation) -> String {
+        self.locale
+            .format("translation", &[("language", translation.language.name)])
+    }
+
+    /// The `commit_links` of `commit` as label and URL.
+    pub fn links(&self, commit: &Commit) -> Vec<(&str, String)> {
+        self.commit_links
+            .iter()
+            .map(|(label, template)| {
+                let url = template
+                    .replace("{hash}", &commit.hash)
+                    .replace("{short}", &commit.hash[..commit.hash.len().min(8)]);
+                (label.as_str(), url)
+            })
+            .collect()
+    }
+
+    /// `--front-matter`: YAML metadata for static site generators and
+    /// knowledge bases to index the report by, ending in a blank line.
+    pub fn front_matter(&self) -> Option<String> {
+        if !self.front_matter {
+            return None;
+        }
+        let mut fields = vec![("title", yaml_string(self.title()))];
+        if let Some(author) = &self.branding.author {
+            fields.push(("author", yaml_string(author)));
+        }
+        if let Some(project) = &self.branding.project {
+            fields.push(("project", yaml_string(project)));
+        }
+        fields.extend([
+            ("repository", yaml_string(&self.repo_path)),
+            ("from", yaml_string(&self.from_commit.hash)),
+            ("to", yaml_string(&self.to_commit.hash)),
+            ("commits", self.commits.len().to_string()),
+            (
+                "generator",
+                yaml_string(concat!("git-report ", env!("CARGO_PKG_VERSION"))),
+            ),
+            (
+                "date",
+                self.generated_at
+                    .to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            ),
+            ("lang", yaml_string(self.locale.code())),
+        ]);
+        let mut yaml = String::from("---\n");
+        for (key, value) in fields {
+            yaml.push_str(&format!("{}: {}\n", key, value));
+        }
+        yaml.push_str("---\n\n");
+        Some(yaml)
+    }
+
+    /// The release tags on the commits of the range, oldest first, with the
+    /// commit each points at.
+    pub fn release_tags(&self) -> Vec<(&str, &Commit)> {
+        self.commits
+            .iter()
+            .flat_map(|commit| {
+                commit
+                    .tags
+                    .iter()
+                    .filter(|tag| version::is_release_tag(tag))
+                    .map(move |tag| (tag.as_str(), commit))
+            })
+            .collect()
+    }
+
+    /// The [`Self::release_tags`] with their dates, e.g.
+    /// `v1.2.0 (2024-03-04 10:00 UTC)`; `None` without any.
+    pub fn releases(&self) -> Option<String> {
+        let releases: Vec<String> = self
+            .release_tags()
+            .into_iter()
+            .map(|(tag, commit)| format!("{} ({})", tag, self.dates.format(&commit.date)))
+            .collect();
+        (!releases.is_empty()).then(|| releases.join(", "))
+    }
+
+    /// The dates of the range ends, e.g. `2024-03-01 09:12 UTC to 2024-03-08 17:40 UTC`.
+    pub fn date_range(&self) -> String {
+        self.locale.format(
+            "date-range-value",
+            &[
+                ("start", &self.dates.format(&self.from_commit.date)),
+                ("end", &self.dates.format(&self.to_commit.date)),
+            ],
+        )
+    }
+
+    /// The lines added and deleted across the range, split into test files
+    /// (by [`classify::path_category`]) and the rest.
+    pub fn line_changes(&self) -> LineChanges {
+        let mut changes = LineChanges::default();
+        for stat in self.commits.iter().flat_map(|commit| &commit.file_stats) {
+            changes.added += stat.additions;
+            changes.deleted += stat.deletions;
+            if classify::path_category(&stat.path) == Some(Category::Test) {
+                changes.test_added += stat.additions;
+                changes.test_deleted += stat.deletions;
+            }
+        }
+        changes
+    }
+
+    /// The line changes as shown in the summary, e.g. `+120 -30 (code +100
+    /// -25, tests +20 -5)`.
+    pub fn lines_changed(&self) -> String {
+        let changes = self.line_changes();
+        self.locale.format(
+            "lines-changed-value",
+            &[
+                ("added", &changes.added.to_string()),
+                ("deleted", &changes.deleted.to_string()),
+                ("code_added", &changes.code_added().to_string()),
+                ("code_deleted", &changes.code_deleted().to_string()),
+                ("test_added", &changes.test_added.to_string()),
+                ("test_deleted", &changes.test_deleted.to_string()),
+            ],
+        )
+    }
+
+    /// Commits in the order of the detailed list. Commits with equal scores
+    /// keep their range order.
+    pub fn detailed_commits(&self) -> Vec<&Commit> {
+        let mut commits: Vec<&Commit> = self.commits.iter().collect();
+        if self.order == CommitOrder::Impact {
+            commits.sort_by_key(|commit| {
+                std::cmp::Reverse(self.impact.get(&commit.hash).copied().unwrap_or(0))
+            });
+        }
+        commits
+    }
+
+    /// Whether the per-commit list is rendered: it needs [`Part::Details`] or
+    /// [`Part::Files`], and an AI summary takes its place.
+    pub fn lists_commits(&self) -> bool {
+        self.ai_summary.is_none() && (self.shows(Part::Details) || self.shows(Part::Files))
+    }
+}
+
+/// A double-quoted YAML scalar.
+fn yaml_string(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// A part of the report that can be switched on and off with `--sections`.
+/// Renderers always emit the selected parts in this order.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    /// Repository, range, dates and commit count
+    Summary,
+    /// Commit type breakdown and the analysis sections (hotspots, metrics, Jira, annotations, ...)
+    Stats,
+    /// One entry per commit with hash, author, date and message, or the AI summary
+    Details,
+    /// Files changed by each commit
+    Files,
+}
+
+impl Part {
+    pub const ALL: [Part; 4] = [Part::Summary, Part::Stats, Part::Details, Part::Files];
+}
+
+/// How the detailed commit list is ordered, chosen with `--sort`.
+#[derive(ValueEnum, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CommitOrder {
+    /// Range order, oldest first
+    #[default]
+    Date,
+    /// Highest impact score first
+    Impact,
+}
+
+/// Colour scheme of HTML reports, chosen with `--high-contrast`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Theme {
+    /// Dark text on a light background; switches to high contrast when the
+    /// reader's system asks for more contrast
+    #[default]
+    Default,
+    /// White text on black with yellow links and focus outlines
+    HighContrast,
+}
+
+/// A format-independent report section. Features describe their output as
+/// blocks and every renderer knows how to display each kind of block.
+pub struct Section {
+    /// Stable key used by machine-readable formats, e.g. `hotspots`.
+    pub id: String,
+    pub title: String,
+    pub blocks: Vec<Block>,
+}
+
+pub enum Block {
+    /// `Label: value` pairs.
+    Fields(Vec<(String, String)>),
+    /// A titled bullet list.
+    List { title: String, items: Vec<String> },
+    /// Tabular data; every row has one cell per header.
+    Table {
+        headers: Vec<String>,
+        rows: Vec<Vec<String>>,
+    },
+    /// A free-form paragraph.
+    Text(String),
+    /// Preformatted text, shown as it is in a monospaced font.
+    Code(String),
+    /// A Mermaid diagram, drawn in Markdown and HTML reports; other formats
+    /// show its source.
+    Mermaid(String),
+}
+
+impl Section {
+    pub fn new(id: &str, title: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            title: title.to_string(),
+            blocks: Vec::new(),
+        }
+    }
+
+    pub fn with_block(mut self, block: Block) -> Self {
+        self.blocks.push(block);
+        self
+    }
+}
@@ -0,0 +1,84 @@
+use serde::Deserialize;
+use std::{fs, path::Path};
+
+use crate::{
+    error::{Context, ReportError, Result},
+    git::Commit,
+    verify,
+};
+
+/// The parts of a JSON report (`--format json`) or an archived dataset that
+/// a report diff compares; both have the same keys for them.
+#[derive(Deserialize)]
+pub struct SavedReport {
+    #[serde(default)]
+    pub from: Option<String>,
+    #[serde(default)]
+    pub to: Option<String>,
+    #[serde(default)]
+    pub commits: Vec<Commit>,
+}
+
+pub fn load(path: &Path) -> Result<SavedReport> {
+    let content = fs::read_to_string(path).with_context(ReportError::io, || {
+        format!("Failed to read report {}", path.display())
+    })?;
+    serde_json::from_str(&content).with_context(ReportError::parse, || {
+        format!(
+            "Failed to parse {} as a JSON report with commit details (written with --format json and the details part)",
+            path.display()
+        )
+    })
+}
+
+fn describe(path: &Path, report: &SavedReport) -> String {
+    let end = |hash: &Option<String>| {
+        hash.as_deref().map_or("?".to_string(), |hash| {
+            hash[..hash.len().min(8)].to_string()
+        })
+    };
+    format!(
+        "{} ({}..{}, {} commits)",
+        path.display(),
+        end(&report.from),
+        end(&report.to),
+        report.commits.len()
+    )
+}
+
+/// Compares the commits of two saved reports, e.g. of the same range before
+/// and after a rebase: added and removed commits, and commits whose data
+/// changed, with rewritten commits paired up by author, date and subject.
+pub fn explain(old_path: &Path, old: &SavedReport, new_path: &Path, new: &SavedReport) -> String {
+    let mut output = format!(
+        "Comparing {}\n     with {}\n\n",
+        describe(old_path, old),
+        describe(new_path, new)
+    );
+    for (name, old_end, new_end) in [("FROM", &old.from, &new.from), ("TO", &old.to, &new.to)] {
+        if let (Some(old_end), Some(new_end)) = (old_end, new_end) {
+            if old_end != new_end {
+                output.push_str(&format!(
+                    "{} moved: {} -> {}\n",
+                    name,
+                    &old_end[..old_end.len().min(8)],
+                    &new_end[..new_end.len().min(8)]
+                ));
+            }
+        }
+    }
+
+    let diff = verify::compare(&old.commits, &new.commits);
+    if diff.is_empty() {
+        output.push_str("Both reports have the same commits\n");
+        return output;
+    }
+    output.push_str(&verify::render(&diff));
+    output.push_str(&format!(
+        "\n{} added, {} removed, {} changed\n",
+        diff.added.len(),
+        diff.removed.len(),
+        diff.changed.len()
+    ));
+    output
+}
@@ -0,0 +1,200 @@
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use clap::ValueEnum;
+use std::collections::HashMap;
+
+use crate::{ai::Backend, archive, error::Result, git::Commit, progress::Progress};
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Period {
+    Week,
+    Month,
+    Quarter,
+    Year,
+}
+
+/// A calendar period with an inclusive start and exclusive end date.
+pub struct PeriodRange {
+    pub label: String,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+/// Returns the calendar period of the given kind that contains `date`.
+pub fn period_containing(period: Period, date: NaiveDate) -> PeriodRange {
+    let first_of_month = |year: i32, month: u32| {
+        NaiveDate::from_ymd_opt(year, month, 1).expect("valid first day of month")
+    };
+    let add_months = |start: NaiveDate, months: u32| {
+        let total = start.month0() + months;
+        first_of_month(start.year() + (total / 12) as i32, total % 12 + 1)
+    };
+
+    match period {
+        Period::Week => {
+            let start = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+            let week = date.iso_week();
+            PeriodRange {
+                label: format!("{}-W{:02}", week.year(), week.week()),
+                start,
+                end: start + Duration::days(7),
+            }
+        }
+        Period::Month => {
+            let start = first_of_month(date.year(), date.month());
+            PeriodRange {
+                label: start.format("%Y-%m").to_string(),
+                start,
+                end: add_months(start, 1),
+            }
+        }
+        Period::Quarter => {
+            let quarter = date.month0() / 3;
+            let start = first_of_month(date.year(), quarter * 3 + 1);
+            PeriodRange {
+                label: format!("{}-Q{}", date.year(), quarter + 1),
+                start,
+                end: add_months(start, 3),
+            }
+        }
+        Period::Year => {
+            let start = first_of_month(date.year(), 1);
+            PeriodRange {
+                label: date.year().to_string(),
+                start,
+                end: add_months(start, 12),
+            }
+        }
+    }
+}
+
+pub struct RollupData {
+    pub reports: usize,
+    pub commits: Vec<Commit>,
+}
+
+/// Merges the commits of all datasets that fall into the period, dropping
+/// duplicates from overlapping reports.
+pub fn collect(datasets: &[archive::Dataset], range: &PeriodRange) -> RollupData {
+    let mut reports = 0;
+    let mut commits: Vec<Commit> = Vec::new();
+
+    for dataset in datasets {
+        let mut contributed = false;
+        for commit in &dataset.commits {
+            let day = commit.date.date_naive();
+            if day < range.start || day >= range.end {
+                continue;
+            }
+            contributed = true;
+            if !commits.iter().any(|c| c.hash == commit.hash) {
+                commits.push(commit.clone());
+            }
+        }
+        if contributed {
+            reports += 1;
+        }
+    }
+
+    commits.sort_by_key(|c| c.date);
+
+    RollupData { reports, commits }
+}
+
+fn count_by<'a>(items: impl Iterator<Item = &'a str>) -> Vec<(String, usize)> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for item in items {
+        *counts.entry(item).or_default() += 1;
+    }
+
+    let mut counts: Vec<(String, usize)> = counts
+        .into_iter()
+        .map(|(item, count)| (item.to_string(), count))
+        .collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
+
+pub fn generate_rollup_report(repo_path: &str, range: &PeriodRange, data: &RollupData) -> String {
+    let mut report = String::new();
+
+    report.push_str(&format!("Git Rollup Report: {}\n", range.label));
+    report.push_str("================\n\n");
+    report.push_str(&format!("Repository: {}\n", repo_path));
+    report.push_str(&format!(
+        "Generated: {}\n",
+        Utc::now().format("%Y-%m-%d %H:%M:%S UTC")
+    ));
+    report.push_str(&format!(
+        "Period: {} to {}\n",
+        range.start,
+        range.end - Duration::days(1)
+    ));
+    report.push_str(&format!("Archived Reports: {}\n", data.reports));
+    report.push_str(&format!("Total Commits: {}\n\n", data.commits.len()));
+
+    report.push_str("Authors\n");
+    report.push_str("-------\n");
+    for (author, count) in count_by(data.commits.iter().map(|c| c.author.as_str())) {
+        report.push_str(&format!("  {} ({} commits)\n", author, count));
+    }
+    report.push('\n');
+
+    report.push_str("Most Changed Files\n");
+    report.push_str("------------------\n");
+    let files = count_by(
+        data.commits
+            .iter()
+            .flat_map(|c| c.files_changed.iter().map(|f| f.as_str())),
+    );
+    for (file, count) in files.iter().take(10) {
+        report.push_str(&format!("  {} ({} commits)\n", file, count));
+    }
+    report.push('\n');
+
+    report.push_str("Commits by Month\n");
+    report.push_str("================\n\n");
+    let mut current_month = String::new();
+    for commit in &data.commits {
+        let month = commit.date.format("%Y-%m").to_string();
+        if month != current_month {
+            report.push_str(&format!("{}\n", month));
+            current_month = month;
+        }
+        report.push_str(&format!(
+            "  - {} ({}, {})\n",
+            commit.subject,
+            &commit.hash[..8],
+            commit.author
+        ));
+    }
+
+    report
+}
+
+pub fn generate_ai_rollup(
+    repo_path: &str,
+    range: &PeriodRange,
+    rollup_report: &str,
+    ai: &dyn Backend,
+) -> Result<String> {
+    let prompt = format!(
+        "You are an assistant who writes high-level periodic summaries of software work.\n\
+        Below is a rollup of all commits from previously generated reports for the period {}.\n\
+        Write a concise overview for a year-end or quarterly review:\n\
+        1. Start with 2-3 sentences describing the overall direction of the work.\n\
+        2. Group the changes into a few major themes and describe each in one or two sentences.\n\
+        3. Mention notable contributors and the areas they worked on.\n\
+        4. Do not list every commit; focus on the bigger picture.\n\
+        \n\
+        Path: {}\n\
+        \n\
+        {}",
+        range.label, repo_path, rollup_report
+    );
+
+    let progress = Progress::spinner(&format!("Waiting for {}", ai.model()));
+    let response = ai.generate(&prompt, 0.7);
+    progress.finish();
+
+    response
+}
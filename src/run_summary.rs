@@ -0,0 +1,124 @@
+use serde::Serialize;
+use std::{collections::HashSet, fs, path::Path, time::Duration};
+
+use crate::{
+    ai::TokenUsage,
+    error::{Context, ReportError, Result},
+    git::Commit,
+};
+
+/// What one report run processed and produced, and how long it took; printed
+/// at the end of the run and written by `--stats-json`.
+#[derive(Debug, Default, Serialize)]
+pub struct RunSummary {
+    pub commits: usize,
+    pub authors: usize,
+    /// Distinct files changed in the range.
+    pub files: usize,
+    pub lines_added: u64,
+    pub lines_deleted: u64,
+    pub output_files: Vec<String>,
+    pub durations: Durations,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ai: Option<AiUsage>,
+}
+
+/// Phase durations in milliseconds; `total_ms` also covers sections and
+/// deliveries.
+#[derive(Debug, Default, Serialize)]
+pub struct Durations {
+    pub collect_ms: u64,
+    pub ai_ms: u64,
+    pub render_ms: u64,
+    pub total_ms: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AiUsage {
+    pub provider: String,
+    pub model: String,
+    #[serde(flatten)]
+    pub usage: TokenUsage,
+    /// Commit summaries taken from the cache instead of the backend.
+    pub reused_summaries: usize,
+}
+
+fn millis(duration: Duration) -> u64 {
+    duration.as_millis().try_into().unwrap_or(u64::MAX)
+}
+
+fn seconds(ms: u64) -> String {
+    format!("{:.2}s", ms as f64 / 1000.0)
+}
+
+impl RunSummary {
+    /// Counts commits, authors, files and line churn of the reported commits.
+    pub fn new(commits: &[Commit]) -> Self {
+        let authors: HashSet<&str> = commits.iter().map(|c| c.author.as_str()).collect();
+        let files: HashSet<&str> = commits
+            .iter()
+            .flat_map(|c| c.files_changed.iter().map(String::as_str))
+            .collect();
+        let stats = commits.iter().flat_map(|c| &c.file_stats);
+
+        Self {
+            commits: commits.len(),
+            authors: authors.len(),
+            files: files.len(),
+            lines_added: stats.clone().map(|f| f.additions).sum(),
+            lines_deleted: stats.map(|f| f.deletions).sum(),
+            ..Default::default()
+        }
+    }
+
+    pub fn set_durations(
+        &mut self,
+        collect: Duration,
+        ai: Duration,
+        render: Duration,
+        total: Duration,
+    ) {
+        self.durations = Durations {
+            collect_ms: millis(collect),
+            ai_ms: millis(ai),
+            render_ms: millis(render),
+            total_ms: millis(total),
+        };
+    }
+
+    /// One `key=value` line that reads well in a terminal and greps well in
+    /// CI logs.
+    pub fn line(&self) -> String {
+        let mut line = format!(
+            "commits={} authors={} files={} added={} deleted={} outputs={} time={} collect={} ai={} render={}",
+            self.commits,
+            self.authors,
+            self.files,
+            self.lines_added,
+            self.lines_deleted,
+            self.output_files.len(),
+            seconds(self.durations.total_ms),
+            seconds(self.durations.collect_ms),
+            seconds(self.durations.ai_ms),
+            seconds(self.durations.render_ms)
+        );
+        if let Some(ai) = &self.ai {
+            line.push_str(&format!(
+                " ai_requests={} input_tokens={} output_tokens={}",
+                ai.usage.requests, ai.usage.input_tokens, ai.usage.output_tokens
+            ));
+            if ai.reused_summaries > 0 {
+                line.push_str(&format!(" reused_summaries={}", ai.reused_summaries));
+            }
+        }
+        line
+    }
+
+    pub fn write_json(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)
+            .context(ReportError::render, "Failed to serialize the run summary")?;
+        fs::write(path, json + "\n").with_context(ReportError::io, || {
+            format!("Failed to write run summary {}", path.display())
+        })
+    }
+}
@@ -0,0 +1,124 @@
+use clap::ValueEnum;
+use regex_automata::meta::Regex;
+
+use crate::{dates::DateStyle, error::Result, filter, git::Commit};
+
+/// The parts of a commit a search looks at.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Subject,
+    Body,
+    Path,
+}
+
+impl Field {
+    pub const ALL: [Field; 3] = [Field::Subject, Field::Body, Field::Path];
+
+    fn label(self) -> &'static str {
+        match self {
+            Field::Subject => "subject",
+            Field::Body => "body",
+            Field::Path => "path",
+        }
+    }
+}
+
+enum Matcher {
+    Text {
+        needle: String,
+        case_sensitive: bool,
+    },
+    Regex(Regex),
+}
+
+/// `search`: finds commits whose subject, body lines or changed paths contain
+/// the query, or match it with `--regex`. Case is ignored unless
+/// `--case-sensitive` is given.
+pub struct Search {
+    matcher: Matcher,
+    fields: Vec<Field>,
+}
+
+/// A matching commit with the lines that matched, by field.
+pub struct Hit<'a> {
+    pub commit: &'a Commit,
+    pub matches: Vec<(Field, &'a str)>,
+}
+
+impl Search {
+    pub fn new(query: &str, regex: bool, case_sensitive: bool, fields: &[Field]) -> Result<Self> {
+        let matcher = if regex {
+            Matcher::Regex(filter::compile_regex(query, "search", !case_sensitive)?)
+        } else {
+            Matcher::Text {
+                needle: if case_sensitive {
+                    query.to_string()
+                } else {
+                    query.to_lowercase()
+                },
+                case_sensitive,
+            }
+        };
+        let fields = if fields.is_empty() {
+            Field::ALL.to_vec()
+        } else {
+            fields.to_vec()
+        };
+        Ok(Self { matcher, fields })
+    }
+
+    fn is_match(&self, text: &str) -> bool {
+        match &self.matcher {
+            Matcher::Text {
+                needle,
+                case_sensitive: true,
+            } => text.contains(needle.as_str()),
+            Matcher::Text { needle, .. } => text.to_lowercase().contains(needle.as_str()),
+            Matcher::Regex(regex) => regex.is_match(text),
+        }
+    }
+
+    /// The matches in `commit`, or `None` when nothing matched.
+    pub fn hit<'a>(&self, commit: &'a Commit) -> Option<Hit<'a>> {
+        let mut matches = Vec::new();
+        for &field in &self.fields {
+            let texts: Vec<&str> = match field {
+                Field::Subject => vec![commit.subject.as_str()],
+                Field::Body => commit.body.lines().map(str::trim).collect(),
+                Field::Path => commit.files_changed.iter().map(String::as_str).collect(),
+            };
+            matches.extend(
+                texts
+                    .into_iter()
+                    .filter(|text| self.is_match(text))
+                    .map(|text| (field, text)),
+            );
+        }
+        (!matches.is_empty()).then_some(Hit { commit, matches })
+    }
+}
+
+/// Lists the hits as numbered entries like those of a text report, with the
+/// lines that matched under each.
+pub fn render(query: &str, hits: &[Hit], searched: usize, dates: &DateStyle) -> String {
+    let mut output = format!(
+        "Found {} of {} commits matching \"{}\"\n",
+        hits.len(),
+        searched,
+        query
+    );
+
+    for (i, hit) in hits.iter().enumerate() {
+        let commit = hit.commit;
+        output.push_str(&format!("\n{}. {}\n", i + 1, commit.subject));
+        output.push_str(&format!("   Hash: {}\n", commit.hash));
+        output.push_str(&format!("   Author: {}\n", commit.author));
+        output.push_str(&format!("   Date: {}\n", dates.format(&commit.date)));
+        output.push_str("   Matches:\n");
+        for (field, text) in &hit.matches {
+            output.push_str(&format!("     {}: {}\n", field.label(), text));
+        }
+    }
+
+    output
+}
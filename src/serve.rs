@@ -0,0 +1,505 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use clap::ValueEnum;
+use colored::*;
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use git_report::{
+    config::Config,
+    diagnostics,
+    git::{self, Commit},
+    impact, labels,
+    render::{self, html::escape, Format, RendererRegistry},
+    report::{CommitOrder, ReportData},
+    squash,
+};
+
+use crate::{
+    add_diagnostics, ancestry_problem, branding, date_style, filter_commits, html_theme,
+    is_reversed, locale, message_filter, report_sections, repository_label, Args,
+};
+
+/// How long a client may take to send its whole request before it is
+/// dropped, however slowly the bytes trickle in.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long a client may stall while the response is sent to it.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Most bytes read of a request line and its headers; longer requests are
+/// refused instead of buffered.
+const MAX_REQUEST_BYTES: u64 = 16 * 1024;
+
+/// Most connections handled at once; more are closed straight away.
+const MAX_CONNECTIONS: usize = 32;
+
+const STYLE: &str =
+    "body{font-family:-apple-system,Segoe UI,Helvetica,Arial,sans-serif;margin:0;color:#1f2328}\
+header{padding:.75rem 1rem;border-bottom:1px solid #d0d7de;background:#f6f8fa}\
+form{display:flex;flex-wrap:wrap;gap:.5rem 1rem;align-items:center}\
+select,button{font:inherit;padding:.2rem .4rem}\
+.downloads{margin-top:.5rem;font-size:.9em}\
+.downloads a{margin-right:.75rem}\
+iframe{width:100%;height:calc(100vh - 7rem);border:0}";
+
+struct Response {
+    status: &'static str,
+    content_type: &'static str,
+    /// Name to save the body as; shown inline when `None`.
+    filename: Option<String>,
+    body: Vec<u8>,
+}
+
+impl Response {
+    fn page(status: &'static str, html: String) -> Self {
+        Self {
+            status,
+            content_type: "text/html; charset=utf-8",
+            filename: None,
+            body: html.into_bytes(),
+        }
+    }
+
+    fn error(status: &'static str, message: &str) -> Self {
+        Self::page(
+            status,
+            format!(
+                "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>git-report</title>\n<style>{}</style>\n</head>\n<body>\n<header><strong>{}</strong></header>\n<pre>{}</pre>\n<p><a href=\"/\">Back</a></p>\n</body>\n</html>\n",
+                STYLE,
+                status,
+                escape(message)
+            ),
+        )
+    }
+}
+
+fn content_type(format: Format) -> &'static str {
+    match format {
+        Format::Txt | Format::Asciidoc | Format::Org => "text/plain; charset=utf-8",
+        Format::Md => "text/markdown; charset=utf-8",
+        Format::Html => "text/html; charset=utf-8",
+        Format::Json => "application/json",
+        Format::Pdf => "application/pdf",
+        Format::Xlsx => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        Format::Ics => "text/calendar; charset=utf-8",
+    }
+}
+
+/// Decodes one `application/x-www-form-urlencoded` component.
+fn decode(component: &str) -> String {
+    let bytes = component.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| bytes.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (escaped, bytes[i]) {
+            (Some(byte), _) => {
+                decoded.push(byte);
+                i += 2;
+            }
+            (None, b'+') => decoded.push(b' '),
+            (None, byte) => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Percent-encodes a query value.
+fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+fn parse_query(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (decode(key), decode(value))
+        })
+        .collect()
+}
+
+/// Serves the report viewer on `listen` until interrupted, each report
+/// rendered from the repository as it is at that moment. The crate is
+/// synchronous throughout, so this is a plain HTTP/1.1 server on a
+/// [`TcpListener`] with a thread per connection rather than an async
+/// framework needing a runtime. Reports are rendered one at a time, since
+/// the data quality issues they list are noted process-wide.
+pub fn run(args: &Args, config: &Config, repo_path: &str, listen: &str) -> Result<()> {
+    let listener =
+        TcpListener::bind(listen).with_context(|| format!("Failed to listen on {}", listen))?;
+    println!(
+        "Serving reports on {} (Ctrl+C to stop)",
+        format!("http://{}/", listener.local_addr()?).bright_blue()
+    );
+
+    let rendering = Mutex::new(());
+    let connections = AtomicUsize::new(0);
+    thread::scope(|scope| {
+        for stream in listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(e) => {
+                    warn_failed(&anyhow::Error::new(e).context("Failed to accept a connection"));
+                    continue;
+                }
+            };
+            if connections.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+                connections.fetch_sub(1, Ordering::SeqCst);
+                continue;
+            }
+            let (rendering, connections) = (&rendering, &connections);
+            scope.spawn(move || {
+                if let Err(e) = handle(stream, args, config, repo_path, rendering) {
+                    warn_failed(&e);
+                }
+                connections.fetch_sub(1, Ordering::SeqCst);
+            });
+        }
+    });
+    Ok(())
+}
+
+fn warn_failed(error: &anyhow::Error) {
+    println!(
+        "{}",
+        format!("Warning: request failed: {:#}", error).yellow()
+    );
+}
+
+/// Reads from a connection within what is left of [`REQUEST_TIMEOUT`], so a
+/// client sending its request a byte at a time is dropped as well.
+struct Deadline<'a> {
+    stream: &'a TcpStream,
+    until: Instant,
+}
+
+impl Read for Deadline<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let left = self.until.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "the client took too long to send its request",
+            ));
+        }
+        self.stream.set_read_timeout(Some(left))?;
+        self.stream.read(buf)
+    }
+}
+
+/// Reads the request line and skips the headers, which say nothing the
+/// viewer needs. Returns `None` when they run past [`MAX_REQUEST_BYTES`] or
+/// the client stops sending before the blank line that ends them.
+fn read_request(stream: &TcpStream) -> Result<Option<String>> {
+    let deadline = Deadline {
+        stream,
+        until: Instant::now() + REQUEST_TIMEOUT,
+    };
+    let mut reader = BufReader::new(deadline.take(MAX_REQUEST_BYTES));
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    if !request_line.ends_with('\n') {
+        return Ok(None);
+    }
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header)?;
+        if !header.ends_with('\n') {
+            return Ok(None);
+        }
+        if header.trim().is_empty() {
+            return Ok(Some(request_line));
+        }
+    }
+}
+
+fn handle(
+    mut stream: TcpStream,
+    args: &Args,
+    config: &Config,
+    repo_path: &str,
+    rendering: &Mutex<()>,
+) -> Result<()> {
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    let Some(request_line) = read_request(&stream)? else {
+        return respond(
+            &mut stream,
+            &Response::error(
+                "400 Bad Request",
+                "The request is incomplete or its headers are too long",
+            ),
+        );
+    };
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let target = parts.next().unwrap_or_default();
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let query = parse_query(query);
+
+    let response = match (method, path) {
+        ("GET", "/") => index(args, &query),
+        ("GET", "/report") => {
+            let _rendering = rendering.lock().unwrap_or_else(|e| e.into_inner());
+            report(args, config, repo_path, &query)
+        }
+        ("GET", _) => Ok(Response::error("404 Not Found", "No such page")),
+        _ => Ok(Response::error(
+            "405 Method Not Allowed",
+            "Only GET requests are served",
+        )),
+    }
+    .unwrap_or_else(|e| Response::error("500 Internal Server Error", &format!("{:#}", e)));
+    println!("{} {} -> {}", method, target, response.status);
+    respond(&mut stream, &response)
+}
+
+fn respond(stream: &mut TcpStream, response: &Response) -> Result<()> {
+    let mut head = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n",
+        response.status,
+        response.content_type,
+        response.body.len()
+    );
+    if let Some(filename) = &response.filename {
+        head.push_str(&format!(
+            "Content-Disposition: attachment; filename=\"{}\"\r\n",
+            filename
+        ));
+    }
+    head.push_str("\r\n");
+    stream.write_all(head.as_bytes())?;
+    stream.write_all(&response.body)?;
+    Ok(())
+}
+
+/// A `<select>` of the branches and tags, with `selected` chosen.
+fn ref_select(name: &str, label: &str, refs: &[String], selected: &str) -> String {
+    let mut html = format!("<label>{} <select name=\"{}\">\n", label, name);
+    for reference in refs {
+        html.push_str(&format!(
+            "<option{}>{}</option>\n",
+            if reference == selected {
+                " selected"
+            } else {
+                ""
+            },
+            escape(reference)
+        ));
+    }
+    html.push_str("</select></label>\n");
+    html
+}
+
+/// The range picker, and once a range is picked, its HTML report below
+/// with download links for the other formats.
+fn index(args: &Args, query: &HashMap<String, String>) -> Result<Response> {
+    let mut refs = vec!["HEAD".to_string()];
+    refs.extend(git::refs()?);
+
+    let to = query
+        .get("to")
+        .cloned()
+        .unwrap_or_else(|| "HEAD".to_string());
+    let from = match query.get("from") {
+        Some(from) => from.clone(),
+        None => git::latest_tag(&format!("{}^", to))?
+            .unwrap_or_else(|| refs.last().cloned().unwrap_or_default()),
+    };
+
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str("<title>git-report</title>\n");
+    html.push_str(&format!("<style>{}</style>\n", STYLE));
+    html.push_str("</head>\n<body>\n<header>\n<form action=\"/\" method=\"get\">\n");
+    html.push_str("<strong>git-report</strong>\n");
+    html.push_str(&ref_select("from", "From", &refs, &from));
+    html.push_str(&ref_select("to", "To", &refs, &to));
+    html.push_str("<button type=\"submit\">Show report</button>\n</form>\n");
+
+    if query.contains_key("from") {
+        let range = format!("from={}&amp;to={}", encode(&from), encode(&to));
+        html.push_str("<div class=\"downloads\">Download:\n");
+        // Every format this build renders, so PDF and XLSX are only offered
+        // with their features.
+        let downloads = RendererRegistry::with_defaults().formats();
+        for format in downloads.iter().filter(|&&format| format != Format::Html) {
+            // The query takes the `--format` name, e.g. `asciidoc` for `.adoc`.
+            let name = format.to_possible_value().map_or_else(
+                || format.extension().to_string(),
+                |value| value.get_name().to_string(),
+            );
+            html.push_str(&format!(
+                "<a href=\"/report?{}&amp;format={}\">{}</a>\n",
+                range,
+                name,
+                format.extension()
+            ));
+        }
+        html.push_str("</div>\n</header>\n");
+        html.push_str(&format!(
+            "<iframe title=\"Report\" src=\"/report?{}&amp;format=html\"></iframe>\n",
+            range
+        ));
+    } else {
+        html.push_str(&format!(
+            "</header>\n<p style=\"padding:0 1rem\">Pick the range to report on{}.</p>\n",
+            if args.inclusive {
+                ", FROM included"
+            } else {
+                ""
+            }
+        ));
+    }
+    html.push_str("</body>\n</html>\n");
+
+    Ok(Response::page("200 OK", html))
+}
+
+/// The range and format a report request asks for, failing when the query
+/// is malformed or names commits that do not resolve or form no range.
+fn report_request(
+    args: &Args,
+    query: &HashMap<String, String>,
+) -> Result<(Commit, Commit, Format)> {
+    let reference = |name: &str| -> Result<&str> {
+        match query.get(name).map(String::as_str) {
+            Some("") | None => anyhow::bail!("Missing {} commit", name),
+            // Keep references from being read as git options.
+            Some(value) if value.starts_with('-') => {
+                anyhow::bail!("Invalid {} commit '{}'", name, value)
+            }
+            Some(value) => Ok(value),
+        }
+    };
+    let from = reference("from")?;
+    let to = reference("to")?;
+    let format = match query.get("format") {
+        Some(format) => Format::from_str(format, true)
+            .map_err(|_| anyhow::anyhow!("Unknown report format '{}'", format))?,
+        None => Format::Html,
+    };
+
+    let mut from_commit = git::get_commit(from)?;
+    let mut to_commit = git::get_commit(to)?;
+    if args.swap_reversed && is_reversed(&from_commit, &to_commit)? {
+        std::mem::swap(&mut from_commit, &mut to_commit);
+    }
+    if let Some(problem) = ancestry_problem(&from_commit, &to_commit, from, to)? {
+        anyhow::bail!(problem);
+    }
+    Ok((from_commit, to_commit, format))
+}
+
+/// One report, rendered in the requested format. A bad request is answered
+/// here; the errors returned are the server's own, from git or rendering.
+fn report(
+    args: &Args,
+    config: &Config,
+    repo_path: &str,
+    query: &HashMap<String, String>,
+) -> Result<Response> {
+    let (from_commit, to_commit, format) = match report_request(args, query) {
+        Ok(request) => request,
+        Err(e) => return Ok(Response::error("400 Bad Request", &format!("{:#}", e))),
+    };
+
+    // Reports are rendered one at a time, so the issues noted from here on
+    // are this report's.
+    diagnostics::take();
+    let mut commits = git::get_matching_commits_in_range(
+        &from_commit.hash,
+        &to_commit.hash,
+        args.inclusive,
+        &message_filter(args),
+    )?;
+    if args.as_squashed {
+        commits = squash::squash(commits, &to_commit.hash, None)?;
+    }
+    filter_commits(args, config, &mut commits)?;
+    labels::Labeler::new(&config.labels)?.apply(&mut commits);
+
+    if args.signatures {
+        git::signatures(&mut commits)?;
+    }
+
+    let locale = locale(args, repo_path)?;
+    let mut sections = report_sections(
+        args,
+        config,
+        &locale,
+        &from_commit,
+        &to_commit,
+        args.inclusive,
+        &mut commits,
+    )?;
+    add_diagnostics(&mut sections, &locale);
+    let impact = if args.impact || args.sort == CommitOrder::Impact {
+        impact::ImpactScorer::new(&config.impact.core_paths)?.scores(&commits)
+    } else {
+        HashMap::new()
+    };
+    let filename = format!(
+        "git-report-{}-{}.{}",
+        &from_commit.hash[..8],
+        &to_commit.hash[..8],
+        format.extension()
+    );
+
+    let data = ReportData {
+        repo_path: repository_label(args, repo_path),
+        generated_at: Utc::now(),
+        from_commit,
+        to_commit,
+        commits,
+        ai_summary: None,
+        convert_ai_summary: false,
+        commit_summaries: HashMap::new(),
+        translations: HashMap::new(),
+        impact,
+        order: args.sort,
+        sections,
+        dates: date_style(args),
+        parts: args.sections.clone(),
+        banner: config.banner.clone(),
+        branding: branding(args, config),
+        locale,
+        commit_links: config.commit_links.clone(),
+        theme: html_theme(args),
+        mermaid_cdn: args.mermaid_cdn,
+        // Front matter is for files a site generator picks up, not the page.
+        front_matter: args.front_matter && format != Format::Html,
+    };
+
+    Ok(Response {
+        status: "200 OK",
+        content_type: content_type(format),
+        filename: (format != Format::Html).then_some(filename),
+        body: render::render(format, &data)?,
+    })
+}
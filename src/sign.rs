@@ -0,0 +1,325 @@
+use clap::ValueEnum;
+use std::{
+    fs::{self, File},
+    io::Read,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use crate::error::{Context, ReportError, Result};
+
+/// The OpenPGP tool that makes detached report signatures.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Signer {
+    /// GnuPG; signs with the default key unless --sign-key is given
+    Gpg,
+    /// Sequoia's sq; needs --sign-key
+    Sq,
+}
+
+const ROUND_CONSTANTS: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// The SHA-256 digest of `data` as lowercase hex (FIPS 180-4), the format
+/// `sha256sum` prints.
+pub fn sha256_hex(data: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    hasher.finish()
+}
+
+/// An incremental SHA-256 (FIPS 180-4), for hashing data that is read in
+/// pieces, such as a report file.
+pub struct Sha256 {
+    state: [u32; 8],
+    /// Bytes of the block that is not full yet.
+    pending: Vec<u8>,
+    length: u64,
+}
+
+impl Default for Sha256 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sha256 {
+    pub fn new() -> Self {
+        Self {
+            state: [
+                0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
+                0x5be0cd19,
+            ],
+            pending: Vec::with_capacity(64),
+            length: 0,
+        }
+    }
+
+    pub fn update(&mut self, mut data: &[u8]) {
+        self.length += data.len() as u64;
+        if !self.pending.is_empty() {
+            let take = data.len().min(64 - self.pending.len());
+            self.pending.extend_from_slice(&data[..take]);
+            data = &data[take..];
+            if self.pending.len() < 64 {
+                return;
+            }
+            let block = std::mem::take(&mut self.pending);
+            self.compress(&block);
+        }
+        let mut blocks = data.chunks_exact(64);
+        for block in &mut blocks {
+            self.compress(block);
+        }
+        self.pending.extend_from_slice(blocks.remainder());
+    }
+
+    /// The digest as lowercase hex.
+    pub fn finish(mut self) -> String {
+        // Padding: a 1 bit, zeros up to 56 bytes mod 64, then the bit length.
+        let bits = self.length * 8;
+        let mut padding = vec![0x80];
+        padding.resize((119 - self.pending.len()) % 64 + 1, 0);
+        padding.extend_from_slice(&bits.to_be_bytes());
+        self.update(&padding);
+        debug_assert!(self.pending.is_empty());
+
+        self.state
+            .iter()
+            .map(|word| format!("{:08x}", word))
+            .collect()
+    }
+
+    fn compress(&mut self, block: &[u8]) {
+        let mut w = [0u32; 64];
+        for (i, word) in block.chunks_exact(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16]
+                .wrapping_add(s0)
+                .wrapping_add(w[i - 7])
+                .wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = self.state;
+        for i in 0..64 {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let choice = (e & f) ^ (!e & g);
+            let t1 = h
+                .wrapping_add(s1)
+                .wrapping_add(choice)
+                .wrapping_add(ROUND_CONSTANTS[i])
+                .wrapping_add(w[i]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let majority = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(majority);
+
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+
+        for (value, add) in self.state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *value = value.wrapping_add(add);
+        }
+    }
+}
+
+fn sidecar(path: &Path, extension: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(extension);
+    PathBuf::from(name)
+}
+
+/// Writes `<report>.sha256` in `sha256sum` format, so the report can be
+/// checked with `sha256sum -c`, and returns the digest. The report is read
+/// in pieces rather than all at once.
+pub fn write_digest(path: &Path) -> Result<String> {
+    let mut file = File::open(path).with_context(ReportError::io, || {
+        format!("Failed to read {} for signing", path.display())
+    })?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer).with_context(ReportError::io, || {
+            format!("Failed to read {} for signing", path.display())
+        })?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    let digest = hasher.finish();
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let digest_path = sidecar(path, ".sha256");
+    fs::write(&digest_path, format!("{}  {}\n", digest, name))
+        .with_context(ReportError::io, || {
+            format!("Failed to write digest file {}", digest_path.display())
+        })?;
+    Ok(digest)
+}
+
+/// Makes an ASCII-armored detached signature `<report>.asc` with gpg or sq
+/// and returns its path.
+pub fn sign_file(path: &Path, signer: Signer, key: Option<&str>) -> Result<PathBuf> {
+    let signature = sidecar(path, ".asc");
+    let mut command = match signer {
+        Signer::Gpg => {
+            let mut command = Command::new("gpg");
+            command.args(["--batch", "--yes", "--armor", "--detach-sign"]);
+            if let Some(key) = key {
+                command.args(["--local-user", key]);
+            }
+            command.arg("--output").arg(&signature).arg(path);
+            command
+        }
+        Signer::Sq => {
+            let key = key.ok_or_else(|| {
+                ReportError::sign(
+                    "Signing with sq needs the signing key, pass --sign-key".to_string(),
+                )
+            })?;
+            let mut command = Command::new("sq");
+            command
+                .args(["sign", "--signer", key, "--signature-file"])
+                .arg(&signature)
+                .arg(path);
+            command
+        }
+    };
+
+    let tool = match signer {
+        Signer::Gpg => "gpg",
+        Signer::Sq => "sq",
+    };
+    let output = command.output().with_context(ReportError::sign, || {
+        format!("Failed to run {}; is it installed?", tool)
+    })?;
+    if !output.status.success() {
+        return Err(ReportError::sign(format!(
+            "{} failed to sign {}: {}",
+            tool,
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    Ok(signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Known answers from the FIPS 180-4 examples (NIST CSRC).
+    const ABC: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+    #[test]
+    fn empty_message() {
+        assert_eq!(
+            sha256_hex(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn one_block_message() {
+        assert_eq!(sha256_hex(b"abc"), ABC);
+    }
+
+    #[test]
+    fn two_block_message() {
+        assert_eq!(
+            sha256_hex(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+        );
+        assert_eq!(
+            sha256_hex(
+                b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmno\
+                  ijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu"
+            ),
+            "cf5b16a778af8380036ce59e7b0492370b249b11e8f07a51afac45037afee9d1"
+        );
+    }
+
+    #[test]
+    fn million_a() {
+        let mut hasher = Sha256::new();
+        for _ in 0..1000 {
+            hasher.update(&[b'a'; 1000]);
+        }
+        assert_eq!(
+            hasher.finish(),
+            "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0"
+        );
+    }
+
+    #[test]
+    fn pieces_hash_like_the_whole() {
+        let data: Vec<u8> = (0..=255).cycle().take(1000).collect();
+        let whole = sha256_hex(&data);
+        for size in [1, 3, 55, 56, 63, 64, 65, 200] {
+            let mut hasher = Sha256::new();
+            for piece in data.chunks(size) {
+                hasher.update(piece);
+            }
+            assert_eq!(hasher.finish(), whole, "pieces of {} bytes", size);
+        }
+    }
+
+    #[test]
+    fn padding_boundaries() {
+        // Around the 56-byte mark the bit length spills into a second
+        // padding block; digests as printed by `sha256sum`.
+        for (len, digest) in [
+            (
+                55,
+                "d5e285683cd4efc02d021a5c62014694958901005d6f71e89e0989fac77e4072",
+            ),
+            (
+                56,
+                "04c26261370ee7541549d16dee320c723e3fd14671e66a099afe0a377c16888e",
+            ),
+            (
+                64,
+                "7ce100971f64e7001e8fe5a51973ecdfe1ced42befe7ee8d5fd6219506b5393c",
+            ),
+        ] {
+            assert_eq!(sha256_hex(&vec![b'x'; len]), digest, "{} bytes", len);
+        }
+    }
+
+    #[test]
+    fn digest_file_matches_sha256sum_format() {
+        let dir = std::env::temp_dir().join(format!("git-report-sign-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let report = dir.join("report.md");
+        fs::write(&report, "abc").unwrap();
+
+        assert_eq!(write_digest(&report).unwrap(), ABC);
+        assert_eq!(
+            fs::read_to_string(dir.join("report.md.sha256")).unwrap(),
+            format!("{}  report.md\n", ABC)
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
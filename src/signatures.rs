@@ -0,0 +1,112 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    git::{Commit, SignatureStatus},
+    i18n::Locale,
+    report::{Block, Section},
+};
+
+/// Counts the signed and unsigned commits of the range, with the keys that
+/// signed them and the commits whose signature is missing or not good.
+/// Commits that were not checked, see [`crate::git::signatures`], are left
+/// out.
+pub fn signatures_section(commits: &[Commit], locale: &Locale) -> Section {
+    let checked: Vec<(&Commit, _)> = commits
+        .iter()
+        .filter_map(|commit| Some((commit, commit.signature.as_ref()?)))
+        .collect();
+    let signed = checked
+        .iter()
+        .filter(|(_, signature)| signature.status.is_signed())
+        .count();
+
+    let mut statuses: BTreeMap<SignatureStatus, usize> = BTreeMap::new();
+    let mut keys: BTreeMap<(&str, &str), usize> = BTreeMap::new();
+    for (_, signature) in &checked {
+        *statuses.entry(signature.status).or_default() += 1;
+        if signature.status.is_signed() {
+            *keys
+                .entry((signature.key.as_str(), signature.signer.as_str()))
+                .or_default() += 1;
+        }
+    }
+
+    let share = |count: usize| match checked.len() {
+        0 => 0,
+        total => count * 100 / total,
+    };
+    let mut fields = vec![
+        (
+            locale.text("signed").to_string(),
+            locale.format(
+                "signed-value",
+                &[
+                    ("signed", &signed.to_string()),
+                    ("total", &checked.len().to_string()),
+                    ("share", &share(signed).to_string()),
+                ],
+            ),
+        ),
+        (
+            locale.text("unsigned").to_string(),
+            format!(
+                "{} ({}%)",
+                checked.len() - signed,
+                share(checked.len() - signed)
+            ),
+        ),
+    ];
+    fields.extend(
+        statuses
+            .iter()
+            .filter(|(status, _)| status.is_signed())
+            .map(|(status, count)| (capitalize(status.label()), count.to_string())),
+    );
+    let mut section = Section::new("signatures", locale.text("commit-signatures"))
+        .with_block(Block::Fields(fields));
+
+    if !keys.is_empty() {
+        let mut rows: Vec<((&str, &str), usize)> = keys.into_iter().collect();
+        rows.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        section = section.with_block(Block::Table {
+            headers: ["key", "signer", "commits"]
+                .map(|key| locale.text(key).to_string())
+                .to_vec(),
+            rows: rows
+                .into_iter()
+                .map(|((key, signer), count)| {
+                    vec![key.to_string(), signer.to_string(), count.to_string()]
+                })
+                .collect(),
+        });
+    }
+
+    let flagged: Vec<String> = checked
+        .iter()
+        .filter(|(_, signature)| signature.status != SignatureStatus::Good)
+        .map(|(commit, signature)| {
+            format!(
+                "{} {} ({})",
+                &commit.hash[..8],
+                commit.subject,
+                signature.status.label()
+            )
+        })
+        .collect();
+    if !flagged.is_empty() {
+        section = section.with_block(Block::List {
+            title: locale.text("without-good-signature").to_string(),
+            items: flagged,
+        });
+    }
+
+    section
+}
+
+fn capitalize(label: &str) -> String {
+    let mut chars = label.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
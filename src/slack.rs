@@ -0,0 +1,158 @@
+use serde_json::json;
+
+use crate::error::{Context, ReportError, Result};
+
+/// Slack accepts up to 40k characters per message, but long messages are
+/// collapsed and hard to read, so reports are split into smaller parts.
+const MAX_MESSAGE_CHARS: usize = 3500;
+
+/// Converts a plain-text or Markdown report into Slack mrkdwn.
+pub fn to_mrkdwn(report: &str) -> String {
+    let lines: Vec<&str> = report.lines().collect();
+    let mut output = Vec::with_capacity(lines.len());
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+        let next = lines.get(i + 1).map(|l| l.trim()).unwrap_or("");
+
+        if !line.trim().is_empty() && is_underline(next) {
+            output.push(format!("*{}*", escape(line.trim())));
+            i += 2;
+            continue;
+        }
+
+        let trimmed = line.trim_start();
+        if let Some(heading) = trimmed
+            .strip_prefix('#')
+            .map(|h| h.trim_start_matches('#').trim())
+        {
+            output.push(format!("*{}*", convert_inline(heading)));
+        } else if let Some(item) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+        {
+            let indent = &line[..line.len() - trimmed.len()];
+            output.push(format!("{}• {}", indent, convert_inline(item)));
+        } else {
+            output.push(convert_inline(line));
+        }
+        i += 1;
+    }
+
+    output.join("\n")
+}
+
+fn is_underline(line: &str) -> bool {
+    line.len() >= 3 && (line.chars().all(|c| c == '=') || line.chars().all(|c| c == '-'))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Converts `**bold**` and `[text](url)` Markdown spans to their mrkdwn form.
+fn convert_inline(text: &str) -> String {
+    let escaped = escape(text).replace("**", "*");
+    let mut result = String::with_capacity(escaped.len());
+    let mut rest = escaped.as_str();
+
+    while let Some(start) = rest.find('[') {
+        let Some(mid) = rest[start..].find("](").map(|m| start + m) else {
+            break;
+        };
+        let Some(end) = rest[mid..].find(')').map(|e| mid + e) else {
+            break;
+        };
+
+        result.push_str(&rest[..start]);
+        result.push_str(&format!(
+            "<{}|{}>",
+            &rest[mid + 2..end],
+            &rest[start + 1..mid]
+        ));
+        rest = &rest[end + 1..];
+    }
+    result.push_str(rest);
+
+    result
+}
+
+/// Splits a message into parts on line boundaries so each part stays within
+/// `MAX_MESSAGE_CHARS`. Single lines longer than the limit are hard-wrapped.
+pub fn split_message(text: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+
+    for line in text.lines() {
+        let mut line = line.to_string();
+        while line.chars().count() > MAX_MESSAGE_CHARS {
+            let split_at = line
+                .char_indices()
+                .nth(MAX_MESSAGE_CHARS)
+                .map(|(i, _)| i)
+                .unwrap_or(line.len());
+            if !current.is_empty() {
+                parts.push(std::mem::take(&mut current));
+            }
+            parts.push(line[..split_at].to_string());
+            line = line[split_at..].to_string();
+        }
+
+        if current.chars().count() + line.chars().count() + 1 > MAX_MESSAGE_CHARS
+            && !current.is_empty()
+        {
+            parts.push(std::mem::take(&mut current));
+        }
+        current.push_str(&line);
+        current.push('\n');
+    }
+
+    if !current.trim().is_empty() {
+        parts.push(current);
+    }
+
+    parts
+}
+
+/// Posts the report to a Slack incoming webhook. Long reports are sent as a
+/// numbered sequence of messages, since webhooks cannot reply in threads.
+pub fn post_report(webhook_url: &str, report: &str) -> Result<usize> {
+    let parts = split_message(&to_mrkdwn(report));
+    let total = parts.len();
+
+    let client = reqwest::blocking::Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .context(ReportError::delivery, "Failed to create HTTP client")?;
+
+    for (i, part) in parts.iter().enumerate() {
+        let text = if total > 1 {
+            format!("_({}/{})_\n{}", i + 1, total, part)
+        } else {
+            part.clone()
+        };
+
+        let response = client
+            .post(webhook_url)
+            .json(&json!({ "text": text, "mrkdwn": true }))
+            .send()
+            .context(ReportError::delivery, "Failed to connect to Slack webhook")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().unwrap_or_default();
+            return Err(ReportError::delivery(format!(
+                "Slack webhook returned error status {} for part {}/{}: {}",
+                status,
+                i + 1,
+                total,
+                body
+            )));
+        }
+    }
+
+    Ok(total)
+}
@@ -0,0 +1,256 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::{
+    ai::Backend,
+    error::Result,
+    git::{self, Commit},
+    progress::Progress,
+};
+
+/// A change as it lands on the mainline: a commit made there directly, or
+/// a merge with the branch commits it brought in, oldest first.
+pub struct Landed {
+    pub commit: Commit,
+    pub branch: Vec<Commit>,
+}
+
+/// Groups the commits of a range by how they landed on the mainline, the
+/// first-parent history of `tip`. Branch commits go with the merge that
+/// brought them in; commits outside the mainline no merge in the range
+/// reaches are kept on their own. Entries keep the order of `commits`.
+pub fn group(commits: Vec<Commit>, tip: &str) -> Vec<Landed> {
+    let index: HashMap<&str, usize> = commits
+        .iter()
+        .enumerate()
+        .map(|(i, commit)| (commit.hash.as_str(), i))
+        .collect();
+
+    let mut mainline = Vec::new();
+    let mut current = index.get(tip).copied();
+    while let Some(i) = current {
+        mainline.push(i);
+        current = commits[i]
+            .parents
+            .first()
+            .and_then(|parent| index.get(parent.as_str()).copied());
+    }
+
+    let mut claimed: HashSet<usize> = mainline.iter().copied().collect();
+    let mut branches: HashMap<usize, Vec<usize>> = HashMap::new();
+    // Oldest merge first, so a branch merged twice goes with its first merge.
+    for &merge in mainline.iter().rev() {
+        let mut queue: VecDeque<&str> = commits[merge]
+            .parents
+            .iter()
+            .skip(1)
+            .map(String::as_str)
+            .collect();
+        let mut branch = Vec::new();
+        while let Some(hash) = queue.pop_front() {
+            let Some(&i) = index.get(hash) else { continue };
+            if claimed.insert(i) {
+                branch.push(i);
+                queue.extend(commits[i].parents.iter().map(String::as_str));
+            }
+        }
+        branch.sort_unstable();
+        branches.insert(merge, branch);
+    }
+
+    let mut commits: Vec<Option<Commit>> = commits.into_iter().map(Some).collect();
+    let mut landed = Vec::new();
+    for i in 0..commits.len() {
+        let branch = branches
+            .get(&i)
+            .map(|branch| {
+                branch
+                    .iter()
+                    .filter_map(|&j| commits[j].take())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        if claimed.contains(&i) && !mainline.contains(&i) {
+            continue;
+        }
+        if let Some(commit) = commits[i].take() {
+            landed.push(Landed { commit, branch });
+        }
+    }
+    landed
+}
+
+impl Landed {
+    /// The pull (`#12`) or merge request (`!12`) the merge closed, from the
+    /// messages GitHub and GitLab write.
+    pub fn request(&self) -> Option<String> {
+        if let Some(rest) = self.commit.subject.strip_prefix("Merge pull request #") {
+            let number: String = rest.chars().take_while(char::is_ascii_digit).collect();
+            return (!number.is_empty()).then(|| format!("#{}", number));
+        }
+        self.commit.body.lines().find_map(|line| {
+            let reference = line.trim().strip_prefix("See merge request ")?;
+            let (_, number) = reference.rsplit_once('!')?;
+            Some(format!("!{}", number))
+        })
+    }
+
+    /// The title of the squashed change: the request title the merge message
+    /// carries, the subject of a single branch commit, or the branch name.
+    pub fn title(&self) -> String {
+        let subject = &self.commit.subject;
+        if !self.commit.is_merge() {
+            return subject.clone();
+        }
+
+        let forge_merge = subject.starts_with("Merge pull request #")
+            || self.commit.body.contains("See merge request ");
+        if forge_merge {
+            let title = self
+                .commit
+                .body
+                .lines()
+                .map(str::trim)
+                .find(|line| !line.is_empty() && !line.starts_with("See merge request "));
+            if let Some(title) = title {
+                return title.to_string();
+            }
+        }
+        if let [commit] = self.branch.as_slice() {
+            return commit.subject.clone();
+        }
+        branch_name(subject).unwrap_or(subject).to_string()
+    }
+
+    /// The message a squash merge would write: the title with the request
+    /// number, then every branch commit as a bullet with its body.
+    pub fn message(&self) -> (String, String) {
+        let subject = match self.request() {
+            Some(request) => format!("{} ({})", self.title(), request),
+            None => self.title(),
+        };
+        if self.branch.len() < 2 {
+            let body = match self.branch.first() {
+                Some(commit) => commit.body.trim().to_string(),
+                None => self.commit.body.trim().to_string(),
+            };
+            return (subject, body);
+        }
+
+        let body = self
+            .branch
+            .iter()
+            .map(|commit| {
+                let body = commit.body.trim();
+                if body.is_empty() {
+                    format!("* {}", commit.subject)
+                } else {
+                    format!("* {}\n\n{}", commit.subject, body)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        (subject, body)
+    }
+
+    /// The prompt asking for one commit message covering every branch commit.
+    fn prompt(&self) -> String {
+        let mut commits = String::new();
+        for commit in &self.branch {
+            commits.push_str(&format!("- {}\n", commit.subject));
+            for line in commit.body.trim().lines() {
+                commits.push_str(&format!("  {}\n", line));
+            }
+        }
+        format!(
+            "The following commits of one branch are squashed into a single commit when merged.\n\
+            Write its commit message: a subject line of at most 72 characters in the imperative mood, \
+            a blank line, and a short body of a few plain sentences or \"- \" bullets describing the \
+            change as a whole rather than each commit. Leave out work the branch later reverted or fixed up. \
+            Reply with the commit message only, without markdown headings or code fences.\n\
+            \n\
+            Title: {}\n\
+            Commits:\n{}",
+            self.title(),
+            commits
+        )
+    }
+
+    /// The single commit the change would be after a squash merge, with the
+    /// merge's hash and date, the branch author and the whole branch diff.
+    fn squash(self, subject: String, body: String) -> Result<Commit> {
+        let mut commit = self.commit;
+        if let Some(first) = self.branch.first() {
+            commit.author = first.author.clone();
+            commit.email = first.email.clone();
+        }
+        if commit.is_merge() {
+            commit.file_stats = git::diff_stats(&commit.parents[0], &commit.hash)?;
+            commit.files_changed = commit.file_stats.iter().map(|f| f.path.clone()).collect();
+            commit.parents.truncate(1);
+        }
+        commit.subject = subject;
+        commit.body = body;
+        Ok(commit)
+    }
+}
+
+/// `feature/x` of `Merge branch 'feature/x' into 'main'`, or of `Merge pull
+/// request #12 from owner/feature/x`.
+fn branch_name(subject: &str) -> Option<&str> {
+    if let Some(rest) = subject.strip_prefix("Merge branch '") {
+        return rest.split_once('\'').map(|(name, _)| name);
+    }
+    let (_, source) = subject.split_once(" from ")?;
+    Some(source.split_once('/').map_or(source, |(_, name)| name))
+}
+
+/// Splits a model reply into subject and body, dropping code fences.
+fn parse_message(reply: &str) -> (String, String) {
+    let reply = reply.trim().trim_matches('`').trim();
+    let (subject, body) = reply.split_once('\n').unwrap_or((reply, ""));
+    (
+        subject.trim().trim_matches('"').to_string(),
+        body.trim().to_string(),
+    )
+}
+
+/// Replaces every merge in `commits` and the branch commits it brought in
+/// with the one commit a squash merge would have made, see [`group`]. With
+/// `ai`, branches of several commits get a message the model writes instead
+/// of the list of their commits.
+pub fn squash(commits: Vec<Commit>, tip: &str, ai: Option<&dyn Backend>) -> Result<Vec<Commit>> {
+    let landed = group(commits, tip);
+    let branches = landed.iter().filter(|entry| entry.branch.len() > 1).count();
+    let progress = match ai {
+        Some(_) if branches > 0 => Some(Progress::bar(branches, "Writing squash messages")),
+        _ => None,
+    };
+
+    let mut squashed = Vec::with_capacity(landed.len());
+    for entry in landed {
+        if entry.branch.is_empty() && !entry.commit.is_merge() {
+            squashed.push(entry.commit);
+            continue;
+        }
+        let (subject, body) = match ai {
+            Some(ai) if entry.branch.len() > 1 => {
+                let (subject, body) = parse_message(&ai.generate(&entry.prompt(), 0.3)?);
+                if let Some(progress) = &progress {
+                    progress.inc();
+                }
+                match entry.request() {
+                    Some(request) if !subject.contains(&request) => {
+                        (format!("{} ({})", subject, request), body)
+                    }
+                    _ => (subject, body),
+                }
+            }
+            _ => entry.message(),
+        };
+        squashed.push(entry.squash(subject, body)?);
+    }
+    if let Some(progress) = &progress {
+        progress.finish();
+    }
+    Ok(squashed)
+}
@@ -0,0 +1,75 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use crate::error::{Context, ReportError, Result};
+
+const DEFAULT_STATE_FILE: &str = ".git-report/state.json";
+
+/// Remembers the tip commit of the last generated report per repository, so
+/// recurring runs can continue where the previous one stopped.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct State {
+    #[serde(default)]
+    pub repositories: BTreeMap<String, RepositoryState>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RepositoryState {
+    pub last_commit: String,
+    pub reported_at: DateTime<Utc>,
+}
+
+/// Resolves the state file: `GIT_REPORT_STATE` when set, otherwise
+/// `.git-report/state.json` in the repository root.
+pub fn state_path(repo_path: &str) -> PathBuf {
+    env::var("GIT_REPORT_STATE")
+        .map(Into::into)
+        .unwrap_or_else(|_| Path::new(repo_path).join(DEFAULT_STATE_FILE))
+}
+
+pub fn load(path: &Path) -> Result<State> {
+    if !path.exists() {
+        return Ok(State::default());
+    }
+
+    let content = fs::read_to_string(path).with_context(ReportError::io, || {
+        format!("Failed to read state file {}", path.display())
+    })?;
+    serde_json::from_str(&content).with_context(ReportError::parse, || {
+        format!("Failed to parse state file {}", path.display())
+    })
+}
+
+pub fn last_commit(path: &Path, repo_path: &str) -> Result<Option<String>> {
+    Ok(load(path)?
+        .repositories
+        .remove(repo_path)
+        .map(|repo| repo.last_commit))
+}
+
+pub fn record_last_commit(path: &Path, repo_path: &str, hash: &str) -> Result<()> {
+    let mut state = load(path)?;
+    state.repositories.insert(
+        repo_path.to_string(),
+        RepositoryState {
+            last_commit: hash.to_string(),
+            reported_at: Utc::now(),
+        },
+    );
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(ReportError::io, || {
+            format!("Failed to create state directory {}", dir.display())
+        })?;
+    }
+    let json = serde_json::to_string_pretty(&state)
+        .context(ReportError::parse, "Failed to serialize state")?;
+    fs::write(path, json).with_context(ReportError::io, || {
+        format!("Failed to write state file {}", path.display())
+    })
+}
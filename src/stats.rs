@@ -0,0 +1,429 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    error::{ReportError, Result},
+    git::Commit,
+    i18n::Locale,
+    report::{Block, Section},
+};
+
+/// Number of files listed in the hotspot section.
+const HOTSPOT_LIMIT: usize = 10;
+
+/// A statistic computed over the commits of a range and rendered as a report
+/// section. Implementations are added to a [`MetricRegistry`] and selected by
+/// name, from `--hotspots` or the `[stats] metrics` config list.
+pub trait Metric {
+    type Output;
+
+    /// Identifier used in the config and as the section id.
+    fn name(&self) -> &'static str;
+    /// Catalog key of the section title; a key no catalog has is shown as
+    /// it is, so metrics of other crates can return the title itself.
+    fn title(&self) -> &'static str;
+    fn compute(&self, commits: &[Commit]) -> Self::Output;
+    fn render(&self, output: &Self::Output, locale: &Locale) -> Vec<Block>;
+}
+
+/// Object-safe view of a [`Metric`], so metrics with different outputs can
+/// live in one registry.
+trait SectionMetric {
+    fn name(&self) -> &'static str;
+    fn section(&self, commits: &[Commit], locale: &Locale) -> Section;
+}
+
+impl<M: Metric> SectionMetric for M {
+    fn name(&self) -> &'static str {
+        Metric::name(self)
+    }
+
+    fn section(&self, commits: &[Commit], locale: &Locale) -> Section {
+        let output = self.compute(commits);
+        self.render(&output, locale).into_iter().fold(
+            Section::new(Metric::name(self), locale.text(self.title())),
+            |section, block| section.with_block(block),
+        )
+    }
+}
+
+#[derive(Default)]
+pub struct MetricRegistry {
+    metrics: Vec<Box<dyn SectionMetric>>,
+}
+
+impl MetricRegistry {
+    /// A registry with every built-in metric.
+    pub fn with_defaults() -> Self {
+        let mut registry = Self::default();
+        registry.register(Hotspots);
+        registry.register(AuthorActivity);
+        registry.register(EmailDomains::default());
+        registry
+    }
+
+    /// Adds a metric; a metric with the same name replaces the earlier one.
+    pub fn register<M: Metric + 'static>(&mut self, metric: M) {
+        self.metrics.retain(|m| m.name() != Metric::name(&metric));
+        self.metrics.push(Box::new(metric));
+    }
+
+    pub fn names(&self) -> Vec<&'static str> {
+        self.metrics.iter().map(|m| m.name()).collect()
+    }
+
+    /// Computes the named metrics in the given order, failing on unknown names.
+    pub fn sections(
+        &self,
+        names: &[String],
+        commits: &[Commit],
+        locale: &Locale,
+    ) -> Result<Vec<Section>> {
+        names
+            .iter()
+            .map(|name| {
+                let metric = self
+                    .metrics
+                    .iter()
+                    .find(|m| m.name() == name)
+                    .ok_or_else(|| {
+                        ReportError::parse(format!(
+                            "Unknown metric '{}' (available: {})",
+                            name,
+                            self.names().join(", ")
+                        ))
+                    })?;
+                Ok(metric.section(commits, locale))
+            })
+            .collect()
+    }
+}
+
+pub struct Hotspot {
+    pub path: String,
+    pub commits: usize,
+    pub additions: u64,
+    pub deletions: u64,
+    pub authors: Vec<String>,
+}
+
+impl Hotspot {
+    pub fn churn(&self) -> u64 {
+        self.additions + self.deletions
+    }
+}
+
+/// Aggregates per-file change counts, churn and authors over the range,
+/// ordered by how often each file changed, then by churn.
+pub fn file_hotspots(commits: &[Commit]) -> Vec<Hotspot> {
+    let mut hotspots: HashMap<&str, Hotspot> = HashMap::new();
+
+    for commit in commits {
+        for stat in &commit.file_stats {
+            let hotspot = hotspots.entry(&stat.path).or_insert_with(|| Hotspot {
+                path: stat.path.clone(),
+                commits: 0,
+                additions: 0,
+                deletions: 0,
+                authors: Vec::new(),
+            });
+            hotspot.commits += 1;
+            hotspot.additions += stat.additions;
+            hotspot.deletions += stat.deletions;
+            if !hotspot.authors.contains(&commit.author) {
+                hotspot.authors.push(commit.author.clone());
+            }
+        }
+    }
+
+    let mut hotspots: Vec<Hotspot> = hotspots.into_values().collect();
+    hotspots.sort_by(|a, b| {
+        b.commits
+            .cmp(&a.commits)
+            .then_with(|| b.churn().cmp(&a.churn()))
+            .then_with(|| a.path.cmp(&b.path))
+    });
+
+    hotspots
+}
+
+pub struct Hotspots;
+
+impl Metric for Hotspots {
+    type Output = Vec<Hotspot>;
+
+    fn name(&self) -> &'static str {
+        "hotspots"
+    }
+
+    fn title(&self) -> &'static str {
+        "file-hotspots"
+    }
+
+    fn compute(&self, commits: &[Commit]) -> Vec<Hotspot> {
+        file_hotspots(commits)
+    }
+
+    fn render(&self, hotspots: &Vec<Hotspot>, locale: &Locale) -> Vec<Block> {
+        if hotspots.is_empty() {
+            return vec![Block::Text(locale.text("no-file-changes").to_string())];
+        }
+
+        let rows = hotspots
+            .iter()
+            .take(HOTSPOT_LIMIT)
+            .map(|hotspot| {
+                vec![
+                    hotspot.path.clone(),
+                    hotspot.commits.to_string(),
+                    hotspot.additions.to_string(),
+                    hotspot.deletions.to_string(),
+                    hotspot.churn().to_string(),
+                    hotspot.authors.join(", "),
+                ]
+            })
+            .collect();
+
+        vec![Block::Table {
+            headers: ["file", "commits", "added", "deleted", "churn", "authors"]
+                .map(|key| locale.text(key).to_string())
+                .to_vec(),
+            rows,
+        }]
+    }
+}
+
+pub struct AuthorStats {
+    pub author: String,
+    pub commits: usize,
+    pub additions: u64,
+    pub deletions: u64,
+}
+
+/// Commits and changed lines per author, most active first.
+pub struct AuthorActivity;
+
+impl Metric for AuthorActivity {
+    type Output = Vec<AuthorStats>;
+
+    fn name(&self) -> &'static str {
+        "authors"
+    }
+
+    fn title(&self) -> &'static str {
+        "author-activity"
+    }
+
+    fn compute(&self, commits: &[Commit]) -> Vec<AuthorStats> {
+        let mut authors: Vec<AuthorStats> = Vec::new();
+
+        for commit in commits {
+            let index = match authors.iter().position(|a| a.author == commit.author) {
+                Some(index) => index,
+                None => {
+                    authors.push(AuthorStats {
+                        author: commit.author.clone(),
+                        commits: 0,
+                        additions: 0,
+                        deletions: 0,
+                    });
+                    authors.len() - 1
+                }
+            };
+            let stats = &mut authors[index];
+            stats.commits += 1;
+            stats.additions += commit.file_stats.iter().map(|f| f.additions).sum::<u64>();
+            stats.deletions += commit.file_stats.iter().map(|f| f.deletions).sum::<u64>();
+        }
+
+        authors.sort_by(|a, b| {
+            b.commits
+                .cmp(&a.commits)
+                .then_with(|| a.author.cmp(&b.author))
+        });
+        authors
+    }
+
+    fn render(&self, authors: &Vec<AuthorStats>, locale: &Locale) -> Vec<Block> {
+        if authors.is_empty() {
+            return vec![Block::Text(locale.text("no-commits").to_string())];
+        }
+
+        let rows = authors
+            .iter()
+            .map(|stats| {
+                vec![
+                    stats.author.clone(),
+                    stats.commits.to_string(),
+                    stats.additions.to_string(),
+                    stats.deletions.to_string(),
+                ]
+            })
+            .collect();
+
+        vec![Block::Table {
+            headers: ["author", "commits", "added", "deleted"]
+                .map(|key| locale.text(key).to_string())
+                .to_vec(),
+            rows,
+        }]
+    }
+}
+
+pub struct DomainStats {
+    pub domain: String,
+    pub company: bool,
+    pub commits: usize,
+    pub authors: Vec<String>,
+    pub additions: u64,
+    pub deletions: u64,
+}
+
+/// Contributions grouped by the domain of the author email, most active
+/// first. Domains listed in `[stats] company_domains`, and their subdomains,
+/// count as company contributions and everything else as external.
+#[derive(Default)]
+pub struct EmailDomains {
+    pub company_domains: Vec<String>,
+}
+
+impl EmailDomains {
+    pub fn new(company_domains: Vec<String>) -> Self {
+        Self {
+            company_domains: company_domains
+                .iter()
+                .map(|domain| domain.trim().trim_start_matches('@').to_lowercase())
+                .collect(),
+        }
+    }
+
+    fn is_company(&self, domain: &str) -> bool {
+        self.company_domains.iter().any(|company| {
+            domain == company
+                || domain
+                    .strip_suffix(company.as_str())
+                    .is_some_and(|sub| sub.ends_with('.'))
+        })
+    }
+}
+
+/// The part after the last `@`, lowercased; `(unknown)` without an address.
+fn email_domain(email: &str) -> String {
+    match email.rsplit_once('@') {
+        Some((_, domain)) if !domain.trim().is_empty() => domain.trim().to_lowercase(),
+        _ => "(unknown)".to_string(),
+    }
+}
+
+impl Metric for EmailDomains {
+    type Output = Vec<DomainStats>;
+
+    fn name(&self) -> &'static str {
+        "domains"
+    }
+
+    fn title(&self) -> &'static str {
+        "email-domains"
+    }
+
+    fn compute(&self, commits: &[Commit]) -> Vec<DomainStats> {
+        let mut domains: HashMap<String, DomainStats> = HashMap::new();
+
+        for commit in commits {
+            let domain = email_domain(&commit.email);
+            let company = self.is_company(&domain);
+            let stats = domains
+                .entry(domain.clone())
+                .or_insert_with(|| DomainStats {
+                    domain,
+                    company,
+                    commits: 0,
+                    authors: Vec::new(),
+                    additions: 0,
+                    deletions: 0,
+                });
+            stats.commits += 1;
+            stats.additions += commit.file_stats.iter().map(|f| f.additions).sum::<u64>();
+            stats.deletions += commit.file_stats.iter().map(|f| f.deletions).sum::<u64>();
+            if !stats.authors.contains(&commit.author) {
+                stats.authors.push(commit.author.clone());
+            }
+        }
+
+        let mut domains: Vec<DomainStats> = domains.into_values().collect();
+        domains.sort_by(|a, b| {
+            b.commits
+                .cmp(&a.commits)
+                .then_with(|| a.domain.cmp(&b.domain))
+        });
+        domains
+    }
+
+    fn render(&self, domains: &Vec<DomainStats>, locale: &Locale) -> Vec<Block> {
+        if domains.is_empty() {
+            return vec![Block::Text(locale.text("no-commits").to_string())];
+        }
+
+        let mut blocks = Vec::new();
+        let classified = !self.company_domains.is_empty();
+        if classified {
+            let total: usize = domains.iter().map(|d| d.commits).sum();
+            let share = |company: bool| {
+                let group: Vec<&DomainStats> =
+                    domains.iter().filter(|d| d.company == company).collect();
+                let commits: usize = group.iter().map(|d| d.commits).sum();
+                let authors: HashSet<&String> = group.iter().flat_map(|d| &d.authors).collect();
+                locale.format(
+                    "domain-share",
+                    &[
+                        ("commits", &commits.to_string()),
+                        (
+                            "share",
+                            &format!("{:.0}", commits as f64 * 100.0 / total as f64),
+                        ),
+                        ("authors", &authors.len().to_string()),
+                    ],
+                )
+            };
+            blocks.push(Block::Fields(vec![
+                (locale.text("company").to_string(), share(true)),
+                (locale.text("external").to_string(), share(false)),
+            ]));
+        }
+
+        let rows = domains
+            .iter()
+            .map(|stats| {
+                let mut row = vec![stats.domain.clone()];
+                if classified {
+                    let kind = if stats.company {
+                        "company-domain"
+                    } else {
+                        "external-domain"
+                    };
+                    row.push(locale.text(kind).to_string());
+                }
+                row.extend([
+                    stats.commits.to_string(),
+                    stats.authors.len().to_string(),
+                    stats.additions.to_string(),
+                    stats.deletions.to_string(),
+                ]);
+                row
+            })
+            .collect();
+
+        let mut headers = vec!["domain"];
+        if classified {
+            headers.push("type");
+        }
+        headers.extend(["commits", "authors", "added", "deleted"]);
+        blocks.push(Block::Table {
+            headers: headers
+                .into_iter()
+                .map(|key| locale.text(key).to_string())
+                .collect(),
+            rows,
+        });
+        blocks
+    }
+}
@@ -0,0 +1,73 @@
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+use std::{
+    fs,
+    path::PathBuf,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::error::{Context, ReportError, Result};
+
+/// Records every exchange with an AI backend as a JSON file, so prompts and
+/// raw responses can be reviewed later (`--save-prompts`).
+pub struct TranscriptLog {
+    dir: Option<PathBuf>,
+    exchanges: AtomicUsize,
+}
+
+impl TranscriptLog {
+    pub fn new(dir: Option<PathBuf>) -> Self {
+        Self {
+            dir,
+            exchanges: AtomicUsize::new(0),
+        }
+    }
+
+    /// Writes the exact request payload and raw response of one exchange to
+    /// `<timestamp>-<n>-<model>.json` in the transcript directory, if enabled.
+    /// Credentials travel in headers and are never part of the transcript.
+    pub fn save(
+        &self,
+        endpoint: &str,
+        model: &str,
+        payload: &Value,
+        sent_at: DateTime<Utc>,
+        status: u16,
+        raw_response: &str,
+    ) -> Result<()> {
+        let Some(dir) = &self.dir else {
+            return Ok(());
+        };
+
+        fs::create_dir_all(dir).with_context(ReportError::io, || {
+            format!("Failed to create prompt directory {}", dir.display())
+        })?;
+
+        let sequence = self.exchanges.fetch_add(1, Ordering::SeqCst) + 1;
+        let path = dir.join(format!(
+            "{}-{:03}-{}.json",
+            sent_at.format("%Y%m%d_%H%M%S"),
+            sequence,
+            model.replace([':', '/', '\\'], "_")
+        ));
+
+        let transcript = json!({
+            "endpoint": endpoint,
+            "model": model,
+            "sent_at": sent_at.to_rfc3339(),
+            "received_at": Utc::now().to_rfc3339(),
+            "status": status,
+            "request": payload,
+            "response": serde_json::from_str::<Value>(raw_response)
+                .unwrap_or_else(|_| Value::String(raw_response.to_string())),
+        });
+
+        let json = serde_json::to_string_pretty(&transcript)
+            .context(ReportError::parse, "Failed to serialize prompt transcript")?;
+        fs::write(&path, json).with_context(ReportError::io, || {
+            format!("Failed to write prompt transcript {}", path.display())
+        })?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use std::path::Path;
+
+use crate::{ai::Backend, error::Result, progress::Progress};
+
+/// Builds the default output path for a translated report, e.g.
+/// `report.md` -> `report.de.md`.
+pub fn default_output_path(report_file: &str, language: &str) -> String {
+    let path = Path::new(report_file);
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| report_file.to_string());
+    let file_name = match path.extension() {
+        Some(ext) => format!("{}.{}.{}", stem, language, ext.to_string_lossy()),
+        None => format!("{}.{}", stem, language),
+    };
+
+    path.with_file_name(file_name).to_string_lossy().to_string()
+}
+
+pub fn translate_report(content: &str, language: &str, ai: &dyn Backend) -> Result<String> {
+    let prompt = format!(
+        "Translate the following report into the language with code '{}'.\n\
+        Rules:\n\
+        1. Preserve the document structure exactly: headings, lists, tables, indentation, blank lines and any Markdown syntax.\n\
+        2. Copy commit hashes, numbers, dates, file paths, URLs, code and identifiers verbatim. Never translate or reformat them.\n\
+        3. Only translate human-readable prose.\n\
+        4. Output only the translated report, without any preamble or comments.\n\
+        \n\
+        Report:\n\
+        {}",
+        language, content
+    );
+
+    let progress = Progress::spinner(&format!("Waiting for {}", ai.model()));
+    let response = ai.generate(&prompt, 0.2);
+    progress.finish();
+
+    response
+}
+
+/// Returns tokens from the original (hashes and numbers) that are missing in
+/// the translation, so the user can be warned about altered facts.
+pub fn missing_verbatim_tokens(original: &str, translated: &str) -> Vec<String> {
+    let mut missing = Vec::new();
+
+    for token in original.split(|c: char| !c.is_ascii_alphanumeric()) {
+        let is_number = !token.is_empty() && token.chars().all(|c| c.is_ascii_digit());
+        let is_hash = token.len() >= 7
+            && token.chars().all(|c| c.is_ascii_hexdigit())
+            && token.chars().any(|c| c.is_ascii_digit());
+
+        if (is_number || is_hash)
+            && !translated.contains(token)
+            && !missing.iter().any(|m| m == token)
+        {
+            missing.push(token.to_string());
+        }
+    }
+
+    missing
+}
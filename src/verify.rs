@@ -0,0 +1,150 @@
+use colored::*;
+use std::collections::HashMap;
+
+use crate::git::Commit;
+
+/// A commit present on both sides whose recorded data differs.
+pub struct ChangedCommit {
+    pub baseline: Commit,
+    pub current: Commit,
+    /// Names of the differing fields; `hash` means the commit was rewritten.
+    pub fields: Vec<&'static str>,
+}
+
+/// Differences between a saved dataset and a fresh collection of the same range.
+pub struct RangeDiff {
+    pub added: Vec<Commit>,
+    pub removed: Vec<Commit>,
+    pub changed: Vec<ChangedCommit>,
+}
+
+impl RangeDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+fn differing_fields(baseline: &Commit, current: &Commit) -> Vec<&'static str> {
+    let checks = [
+        ("hash", baseline.hash != current.hash),
+        ("author", baseline.author != current.author),
+        ("date", baseline.date != current.date),
+        ("subject", baseline.subject != current.subject),
+        ("body", baseline.body != current.body),
+        ("files", baseline.files_changed != current.files_changed),
+    ];
+
+    checks
+        .into_iter()
+        .filter(|(_, differs)| *differs)
+        .map(|(field, _)| field)
+        .collect()
+}
+
+/// Compares commits by hash. Commits missing on one side are paired up by
+/// author, date and subject when possible and reported as rewritten.
+pub fn compare(baseline: &[Commit], current: &[Commit]) -> RangeDiff {
+    let baseline_by_hash: HashMap<&str, &Commit> =
+        baseline.iter().map(|c| (c.hash.as_str(), c)).collect();
+    let current_by_hash: HashMap<&str, &Commit> =
+        current.iter().map(|c| (c.hash.as_str(), c)).collect();
+
+    let mut changed = Vec::new();
+    for commit in baseline {
+        if let Some(current) = current_by_hash.get(commit.hash.as_str()) {
+            let fields = differing_fields(commit, current);
+            if !fields.is_empty() {
+                changed.push(ChangedCommit {
+                    baseline: commit.clone(),
+                    current: (*current).clone(),
+                    fields,
+                });
+            }
+        }
+    }
+
+    let mut removed: Vec<Commit> = baseline
+        .iter()
+        .filter(|c| !current_by_hash.contains_key(c.hash.as_str()))
+        .cloned()
+        .collect();
+    let mut added = Vec::new();
+    for commit in current
+        .iter()
+        .filter(|c| !baseline_by_hash.contains_key(c.hash.as_str()))
+    {
+        let original = removed.iter().position(|old| {
+            old.author == commit.author && old.date == commit.date && old.subject == commit.subject
+        });
+        match original {
+            Some(index) => {
+                let baseline = removed.remove(index);
+                changed.push(ChangedCommit {
+                    fields: differing_fields(&baseline, commit),
+                    baseline,
+                    current: commit.clone(),
+                });
+            }
+            None => added.push(commit.clone()),
+        }
+    }
+
+    RangeDiff {
+        added,
+        removed,
+        changed,
+    }
+}
+
+fn describe(commit: &Commit) -> String {
+    format!(
+        "{} {} ({}, {})",
+        &commit.hash[..8],
+        commit.subject,
+        commit.author,
+        commit.date.format("%Y-%m-%d")
+    )
+}
+
+/// Formats the differences for the terminal, one commit per line.
+pub fn render(diff: &RangeDiff) -> String {
+    let mut output = String::new();
+
+    if !diff.added.is_empty() {
+        output.push_str(&format!("Added commits ({}):\n", diff.added.len()));
+        for commit in &diff.added {
+            output.push_str(&format!("  {} {}\n", "+".green(), describe(commit)));
+        }
+    }
+
+    if !diff.removed.is_empty() {
+        output.push_str(&format!("Removed commits ({}):\n", diff.removed.len()));
+        for commit in &diff.removed {
+            output.push_str(&format!("  {} {}\n", "-".red(), describe(commit)));
+        }
+    }
+
+    if !diff.changed.is_empty() {
+        output.push_str(&format!("Changed commits ({}):\n", diff.changed.len()));
+        for change in &diff.changed {
+            let hashes = if change.fields.contains(&"hash") {
+                format!(
+                    "{} -> {}",
+                    &change.baseline.hash[..8],
+                    &change.current.hash[..8]
+                )
+            } else {
+                change.current.hash[..8].to_string()
+            };
+            output.push_str(&format!(
+                "  {} {} {} (differs in: {})\n",
+                "~".yellow(),
+                hashes,
+                change.current.subject,
+                change.fields.join(", ")
+            ));
+        }
+    }
+
+    output
+}
@@ -0,0 +1,176 @@
+use std::fmt;
+
+use crate::{
+    git::Commit,
+    i18n::Locale,
+    report::{Block, Section},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Bump {
+    Patch,
+    Minor,
+    Major,
+}
+
+impl fmt::Display for Bump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Bump::Patch => "patch",
+            Bump::Minor => "minor",
+            Bump::Major => "major",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// The parsed header of a Conventional Commits subject: `type(scope)!: description`.
+pub struct ConventionalHeader<'a> {
+    pub kind: &'a str,
+    pub scope: Option<&'a str>,
+    pub breaking: bool,
+    /// The subject after the colon.
+    pub description: &'a str,
+}
+
+pub fn parse_conventional(subject: &str) -> Option<ConventionalHeader<'_>> {
+    let (header, description) = subject.split_once(':')?;
+    let (header, breaking) = match header.strip_suffix('!') {
+        Some(header) => (header, true),
+        None => (header, false),
+    };
+
+    let (kind, scope) = match header.split_once('(') {
+        Some((kind, scope)) => (kind, Some(scope.strip_suffix(')')?)),
+        None => (header, None),
+    };
+
+    if kind.is_empty() || !kind.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    Some(ConventionalHeader {
+        kind,
+        scope,
+        breaking,
+        description: description.trim(),
+    })
+}
+
+pub fn is_breaking(commit: &Commit) -> bool {
+    parse_conventional(&commit.subject).is_some_and(|h| h.breaking)
+        || commit.body.lines().any(|line| {
+            line.starts_with("BREAKING CHANGE:") || line.starts_with("BREAKING-CHANGE:")
+        })
+}
+
+pub struct VersionSuggestion {
+    pub bump: Bump,
+    pub current: Option<String>,
+    pub next: Option<String>,
+    pub reasons: Vec<String>,
+}
+
+/// Recommends the next semantic version from the conventional commit types
+/// in the range. Before 1.0.0 breaking changes only bump the minor version.
+pub fn suggest(commits: &[Commit], current_tag: Option<&str>) -> VersionSuggestion {
+    let breaking: Vec<&Commit> = commits.iter().filter(|c| is_breaking(c)).collect();
+    let count_kind = |kinds: &[&str]| {
+        commits
+            .iter()
+            .filter(|c| {
+                parse_conventional(&c.subject)
+                    .is_some_and(|h| kinds.contains(&h.kind.to_lowercase().as_str()))
+            })
+            .count()
+    };
+    let features = count_kind(&["feat"]);
+    let fixes = count_kind(&["fix", "perf"]);
+
+    let mut reasons = Vec::new();
+    let bump = if !breaking.is_empty() {
+        for commit in &breaking {
+            reasons.push(format!(
+                "Breaking change: {} ({})",
+                commit.subject,
+                &commit.hash[..8]
+            ));
+        }
+        Bump::Major
+    } else if features > 0 {
+        reasons.push(format!("{} new feature(s) (feat)", features));
+        Bump::Minor
+    } else {
+        if fixes > 0 {
+            reasons.push(format!("{} fix(es) (fix/perf)", fixes));
+        } else {
+            reasons.push("No features or fixes; maintenance changes only".to_string());
+        }
+        Bump::Patch
+    };
+
+    let current = current_tag.and_then(parse_semver);
+    let next = current.map(|(major, minor, patch)| {
+        let (major, minor, patch) = match bump {
+            Bump::Major if major == 0 => (0, minor + 1, 0),
+            Bump::Major => (major + 1, 0, 0),
+            Bump::Minor => (major, minor + 1, 0),
+            Bump::Patch => (major, minor, patch + 1),
+        };
+        let prefix = if current_tag.is_some_and(|t| t.starts_with('v')) {
+            "v"
+        } else {
+            ""
+        };
+        format!("{}{}.{}.{}", prefix, major, minor, patch)
+    });
+
+    if bump == Bump::Major && current.is_some_and(|(major, _, _)| major == 0) {
+        reasons
+            .push("Version is below 1.0.0, so breaking changes bump the minor version".to_string());
+    }
+
+    VersionSuggestion {
+        bump,
+        current: current_tag.map(|t| t.to_string()),
+        next,
+        reasons,
+    }
+}
+
+/// Whether a tag names a release: a version such as `v1.2.0` or `2.0.0-rc.1`,
+/// also behind a package prefix as in monorepo tags like `cli@1.4.0` or
+/// `cli/v1.4.0`.
+pub fn is_release_tag(tag: &str) -> bool {
+    let version = tag.rsplit(['/', '@']).next().unwrap_or(tag);
+    parse_semver(version).is_some()
+}
+
+fn parse_semver(tag: &str) -> Option<(u64, u64, u64)> {
+    let version = tag.strip_prefix('v').unwrap_or(tag);
+    let version = version.split(['-', '+']).next()?;
+    let mut parts = version.split('.').map(|p| p.parse::<u64>().ok());
+    let (major, minor, patch) = (parts.next()??, parts.next()??, parts.next()??);
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((major, minor, patch))
+}
+
+pub fn version_section(suggestion: &VersionSuggestion, locale: &Locale) -> Section {
+    let label = |key: &str| locale.text(key).to_string();
+    let mut fields = vec![(label("recommended-bump"), suggestion.bump.to_string())];
+    if let Some(current) = &suggestion.current {
+        fields.push((label("current-version"), current.clone()));
+    }
+    if let Some(next) = &suggestion.next {
+        fields.push((label("next-version"), next.clone()));
+    }
+
+    Section::new("version", locale.text("version-recommendation"))
+        .with_block(Block::Fields(fields))
+        .with_block(Block::List {
+            title: label("reasoning"),
+            items: suggestion.reasons.clone(),
+        })
+}
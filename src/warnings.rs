@@ -0,0 +1,30 @@
+//! Warnings from library code about something the caller may want to know
+//! while the work goes on, such as a retried AI request or a ticket that
+//! could not be fetched. They go to the handler set with [`set_handler`];
+//! without one they are written to stderr, never to stdout, where a report
+//! may be piped.
+
+use std::sync::RwLock;
+
+type Handler = Box<dyn Fn(&str) + Send + Sync>;
+
+static HANDLER: RwLock<Option<Handler>> = RwLock::new(None);
+
+/// Hands every later warning to `handler` instead of writing it to stderr.
+pub fn set_handler(handler: impl Fn(&str) + Send + Sync + 'static) {
+    *HANDLER
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(Box::new(handler));
+}
+
+/// Passes a warning, without a `Warning:` prefix, to the handler.
+pub fn warn(message: impl AsRef<str>) {
+    let message = message.as_ref();
+    match &*HANDLER
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+    {
+        Some(handler) => handler(message),
+        None => eprintln!("Warning: {}", message),
+    }
+}
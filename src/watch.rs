@@ -0,0 +1,147 @@
+use anyhow::{Context, Result};
+use colored::*;
+use std::{
+    fs,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use git_report::{config::Config, git, state};
+
+use crate::{generate_report, message_filter, Args, ReportRange};
+
+/// Parses intervals like `90s`, `30m`, `12h`, `1d` or `1w`.
+pub fn parse_interval(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(split);
+    let invalid = || {
+        format!(
+            "invalid interval '{}', expected e.g. 30m, 12h, 1d or 1w",
+            value
+        )
+    };
+    let amount: u64 = amount.parse().map_err(|_| invalid())?;
+
+    let seconds = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => {
+            return Err(format!(
+                "unknown interval unit '{}', expected one of s, m, h, d, w",
+                unit
+            ))
+        }
+    };
+
+    if amount == 0 {
+        return Err("interval must be greater than zero".to_string());
+    }
+
+    amount
+        .checked_mul(seconds)
+        .map(Duration::from_secs)
+        .ok_or_else(invalid)
+}
+
+/// Generates a report of everything new since the previous run every
+/// `interval`, until interrupted. The last reported commit is kept in the
+/// state file, so restarting the watcher does not report commits twice.
+pub fn run(
+    args: &Args,
+    config: &Config,
+    repo_path: &str,
+    interval: Duration,
+    output_dir: &Path,
+) -> Result<()> {
+    fs::create_dir_all(output_dir)
+        .with_context(|| format!("Failed to create output directory {}", output_dir.display()))?;
+
+    let state_path = state::state_path(repo_path);
+    let tip = args.to.as_deref().unwrap_or("HEAD");
+
+    println!(
+        "Watching {} every {}s, reports go to {}",
+        tip,
+        interval.as_secs(),
+        output_dir.display().to_string().bright_blue()
+    );
+
+    loop {
+        if let Err(e) = run_once(args, config, repo_path, tip, &state_path, output_dir) {
+            println!(
+                "{}",
+                format!("Warning: report run failed: {:#}", e).yellow()
+            );
+        }
+
+        std::thread::sleep(interval);
+    }
+}
+
+fn run_once(
+    args: &Args,
+    config: &Config,
+    repo_path: &str,
+    tip: &str,
+    state_path: &Path,
+    output_dir: &Path,
+) -> Result<()> {
+    let to_commit = git::get_commit(tip)?;
+
+    let last = match state::last_commit(state_path, repo_path)? {
+        Some(last) => last,
+        None => match &args.from {
+            Some(from) => git::get_commit(from)?.hash,
+            None => {
+                state::record_last_commit(state_path, repo_path, &to_commit.hash)?;
+                println!(
+                    "No previous report, recorded {} as the starting point",
+                    &to_commit.hash[..8]
+                );
+                return Ok(());
+            }
+        },
+    };
+
+    if last == to_commit.hash {
+        println!("No new commits since {}", &last[..8]);
+        return Ok(());
+    }
+
+    let from_commit = git::get_commit(&last)?;
+    let started = Instant::now();
+    let commits = git::get_matching_commits_in_range(
+        &from_commit.hash,
+        &to_commit.hash,
+        false,
+        &message_filter(args),
+    )?;
+    println!(
+        "{}",
+        format!(
+            "Found {} new commits since {}",
+            commits.len(),
+            &from_commit.hash[..8]
+        )
+        .blue()
+    );
+
+    let range = ReportRange {
+        from_commit,
+        to_commit: to_commit.clone(),
+        inclusive: false,
+        commits,
+        sections: Vec::new(),
+        collect_time: started.elapsed(),
+    };
+    generate_report(args, config, repo_path, range, Some(output_dir))?;
+
+    state::record_last_commit(state_path, repo_path, &to_commit.hash)?;
+    Ok(())
+}
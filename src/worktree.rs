@@ -0,0 +1,82 @@
+use crate::{
+    error::Result,
+    git::{self, WorktreeChange},
+    i18n::Locale,
+    report::{Block, Section},
+};
+
+/// Describes staged, unstaged and untracked changes, so a report can show the
+/// current state of the checkout and not only committed history.
+pub fn worktree_section(locale: &Locale) -> Result<Section> {
+    let label = |key: &str| locale.text(key).to_string();
+    let mut section = Section::new("worktree", locale.text("working-tree"));
+
+    let staged = git::worktree_changes(true)?;
+    let unstaged = git::worktree_changes(false)?;
+    let untracked = git::untracked_files()?;
+
+    if staged.is_empty() && unstaged.is_empty() && untracked.is_empty() {
+        return Ok(section.with_block(Block::Text(label("worktree-clean"))));
+    }
+
+    section = section.with_block(Block::Fields(vec![
+        (label("staged-files"), summary(&staged)),
+        (label("unstaged-files"), summary(&unstaged)),
+        (label("untracked-files"), untracked.len().to_string()),
+    ]));
+
+    for (title, changes) in [("staged-changes", &staged), ("unstaged-changes", &unstaged)] {
+        if !changes.is_empty() {
+            section = section
+                .with_block(Block::Text(format!("{}:", label(title))))
+                .with_block(changes_table(changes, locale));
+        }
+    }
+
+    if !untracked.is_empty() {
+        section = section.with_block(Block::List {
+            title: label("untracked"),
+            items: untracked,
+        });
+    }
+
+    Ok(section)
+}
+
+fn summary(changes: &[WorktreeChange]) -> String {
+    let additions: u64 = changes.iter().map(|c| c.stat.additions).sum();
+    let deletions: u64 = changes.iter().map(|c| c.stat.deletions).sum();
+    format!("{} (+{} -{})", changes.len(), additions, deletions)
+}
+
+fn changes_table(changes: &[WorktreeChange], locale: &Locale) -> Block {
+    let rows = changes
+        .iter()
+        .map(|change| {
+            let (added, deleted) = if change.stat.binary {
+                (
+                    locale.text("binary").to_string(),
+                    locale.text("binary").to_string(),
+                )
+            } else {
+                (
+                    change.stat.additions.to_string(),
+                    change.stat.deletions.to_string(),
+                )
+            };
+            vec![
+                change.status.clone(),
+                change.stat.path.clone(),
+                added,
+                deleted,
+            ]
+        })
+        .collect();
+
+    Block::Table {
+        headers: ["status", "file", "added", "deleted"]
+            .map(|key| locale.text(key).to_string())
+            .to_vec(),
+        rows,
+    }
+}